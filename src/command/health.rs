@@ -0,0 +1,82 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use clap::Args;
+use serde_json::Value;
+
+use crate::configure::get_jobs;
+use crate::utils::duration_util::parse_duration;
+
+#[derive(Debug, Args)]
+pub struct HealthArgs {
+    /// The address of the admin server of the instance, read from the 'jobs' file if not given.
+    #[arg(long)]
+    pub address: Option<SocketAddr>,
+
+    /// How long to wait for the instance to respond, e.g. '3s'.
+    #[arg(long, default_value = "5s", value_parser = parse_timeout)]
+    pub timeout: Duration,
+}
+
+/// Checks that the running instance is healthy, for container health checks.
+///
+/// Requests the `/healthz` endpoint of the admin server of the instance, which reports a
+/// stalled scheduler with `503 Service Unavailable`, see `start_admin_server`. An instance that
+/// doesn't respond within the timeout is unhealthy too. With `--address` the 'jobs' file is not
+/// read, which keeps the check light. An admin server listening on all interfaces is reached
+/// through the loopback interface.
+///
+/// # Arguments
+///
+/// * `args` - The arguments of the `health` command.
+///
+/// # Returns
+///
+/// `true` if the instance responded and is healthy.
+///
+/// # Examples
+///
+/// ```
+/// let args = HealthArgs { address: Some("127.0.0.1:9090".parse()?), timeout: Duration::from_secs(5) };
+/// let healthy = health(args).await;
+/// ```
+pub async fn health(args: HealthArgs) -> bool {
+    let Some(mut address) = args.address.or_else(|| get_jobs().admin_address) else {
+        eprintln!("The admin server is not configured, add an 'admin' section to the 'jobs' file or pass --address.");
+        return false;
+    };
+    if address.ip().is_unspecified() {
+        address.set_ip(if address.is_ipv4() { IpAddr::V4(Ipv4Addr::LOCALHOST) } else { IpAddr::V6(Ipv6Addr::LOCALHOST) });
+    }
+
+    let client = match reqwest::Client::builder().timeout(args.timeout).build() {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("Failed to create the HTTP client: {}", err);
+            return false;
+        }
+    };
+    let response = match client.get(format!("http://{}/healthz", address)).send().await {
+        Ok(response) => response,
+        Err(err) => {
+            eprintln!("Unhealthy, the instance at {} did not respond: {}", address, err);
+            return false;
+        }
+    };
+
+    let status = response.status();
+    let body = response.json::<Value>().await.unwrap_or_default();
+    if !status.is_success() {
+        eprintln!("Unhealthy, status: {}, health: {}", status, body);
+        return false;
+    }
+
+    println!("Healthy, health: {}", body);
+    true
+}
+
+/// Parses the `--timeout` of the command, e.g. `3s`.
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+    parse_duration(value)
+        .filter(|d| !d.is_zero())
+        .ok_or(format!("Invalid duration '{}', expected e.g. '3s'.", value))
+}
\ No newline at end of file
@@ -0,0 +1,141 @@
+use std::time::Duration;
+use clap::Args;
+
+use crate::command::TagArgs;
+use crate::configure::get_jobs;
+use crate::models::job_stats::JobStats;
+use crate::scheduler::state::{read_state, RECENT_RUNS};
+use crate::utils::cron_util::get_next_runs;
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    /// The number of upcoming fire times to show for each job.
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u8).range(1..=3))]
+    pub next: u8,
+
+    /// Show the rolling success rate, latencies and consecutive failures of each job instead of
+    /// its schedule.
+    #[arg(long)]
+    pub stats: bool,
+
+    #[command(flatten)]
+    pub tags: TagArgs,
+}
+
+/// Prints a table of the configured jobs with their upcoming fire times.
+///
+/// Only the jobs selected by the `--tags` and `--exclude-tags` of the command are listed.
+/// With `--stats`, the table shows the statistics of the last runs of each job instead, read
+/// from the state file: the success rate, the average, p50, p95 and maximum duration, and the
+/// number of failed runs since the last successful run. A job without a finished run shows `-`.
+///
+/// # Arguments
+///
+/// * `args` - The arguments of the `list` command.
+///
+/// # Examples
+///
+/// ```
+/// let args = ListArgs { next: 3, stats: false, tags: TagArgs { tags: vec![], exclude_tags: vec![] } };
+/// list(args);
+/// ```
+pub fn list(args: ListArgs) {
+    let jobs = get_jobs();
+    let timezone = jobs.timezone.to_string();
+
+    let state = read_state(&jobs.state_file).unwrap_or_else(|err| {
+        eprintln!("Failed to read state file: {}", err);
+        Default::default()
+    });
+
+    let header: &[&str] = if args.stats {
+        &["NAME", "ENABLED", "RUNS", "SUCCESS", "AVG", "P50", "P95", "MAX", "CONSECUTIVE FAILURES"]
+    } else {
+        &["NAME", "ENABLED", "CRON", "SCHEDULE", "TIMEZONE", "TAGS", "NEXT RUNS"]
+    };
+    let mut rows = vec![header.iter().map(|column| column.to_string()).collect::<Vec<_>>()];
+
+    let tag_filter = args.tags.to_filter();
+    for job in jobs.all_jobs().iter().filter(|job| tag_filter.matches(&job.options().tags)) {
+        let (name, enable, schedule, tags) = (job.name(), job.enable(), job.schedule(), &job.options().tags);
+        let job_state = state.jobs.get(name);
+        let enabled = match job_state {
+            Some(job_state) if job_state.paused => format!("{} (paused)", enable),
+            _ => enable.to_string(),
+        };
+
+        if args.stats {
+            let stats = job_state.and_then(JobStats::from_state);
+            let row = match stats {
+                Some(stats) => vec![
+                    name.to_string(),
+                    enabled,
+                    stats.runs.to_string(),
+                    format!("{:.1}%", stats.success_rate * 100.0),
+                    format_latency(stats.average),
+                    format_latency(stats.p50),
+                    format_latency(stats.p95),
+                    format_latency(stats.max),
+                    stats.consecutive_failures.to_string(),
+                ],
+                None => vec![name.to_string(), enabled, "0".to_string(), "-".to_string(), "-".to_string(),
+                             "-".to_string(), "-".to_string(), "-".to_string(), "-".to_string()],
+            };
+            rows.push(row);
+            continue;
+        }
+
+        let next_runs = get_next_runs(schedule, &jobs.timezone, args.next as usize);
+        let next_runs = if next_runs.is_empty() {
+            "-".to_string()
+        } else {
+            next_runs.iter()
+                .map(|run| run.format("%Y-%m-%d %H:%M:%S").to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        rows.push(vec![
+            name.to_string(),
+            enabled,
+            schedule.to_string(),
+            schedule.describe(),
+            timezone.clone(),
+            if tags.is_empty() { "-".to_string() } else { tags.join(",") },
+            next_runs,
+        ]);
+    }
+
+    print_table(&rows);
+    if args.stats {
+        println!();
+        println!("Statistics over the last {} runs of each job, read from the state file: {}", RECENT_RUNS, jobs.state_file);
+    }
+}
+
+/// Prints the rows of a table with aligned columns, the last column is not padded.
+fn print_table(rows: &[Vec<String>]) {
+    let columns = rows.first().map_or(0, |row| row.len());
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| rows.iter().map(|row| row[i].chars().count()).max().unwrap_or(0))
+        .collect();
+
+    for row in rows {
+        let line = row.iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, width))| if i + 1 == columns { cell.clone() } else { format!("{:<w$}", cell, w = width) })
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line);
+    }
+}
+
+/// Formats a latency of the statistics, in milliseconds below 10 seconds.
+fn format_latency(duration: Duration) -> String {
+    if duration < Duration::from_secs(10) {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,128 @@
+use clap::{Args, Parser, Subcommand};
+
+use crate::command::health::HealthArgs;
+use crate::command::list::ListArgs;
+use crate::command::next::NextArgs;
+use crate::command::pause::PauseArgs;
+use crate::command::replay::ReplayArgs;
+use crate::command::run::RunArgs;
+use crate::command::schema::SchemaArgs;
+use crate::command::set::SetArgs;
+#[cfg(windows)]
+use crate::command::service::ServiceArgs;
+use crate::models::tag_filter::TagFilter;
+
+pub mod health;
+pub mod list;
+pub mod next;
+pub mod pause;
+pub mod replay;
+pub mod run;
+pub mod schema;
+pub mod set;
+#[cfg(windows)]
+pub mod service;
+
+/// A Simple Job Scheduler By Rust.
+///
+/// Without a subcommand, rjob loads the 'jobs' file from the current directory, or the document
+/// of `--config`, and starts the scheduler.
+#[derive(Debug, Parser)]
+#[command(name = "rjob", version, about)]
+pub struct Cli {
+    /// Fire jobs on schedule but only log the requests instead of sending them.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    #[command(flatten)]
+    pub tags: TagArgs,
+
+    /// Appends a JSON record of each completed run to this file, one per line, for data
+    /// pipelines, e.g. './runs.jsonl'.
+    #[arg(long)]
+    pub run_log: Option<std::path::PathBuf>,
+
+    /// Reads the jobs document from a Consul or etcd key instead of the 'jobs' file, and reloads
+    /// the scheduler when it changes, e.g. 'consul://127.0.0.1:8500/rjob/jobs.yaml'.
+    #[arg(long, global = true)]
+    pub config: Option<String>,
+
+    #[cfg(unix)]
+    #[command(flatten)]
+    pub daemon: DaemonArgs,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Checks that the running instance is healthy, exits with 1 if not.
+    Health(HealthArgs),
+
+    /// Lists the configured jobs and their next fire times.
+    List(ListArgs),
+
+    /// Prints a merged timeline of the upcoming fire times of all jobs.
+    Next(NextArgs),
+
+    /// Pauses a job, its triggers are skipped until it is resumed.
+    Pause(PauseArgs),
+
+    /// Resumes a paused job.
+    Resume(PauseArgs),
+
+    /// Sends the requests recorded in the dead-letter file again.
+    Replay(ReplayArgs),
+
+    /// Runs a job once immediately and exits with its result.
+    Run(RunArgs),
+
+    /// Prints the JSON Schema of the jobs file for editors and CI validators.
+    Schema(SchemaArgs),
+
+    /// Stores a value read by the 'when' conditions of the jobs, or removes it without a value.
+    Set(SetArgs),
+
+    /// Installs, removes, starts or stops rjob as a Windows service.
+    #[cfg(windows)]
+    Service(ServiceArgs),
+}
+
+/// The options selecting jobs by their tags.
+#[derive(Debug, Args)]
+pub struct TagArgs {
+    /// Only the jobs with one of these tags, comma-separated or repeated.
+    #[arg(long, value_delimiter = ',')]
+    pub tags: Vec<String>,
+
+    /// Skips the jobs with one of these tags, comma-separated or repeated.
+    #[arg(long, value_delimiter = ',')]
+    pub exclude_tags: Vec<String>,
+}
+
+/// The options running the scheduler as a traditional Unix daemon.
+#[cfg(unix)]
+#[derive(Debug, Args)]
+pub struct DaemonArgs {
+    /// Runs the scheduler in the background, detached from the terminal.
+    #[arg(long)]
+    pub daemon: bool,
+
+    /// Writes the process id to this file, and refuses to start while another instance holds it,
+    /// e.g. '/var/run/rjob.pid'.
+    #[arg(long)]
+    pub pid_file: Option<std::path::PathBuf>,
+
+    /// The file the daemon appends its logs to, they are discarded otherwise.
+    #[arg(long, requires = "daemon")]
+    pub log_file: Option<std::path::PathBuf>,
+}
+
+impl TagArgs {
+
+    /// Returns the filter selecting the jobs by the given tags.
+    pub fn to_filter(&self) -> TagFilter {
+        TagFilter::new(self.tags.clone(), self.exclude_tags.clone())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use clap::Args;
+
+use crate::command::TagArgs;
+use crate::configure::get_jobs;
+use crate::utils::cron_util::get_runs_until;
+use crate::utils::duration_util::{format_duration, parse_duration};
+
+/// The maximum number of fire times simulated for all jobs, so a job firing every second
+/// doesn't exhaust the memory over a long window.
+const MAX_SIMULATED_FIRES: usize = 100_000;
+
+#[derive(Debug, Args)]
+pub struct NextArgs {
+    /// How far ahead to simulate the schedule, e.g. '12h' or '7d'.
+    #[arg(long = "for", default_value = "24h", value_parser = parse_window)]
+    pub window: Duration,
+
+    /// The maximum number of fire times to print.
+    #[arg(long, default_value_t = 50)]
+    pub limit: usize,
+
+    #[command(flatten)]
+    pub tags: TagArgs,
+}
+
+/// Prints a merged timeline of the upcoming fire times of all enabled jobs.
+///
+/// Each line is a fire time in the configured timezone with the jobs firing at that time, so
+/// jobs firing at once stand out. A summary of the busiest times follows the timeline, to spot
+/// schedule collisions and load spikes before they happen. Only the jobs selected by the
+/// `--tags` and `--exclude-tags` of the command are simulated.
+///
+/// # Arguments
+///
+/// * `args` - The arguments of the `next` command.
+///
+/// # Examples
+///
+/// ```
+/// let args = NextArgs { window: Duration::from_secs(86400), limit: 50, tags: TagArgs { tags: vec![], exclude_tags: vec![] } };
+/// next(args);
+/// ```
+pub fn next(args: NextArgs) {
+    let jobs = get_jobs();
+    let Some(until) = get_until(args.window) else {
+        eprintln!("The window '{}' is too large.", format_duration(args.window));
+        std::process::exit(1);
+    };
+
+    let tag_filter = args.tags.to_filter();
+
+    let mut timeline = BTreeMap::new();
+    let mut fires = 0;
+    let mut truncated = false;
+
+    let all_jobs = jobs.all_jobs();
+    for job in all_jobs.iter().filter(|job| job.enable() && tag_filter.matches(&job.options().tags)) {
+        let remaining = MAX_SIMULATED_FIRES - fires;
+        let runs = get_runs_until(job.schedule(), &jobs.timezone, until, remaining + 1);
+        truncated |= runs.len() > remaining;
+        for run in runs.into_iter().take(remaining) {
+            timeline.entry(run).or_insert_with(Vec::new).push(job.name());
+            fires += 1;
+        }
+    }
+
+    if timeline.is_empty() {
+        println!("No job fires in the next {}.", format_duration(args.window));
+        return;
+    }
+
+    let width = timeline.values().map(|names| names.len()).max().unwrap_or(1).to_string().len();
+    for (time, names) in timeline.iter().take(args.limit) {
+        println!("{}  {:>w$}  {}", time.format("%Y-%m-%d %H:%M:%S"), names.len(), names.join(", "), w = width);
+    }
+    if timeline.len() > args.limit {
+        println!("... {} more fire times, see --limit.", timeline.len() - args.limit);
+    }
+
+    println!();
+    println!("Timezone: {}", jobs.timezone);
+    println!("Fires in the next {}: {}{}", format_duration(args.window), fires,
+             if truncated { format!(" (simulation stopped at {} fires)", MAX_SIMULATED_FIRES) } else { String::new() });
+
+    let collisions = timeline.values().filter(|names| names.len() > 1).count();
+    let (busiest_time, busiest_jobs) = timeline.iter()
+        .max_by_key(|(time, names)| (names.len(), std::cmp::Reverse(*time)))
+        .unwrap();
+    println!("Times with several jobs firing at once: {}", collisions);
+    if busiest_jobs.len() > 1 {
+        println!("Busiest time: {} with {} jobs ({})", busiest_time.format("%Y-%m-%d %H:%M:%S"), busiest_jobs.len(), busiest_jobs.join(", "));
+    }
+}
+
+/// Returns the end of a window starting now, or `None` if it is past the supported dates.
+fn get_until(window: Duration) -> Option<DateTime<Utc>> {
+    chrono::Duration::from_std(window).ok().and_then(|window| Utc::now().checked_add_signed(window))
+}
+
+/// Parses the `--for` window of the command, e.g. `12h` or `7d`.
+fn parse_window(value: &str) -> Result<Duration, String> {
+    let window = parse_duration(value)
+        .filter(|d| !d.is_zero())
+        .ok_or(format!("Invalid duration '{}', expected e.g. '12h' or '7d'.", value))?;
+    match get_until(window) {
+        Some(_) => Ok(window),
+        None => Err(format!("The duration '{}' is too large.", value))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,46 @@
+use clap::Args;
+
+use crate::configure::get_jobs;
+use crate::scheduler::state::set_paused;
+
+#[derive(Debug, Args)]
+pub struct PauseArgs {
+    /// The name of the job.
+    pub job_name: String,
+}
+
+/// Pauses or resumes a job by updating the state file.
+///
+/// A running scheduler reads the state file on every trigger, so the change applies without a
+/// restart.
+///
+/// # Arguments
+///
+/// * `args` - The arguments of the `pause` or `resume` command.
+/// * `paused` - `true` to pause the job, `false` to resume it.
+///
+/// # Returns
+///
+/// `true` if the job exists and the state file was updated.
+///
+/// # Examples
+///
+/// ```
+/// let args = PauseArgs { job_name: "login".to_string() };
+/// let success = pause(args, true);
+/// ```
+pub fn pause(args: PauseArgs, paused: bool) -> bool {
+    let exists = get_jobs().all_jobs().iter().any(|job| job.name() == args.job_name);
+    if !exists {
+        eprintln!("No job named '{}' found in the 'jobs' file.", args.job_name);
+        return false;
+    }
+
+    if let Err(err) = set_paused(&args.job_name, paused) {
+        eprintln!("Failed to update state file: {}", err);
+        return false;
+    }
+
+    println!("Job {}, job name: {}", if paused { "paused" } else { "resumed" }, args.job_name);
+    true
+}
\ No newline at end of file
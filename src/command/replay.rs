@@ -0,0 +1,156 @@
+use clap::Args;
+
+use crate::configure::get_jobs;
+use crate::models::dead_letter_record::DeadLetterRecord;
+use crate::scheduler::cron_scheduler::{build_request, check_graphql_response, get_request_body};
+use crate::scheduler::dead_letter::{read_dead_letters, write_dead_letters};
+use crate::scheduler::http_client::{ClientOptions, get_client};
+use crate::utils::datetime_util::get_local_datetime;
+
+#[derive(Debug, Args)]
+pub struct ReplayArgs {
+    /// The dead-letter file to replay. Defaults to the 'dead_letter_file' of the jobs file.
+    #[arg(long)]
+    pub file: Option<String>,
+
+    /// Only replay the records of the job with this name.
+    #[arg(long)]
+    pub job: Option<String>,
+}
+
+/// Replays the records of the dead-letter file.
+///
+/// Each matching record is sent once with the request snapshot it contains. Records that are
+/// replayed successfully are removed from the file, the others are kept for a later replay.
+///
+/// # Arguments
+///
+/// * `args` - The arguments of the `replay` command.
+///
+/// # Returns
+///
+/// `true` if every replayed record succeeded.
+///
+/// # Examples
+///
+/// ```
+/// let args = ReplayArgs { file: Some("./dead_letter.jsonl".to_string()), job: None };
+/// let success = replay(args).await;
+/// ```
+pub async fn replay(args: ReplayArgs) -> bool {
+    let path = match args.file.or_else(|| get_jobs().dead_letter_file.clone()) {
+        Some(path) => path,
+        None => {
+            eprintln!("No dead-letter file specified. Use '--file' or set 'dead_letter_file' in the 'jobs' file.");
+            return false;
+        }
+    };
+
+    let records = match read_dead_letters(&path) {
+        Ok(records) => records,
+        Err(err) => {
+            eprintln!("Failed to read dead-letter file: {}", err);
+            return false;
+        }
+    };
+
+    let mut remaining = Vec::new();
+    let mut replayed = 0;
+    let mut failed = 0;
+
+    for record in records {
+        if args.job.as_ref().is_some_and(|job| job != &record.job_name) {
+            remaining.push(record);
+            continue;
+        }
+
+        replayed += 1;
+        if replay_record(&record).await {
+            continue;
+        }
+        failed += 1;
+        remaining.push(record);
+    }
+
+    if let Err(err) = write_dead_letters(&path, &remaining) {
+        eprintln!("Failed to update dead-letter file: {}", err);
+        return false;
+    }
+
+    println!("Replayed {} record(s), {} succeeded, {} failed.", replayed, replayed - failed, failed);
+    failed == 0
+}
+
+/// Sends the request snapshot of a dead-letter record once.
+///
+/// # Arguments
+///
+/// * `record` - The record to replay.
+///
+/// # Returns
+///
+/// `true` if the request succeeded with a 2xx status, and without errors for GraphQL requests.
+async fn replay_record(record: &DeadLetterRecord) -> bool {
+    let local_time = get_local_datetime();
+    let prefix = format!("{} {}", record.run_id, local_time);
+
+    let request = match record.request.to_request() {
+        Ok(request) => request,
+        Err(err) => {
+            println!("{} Invalid dead-letter record, job name: {}, error: {}", prefix, record.job_name, err);
+            return false;
+        }
+    };
+
+    // The job may have been renamed or removed since the request failed.
+    let jobs = get_jobs();
+    let user_agent = jobs.http_jobs.iter()
+        .find(|job| job.name == record.job_name)
+        .and_then(|job| job.user_agent.clone())
+        .unwrap_or_else(|| jobs.user_agent.clone());
+
+    let client = match get_client(&ClientOptions::new(record.timeout, None, request.http_version, user_agent)) {
+        Ok(client) => client,
+        Err(err) => {
+            println!("{} Failed to create HTTP client, job name: {}, error: {}", prefix, record.job_name, err);
+            return false;
+        }
+    };
+
+    let body = match get_request_body(&request) {
+        Ok(body) => body,
+        Err(err) => {
+            println!("{} Failed to encode request body, job name: {}, error: {}", prefix, record.job_name, err);
+            return false;
+        }
+    };
+
+    match build_request(&client, &request, body).send().await {
+        Ok(resp) if resp.status().is_success() && request.graphql => {
+            let status = resp.status().as_u16();
+            let body = resp.bytes().await.unwrap_or_default();
+            match check_graphql_response(&body) {
+                Ok(_) => {
+                    println!("{} Replay success, job name: {}, http status: {}", prefix, record.job_name, status);
+                    true
+                }
+                Err(err) => {
+                    println!("{} Replay failed, job name: {}, error: {}", prefix, record.job_name, err);
+                    false
+                }
+            }
+        }
+        Ok(resp) if resp.status().is_success() => {
+            println!("{} Replay success, job name: {}, http status: {}", prefix, record.job_name, resp.status().as_u16());
+            true
+        }
+        Ok(resp) => {
+            println!("{} Replay failed, job name: {}, http status: {}", prefix, record.job_name, resp.status().as_u16());
+            false
+        }
+        Err(err) => {
+            println!("{} Replay failed, job name: {}, error: {}", prefix, record.job_name, err);
+            false
+        }
+    }
+}
\ No newline at end of file
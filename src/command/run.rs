@@ -0,0 +1,58 @@
+use clap::Args;
+
+use crate::configure::get_jobs;
+use crate::scheduler::cron_scheduler::execute_http_job;
+use crate::utils::datetime_util::get_local_datetime_in_timezone;
+use crate::utils::uuid_util::generate_run_id;
+
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    /// The name of the job to run.
+    pub job_name: String,
+}
+
+/// Executes a single job immediately and prints the outcome of the run.
+///
+/// The job may be of any type, see `Jobs::all_jobs`. It is run even if it is disabled, with its
+/// retries and response handling, but without sending notifications, heartbeats, reports,
+/// dead-letter records or archived records.
+///
+/// # Arguments
+///
+/// * `args` - The arguments of the `run` command.
+///
+/// # Returns
+///
+/// `true` if the job exists and the run succeeded.
+///
+/// # Examples
+///
+/// ```
+/// let args = RunArgs { job_name: "login".to_string() };
+/// let success = run(args).await;
+/// ```
+pub async fn run(args: RunArgs) -> bool {
+    let jobs = get_jobs();
+    let job = match jobs.all_jobs().into_iter().find(|job| job.name() == args.job_name) {
+        Some(job) => job,
+        None => {
+            eprintln!("No job named '{}' found in the 'jobs' file.", args.job_name);
+            return false;
+        }
+    };
+
+    let uuid = generate_run_id(job.name());
+    let local_time = get_local_datetime_in_timezone(&jobs.timezone);
+
+    println!("{} {} {} job start, job name: {}", uuid, local_time, job.kind(), job.name());
+    // `Job::execute` of an HTTP job also sends its report and writes its archived and
+    // dead-letter records, see `run_http_job`, so only its request is executed.
+    let outcome = match jobs.http_jobs.iter().find(|http_job| http_job.name == args.job_name) {
+        Some(http_job) => execute_http_job(http_job, &uuid, &local_time, &jobs.timezone, &mut None).await,
+        None => job.clone().execute(uuid.clone(), local_time.clone()).await
+    };
+    println!("{} {} {} job end, job name: {}", uuid, local_time, job.kind(), job.name());
+
+    println!("Result: [{}]", outcome);
+    outcome.success
+}
\ No newline at end of file
@@ -0,0 +1,57 @@
+use std::fs;
+use clap::Args;
+
+use crate::configure::schema::get_jobs_schema;
+
+#[derive(Debug, Args)]
+pub struct SchemaArgs {
+    /// The file to write the schema to. Defaults to stdout.
+    #[arg(long)]
+    pub output: Option<String>,
+}
+
+/// Prints the JSON Schema of the 'jobs' file.
+///
+/// The schema doesn't depend on the 'jobs' file, so the command works without one, e.g. to
+/// generate the schema in CI before validating the jobs file with it.
+///
+/// # Arguments
+///
+/// * `args` - The arguments of the `schema` command.
+///
+/// # Returns
+///
+/// `true` if the schema was printed or written to the output file.
+///
+/// # Examples
+///
+/// ```
+/// let args = SchemaArgs { output: Some("./jobs.schema.json".to_string()) };
+/// let success = schema(args);
+/// ```
+pub fn schema(args: SchemaArgs) -> bool {
+    let schema = match serde_json::to_string_pretty(&get_jobs_schema()) {
+        Ok(schema) => schema,
+        Err(err) => {
+            eprintln!("Failed to serialize the schema: {}", err);
+            return false;
+        }
+    };
+
+    match args.output {
+        Some(output) => match fs::write(&output, schema + "\n") {
+            Ok(_) => {
+                println!("Schema written to {}", output);
+                true
+            }
+            Err(err) => {
+                eprintln!("Failed to write the schema to '{}': {}", output, err);
+                false
+            }
+        },
+        None => {
+            println!("{}", schema);
+            true
+        }
+    }
+}
\ No newline at end of file
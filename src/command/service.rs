@@ -0,0 +1,461 @@
+use std::env;
+use std::error::Error;
+use std::ffi::{c_void, OsStr};
+use std::fs::OpenOptions;
+use std::future::Future;
+use std::io;
+use std::os::windows::ffi::OsStrExt;
+use std::os::windows::io::IntoRawHandle;
+use std::path::{Path, PathBuf};
+use std::ptr;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use clap::{Args, Subcommand};
+use once_cell::sync::{Lazy, OnceCell};
+use tokio::sync::{oneshot, Notify};
+use windows_sys::core::PWSTR;
+use windows_sys::Win32::Foundation::{ERROR_CALL_NOT_IMPLEMENTED, NO_ERROR};
+use windows_sys::Win32::System::Console::{SetStdHandle, STD_ERROR_HANDLE, STD_OUTPUT_HANDLE};
+use windows_sys::Win32::System::Services::{
+    ChangeServiceConfig2W, CloseServiceHandle, ControlService, CreateServiceW, DeleteService, OpenSCManagerW,
+    OpenServiceW, QueryServiceStatus, RegisterServiceCtrlHandlerExW, SetServiceStatus, StartServiceCtrlDispatcherW,
+    StartServiceW, SC_HANDLE, SC_MANAGER_CONNECT, SC_MANAGER_CREATE_SERVICE, SERVICE_ACCEPT_SHUTDOWN,
+    SERVICE_ACCEPT_STOP, SERVICE_AUTO_START, SERVICE_CHANGE_CONFIG, SERVICE_CONFIG_DESCRIPTION,
+    SERVICE_CONTROL_INTERROGATE, SERVICE_CONTROL_SHUTDOWN, SERVICE_CONTROL_STOP, SERVICE_DESCRIPTIONW,
+    SERVICE_ERROR_NORMAL, SERVICE_QUERY_STATUS, SERVICE_RUNNING, SERVICE_START, SERVICE_START_PENDING,
+    SERVICE_STATUS, SERVICE_STATUS_HANDLE, SERVICE_STOP, SERVICE_STOPPED, SERVICE_STOP_PENDING,
+    SERVICE_TABLE_ENTRYW, SERVICE_WIN32_OWN_PROCESS,
+};
+
+use crate::command::Cli;
+use crate::scheduler::cron_scheduler::{stop_cron_scheduler, SHUTDOWN_TIMEOUT};
+
+/// The name of the service if none is given.
+const DEFAULT_SERVICE_NAME: &str = "rjob";
+
+/// The standard access right to delete an object, here the service.
+const DELETE: u32 = 0x0001_0000;
+
+/// The time the service may take to start, reported to the service control manager.
+const START_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The name of the running service, for the service control dispatcher.
+static SERVICE_NAME: OnceCell<Vec<u16>> = OnceCell::new();
+
+/// The handle the status of the running service is reported with, 0 until it is registered.
+static STATUS_HANDLE: AtomicUsize = AtomicUsize::new(0);
+
+/// The check point of the pending start or stop, incremented on each report.
+static CHECK_POINT: AtomicU32 = AtomicU32::new(0);
+
+/// Tells the scheduler that the service control manager started the service.
+static STARTED: Lazy<Mutex<Option<oneshot::Sender<()>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Notified when the service control manager asks the service to stop.
+static STOP: Lazy<Notify> = Lazy::new(Notify::new);
+
+#[derive(Debug, Args)]
+pub struct ServiceArgs {
+    #[command(subcommand)]
+    pub action: ServiceAction,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ServiceAction {
+    /// Installs rjob as a service started with Windows, running the jobs of the current directory.
+    Install {
+        /// The name of the service.
+        #[arg(long, default_value = DEFAULT_SERVICE_NAME)]
+        name: String,
+
+        /// The file the service writes its logs to, relative to the current directory.
+        #[arg(long, default_value = "rjob.log")]
+        log_file: PathBuf,
+    },
+
+    /// Removes the service, once it is stopped.
+    Uninstall {
+        /// The name of the service.
+        #[arg(long, default_value = DEFAULT_SERVICE_NAME)]
+        name: String,
+    },
+
+    /// Starts the service and waits until the jobs are scheduled.
+    Start {
+        /// The name of the service.
+        #[arg(long, default_value = DEFAULT_SERVICE_NAME)]
+        name: String,
+    },
+
+    /// Stops the service and waits until the runs in progress have ended.
+    Stop {
+        /// The name of the service.
+        #[arg(long, default_value = DEFAULT_SERVICE_NAME)]
+        name: String,
+    },
+
+    /// Runs the scheduler as the service, started by the service control manager.
+    #[command(hide = true)]
+    Run {
+        /// The name of the service.
+        #[arg(long, default_value = DEFAULT_SERVICE_NAME)]
+        name: String,
+
+        /// The directory of the 'jobs' file.
+        #[arg(long)]
+        dir: PathBuf,
+
+        /// The file the logs are written to.
+        #[arg(long)]
+        log_file: PathBuf,
+    },
+}
+
+/// A handle of the service control manager or of a service, closed when dropped.
+struct ServiceHandle(SC_HANDLE);
+
+impl Drop for ServiceHandle {
+    fn drop(&mut self) {
+        unsafe { CloseServiceHandle(self.0) };
+    }
+}
+
+/// Installs, removes, starts, stops or runs rjob as a Windows service.
+///
+/// The service is installed with the command line of the scheduler, i.e. `--config`,
+/// `--dry-run`, `--run-log` and the tags of the command, and the current directory. Windows starts services
+/// in its system directory, so the service changes to that directory before it reads the
+/// 'jobs' file. A service has no console, its logs are written to the log file instead.
+/// Stopping the service, or shutting down Windows, stops the scheduler cleanly, see
+/// `stop_cron_scheduler`.
+///
+/// # Arguments
+///
+/// * `args` - The arguments of the `service` command.
+/// * `cli` - The command line, whose scheduler options are installed with the service.
+/// * `start` - Starts the scheduler, run when the service is started.
+///
+/// # Returns
+///
+/// `true` if the action succeeded, or the service ran and stopped.
+///
+/// # Examples
+///
+/// ```
+/// let args = ServiceArgs { action: ServiceAction::Start { name: "rjob".to_string() } };
+/// let success = service(args, &cli, start_scheduler(cli.dry_run, cli.tags.to_filter(), cli.run_log.as_deref())).await;
+/// ```
+pub async fn service<F: Future<Output = ()>>(args: ServiceArgs, cli: &Cli, start: F) -> bool {
+    let result = match args.action {
+        ServiceAction::Install { name, log_file } => install_service(&name, cli, &log_file),
+        ServiceAction::Uninstall { name } => uninstall_service(&name),
+        ServiceAction::Start { name } => start_service(&name),
+        ServiceAction::Stop { name } => stop_service(&name),
+        ServiceAction::Run { name, dir, log_file } => run_service(&name, &dir, &log_file, start).await
+    };
+
+    match result {
+        Ok(()) => true,
+        Err(err) => {
+            eprintln!("{}", err);
+            false
+        }
+    }
+}
+
+/// Installs the service, started automatically with Windows.
+fn install_service(name: &str, cli: &Cli, log_file: &Path) -> Result<(), Box<dyn Error>> {
+    let dir = env::current_dir()?;
+    let mut arguments = vec![env::current_exe()?.to_string_lossy().into_owned()];
+    if let Some(config) = &cli.config {
+        arguments.extend(["--config".to_string(), config.clone()]);
+    }
+    if cli.dry_run {
+        arguments.push("--dry-run".to_string());
+    }
+    if !cli.tags.tags.is_empty() {
+        arguments.extend(["--tags".to_string(), cli.tags.tags.join(",")]);
+    }
+    if !cli.tags.exclude_tags.is_empty() {
+        arguments.extend(["--exclude-tags".to_string(), cli.tags.exclude_tags.join(",")]);
+    }
+    if let Some(run_log) = &cli.run_log {
+        arguments.extend(["--run-log".to_string(), dir.join(run_log).to_string_lossy().into_owned()]);
+    }
+    arguments.extend(["service".to_string(), "run".to_string(), "--name".to_string(), name.to_string()]);
+    arguments.extend(["--dir".to_string(), dir.to_string_lossy().into_owned()]);
+    arguments.extend(["--log-file".to_string(), dir.join(log_file).to_string_lossy().into_owned()]);
+    let command_line = arguments.iter().map(|argument| quote_argument(argument)).collect::<Vec<_>>().join(" ");
+
+    let manager = open_manager(SC_MANAGER_CREATE_SERVICE)?;
+    let service_name = to_wide(name);
+    let binary_path = to_wide(&command_line);
+    let handle = unsafe {
+        CreateServiceW(manager.0, service_name.as_ptr(), service_name.as_ptr(), SERVICE_CHANGE_CONFIG,
+                       SERVICE_WIN32_OWN_PROCESS, SERVICE_AUTO_START, SERVICE_ERROR_NORMAL, binary_path.as_ptr(),
+                       ptr::null(), ptr::null_mut(), ptr::null(), ptr::null(), ptr::null())
+    };
+    if handle.is_null() {
+        return Err(format!("Failed to install the service '{}': {}", name, io::Error::last_os_error()).into());
+    }
+    let service = ServiceHandle(handle);
+
+    let mut description = to_wide(env!("CARGO_PKG_DESCRIPTION"));
+    let description = SERVICE_DESCRIPTIONW { lpDescription: description.as_mut_ptr() };
+    unsafe { ChangeServiceConfig2W(service.0, SERVICE_CONFIG_DESCRIPTION, &description as *const _ as *const c_void) };
+
+    println!("Service installed, name: {}, command: {}", name, command_line);
+    Ok(())
+}
+
+/// Removes the service. A running service is removed once it stops.
+fn uninstall_service(name: &str) -> Result<(), Box<dyn Error>> {
+    let service = open_service(name, DELETE)?;
+    if unsafe { DeleteService(service.0) } == 0 {
+        return Err(format!("Failed to remove the service '{}': {}", name, io::Error::last_os_error()).into());
+    }
+
+    println!("Service removed, name: {}", name);
+    Ok(())
+}
+
+/// Starts the service and waits until it runs, i.e. the jobs are scheduled.
+fn start_service(name: &str) -> Result<(), Box<dyn Error>> {
+    let service = open_service(name, SERVICE_START | SERVICE_QUERY_STATUS)?;
+    if unsafe { StartServiceW(service.0, 0, ptr::null()) } == 0 {
+        return Err(format!("Failed to start the service '{}': {}", name, io::Error::last_os_error()).into());
+    }
+
+    match wait_while_pending(&service, SERVICE_START_PENDING, START_TIMEOUT)? {
+        SERVICE_RUNNING => {
+            println!("Service started, name: {}", name);
+            Ok(())
+        }
+        _ => Err(format!("The service '{}' failed to start, see its log file.", name).into())
+    }
+}
+
+/// Stops the service and waits until the runs in progress have ended.
+fn stop_service(name: &str) -> Result<(), Box<dyn Error>> {
+    let service = open_service(name, SERVICE_STOP | SERVICE_QUERY_STATUS)?;
+    let mut status = SERVICE_STATUS::default();
+    if unsafe { ControlService(service.0, SERVICE_CONTROL_STOP, &mut status) } == 0 {
+        return Err(format!("Failed to stop the service '{}': {}", name, io::Error::last_os_error()).into());
+    }
+
+    match wait_while_pending(&service, SERVICE_STOP_PENDING, SHUTDOWN_TIMEOUT + START_TIMEOUT)? {
+        SERVICE_STOPPED => {
+            println!("Service stopped, name: {}", name);
+            Ok(())
+        }
+        _ => Err(format!("The service '{}' did not stop in time.", name).into())
+    }
+}
+
+/// Runs the scheduler as the service, until the service control manager stops it.
+///
+/// The service control dispatcher blocks its thread while the service runs, so it runs on a
+/// thread of its own, and the scheduler on the runtime. The dispatcher fails if rjob was not
+/// started by the service control manager.
+async fn run_service<F: Future<Output = ()>>(name: &str, dir: &Path, log_file: &Path, start: F) -> Result<(), Box<dyn Error>> {
+    env::set_current_dir(dir).map_err(|e| format!("Failed to change to the directory '{}': {}", dir.display(), e))?;
+    redirect_output(log_file)?;
+
+    let _ = SERVICE_NAME.set(to_wide(name));
+    let (started, started_receiver) = oneshot::channel();
+    *STARTED.lock().unwrap() = Some(started);
+    let (done, done_receiver) = oneshot::channel();
+    std::thread::spawn(move || {
+        let _ = done.send(start_dispatcher());
+    });
+
+    if started_receiver.await.is_err() {
+        return match done_receiver.await {
+            Ok(Err(e)) => Err(format!("Failed to run the service '{}', it is started by Windows: {}", name, e).into()),
+            _ => Err(format!("Failed to run the service '{}'.", name).into())
+        };
+    }
+
+    start.await;
+    set_service_status(SERVICE_RUNNING, Duration::ZERO);
+    println!("Service running, name: {}", name);
+
+    STOP.notified().await;
+    set_service_status(SERVICE_STOP_PENDING, SHUTDOWN_TIMEOUT + START_TIMEOUT);
+    stop_cron_scheduler().await;
+    set_service_status(SERVICE_STOPPED, Duration::ZERO);
+
+    match done_receiver.await {
+        Ok(result) => result.map_err(|e| e.into()),
+        Err(_) => Err(format!("The dispatcher of the service '{}' ended unexpectedly.", name).into())
+    }
+}
+
+/// Connects to the service control manager and runs the control dispatcher until the service
+/// stopped.
+fn start_dispatcher() -> io::Result<()> {
+    let mut name = SERVICE_NAME.get().cloned().unwrap_or_default();
+    let table = [
+        SERVICE_TABLE_ENTRYW { lpServiceName: name.as_mut_ptr(), lpServiceProc: Some(service_main) },
+        SERVICE_TABLE_ENTRYW::default(),
+    ];
+
+    let result = unsafe { StartServiceCtrlDispatcherW(table.as_ptr()) };
+    // Without a service started, the scheduler is told by dropping the sender.
+    STARTED.lock().unwrap().take();
+    if result == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// The entry point of the service, called by the control dispatcher when the service starts.
+///
+/// Registers the control handler and hands over to the scheduler, which reports the status of
+/// the service from then on.
+unsafe extern "system" fn service_main(_argc: u32, _argv: *mut PWSTR) {
+    let Some(name) = SERVICE_NAME.get() else {
+        return;
+    };
+    let handle = RegisterServiceCtrlHandlerExW(name.as_ptr(), Some(control_handler), ptr::null());
+    if handle.is_null() {
+        println!("Failed to register the service control handler, error: {}", io::Error::last_os_error());
+        STARTED.lock().unwrap().take();
+        return;
+    }
+
+    STATUS_HANDLE.store(handle as usize, Ordering::Relaxed);
+    set_service_status(SERVICE_START_PENDING, START_TIMEOUT);
+    if let Some(started) = STARTED.lock().unwrap().take() {
+        let _ = started.send(());
+    }
+}
+
+/// Handles the requests of the service control manager, `stop` and the shutdown of Windows.
+unsafe extern "system" fn control_handler(control: u32, _event_type: u32, _event_data: *mut c_void, _context: *mut c_void) -> u32 {
+    match control {
+        SERVICE_CONTROL_STOP | SERVICE_CONTROL_SHUTDOWN => {
+            println!("Service stop requested");
+            STOP.notify_one();
+            NO_ERROR
+        }
+        SERVICE_CONTROL_INTERROGATE => NO_ERROR,
+        _ => ERROR_CALL_NOT_IMPLEMENTED
+    }
+}
+
+/// Reports the status of the service to the service control manager.
+///
+/// A running service accepts the `stop` and `shutdown` requests. A pending start or stop is
+/// expected to progress within the wait hint.
+fn set_service_status(state: u32, wait_hint: Duration) {
+    let handle = STATUS_HANDLE.load(Ordering::Relaxed);
+    if handle == 0 {
+        return;
+    }
+    let pending = state == SERVICE_START_PENDING || state == SERVICE_STOP_PENDING;
+
+    let status = SERVICE_STATUS {
+        dwServiceType: SERVICE_WIN32_OWN_PROCESS,
+        dwCurrentState: state,
+        dwControlsAccepted: if state == SERVICE_RUNNING { SERVICE_ACCEPT_STOP | SERVICE_ACCEPT_SHUTDOWN } else { 0 },
+        dwWin32ExitCode: NO_ERROR,
+        dwServiceSpecificExitCode: 0,
+        dwCheckPoint: if pending { CHECK_POINT.fetch_add(1, Ordering::Relaxed) + 1 } else { 0 },
+        dwWaitHint: wait_hint.as_millis().try_into().unwrap_or(u32::MAX),
+    };
+    if unsafe { SetServiceStatus(handle as SERVICE_STATUS_HANDLE, &status) } == 0 {
+        println!("Failed to report the service status, state: {}, error: {}", state, io::Error::last_os_error());
+    }
+}
+
+/// Waits while the service is in a pending state, at most for a timeout.
+///
+/// # Returns
+///
+/// The state of the service when it left the pending state or the timeout elapsed.
+fn wait_while_pending(service: &ServiceHandle, pending: u32, timeout: Duration) -> Result<u32, Box<dyn Error>> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        let mut status = SERVICE_STATUS::default();
+        if unsafe { QueryServiceStatus(service.0, &mut status) } == 0 {
+            return Err(format!("Failed to query the service status: {}", io::Error::last_os_error()).into());
+        }
+        if status.dwCurrentState != pending || Instant::now() >= deadline {
+            return Ok(status.dwCurrentState);
+        }
+        std::thread::sleep(Duration::from_millis(500));
+    }
+}
+
+/// Opens the service control manager of the local computer.
+fn open_manager(access: u32) -> Result<ServiceHandle, Box<dyn Error>> {
+    let handle = unsafe { OpenSCManagerW(ptr::null(), ptr::null(), access) };
+    if handle.is_null() {
+        return Err(format!("Failed to open the service control manager: {}", io::Error::last_os_error()).into());
+    }
+    Ok(ServiceHandle(handle))
+}
+
+/// Opens an installed service.
+fn open_service(name: &str, access: u32) -> Result<ServiceHandle, Box<dyn Error>> {
+    let manager = open_manager(SC_MANAGER_CONNECT)?;
+    let service_name = to_wide(name);
+    let handle = unsafe { OpenServiceW(manager.0, service_name.as_ptr(), access) };
+    if handle.is_null() {
+        return Err(format!("Failed to open the service '{}': {}", name, io::Error::last_os_error()).into());
+    }
+    Ok(ServiceHandle(handle))
+}
+
+/// Writes the standard output and error of the service to a log file, appending to it.
+fn redirect_output(log_file: &Path) -> Result<(), Box<dyn Error>> {
+    let file = OpenOptions::new().create(true).append(true).open(log_file)
+        .map_err(|e| format!("Failed to open the log file '{}': {}", log_file.display(), e))?;
+
+    // The handle stays open for the lifetime of the process.
+    let handle = file.into_raw_handle();
+    unsafe {
+        SetStdHandle(STD_OUTPUT_HANDLE, handle);
+        SetStdHandle(STD_ERROR_HANDLE, handle);
+    }
+    Ok(())
+}
+
+/// Converts a string to a null-terminated wide string for the Windows API.
+fn to_wide(value: impl AsRef<OsStr>) -> Vec<u16> {
+    value.as_ref().encode_wide().chain(std::iter::once(0)).collect()
+}
+
+/// Quotes an argument of a command line, as parsed by the C runtime of Windows.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(quote_argument("run"), "run");
+/// assert_eq!(quote_argument("C:\\Program Files\\rjob\\"), "\"C:\\Program Files\\rjob\\\\\"");
+/// ```
+fn quote_argument(argument: &str) -> String {
+    if !argument.is_empty() && !argument.contains([' ', '\t', '"']) {
+        return argument.to_string();
+    }
+
+    let mut quoted = String::from('"');
+    let mut backslashes = 0;
+    for c in argument.chars() {
+        if c == '\\' {
+            backslashes += 1;
+            continue;
+        }
+        // Backslashes are only escaped before a quote.
+        let escaped = if c == '"' { backslashes * 2 + 1 } else { backslashes };
+        quoted.push_str(&"\\".repeat(escaped));
+        quoted.push(c);
+        backslashes = 0;
+    }
+    quoted.push_str(&"\\".repeat(backslashes * 2));
+    quoted.push('"');
+    quoted
+}
\ No newline at end of file
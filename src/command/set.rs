@@ -0,0 +1,44 @@
+use clap::Args;
+
+use crate::scheduler::state::set_value;
+
+#[derive(Debug, Args)]
+pub struct SetArgs {
+    /// The name of the value, read by the 'when' conditions as 'kv.NAME'.
+    pub name: String,
+
+    /// The value to store, the value is removed without it.
+    pub value: Option<String>,
+}
+
+/// Stores or removes a value read by the `when` conditions of the jobs, in the state file.
+///
+/// A running scheduler reads the state file when it evaluates a condition, so the change
+/// applies to the next trigger without a restart.
+///
+/// # Arguments
+///
+/// * `args` - The arguments of the `set` command.
+///
+/// # Returns
+///
+/// `true` if the state file was updated.
+///
+/// # Examples
+///
+/// ```
+/// let args = SetArgs { name: "maintenance".to_string(), value: Some("on".to_string()) };
+/// let success = set(args);
+/// ```
+pub fn set(args: SetArgs) -> bool {
+    if let Err(err) = set_value(&args.name, args.value.as_deref()) {
+        eprintln!("Failed to update state file: {}", err);
+        return false;
+    }
+
+    match args.value {
+        Some(value) => println!("Value set, name: {}, value: {}", args.name, value),
+        None => println!("Value removed, name: {}", args.name),
+    }
+    true
+}
\ No newline at end of file
@@ -0,0 +1,104 @@
+use std::error::Error;
+use serde_json::Value;
+
+use crate::configure::get_job_array;
+use crate::configure::job_options::get_job_options;
+use crate::configure::schedules::get_job_schedule;
+use crate::models::on_invalid_job::OnInvalidJob;
+use crate::models::backup_job::BackupJob;
+
+/// The default archive name of a backup job.
+const DEFAULT_FILE_NAME: &str = "{{job_name}}-{{now}}.tar.gz";
+
+/// Parses the optional 'backup_jobs' array of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+/// * `on_invalid_job` - Whether to fail or skip when a job is invalid.
+///
+/// # Returns
+///
+/// The list of backup jobs, empty if the field is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'backup_jobs' field is not an array.
+/// * The 'name', 'cron', 'source' or 'target_dir' field is missing or not a string for any job.
+/// * The 'cron' field is not a valid cron expression for any job.
+/// * The 'file_name' field is not a string or contains a path separator for any job.
+/// * The 'keep' field is not a positive number for any job.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({
+///     "backup_jobs": [{
+///         "name": "uploads",
+///         "cron": "0 0 1 * * ?",
+///         "source": "/var/www/uploads",
+///         "target_dir": "/backup/uploads",
+///         "keep": 7
+///     }]
+/// });
+/// let jobs = get_backup_jobs(&value, OnInvalidJob::Fail)?;
+/// ```
+pub fn get_backup_jobs(value: &Value, on_invalid_job: OnInvalidJob) -> Result<Vec<BackupJob>, Box<dyn Error>> {
+    get_job_array(value, "backup_jobs", on_invalid_job, get_backup_job)
+}
+
+/// Parses a single job of the 'backup_jobs' array, see `get_backup_jobs`.
+fn get_backup_job(it: &Value) -> Result<BackupJob, Box<dyn Error>> {
+    let name = it.get("name")
+        .and_then(|n| n.as_str())
+        .ok_or("The 'name' field is missing or not a string.")?
+        .to_string();
+
+    let enable = it.get("enable")
+        .and_then(|e| e.as_bool())
+        .unwrap_or(true);
+
+    let schedule = get_job_schedule(it, &name)?;
+
+    let source = it.get("source")
+        .and_then(|s| s.as_str())
+        .ok_or("The 'source' field is required and must be a string.")?
+        .to_string();
+
+    let target_dir = it.get("target_dir")
+        .and_then(|t| t.as_str())
+        .ok_or("The 'target_dir' field is required and must be a string.")?
+        .to_string();
+
+    let file_name = match it.get("file_name") {
+        Some(f) => f.as_str().ok_or("The 'file_name' field must be a string.")?.to_string(),
+        None => DEFAULT_FILE_NAME.to_string()
+    };
+    if file_name.contains('/') || file_name.contains('\\') {
+        return Err(format!("The 'file_name' of the backup job '{}' must not contain a path separator.", name).into());
+    }
+
+    let keep = match it.get("keep") {
+        Some(k) => Some(k.as_u64()
+            .filter(|k| *k > 0)
+            .ok_or(format!("The 'keep' of the backup job '{}' must be a positive number.", name))? as usize),
+        None => None
+    };
+
+    let options = get_job_options(it)?;
+
+    Ok(BackupJob {
+        name,
+        enable,
+        schedule,
+        source,
+        target_dir,
+        file_name,
+        keep,
+        options,
+    })
+}
\ No newline at end of file
@@ -0,0 +1,113 @@
+use std::error::Error;
+use std::str::FromStr;
+use chrono::{NaiveDateTime, Weekday};
+use serde_json::Value;
+use crate::configure::schedules::parse_time_of_day;
+use crate::models::blackout::{Blackout, BlackoutWindow};
+
+/// Parses the optional `blackouts` of the configuration or of a job.
+///
+/// A blackout is an object with a `start` and an `end`, both local times in the configured
+/// timezone, and an optional `name` shown in the logs:
+///
+/// * With date-times (`YYYY-MM-DD HH:MM[:SS]`), the blackout happens once, e.g. for a planned
+///   deployment.
+/// * With times of day (`HH:MM[:SS]`), the blackout recurs every day, or only on the
+///   `weekdays` given, e.g. `["SAT", "SUN"]`. An `end` before the `start` spans midnight.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value of the configuration or of the job.
+/// * `owner` - The owner of the blackouts, used in the errors, e.g. `job 'report'`.
+///
+/// # Returns
+///
+/// The blackouts, empty if the field is missing.
+///
+/// # Errors
+///
+/// Returns an error if the field is not an array, or a blackout has a missing or invalid
+/// `start`, `end` or `weekdays`, or ends when or before it starts.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "blackouts": [
+///     { "name": "deploy", "start": "2026-11-01 22:00", "end": "2026-11-02 02:00" },
+///     { "start": "02:00", "end": "04:00", "weekdays": ["SUN"] }
+/// ] });
+/// let blackouts = get_blackouts(&value, "the configuration")?;
+/// assert_eq!(blackouts.len(), 2);
+/// ```
+pub fn get_blackouts(value: &Value, owner: &str) -> Result<Vec<Blackout>, Box<dyn Error>> {
+    let blackouts = match value.get("blackouts") {
+        Some(b) => b.as_array().ok_or(format!("The 'blackouts' field of {} must be an array.", owner))?,
+        None => return Ok(vec![])
+    };
+
+    blackouts.iter()
+        .map(|blackout| get_blackout(blackout).map_err(|e| format!("Invalid blackout of {}: {}", owner, e).into()))
+        .collect()
+}
+
+/// Parses a blackout, see `get_blackouts`.
+fn get_blackout(value: &Value) -> Result<Blackout, Box<dyn Error>> {
+    let name = value.get("name")
+        .map(|n| n.as_str().map(|n| n.to_string()).ok_or("The 'name' field must be a string."))
+        .transpose()?;
+    let start = value.get("start")
+        .and_then(|s| s.as_str())
+        .ok_or("The 'start' field is required and must be a string.")?;
+    let end = value.get("end")
+        .and_then(|e| e.as_str())
+        .ok_or("The 'end' field is required and must be a string.")?;
+
+    let window = match (parse_date_time(start), parse_date_time(end)) {
+        (Some(start), Some(end)) => {
+            if value.get("weekdays").is_some() {
+                return Err("The 'weekdays' field only applies to blackouts with times of day.".into());
+            }
+            if end <= start {
+                return Err(format!("The blackout ends at {} before it starts at {}.", end, start).into());
+            }
+            BlackoutWindow::Once { start, end }
+        }
+        (None, None) => {
+            let invalid = |time: &str| format!("Invalid time '{}', expected e.g. '02:00' or '2026-11-01 22:00'.", time);
+            let start = parse_time_of_day(start).ok_or_else(|| invalid(start))?;
+            let end = parse_time_of_day(end).ok_or_else(|| invalid(end))?;
+            if start == end {
+                return Err("The blackout starts and ends at the same time.".into());
+            }
+            BlackoutWindow::Recurring { start, end, weekdays: get_weekdays(value)? }
+        }
+        _ => return Err(format!("The 'start' '{}' and the 'end' '{}' must both be date-times or both be times of day.", start, end).into()),
+    };
+
+    Ok(Blackout { name, window })
+}
+
+/// Parses the optional `weekdays` of a recurring blackout, all days if missing.
+fn get_weekdays(value: &Value) -> Result<Vec<Weekday>, Box<dyn Error>> {
+    let Some(weekdays) = value.get("weekdays") else {
+        return Ok(vec![]);
+    };
+
+    weekdays.as_array()
+        .ok_or("The 'weekdays' field must be an array.")?
+        .iter()
+        .map(|day| day.as_str()
+            .and_then(|d| Weekday::from_str(d).ok())
+            .ok_or(format!("Invalid weekday {}, expected e.g. 'MON' or 'Sunday'.", day).into()))
+        .collect()
+}
+
+/// Parses a local date-time, `YYYY-MM-DD HH:MM[:SS]`, with a space or a `T`.
+fn parse_date_time(value: &str) -> Option<NaiveDateTime> {
+    let value = value.replacen('T', " ", 1);
+    NaiveDateTime::parse_from_str(&value, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(&value, "%Y-%m-%d %H:%M"))
+        .ok()
+}
\ No newline at end of file
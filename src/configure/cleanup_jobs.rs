@@ -0,0 +1,110 @@
+use std::error::Error;
+use glob::Pattern;
+use serde_json::Value;
+
+use crate::configure::get_job_array;
+use crate::configure::job_options::get_job_options;
+use crate::configure::schedules::get_job_schedule;
+use crate::models::on_invalid_job::OnInvalidJob;
+use crate::models::cleanup_job::{CleanupAction, CleanupJob};
+
+/// Parses the optional 'cleanup_jobs' array of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+/// * `on_invalid_job` - Whether to fail or skip when a job is invalid.
+///
+/// # Returns
+///
+/// The list of cleanup jobs, empty if the field is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'cleanup_jobs' field is not an array.
+/// * The 'name', 'cron' or 'directory' field is missing or not a string for any job.
+/// * The 'cron' field is not a valid cron expression for any job.
+/// * The 'pattern' field is not a valid glob pattern for any job.
+/// * The 'older_than_days' field is missing or not a number for any job.
+/// * The 'action' field is not 'delete' or 'archive', or 'archive_dir' is missing for 'archive'.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({
+///     "cleanup_jobs": [{
+///         "name": "tmp_cleanup",
+///         "cron": "0 0 3 * * ?",
+///         "directory": "/var/tmp/exports",
+///         "pattern": "*.csv",
+///         "older_than_days": 7
+///     }]
+/// });
+/// let jobs = get_cleanup_jobs(&value, OnInvalidJob::Fail)?;
+/// ```
+pub fn get_cleanup_jobs(value: &Value, on_invalid_job: OnInvalidJob) -> Result<Vec<CleanupJob>, Box<dyn Error>> {
+    get_job_array(value, "cleanup_jobs", on_invalid_job, get_cleanup_job)
+}
+
+/// Parses a single job of the 'cleanup_jobs' array, see `get_cleanup_jobs`.
+fn get_cleanup_job(it: &Value) -> Result<CleanupJob, Box<dyn Error>> {
+    let name = it.get("name")
+        .and_then(|n| n.as_str())
+        .ok_or("The 'name' field is missing or not a string.")?
+        .to_string();
+
+    let enable = it.get("enable")
+        .and_then(|e| e.as_bool())
+        .unwrap_or(true);
+
+    let schedule = get_job_schedule(it, &name)?;
+
+    let directory = it.get("directory")
+        .and_then(|d| d.as_str())
+        .ok_or("The 'directory' field is required and must be a string.")?
+        .to_string();
+
+    let pattern = it.get("pattern")
+        .and_then(|p| p.as_str())
+        .unwrap_or("*")
+        .to_string();
+    Pattern::new(&pattern)
+        .map_err(|e| format!("Invalid pattern '{}' of job '{}': {}", pattern, name, e))?;
+
+    let recursive = it.get("recursive")
+        .and_then(|r| r.as_bool())
+        .unwrap_or(false);
+
+    let older_than_days = it.get("older_than_days")
+        .and_then(|o| o.as_u64())
+        .ok_or("The 'older_than_days' field is required and must be a number.")?;
+
+    let action = match it.get("action").and_then(|a| a.as_str()) {
+        None | Some("delete") => CleanupAction::Delete,
+        Some("archive") => {
+            let archive_dir = it.get("archive_dir")
+                .and_then(|a| a.as_str())
+                .ok_or(format!("The 'archive_dir' field is required for the 'archive' action of job '{}'.", name))?;
+            CleanupAction::Archive(archive_dir.to_string())
+        }
+        Some(_) => return Err(format!("The 'action' of the cleanup job '{}' must be 'delete' or 'archive'.", name).into())
+    };
+
+    let options = get_job_options(it)?;
+
+    Ok(CleanupJob {
+        name,
+        enable,
+        schedule,
+        directory,
+        pattern,
+        recursive,
+        older_than_days,
+        action,
+        options,
+    })
+}
\ No newline at end of file
@@ -0,0 +1,257 @@
+use std::env;
+use std::error::Error;
+use std::sync::RwLock;
+use std::time::Duration;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use reqwest::Client;
+use serde_json::{json, Value};
+
+use crate::configure::FileContent;
+use crate::configure::reload::reload_configuration;
+use crate::models::config_source::{ConfigBackend, ConfigSource};
+
+/// How long the backend holds a watch request without a change, before it is sent again.
+const WATCH_WAIT: Duration = Duration::from_secs(300);
+
+/// The timeout of a request reading the document.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The delay before watching again after the backend could not be reached.
+const WATCH_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// The environment variable holding the ACL token of Consul, as for the Consul CLI.
+const CONSUL_TOKEN_VARIABLE: &str = "CONSUL_HTTP_TOKEN";
+
+/// The jobs document read from the configuration source, before the configuration is parsed.
+///
+/// It is replaced by a changed document before the configuration is reloaded.
+static DOCUMENT: RwLock<Option<SourceDocument>> = RwLock::new(None);
+
+/// The jobs document of a configuration source.
+#[derive(Debug, Clone)]
+pub struct SourceDocument {
+    pub source: ConfigSource,
+    pub content: String,
+    /// The version of the key the document was read at, the `X-Consul-Index` of Consul or the
+    /// revision of etcd.
+    version: u64,
+}
+
+/// Reads the jobs document from a Consul or etcd key, instead of the 'jobs' file.
+///
+/// Must be called before the configuration is first used, see `get_jobs`, since the document
+/// is read asynchronously and the configuration is parsed on first use.
+///
+/// # Arguments
+///
+/// * `url` - The source, e.g. `consul://127.0.0.1:8500/rjob/jobs.yaml`, see `ConfigSource::parse`.
+///
+/// # Errors
+///
+/// Returns an error if the URL is invalid, the backend cannot be reached, or the key doesn't
+/// exist.
+///
+/// # Examples
+///
+/// ```
+/// load_config_source("etcd://127.0.0.1:2379/rjob/jobs.json").await?;
+/// println!("Jobs: {}", get_jobs().http_jobs.len());
+/// ```
+pub async fn load_config_source(url: &str) -> Result<(), Box<dyn Error>> {
+    let source = ConfigSource::parse(url)?;
+    let (content, version) = read_document(&build_source_client()?, &source, None).await?;
+    println!("Configuration read, source: {}", source);
+
+    let mut document = DOCUMENT.write().unwrap();
+    if document.is_some() {
+        return Err("The configuration source is already loaded.".into());
+    }
+    *document = Some(SourceDocument { source, content, version });
+    Ok(())
+}
+
+/// Returns the jobs document read by `load_config_source`, or `None` if the 'jobs' file is used.
+///
+/// After a reload, the document is the changed one the configuration was reloaded from.
+pub fn get_source_document() -> Option<SourceDocument> {
+    DOCUMENT.read().unwrap().clone()
+}
+
+/// Replaces the jobs document of the configuration source, before the configuration is
+/// reloaded from it.
+///
+/// # Arguments
+///
+/// * `document` - The changed document.
+pub fn set_source_document(document: SourceDocument) {
+    *DOCUMENT.write().unwrap() = Some(document);
+}
+
+/// Builds the HTTP client of the API of the configuration source.
+///
+/// The client uses rustls, like the clients of the jobs, see `build_client`.
+///
+/// # Errors
+///
+/// Returns an error if the client cannot be built.
+fn build_source_client() -> Result<Client, reqwest::Error> {
+    Client::builder().use_rustls_tls().build()
+}
+
+/// Watches the key of the configuration source in the background, and reloads the scheduler
+/// when the document changes, see `watch_configuration`.
+///
+/// The key is watched with a blocking query on Consul and a watch on etcd, so a change is seen
+/// right away. A changed document reloads the scheduler, see `reload_configuration`: an invalid
+/// document is logged and ignored, the scheduler keeps running with the current one. Does
+/// nothing without a configuration source.
+///
+/// # Examples
+///
+/// ```
+/// start_cron_scheduler(false, TagFilter::default()).await;
+/// watch_config_source();
+/// ```
+pub fn watch_config_source() {
+    let Some(document) = get_source_document() else {
+        return;
+    };
+
+    tokio::spawn(async move {
+        let source = &document.source;
+        let client = match build_source_client() {
+            Ok(client) => client,
+            Err(e) => {
+                println!("Failed to watch the configuration, source: {}, error: {}", source, e);
+                return;
+            }
+        };
+        let mut content = document.content.clone();
+        let mut version = document.version;
+
+        loop {
+            let read = read_document(&client, source, Some(version)).await
+                .map_err(|e| println!("Failed to watch the configuration, source: {}, error: {}", source, e));
+            let Ok((new_content, new_version)) = read else {
+                tokio::time::sleep(WATCH_RETRY_DELAY).await;
+                continue;
+            };
+            // The index of Consul may go backwards, e.g. after a restore, the watch restarts then.
+            version = if new_version < version { 0 } else { new_version };
+            if new_content == content {
+                continue;
+            }
+            content = new_content;
+
+            println!("Configuration changed, source: {}", source);
+            let changed = SourceDocument { source: source.clone(), content: content.clone(), version };
+            reload_configuration(FileContent::from(content.clone(), &source.key), Some(changed)).await;
+        }
+    });
+}
+
+/// Reads the document of the key, once it changed since `version` if a version is given.
+///
+/// # Returns
+///
+/// The document and the version of the key it was read at.
+async fn read_document(client: &Client, source: &ConfigSource, version: Option<u64>) -> Result<(String, u64), Box<dyn Error>> {
+    match source.backend {
+        ConfigBackend::Consul => read_consul_key(client, source, version).await,
+        ConfigBackend::Etcd => {
+            if let Some(version) = version {
+                watch_etcd_key(client, source, version).await?;
+            }
+            read_etcd_key(client, source).await
+        }
+    }
+}
+
+/// Reads a key of Consul, with a blocking query waiting for a change after the index `version`.
+async fn read_consul_key(client: &Client, source: &ConfigSource, version: Option<u64>) -> Result<(String, u64), Box<dyn Error>> {
+    let mut url = source.api_url.join(&format!("v1/kv/{}", source.key))?;
+    url.query_pairs_mut().append_pair("raw", "true");
+    let mut timeout = READ_TIMEOUT;
+    if let Some(version) = version {
+        url.query_pairs_mut()
+            .append_pair("index", &version.to_string())
+            .append_pair("wait", &format!("{}s", WATCH_WAIT.as_secs()));
+        timeout += WATCH_WAIT;
+    }
+
+    let mut request = client.get(url).timeout(timeout);
+    if let Ok(token) = env::var(CONSUL_TOKEN_VARIABLE) {
+        request = request.header("X-Consul-Token", token);
+    }
+    let response = request.send().await?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to read the key '{}' from Consul, status: {}", source.key, response.status()).into());
+    }
+
+    let index = response.headers().get("X-Consul-Index")
+        .and_then(|index| index.to_str().ok())
+        .and_then(|index| index.parse().ok())
+        .unwrap_or_default();
+    Ok((response.text().await?, index))
+}
+
+/// Reads a key of etcd.
+async fn read_etcd_key(client: &Client, source: &ConfigSource) -> Result<(String, u64), Box<dyn Error>> {
+    let url = source.api_url.join("v3/kv/range")?;
+    let response = client.post(url)
+        .timeout(READ_TIMEOUT)
+        .json(&json!({ "key": STANDARD.encode(&source.key) }))
+        .send().await?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to read the key '{}' from etcd, status: {}", source.key, response.status()).into());
+    }
+
+    let body: Value = response.json().await?;
+    let revision = get_revision(&body["header"]["revision"]);
+    let value = body["kvs"].get(0)
+        .and_then(|kv| kv["value"].as_str())
+        .ok_or(format!("The key '{}' doesn't exist in etcd.", source.key))?;
+    let content = String::from_utf8(STANDARD.decode(value)?)?;
+
+    Ok((content, revision))
+}
+
+/// Waits until the key of etcd changes after the revision `version`, or for `WATCH_WAIT`.
+async fn watch_etcd_key(client: &Client, source: &ConfigSource, version: u64) -> Result<(), Box<dyn Error>> {
+    let url = source.api_url.join("v3/watch")?;
+    let request = json!({ "create_request": { "key": STANDARD.encode(&source.key), "start_revision": (version + 1).to_string() } });
+    let mut response = client.post(url).json(&request).send().await?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to watch the key '{}' in etcd, status: {}", source.key, response.status()).into());
+    }
+
+    // The watch responses are streamed as JSON objects, one per line.
+    let watch = async {
+        let mut buffer = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            buffer.extend_from_slice(&chunk);
+            while let Some(end) = buffer.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=end).collect();
+                let message: Value = serde_json::from_slice(&line)?;
+                if let Some(error) = message.get("error") {
+                    return Err(format!("Failed to watch the key '{}' in etcd: {}", source.key, error).into());
+                }
+                if message["result"]["events"].as_array().is_some_and(|events| !events.is_empty()) {
+                    return Ok(());
+                }
+            }
+        }
+        Err::<(), Box<dyn Error>>("The watch of etcd ended.".into())
+    };
+
+    match tokio::time::timeout(WATCH_WAIT, watch).await {
+        Ok(result) => result,
+        Err(_) => Ok(())
+    }
+}
+
+/// Returns a revision of etcd, a number encoded as a string in the JSON gateway.
+fn get_revision(value: &Value) -> u64 {
+    value.as_str().and_then(|r| r.parse().ok()).or(value.as_u64()).unwrap_or_default()
+}
\ No newline at end of file
@@ -0,0 +1,154 @@
+use std::error::Error;
+use reqwest::header::HeaderMap;
+use serde_json::{Map, Value};
+
+use crate::configure::http_jobs::{get_connection_duration, get_headers, get_user_agent};
+use crate::utils::duration_util::parse_duration;
+
+/// The fields of `defaults` applied to every job type that has them.
+const COMMON_FIELDS: [&str; 3] = ["timeout", "max_retry", "initial_delay"];
+
+/// The fields of `defaults` only applied to HTTP jobs.
+const HTTP_FIELDS: [&str; 6] = ["connect_timeout_ms", "pool_idle_timeout", "pool_max_idle_per_host", "retry_delay_ms", "tcp_keepalive", "user_agent"];
+
+/// Parses the optional `defaults` section of the configuration.
+///
+/// The defaults are the `timeout`, `max_retry`, `initial_delay`, `connect_timeout_ms`,
+/// `pool_idle_timeout`, `pool_max_idle_per_host`, `tcp_keepalive`, `retry_delay_ms`, `headers`
+/// and `user_agent` used by jobs that don't set them, instead of the built-in defaults.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The default fields by name, empty if the field is missing.
+///
+/// # Errors
+///
+/// Returns an error if the field is not an object, contains an unknown field or a field with an
+/// invalid value.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "defaults": { "timeout": 10000, "max_retry": 5, "headers": { "X-Env": "prod" } } });
+/// let defaults = get_defaults(&value)?;
+/// ```
+pub fn get_defaults(value: &Value) -> Result<Map<String, Value>, Box<dyn Error>> {
+    let section = match value.get("defaults") {
+        Some(d) => d,
+        None => return Ok(Map::new())
+    };
+    let defaults = section.as_object().ok_or("The 'defaults' field must be an object.")?;
+
+    for (key, default) in defaults {
+        match key.as_str() {
+            "timeout" | "max_retry" | "retry_delay_ms" | "pool_max_idle_per_host" => {
+                if !default.is_u64() {
+                    return Err(format!("The '{}' field of 'defaults' must be a non-negative integer.", key).into());
+                }
+            }
+            "connect_timeout_ms" => {
+                if default.as_u64().filter(|c| *c > 0).is_none() {
+                    return Err("The 'connect_timeout_ms' field of 'defaults' must be a positive integer.".into());
+                }
+            }
+            "pool_idle_timeout" | "tcp_keepalive" => {
+                get_connection_duration(section, key)
+                    .map_err(|e| format!("Invalid '{}' of 'defaults': {}", key, e))?;
+            }
+            "initial_delay" => {
+                if default.as_str().and_then(parse_duration).is_none() {
+                    return Err("The 'initial_delay' field of 'defaults' must be a duration like '2m'.".into());
+                }
+            }
+            "headers" => {
+                if !default.is_object() {
+                    return Err("The 'headers' field of 'defaults' must be an object.".into());
+                }
+                get_headers(section)
+                    .map_err(|e| format!("Invalid 'headers' of 'defaults': {}", e))?;
+            }
+            "user_agent" => {
+                get_user_agent(section)
+                    .map_err(|e| format!("Invalid 'user_agent' of 'defaults': {}", e))?;
+            }
+            _ => return Err(format!("Unknown field '{}' in 'defaults'.", key).into())
+        }
+    }
+
+    Ok(defaults.clone())
+}
+
+/// Parses the `headers` of the defaults.
+///
+/// The default headers are merged with the headers of an HTTP request when the request is
+/// built, see `build_request`.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The default headers, empty if there are none.
+///
+/// # Errors
+///
+/// Returns an error if a header name or value is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "defaults": { "headers": { "X-Env": "prod" } } });
+/// let headers = get_default_headers(&value)?;
+/// assert_eq!(headers["X-Env"], "prod");
+/// ```
+pub fn get_default_headers(value: &Value) -> Result<HeaderMap, Box<dyn Error>> {
+    let headers = value.get("defaults")
+        .map(get_headers)
+        .transpose()?
+        .flatten();
+
+    Ok(headers.unwrap_or_default())
+}
+
+/// Applies the defaults to the fields a job doesn't set.
+///
+/// `timeout`, `max_retry` and `initial_delay` apply to every job type that has them.
+/// `connect_timeout_ms`, the settings of the connection pool, `retry_delay_ms` and `user_agent`
+/// only apply to HTTP jobs. The default `headers` are not applied here but when
+/// a request is built, see `get_default_headers`.
+///
+/// # Arguments
+///
+/// * `job` - The JSON value of the job, with its template applied.
+/// * `defaults` - The defaults, see `get_defaults`.
+/// * `field` - The name of the array of the job, e.g. `http_jobs`.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let mut job = json!({ "name": "users", "cron": "0 * * * * ?", "request": { "url": "https://api.example.com/users" } });
+/// apply_defaults(&mut job, &defaults, "http_jobs");
+/// ```
+pub fn apply_defaults(job: &mut Value, defaults: &Map<String, Value>, field: &str) {
+    let is_http = field == "http_jobs";
+    let Some(job) = job.as_object_mut() else {
+        return;
+    };
+
+    for key in COMMON_FIELDS.iter().chain(HTTP_FIELDS.iter().filter(|_| is_http)) {
+        if let Some(default) = defaults.get(*key) {
+            job.entry(key.to_string()).or_insert_with(|| default.clone());
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,72 @@
+use std::error::Error;
+use serde_json::Value;
+use crate::models::distributed_lock::{DEFAULT_LOCK_KEY_PREFIX, DEFAULT_LOCK_TTL, DistributedLock, OnLockError, RedisServer};
+use crate::utils::duration_util::parse_duration;
+
+/// Parses the optional `distributed_lock` section of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The lock shared by the instances of the scheduler, or `None` if the section is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'distributed_lock' field is not an object.
+/// * The 'redis' field is missing or is not a valid `redis://` URL.
+/// * The 'ttl' field is not a positive duration.
+/// * The 'key_prefix' field is not a non-empty string.
+/// * The 'on_error' field is not one of 'skip' or 'run'.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "distributed_lock": { "redis": "redis://:secret@10.0.0.5:6379/0", "ttl": "10m" } });
+/// let distributed_lock = get_distributed_lock(&value)?;
+/// println!("Distributed lock: {:?}", distributed_lock);
+/// ```
+pub fn get_distributed_lock(value: &Value) -> Result<Option<DistributedLock>, Box<dyn Error>> {
+    let lock = match value.get("distributed_lock") {
+        Some(l) => l,
+        None => return Ok(None)
+    };
+    if !lock.is_object() {
+        return Err("The 'distributed_lock' field must be an object.".into());
+    }
+
+    let redis = lock.get("redis")
+        .and_then(|r| r.as_str())
+        .ok_or("The 'redis' field of the distributed lock is required and must be a string.")?;
+    let redis = RedisServer::parse(redis)?;
+
+    let ttl = match lock.get("ttl") {
+        Some(t) => t.as_str()
+            .and_then(parse_duration)
+            .filter(|t| !t.is_zero())
+            .ok_or("The 'ttl' field of the distributed lock must be a duration like '10m'.")?,
+        None => DEFAULT_LOCK_TTL
+    };
+
+    let key_prefix = match lock.get("key_prefix") {
+        Some(p) => p.as_str()
+            .filter(|p| !p.is_empty())
+            .ok_or("The 'key_prefix' field of the distributed lock must be a non-empty string.")?,
+        None => DEFAULT_LOCK_KEY_PREFIX
+    };
+
+    let on_error = match lock.get("on_error") {
+        Some(o) => o.as_str()
+            .and_then(OnLockError::parse)
+            .ok_or("The 'on_error' field of the distributed lock must be one of 'skip' or 'run'.")?,
+        None => OnLockError::default()
+    };
+
+    Ok(Some(DistributedLock::new(redis, ttl, key_prefix.to_string(), on_error)))
+}
\ No newline at end of file
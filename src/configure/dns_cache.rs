@@ -0,0 +1,50 @@
+use std::error::Error;
+use serde_json::Value;
+use crate::models::dns_cache::{DEFAULT_DNS_CACHE_TTL, DnsCache};
+use crate::utils::duration_util::parse_duration;
+
+/// Parses the optional `dns_cache` section of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The DNS cache of the HTTP clients, or `None` if the section is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'dns_cache' field is not an object.
+/// * The 'ttl' field is not a non-zero duration.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "dns_cache": { "ttl": "30s" } });
+/// let dns_cache = get_dns_cache(&value)?;
+/// println!("DNS cache: {:?}", dns_cache);
+/// ```
+pub fn get_dns_cache(value: &Value) -> Result<Option<DnsCache>, Box<dyn Error>> {
+    let dns_cache = match value.get("dns_cache") {
+        Some(d) => d,
+        None => return Ok(None)
+    };
+    if !dns_cache.is_object() {
+        return Err("The 'dns_cache' field must be an object.".into());
+    }
+
+    let ttl = match dns_cache.get("ttl") {
+        Some(t) => t.as_str()
+            .and_then(parse_duration)
+            .filter(|t| !t.is_zero())
+            .ok_or("The 'ttl' field of the DNS cache must be a duration like '30s'.")?,
+        None => DEFAULT_DNS_CACHE_TTL
+    };
+
+    Ok(Some(DnsCache::new(ttl)))
+}
\ No newline at end of file
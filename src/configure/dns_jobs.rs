@@ -0,0 +1,162 @@
+use std::error::Error;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use hickory_resolver::proto::rr::RecordType;
+use serde_json::Value;
+
+use crate::configure::get_job_array;
+use crate::configure::job_options::get_job_options;
+use crate::configure::schedules::get_job_schedule;
+use crate::models::on_invalid_job::OnInvalidJob;
+use crate::models::dns_job::DnsJob;
+
+/// The record types a DNS job can query.
+const RECORD_TYPES: [RecordType; 8] = [
+    RecordType::A,
+    RecordType::AAAA,
+    RecordType::CAA,
+    RecordType::CNAME,
+    RecordType::MX,
+    RecordType::NS,
+    RecordType::SRV,
+    RecordType::TXT,
+];
+
+/// Parses the optional 'dns_jobs' array of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+/// * `on_invalid_job` - Whether to fail or skip when a job is invalid.
+///
+/// # Returns
+///
+/// The list of DNS jobs, empty if the field is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'dns_jobs' field is not an array.
+/// * The 'name', 'cron' or 'query' field is missing or not a string for any job.
+/// * The 'cron' field is not a valid cron expression for any job.
+/// * The 'record_type' field is not one of the supported record types for any job.
+/// * The 'resolver' field is not an IP address with an optional port for any job.
+/// * The 'expect' field is not a string or an array of strings for any job.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({
+///     "dns_jobs": [{
+///         "name": "www",
+///         "cron": "0 */10 * * * ?",
+///         "query": "www.example.com",
+///         "record_type": "A",
+///         "resolver": "1.1.1.1",
+///         "expect": ["93.184.216.34"]
+///     }]
+/// });
+/// let jobs = get_dns_jobs(&value, OnInvalidJob::Fail)?;
+/// ```
+pub fn get_dns_jobs(value: &Value, on_invalid_job: OnInvalidJob) -> Result<Vec<DnsJob>, Box<dyn Error>> {
+    get_job_array(value, "dns_jobs", on_invalid_job, get_dns_job)
+}
+
+/// Parses a single job of the 'dns_jobs' array, see `get_dns_jobs`.
+fn get_dns_job(it: &Value) -> Result<DnsJob, Box<dyn Error>> {
+    let name = it.get("name")
+        .and_then(|n| n.as_str())
+        .ok_or("The 'name' field is missing or not a string.")?
+        .to_string();
+
+    let enable = it.get("enable")
+        .and_then(|e| e.as_bool())
+        .unwrap_or(true);
+
+    let schedule = get_job_schedule(it, &name)?;
+
+    let timeout = it.get("timeout")
+        .and_then(|t| t.as_u64())
+        .unwrap_or(5000);
+
+    let max_retry = it.get("max_retry")
+        .and_then(|m| m.as_u64())
+        .unwrap_or(3);
+
+    let query = it.get("query")
+        .and_then(|q| q.as_str())
+        .ok_or("The 'query' field is required and must be a string.")?
+        .to_string();
+
+    let record_type = get_record_type(it, &name)?;
+    let resolver = get_resolver(it, &name)?;
+    let expect = get_expect(it)?;
+    let options = get_job_options(it)?;
+
+    Ok(DnsJob {
+        name,
+        enable,
+        schedule,
+        timeout,
+        max_retry,
+        query,
+        record_type,
+        resolver,
+        expect,
+        options,
+    })
+}
+
+/// Parses the 'record_type' field of a DNS job, `A` if missing.
+fn get_record_type(it: &Value, name: &str) -> Result<RecordType, Box<dyn Error>> {
+    let record_type = match it.get("record_type") {
+        Some(r) => r.as_str().ok_or("The 'record_type' field must be a string.")?,
+        None => return Ok(RecordType::A)
+    };
+
+    RecordType::from_str(&record_type.to_uppercase())
+        .ok()
+        .filter(|r| RECORD_TYPES.contains(r))
+        .ok_or_else(|| {
+            let supported = RECORD_TYPES.iter().map(|r| r.to_string()).collect::<Vec<_>>().join(", ");
+            format!("The 'record_type' of the DNS job '{}' must be one of {}.", name, supported).into()
+        })
+}
+
+/// Parses the 'resolver' field of a DNS job, an IP address with an optional port.
+///
+/// The port defaults to 53.
+fn get_resolver(it: &Value, name: &str) -> Result<Option<SocketAddr>, Box<dyn Error>> {
+    let resolver = match it.get("resolver") {
+        Some(r) => r.as_str().ok_or("The 'resolver' field must be a string.")?,
+        None => return Ok(None)
+    };
+
+    if let Ok(ip) = IpAddr::from_str(resolver) {
+        return Ok(Some(SocketAddr::new(ip, 53)));
+    }
+
+    SocketAddr::from_str(resolver)
+        .map(Some)
+        .map_err(|_| format!("The 'resolver' of the DNS job '{}' must be an IP address with an optional port, found '{}'.", name, resolver).into())
+}
+
+/// Parses the 'expect' field of a DNS job, a single value or an array of values.
+fn get_expect(it: &Value) -> Result<Option<Vec<String>>, Box<dyn Error>> {
+    let expect = match it.get("expect") {
+        Some(e) => e,
+        None => return Ok(None)
+    };
+
+    if let Some(value) = expect.as_str() {
+        return Ok(Some(vec![value.to_string()]));
+    }
+
+    expect.as_array()
+        .and_then(|values| values.iter().map(|v| v.as_str().map(|v| v.to_string())).collect::<Option<Vec<_>>>())
+        .map(Some)
+        .ok_or("The 'expect' field must be a string or an array of strings.".into())
+}
\ No newline at end of file
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::error::Error;
+use serde_json::Value;
+use crate::models::job_group::{DEFAULT_PRIORITY_AGING, JobGroup};
+use crate::models::jobs::Jobs;
+use crate::utils::duration_util::parse_duration;
+
+/// Parses the optional `groups` section of the configuration.
+///
+/// The section maps group names to their settings: the `max_concurrent` number of runs of the
+/// jobs of the group, and the `priority_aging` duration after which a waiting run gains one
+/// priority level.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// A map of group names to their settings. The map is empty if the section is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'groups' field is not an object.
+/// * A group is not an object or its 'max_concurrent' field is not a positive integer.
+/// * The 'priority_aging' field of a group is not a duration like '30s'.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({
+///     "groups": {
+///         "reports": { "max_concurrent": 1, "priority_aging": "1m" }
+///     }
+/// });
+///
+/// let groups = get_groups(&value)?;
+/// assert_eq!(groups["reports"].max_concurrent, 1);
+/// ```
+pub fn get_groups(value: &Value) -> Result<HashMap<String, JobGroup>, Box<dyn Error>> {
+    let groups_val = match value.get("groups") {
+        Some(g) => g.as_object()
+            .ok_or("The 'groups' field must be an object.")?,
+        None => return Ok(HashMap::new())
+    };
+
+    let mut groups = HashMap::new();
+
+    for (name, group) in groups_val {
+        if !group.is_object() {
+            return Err(format!("The group '{}' must be an object.", name).into());
+        }
+        let max_concurrent = group.get("max_concurrent")
+            .and_then(|m| m.as_u64())
+            .filter(|m| *m > 0)
+            .ok_or(format!("The 'max_concurrent' field of the group '{}' is required and must be a positive integer.", name))?;
+        let priority_aging = group.get("priority_aging")
+            .map(|p| {
+                p.as_str()
+                    .and_then(parse_duration)
+                    .filter(|p| !p.is_zero())
+                    .ok_or(format!("The 'priority_aging' field of the group '{}' must be a duration like '30s'.", name))
+            })
+            .transpose()?
+            .unwrap_or(DEFAULT_PRIORITY_AGING);
+        groups.insert(name.clone(), JobGroup::new(max_concurrent as usize, priority_aging));
+    }
+
+    Ok(groups)
+}
+
+/// Parses the optional `group` of a job.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value of the job.
+///
+/// # Returns
+///
+/// The name of the group of the job, or `None` if the field is missing.
+///
+/// # Errors
+///
+/// Returns an error if the field is not a non-empty string.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "group": "reports" });
+/// let group = get_job_group(&value)?;
+/// assert_eq!(group, Some("reports".to_string()));
+/// ```
+pub fn get_job_group(value: &Value) -> Result<Option<String>, Box<dyn Error>> {
+    value.get("group")
+        .map(|g| {
+            g.as_str()
+                .filter(|g| !g.trim().is_empty())
+                .map(|g| g.to_string())
+                .ok_or_else(|| "The 'group' field must be a non-empty string.".into())
+        })
+        .transpose()
+}
+
+/// Parses the optional `priority` of a job.
+///
+/// When the runs of a group wait for each other, the run with the highest priority starts
+/// first.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value of the job.
+///
+/// # Returns
+///
+/// The priority of the job, 0 if the field is missing.
+///
+/// # Errors
+///
+/// Returns an error if the field is not an integer.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "group": "billing", "priority": 10 });
+/// let priority = get_job_priority(&value)?;
+/// assert_eq!(priority, 10);
+/// ```
+pub fn get_job_priority(value: &Value) -> Result<i64, Box<dyn Error>> {
+    let Some(priority) = value.get("priority") else {
+        return Ok(0);
+    };
+
+    priority.as_i64().ok_or_else(|| "The 'priority' field must be an integer.".into())
+}
+
+/// Checks that the group of every job is defined in the `groups` section.
+///
+/// # Arguments
+///
+/// * `jobs` - The parsed configuration.
+///
+/// # Errors
+///
+/// Returns an error naming the first job with an unknown group.
+pub fn validate_job_groups(jobs: &Jobs) -> Result<(), Box<dyn Error>> {
+
+    for job in jobs.all_jobs() {
+        if let Some(group) = job.options().group.as_ref().filter(|g| !jobs.groups.contains_key(*g)) {
+            return Err(format!("The job '{}' belongs to the unknown group '{}'.", job.name(), group).into());
+        }
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,158 @@
+use std::error::Error;
+use std::fs;
+use chrono::{Days, NaiveDate};
+use serde_json::Value;
+use crate::models::holiday_calendar::{HolidayCalendar, YearlyHoliday};
+
+/// Parses the optional `holidays` of a job, the days its runs are skipped.
+///
+/// The field is either an inline list of dates (`YYYY-MM-DD`), or the path of an iCal file
+/// (`.ics`), e.g. a public holiday calendar exported from a calendar application. Every day
+/// covered by an event of the file is a holiday. Events recurring every year on the same date
+/// (`RRULE:FREQ=YEARLY`, optionally with `COUNT` or `UNTIL`) are supported, other recurrence
+/// rules are not.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value of the job.
+/// * `name` - The name of the job, used in the errors.
+///
+/// # Returns
+///
+/// The holiday calendar of the job, or `None` if the field is missing.
+///
+/// # Errors
+///
+/// Returns an error if the field is neither an array of dates nor a string, if the iCal file
+/// cannot be read, or if it has an event with an invalid date or an unsupported recurrence rule.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "name": "report", "cron": "0 0 8 * * MON-FRI", "holidays": ["2026-12-25", "2027-01-01"] });
+/// let holidays = get_job_holidays(&value, "report")?.unwrap();
+/// assert!(holidays.is_holiday(NaiveDate::from_ymd_opt(2026, 12, 25).unwrap()));
+///
+/// let value = json!({ "name": "report", "cron": "0 0 8 * * MON-FRI", "holidays": "holidays/de.ics" });
+/// let holidays = get_job_holidays(&value, "report")?;
+/// ```
+pub fn get_job_holidays(value: &Value, name: &str) -> Result<Option<HolidayCalendar>, Box<dyn Error>> {
+    let holidays = match value.get("holidays") {
+        None => return Ok(None),
+        Some(Value::String(file)) => {
+            let content = fs::read_to_string(file)
+                .map_err(|e| format!("Failed to read the holiday calendar '{}' of job '{}': {}", file, name, e))?;
+            parse_ical(&content)
+                .map_err(|e| format!("Invalid holiday calendar '{}' of job '{}': {}", file, name, e))?
+        }
+        Some(Value::Array(dates)) => {
+            let dates = dates.iter()
+                .map(|date| date.as_str().and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+                    .ok_or(format!("Invalid holiday {} of job '{}', expected a date such as \"2026-12-25\".", date, name)))
+                .collect::<Result<_, _>>()?;
+            HolidayCalendar { dates, yearly: vec![] }
+        }
+        Some(_) => return Err(format!("The 'holidays' field of job '{}' must be an array of dates or the path of an iCal file.", name).into()),
+    };
+
+    Ok(Some(holidays))
+}
+
+/// Parses the events of an iCal file into a holiday calendar.
+///
+/// Only the `DTSTART`, `DTEND` and `RRULE` properties of the events are read. An event without
+/// a `DTEND` lasts one day, and the date of a date-time is taken as is, whatever its timezone.
+fn parse_ical(content: &str) -> Result<HolidayCalendar, Box<dyn Error>> {
+    let mut calendar = HolidayCalendar::default();
+    let mut event: Option<Vec<(String, String)>> = None;
+
+    for line in unfold_lines(content) {
+        let Some((property, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Drops the parameters of the property, e.g. `DTSTART;VALUE=DATE`.
+        let property = property.split(';').next().unwrap_or_default().to_uppercase();
+
+        match (property.as_str(), value.trim()) {
+            ("BEGIN", "VEVENT") => event = Some(vec![]),
+            ("END", "VEVENT") => {
+                if let Some(properties) = event.take() {
+                    add_event(&mut calendar, &properties)?;
+                }
+            }
+            (_, value) => {
+                if let Some(properties) = event.as_mut() {
+                    properties.push((property.clone(), value.to_string()));
+                }
+            }
+        }
+    }
+
+    Ok(calendar)
+}
+
+/// Adds the days of an event to a holiday calendar.
+fn add_event(calendar: &mut HolidayCalendar, properties: &[(String, String)]) -> Result<(), Box<dyn Error>> {
+    let get = |name: &str| properties.iter().find(|(property, _)| property == name).map(|(_, value)| value.as_str());
+
+    let start = get("DTSTART").ok_or("An event has no DTSTART.")?;
+    let start = parse_ical_date(start).ok_or(format!("Invalid DTSTART '{}'.", start))?;
+    let days = match get("DTEND") {
+        Some(end) => {
+            let end = parse_ical_date(end).ok_or(format!("Invalid DTEND '{}'.", end))?;
+            // The end of an all-day event is exclusive.
+            (end - start).num_days().max(1) as u32
+        }
+        None => 1,
+    };
+
+    match get("RRULE") {
+        Some(rule) => calendar.yearly.push(parse_yearly_rule(rule, start, days)?),
+        None => calendar.dates.extend((0..days).filter_map(|day| start.checked_add_days(Days::new(day as u64)))),
+    }
+
+    Ok(())
+}
+
+/// Parses the recurrence rule of an event recurring every year on the same date.
+fn parse_yearly_rule(rule: &str, start: NaiveDate, days: u32) -> Result<YearlyHoliday, Box<dyn Error>> {
+    let unsupported = || format!("Unsupported recurrence rule '{}', only yearly events on the same date are supported.", rule);
+    let mut holiday = YearlyHoliday { start, days, count: None, until: None };
+    let mut yearly = false;
+
+    for part in rule.split(';') {
+        match part.split_once('=').ok_or_else(unsupported)? {
+            ("FREQ", "YEARLY") => yearly = true,
+            ("INTERVAL", "1") | ("WKST", _) => {}
+            ("COUNT", count) => holiday.count = Some(count.parse().map_err(|_| unsupported())?),
+            ("UNTIL", until) => holiday.until = Some(parse_ical_date(until).ok_or_else(unsupported)?),
+            _ => return Err(unsupported().into()),
+        }
+    }
+
+    if !yearly {
+        return Err(unsupported().into());
+    }
+    Ok(holiday)
+}
+
+/// Parses the date of an iCal date (`20261225`) or date-time (`20261225T080000Z`).
+fn parse_ical_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()
+}
+
+/// Splits the content of an iCal file into lines, joining the lines folded over several lines.
+fn unfold_lines(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    lines
+}
\ No newline at end of file
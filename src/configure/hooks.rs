@@ -0,0 +1,167 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use serde_json::Value;
+use crate::models::hooks::{HookAction, JobHooks};
+use crate::models::jobs::Jobs;
+
+/// Parses the optional `on_success` and `on_failure` hooks of a job.
+///
+/// Each hook is an array of actions, and each action is an object with exactly one of the
+/// `notify`, `trigger_job`, `command` or `webhook` fields.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value of the job.
+///
+/// # Returns
+///
+/// The hooks of the job. Missing hooks have no actions.
+///
+/// # Errors
+///
+/// Returns an error if a hook is not an array or an action is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({
+///     "on_success": [{ "trigger_job": "report" }],
+///     "on_failure": [{ "command": "systemctl restart app" }, { "notify": true }]
+/// });
+///
+/// let hooks = get_job_hooks(&value)?;
+/// assert_eq!(hooks.on_failure.len(), 2);
+/// ```
+pub fn get_job_hooks(value: &Value) -> Result<JobHooks, Box<dyn Error>> {
+    let on_success = get_hook_actions(value, "on_success")?;
+    let on_failure = get_hook_actions(value, "on_failure")?;
+
+    Ok(JobHooks::new(on_success, on_failure))
+}
+
+/// Parses the actions of a hook.
+fn get_hook_actions(value: &Value, field: &str) -> Result<Vec<HookAction>, Box<dyn Error>> {
+    let actions = match value.get(field) {
+        Some(a) => a.as_array().ok_or(format!("The '{}' field must be an array of actions.", field))?,
+        None => return Ok(vec![])
+    };
+
+    actions.iter()
+        .map(|action| get_hook_action(action).map_err(|e| format!("Invalid '{}' action: {}", field, e).into()))
+        .collect()
+}
+
+/// Parses a single hook action.
+fn get_hook_action(action: &Value) -> Result<HookAction, Box<dyn Error>> {
+    let object = action.as_object()
+        .filter(|o| o.len() == 1)
+        .ok_or("An action must be an object with exactly one of the 'notify', 'trigger_job', 'command' or 'webhook' fields.")?;
+    let (kind, value) = object.iter().next().unwrap();
+
+    let string = |field: &str| {
+        value.as_str()
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| v.to_string())
+            .ok_or(format!("The '{}' field must be a non-empty string.", field))
+    };
+
+    match kind.as_str() {
+        "notify" => match value.as_bool() {
+            Some(true) => Ok(HookAction::Notify),
+            _ => Err("The 'notify' field must be true.".into())
+        },
+        "trigger_job" => Ok(HookAction::TriggerJob(string("trigger_job")?)),
+        "command" => Ok(HookAction::Command(string("command")?)),
+        "webhook" => {
+            let url = string("webhook")?;
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(format!("The webhook URL '{}' must start with 'http://' or 'https://'.", url).into());
+            }
+            Ok(HookAction::Webhook(url))
+        }
+        _ => Err(format!("Unknown action '{}', expected one of 'notify', 'trigger_job', 'command' or 'webhook'.", kind).into())
+    }
+}
+
+/// Checks that the jobs triggered by hooks exist and that the `trigger_job` actions form no
+/// cycle, such as a job triggering itself or `a` triggering `b` triggering `a`, which would
+/// run the jobs in an endless loop.
+///
+/// # Arguments
+///
+/// * `jobs` - The parsed configuration.
+///
+/// # Errors
+///
+/// Returns an error naming the first job with an invalid `trigger_job` action, or the jobs of
+/// the first cycle found.
+pub fn validate_hook_targets(jobs: &Jobs) -> Result<(), Box<dyn Error>> {
+    let all_jobs = jobs.all_jobs();
+    let names: HashSet<&str> = all_jobs.iter().map(|job| job.name()).collect();
+
+    let mut triggers: HashMap<&str, Vec<&str>> = HashMap::new();
+    for job in &all_jobs {
+        let (name, hooks) = (job.name(), &job.options().hooks);
+        for action in hooks.on_success.iter().chain(&hooks.on_failure) {
+            if let HookAction::TriggerJob(target) = action {
+                if target == name {
+                    return Err(format!("The job '{}' cannot trigger itself.", name).into());
+                }
+                if !names.contains(target.as_str()) {
+                    return Err(format!("The job '{}' triggers the unknown job '{}'.", name, target).into());
+                }
+                triggers.entry(name).or_default().push(target);
+            }
+        }
+    }
+
+    let mut visited = HashSet::new();
+    for job in &all_jobs {
+        let mut path = vec![];
+        if let Some(cycle) = find_trigger_cycle(job.name(), &triggers, &mut visited, &mut path) {
+            return Err(format!("The hooks of the jobs trigger each other in a cycle: {}.", cycle.join(" -> ")).into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Searches the jobs triggered from a job, depth first, for a cycle of `trigger_job` actions.
+///
+/// # Arguments
+///
+/// * `name` - The name of the job to search from.
+/// * `triggers` - The jobs triggered by the hooks of each job.
+/// * `visited` - The jobs already searched, from which no cycle is reachable.
+/// * `path` - The jobs triggering each other down to the job.
+///
+/// # Returns
+///
+/// The jobs of the cycle, starting and ending with the same job, or `None` if there is none.
+fn find_trigger_cycle<'a>(
+    name: &'a str,
+    triggers: &HashMap<&'a str, Vec<&'a str>>,
+    visited: &mut HashSet<&'a str>,
+    path: &mut Vec<&'a str>,
+) -> Option<Vec<&'a str>> {
+    if let Some(start) = path.iter().position(|job| *job == name) {
+        let mut cycle = path[start..].to_vec();
+        cycle.push(name);
+        return Some(cycle);
+    }
+    if !visited.insert(name) {
+        return None;
+    }
+
+    path.push(name);
+    for target in triggers.get(name).into_iter().flatten() {
+        if let Some(cycle) = find_trigger_cycle(target, triggers, visited, path) {
+            return Some(cycle);
+        }
+    }
+    path.pop();
+
+    None
+}
\ No newline at end of file
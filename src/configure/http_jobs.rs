@@ -1,75 +1,277 @@
+use std::collections::HashMap;
 use std::error::Error;
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::net::IpAddr;
+use std::time::Duration;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use reqwest::header::{ACCEPT_ENCODING, HeaderMap, HeaderName, HeaderValue, HOST};
+use rustls::ServerName;
 use serde_json::Value;
-use crate::models::http_job::HttpJob;
-use crate::models::http_job_request::HttpJobRequest;
+use crate::models::http_job::{HttpJob, IpVersion};
+use crate::models::http_job_request::{BodyCompression, DECODABLE_ENCODINGS, HttpJobRequest, HttpVersion, JSON_CONTENT_TYPE, TEXT_CONTENT_TYPE};
+use crate::models::http_job_step::{Extraction, HttpJobStep};
+use crate::models::http_job_response::{DEFAULT_LOG_RESPONSE_BYTES, DEFAULT_MAX_RESPONSE_BYTES, HttpJobResponse, ResponseLogging};
+use crate::models::on_invalid_job::OnInvalidJob;
+use crate::models::tls::{TlsOptions, TlsVersion};
+use crate::configure::get_job_array;
+use crate::configure::job_options::get_job_options;
+use crate::configure::schedules::get_job_schedule;
+use crate::utils::duration_util::parse_duration;
+use crate::utils::template_util::render_template;
+
+/// The request timeout in milliseconds when neither the job nor `defaults` sets one.
+const DEFAULT_TIMEOUT: u64 = 5000;
+
+/// The maximum number of retries when neither the job nor `defaults` sets one.
+const DEFAULT_MAX_RETRY: u64 = 3;
 
 /// Parses the JSON configuration and retrieves the list of HTTP jobs.
 ///
 /// # Arguments
 ///
 /// * `value` - The JSON configuration value.
+/// * `on_invalid_job` - Whether to fail or skip when a job is invalid.
 ///
 /// # Returns
 ///
 /// A `Result` containing a vector of `HttpJob` on success, or an error message on failure.
+/// The vector is empty if the 'http_jobs' field is missing.
 ///
 /// # Errors
 ///
 /// This function can return an error under the following conditions:
 ///
-/// * The 'http_jobs' field is missing in the JSON configuration.
 /// * The 'http_jobs' field is not an array in the JSON configuration.
 /// * The 'name' field is missing or not a string for any HTTP job.
 /// * The 'enable' field is missing or not a boolean for any HTTP job.
-/// * The 'cron' field is missing or not a string for any HTTP job.
+/// * The 'cron' or 'daily_between' field is missing for any HTTP job.
+/// * The 'cron' field is not a valid cron expression for any HTTP job.
 /// * Failed to parse the 'request' field for any HTTP job.
 ///
-pub fn get_http_jobs(value: Value) -> Result<Vec<HttpJob>, Box<dyn Error>> {
-    let http_jobs_val = value.get("http_jobs")
-        .ok_or("The 'http_jobs' field is missing in the JSON configuration.")?;
+pub fn get_http_jobs(value: &Value, on_invalid_job: OnInvalidJob) -> Result<Vec<HttpJob>, Box<dyn Error>> {
+    get_job_array(value, "http_jobs", on_invalid_job, get_http_job)
+}
+
+/// Parses a single HTTP job of the configuration.
+///
+/// # Arguments
+///
+/// * `it` - The JSON value of the job, an element of the 'http_jobs' array.
+///
+/// # Returns
+///
+/// The parsed `HttpJob`.
+///
+/// # Errors
+///
+/// Returns an error if a field of the job is missing or invalid, see `get_http_jobs`.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "name": "users2", "cron": "*/5 * * * * ?", "request": { "url": "https://reqres.in/api/users/2" } });
+/// let http_job = get_http_job(&value)?;
+/// println!("Job: {}", http_job);
+/// ```
+pub fn get_http_job(it: &Value) -> Result<HttpJob, Box<dyn Error>> {
+    let name = it.get("name")
+        .and_then(|n| n.as_str())
+        .ok_or("The 'name' field is missing or not a string.")?
+        .to_string();
+
+    let enable = it.get("enable")
+        .and_then(|e| e.as_bool())
+        .unwrap_or(true);
+
+    let schedule = get_job_schedule(it, &name)?;
+
+    let timeout = it.get("timeout")
+        .and_then(|t| t.as_u64())
+        .unwrap_or(DEFAULT_TIMEOUT);
+
+    let connect_timeout_ms = it.get("connect_timeout_ms")
+        .map(|c| c.as_u64().filter(|c| *c > 0).ok_or("The 'connect_timeout_ms' field must be a positive integer."))
+        .transpose()?;
+
+    let execution_timeout = it.get("execution_timeout")
+        .map(|e| e.as_u64().filter(|e| *e > 0).ok_or("The 'execution_timeout' field must be a positive integer."))
+        .transpose()?;
+
+    let ip_version = match it.get("ip_version") {
+        Some(v) => v.as_str()
+            .and_then(IpVersion::parse)
+            .ok_or("The 'ip_version' field must be one of 'v4', 'v6' or 'any'.")?,
+        None => IpVersion::Any
+    };
+
+    let local_address = it.get("local_address")
+        .map(|l| {
+            l.as_str()
+                .and_then(|l| l.parse::<IpAddr>().ok())
+                .ok_or("The 'local_address' field must be an IP address.")
+        })
+        .transpose()?;
+    if let Some(local_address) = local_address.filter(|l| !ip_version.matches(l)) {
+        return Err(format!("The 'local_address' {} doesn't match the 'ip_version' '{}'.", local_address, ip_version).into());
+    }
 
-    let http_jobs_val = http_jobs_val.as_array()
-        .ok_or("The 'http_jobs' field must be an array in the JSON configuration.")?;
+    let pool_idle_timeout = get_connection_duration(it, "pool_idle_timeout")?;
 
-    let mut http_jobs: Vec<HttpJob> = Vec::new();
+    let pool_max_idle_per_host = it.get("pool_max_idle_per_host")
+        .map(|p| p.as_u64().ok_or("The 'pool_max_idle_per_host' field must be a non-negative integer."))
+        .transpose()?
+        .map(|p| p as usize);
 
-    for it in http_jobs_val {
-        let name = it.get("name")
-            .and_then(|n| n.as_str())
-            .ok_or("The 'name' field is missing or not a string.")?
-            .to_string();
+    let tcp_keepalive = get_connection_duration(it, "tcp_keepalive")?;
 
-        let enable = it.get("enable")
-            .and_then(|e| e.as_bool())
-            .unwrap_or(true);
+    let tls = get_tls_options(it)?;
 
-        let cron = it.get("cron")
-            .and_then(|c| c.as_str())
-            .ok_or("The 'cron' field is missing or not a string.")?
-            .to_string();
+    let max_retry = it.get("max_retry")
+        .and_then(|m| m.as_u64())
+        .unwrap_or(DEFAULT_MAX_RETRY);
 
-        let timeout = it.get("timeout")
-            .and_then(|t| t.as_u64())
-            .unwrap_or(5000);
+    let retry_delay_ms = it.get("retry_delay_ms")
+        .map(|r| r.as_u64().ok_or("The 'retry_delay_ms' field must be a non-negative integer."))
+        .transpose()?
+        .unwrap_or(0);
 
-        let max_retry = it.get("max_retry")
-            .and_then(|m| m.as_u64())
-            .unwrap_or(3);
+    let retry_budget = it.get("retry_budget")
+        .map(|r| {
+            r.as_str()
+                .and_then(parse_duration)
+                .filter(|r| !r.is_zero())
+                .ok_or("The 'retry_budget' field must be a duration like '60s'.")
+        })
+        .transpose()?;
+
+    let retry_non_idempotent = it.get("retry_non_idempotent")
+        .map(|r| r.as_bool().ok_or("The 'retry_non_idempotent' field must be a boolean."))
+        .transpose()?
+        .unwrap_or(true);
+
+    let user_agent = get_user_agent(it)?;
+
+    let request = it.get("request")
+        .ok_or("The 'request' field is required in the JSON value.")?;
+    let (request, steps) = match request {
+        Value::Array(_) => {
+            let steps = get_http_job_steps(request)?;
+            (steps[0].request.clone(), steps)
+        }
+        _ => (get_http_job_request(request)?, Vec::new())
+    };
 
-        let request = get_http_job_request(&it)?;
+    let fallback_request = it.get("fallback_request")
+        .map(|f| get_http_job_request(f).map_err(|e| format!("Invalid 'fallback_request': {}", e)))
+        .transpose()?;
 
-        let http_job = HttpJob::new(name, enable, cron, timeout, max_retry, request);
-        http_jobs.push(http_job);
+    let response = get_http_job_response(it)?;
+    let requests: Vec<&HttpJobRequest> = match steps.is_empty() {
+        true => vec![&request],
+        false => steps.iter().map(|s| &s.request).collect()
+    };
+    if response.discard_body && requests.iter().any(|r| r.graphql) {
+        return Err("The 'discard_body' field of 'response' can't be set for a GraphQL request, whose errors are read from the body.".into());
+    }
+    let extracts_body = steps.iter().flat_map(|s| &s.extract).any(|(_, e)| !matches!(e, Extraction::Header(_)));
+    if response.discard_body && extracts_body {
+        return Err("The 'discard_body' field of 'response' can't be set when a step extracts a value from the body.".into());
     }
+    if response.decompress {
+        let mut encodings = requests.into_iter().chain(&fallback_request).filter_map(|r| r.accept_encoding.as_ref()).flatten();
+        if let Some(encoding) = encodings.find(|e| !DECODABLE_ENCODINGS.contains(&e.as_str())) {
+            return Err(format!("The content encoding '{}' can't be decompressed, expected one of {}.", encoding, DECODABLE_ENCODINGS.join(", ")).into());
+        }
+    }
+
+    let options = get_job_options(it)?;
+
+    let report_to = it.get("report_to")
+        .map(|r| {
+            let url = r.as_str().ok_or("The 'report_to' field must be a string.")?;
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(format!("The 'report_to' URL '{}' must start with 'http://' or 'https://'.", url).into());
+            }
+            Ok::<_, Box<dyn Error>>(url.to_string())
+        })
+        .transpose()?;
+
+    Ok(HttpJob::new(
+        name,
+        enable,
+        schedule,
+        timeout,
+        connect_timeout_ms,
+        execution_timeout,
+        ip_version,
+        local_address,
+        pool_idle_timeout,
+        pool_max_idle_per_host,
+        tcp_keepalive,
+        tls,
+        max_retry,
+        retry_delay_ms,
+        retry_budget,
+        retry_non_idempotent,
+        user_agent,
+        request,
+        steps,
+        fallback_request,
+        response,
+        options,
+        report_to,
+    ))
+}
+
+/// Parses the optional `user_agent` field of the configuration or of an HTTP job.
+///
+/// The `{{version}}` placeholder is replaced with the version of rjob.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value containing the `user_agent` field.
+///
+/// # Returns
+///
+/// The rendered user agent, or `None` if the field is missing.
+///
+/// # Errors
+///
+/// Returns an error if the field is not a non-empty string or is not a valid header value.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "user_agent": "rjob/{{version}} (prod)" });
+/// let user_agent = get_user_agent(&value)?;
+/// assert_eq!(user_agent, Some(format!("rjob/{} (prod)", env!("CARGO_PKG_VERSION"))));
+/// ```
+pub fn get_user_agent(value: &Value) -> Result<Option<String>, Box<dyn Error>> {
+    let template = match value.get("user_agent") {
+        Some(u) => u.as_str()
+            .filter(|u| !u.trim().is_empty())
+            .ok_or("The 'user_agent' field must be a non-empty string.")?,
+        None => return Ok(None)
+    };
+
+    let vars = HashMap::from([("version", env!("CARGO_PKG_VERSION").to_string())]);
+    let user_agent = render_template(template, &vars);
+    HeaderValue::from_str(&user_agent)
+        .map_err(|e| format!("Invalid user agent '{}': {}", user_agent, e))?;
 
-    Ok(http_jobs)
+    Ok(Some(user_agent))
 }
 
 /// Parses the given JSON value and constructs an HTTP request.
 ///
+/// Used for both the `request` and the `fallback_request` of a job.
+///
 /// Parameters:
-/// - `value`: JSON value containing the request information.
+/// - `request`: JSON value of the request.
 ///
 /// Returns:
 /// The constructed `HttpJobRequest` instance.
@@ -81,15 +283,13 @@ pub fn get_http_jobs(value: Value) -> Result<Vec<HttpJob>, Box<dyn Error>> {
 /// use crate::http_job::HttpJobRequest;
 ///
 /// let json_value = json!({
-///     "request": {
-///         "url": "https://example.com",
-///         "method": "GET",
-///         "headers": {
-///             "Content-Type": "application/json"
-///         },
-///         "body": {
-///             "key": "value"
-///         }
+///     "url": "https://example.com",
+///     "method": "GET",
+///     "headers": {
+///         "Content-Type": "application/json"
+///     },
+///     "body": {
+///         "key": "value"
 ///     }
 /// });
 ///
@@ -98,21 +298,508 @@ pub fn get_http_jobs(value: Value) -> Result<Vec<HttpJob>, Box<dyn Error>> {
 /// println!("Method: {}", request.method);
 /// // ...
 /// ```
-fn get_http_job_request(value: &Value) -> Result<HttpJobRequest, Box<dyn Error>> {
-    let request = value.get("request")
-        .ok_or("The 'request' field is required in the JSON value.")?;
-
+fn get_http_job_request(request: &Value) -> Result<HttpJobRequest, Box<dyn Error>> {
     let url = request.get("url")
         .and_then(|u| u.as_str())
-        .ok_or("The 'url' field is required and must be a string.")?
-        .to_string();
+        .ok_or("The 'url' field is required and must be a string.")?;
+
+    // A relative URL is joined to the base URL, e.g. of a template.
+    let url = match request.get("base_url") {
+        Some(base_url) if !url.starts_with("http://") && !url.starts_with("https://") => {
+            let base_url = base_url.as_str().ok_or("The 'base_url' field must be a string.")?;
+            format!("{}/{}", base_url.trim_end_matches('/'), url.trim_start_matches('/'))
+        }
+        Some(base_url) if !base_url.is_string() => return Err("The 'base_url' field must be a string.".into()),
+        _ => url.to_string()
+    };
+
+    let graphql = request.get("graphql")
+        .map(get_graphql_body)
+        .transpose()?;
+    let body_raw = request.get("body_raw")
+        .map(|b| b.as_str().ok_or("The 'body_raw' field must be a string."))
+        .transpose()?;
 
+    let json_body = request.get("body").filter(|b| !b.is_null());
+
+    let body_fields = [graphql.is_some(), json_body.is_some(), body_raw.is_some()];
+    if body_fields.iter().filter(|set| **set).count() > 1 {
+        return Err("Only one of the 'body', 'body_raw' and 'graphql' fields can be set.".into());
+    }
+
+    // GraphQL requests are sent as POST unless the method is configured.
     let method = request.get("method")
         .and_then(|m| m.as_str())
-        .unwrap_or("GET")
+        .unwrap_or(if graphql.is_some() { "POST" } else { "GET" })
         .to_string();
 
-    let headers: Result<Option<HeaderMap>, Box<dyn Error>> = request.get("headers")
+    let (headers, removed_headers) = get_request_headers(request)?;
+
+    // A string `body` is sent as plain text, any other JSON value is serialized. Raw bodies
+    // and requests without a body have no content type unless one is configured.
+    let (body, default_content_type) = match (&graphql, body_raw, json_body) {
+        (Some(graphql), _, _) => (Some(graphql.clone()), Some(JSON_CONTENT_TYPE)),
+        (None, Some(body_raw), _) => (Some(body_raw.to_string()), None),
+        (None, None, Some(Value::String(text))) => (Some(text.clone()), Some(TEXT_CONTENT_TYPE)),
+        (None, None, Some(body)) => {
+            let body = serde_json::to_string(body)
+                .map_err(|_| "Error parsing request body.")?;
+            (Some(body), Some(JSON_CONTENT_TYPE))
+        }
+        (None, None, None) => (None, None)
+    };
+
+    let content_type = match request.get("content_type") {
+        Some(c) => Some(c.as_str()
+            .filter(|c| !c.trim().is_empty())
+            .ok_or("The 'content_type' field must be a non-empty string.")?
+            .to_string()),
+        None => default_content_type.map(|c| c.to_string())
+    };
+
+    let http_version = match request.get("http_version") {
+        Some(v) => v.as_str()
+            .and_then(HttpVersion::parse)
+            .ok_or("The 'http_version' field must be one of 'http1', 'http2' or 'http2-prior-knowledge'.")?,
+        None => HttpVersion::Auto
+    };
+
+    let compress_body = request.get("compress_body")
+        .map(|c| {
+            c.as_str()
+                .and_then(BodyCompression::parse)
+                .ok_or("The 'compress_body' field must be 'gzip'.")
+        })
+        .transpose()?;
+
+    let host = request.get("host")
+        .map(|h| {
+            h.as_str()
+                .filter(|h| !h.trim().is_empty() && HeaderValue::from_str(h).is_ok())
+                .map(|h| h.to_string())
+                .ok_or("The 'host' field must be a non-empty string.")
+        })
+        .transpose()?;
+    if host.is_some() && headers.as_ref().is_some_and(|h| h.contains_key(HOST)) {
+        return Err("The 'host' field can't be set together with a 'Host' header.".into());
+    }
+
+    let server_name = request.get("server_name")
+        .map(|s| {
+            s.as_str()
+                .filter(|s| matches!(ServerName::try_from(*s), Ok(ServerName::DnsName(_))))
+                .map(|s| s.to_string())
+                .ok_or("The 'server_name' field must be a DNS name.")
+        })
+        .transpose()?;
+
+    let accept_encoding = request.get("accept_encoding")
+        .map(|a| {
+            a.as_array()
+                .and_then(|encodings| encodings.iter()
+                    .map(|e| e.as_str().filter(|e| !e.is_empty() && e.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '*')))
+                    .map(|e| e.map(|e| e.to_lowercase()))
+                    .collect::<Option<Vec<_>>>())
+                .ok_or("The 'accept_encoding' field must be an array of content encodings, e.g. [\"gzip\", \"br\"].")
+        })
+        .transpose()?;
+    if accept_encoding.is_some() && headers.as_ref().is_some_and(|h| h.contains_key(ACCEPT_ENCODING)) {
+        return Err("The 'accept_encoding' field can't be set together with an 'Accept-Encoding' header.".into());
+    }
+
+    let request = HttpJobRequest {
+        url,
+        method,
+        headers,
+        removed_headers,
+        body,
+        content_type,
+        http_version,
+        compress_body,
+        graphql: graphql.is_some(),
+        host,
+        server_name,
+        accept_encoding,
+    };
+
+    // The server name replaces the host of the URL, which is the address to connect to.
+    if request.server_name.is_some() && request.connect_address().is_none() {
+        return Err("The 'server_name' field requires the host of the 'url' to be an IP address.".into());
+    }
+
+    Ok(request)
+}
+
+/// Parses the steps of a request pipeline, a `request` given as an array.
+///
+/// Each step has the fields of a request, an optional `name` and an optional `extract` object
+/// mapping the name of a variable to where its value is extracted from the response of the
+/// step, see `Extraction::parse`.
+///
+/// # Arguments
+///
+/// * `value` - The JSON array of the steps.
+///
+/// # Returns
+///
+/// The steps, at least one. A step without a name is named after its position, e.g. `step2`.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The array is empty, or a step is not an object.
+/// * Two steps have the same name.
+/// * The request of a step is invalid.
+/// * The 'extract' field is not an object of valid sources, or a variable name is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!([
+///     { "name": "login", "url": "https://api.example.com/login", "method": "POST", "extract": { "token": "/token" } },
+///     { "url": "https://api.example.com/users", "headers": { "Authorization": "Bearer {{token}}" } }
+/// ]);
+/// let steps = get_http_job_steps(&value)?;
+/// ```
+fn get_http_job_steps(value: &Value) -> Result<Vec<HttpJobStep>, Box<dyn Error>> {
+    let items = value.as_array()
+        .filter(|items| !items.is_empty())
+        .ok_or("The 'request' field must be an object or a non-empty array of steps.")?;
+
+    let mut steps: Vec<HttpJobStep> = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        if !item.is_object() {
+            return Err("Each step of 'request' must be an object.".into());
+        }
+
+        let name = match item.get("name") {
+            Some(n) => n.as_str()
+                .filter(|n| !n.trim().is_empty())
+                .ok_or("The 'name' field of a step must be a non-empty string.")?
+                .to_string(),
+            None => format!("step{}", index + 1)
+        };
+        if steps.iter().any(|s| s.name == name) {
+            return Err(format!("The step name '{}' is used more than once.", name).into());
+        }
+
+        let request = get_http_job_request(item)
+            .map_err(|e| format!("Invalid step '{}': {}", name, e))?;
+
+        let extract = match item.get("extract") {
+            Some(Value::Object(map)) => map.iter()
+                .map(|(variable, source)| {
+                    if variable.is_empty() || !variable.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                        return Err(format!("Invalid variable name '{}' in 'extract' of step '{}', expected letters, digits and '_'.", variable, name));
+                    }
+                    source.as_str()
+                        .and_then(Extraction::parse)
+                        .map(|extraction| (variable.clone(), extraction))
+                        .ok_or(format!("Invalid source of '{}' in 'extract' of step '{}', expected 'body', a JSON pointer such as '/data/token' or 'header:<name>'.", variable, name))
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            Some(_) => return Err(format!("The 'extract' field of step '{}' must be an object.", name).into()),
+            None => Vec::new()
+        };
+
+        steps.push(HttpJobStep::new(name, request, extract));
+    }
+
+    Ok(steps)
+}
+
+/// Parses the optional `tls` object of an HTTP job.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value of the job.
+///
+/// # Returns
+///
+/// The TLS settings of the job, unrestricted if the field is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'tls' field is not an object.
+/// * The 'min_version' or 'max_version' field is not '1.0', '1.1', '1.2' or '1.3'.
+/// * The 'min_version' is higher than the 'max_version'.
+/// * The versions allow TLS 1.0 or 1.1 and the certificates are pinned, see
+///   `TlsOptions::allows_legacy_versions`.
+/// * The 'pinned_spki_sha256' field is not a non-empty array of base64 encoded SHA-256 hashes.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "tls": { "min_version": "1.3" } });
+/// let tls = get_tls_options(&value)?;
+/// assert_eq!(tls.min_version, Some(TlsVersion::Tls1_3));
+/// ```
+fn get_tls_options(value: &Value) -> Result<TlsOptions, Box<dyn Error>> {
+    let tls = match value.get("tls") {
+        Some(t) => t,
+        None => return Ok(TlsOptions::default())
+    };
+    if !tls.is_object() {
+        return Err("The 'tls' field must be an object.".into());
+    }
+
+    let version = |field: &str| {
+        tls.get(field)
+            .map(|v| {
+                v.as_str()
+                    .and_then(TlsVersion::parse)
+                    .ok_or(format!("The '{}' field of 'tls' must be '1.0', '1.1', '1.2' or '1.3'.", field))
+            })
+            .transpose()
+    };
+    let min_version = version("min_version")?;
+    let max_version = version("max_version")?;
+
+    if let (Some(min_version), Some(max_version)) = (min_version, max_version) {
+        if min_version > max_version {
+            return Err(format!("The 'min_version' {} of 'tls' is higher than its 'max_version' {}.", min_version, max_version).into());
+        }
+    }
+
+    let pinned_spki_sha256 = match tls.get("pinned_spki_sha256") {
+        Some(Value::Array(pins)) if !pins.is_empty() => pins.iter()
+            .map(|pin| {
+                pin.as_str()
+                    .and_then(|pin| STANDARD.decode(pin).ok())
+                    .and_then(|hash| <[u8; 32]>::try_from(hash).ok())
+                    .ok_or(format!("Invalid pin {} in 'pinned_spki_sha256', expected the base64 encoded SHA-256 hash of a public key.", pin).into())
+            })
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?,
+        Some(_) => return Err("The 'pinned_spki_sha256' field of 'tls' must be a non-empty array of strings.".into()),
+        None => Vec::new()
+    };
+
+    let tls = TlsOptions::new(min_version, max_version, pinned_spki_sha256);
+    // The pins are checked by a certificate verifier of rustls, which doesn't implement the
+    // versions before TLS 1.2.
+    if tls.allows_legacy_versions() && !tls.pinned_spki_sha256.is_empty() {
+        return Err("The 'pinned_spki_sha256' field of 'tls' is not supported with TLS 1.0 or 1.1.".into());
+    }
+
+    Ok(tls)
+}
+
+/// Parses a duration of the connections of a job, `pool_idle_timeout` or `tcp_keepalive`.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value of the job, or of the `defaults`.
+/// * `field` - The name of the field.
+///
+/// # Returns
+///
+/// The duration, or `None` if the field is missing.
+///
+/// # Errors
+///
+/// Returns an error if the field is not a non-zero duration.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "pool_idle_timeout": "30s" });
+/// assert_eq!(get_connection_duration(&value, "pool_idle_timeout")?, Some(Duration::from_secs(30)));
+/// ```
+pub fn get_connection_duration(value: &Value, field: &str) -> Result<Option<Duration>, Box<dyn Error>> {
+    value.get(field)
+        .map(|d| {
+            d.as_str()
+                .and_then(parse_duration)
+                .filter(|d| !d.is_zero())
+                .ok_or(format!("The '{}' field must be a duration like '90s'.", field).into())
+        })
+        .transpose()
+}
+
+/// Parses the `headers` of a request.
+///
+/// A `null` value removes the default header of the same name, see `defaults`, instead of
+/// setting a header.
+///
+/// # Returns
+///
+/// The headers of the request, or `None` if the `headers` field is missing, and the names of the
+/// removed headers.
+fn get_request_headers(request: &Value) -> Result<(Option<HeaderMap>, Vec<HeaderName>), Box<dyn Error>> {
+    let Some(map) = request.get("headers").and_then(|h| h.as_object()) else {
+        return Ok((None, vec![]));
+    };
+
+    let mut header_map = HeaderMap::new();
+    let mut removed_headers = Vec::new();
+    for (k, v) in map {
+        let k = HeaderName::try_from(k)?;
+        if v.is_null() {
+            removed_headers.push(k);
+            continue;
+        }
+        let v = v.as_str().ok_or("The value of the header must be a string or null.")?;
+        header_map.append(k, HeaderValue::try_from(v)?);
+    }
+
+    Ok((Some(header_map), removed_headers))
+}
+
+/// Builds the JSON body of a GraphQL request from the `graphql` field of a request.
+///
+/// Parameters:
+/// - `value`: JSON value of the `graphql` field, with a `query` and optional `variables` and
+///   `operation_name`.
+///
+/// Returns:
+/// The encoded `{"query", "variables", "operationName"}` envelope.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({
+///     "query": "query User($id: ID!) { user(id: $id) { name } }",
+///     "variables": { "id": "2" }
+/// });
+///
+/// let body = get_graphql_body(&value)?;
+/// println!("Body: {}", body);
+/// ```
+fn get_graphql_body(value: &Value) -> Result<String, Box<dyn Error>> {
+    let query = value.get("query")
+        .and_then(|q| q.as_str())
+        .ok_or("The 'query' field of 'graphql' is required and must be a string.")?;
+
+    let mut envelope = serde_json::Map::new();
+    envelope.insert("query".to_string(), Value::from(query));
+
+    if let Some(variables) = value.get("variables") {
+        if !variables.is_object() {
+            return Err("The 'variables' field of 'graphql' must be an object.".into());
+        }
+        envelope.insert("variables".to_string(), variables.clone());
+    }
+
+    if let Some(operation_name) = value.get("operation_name") {
+        let operation_name = operation_name.as_str()
+            .ok_or("The 'operation_name' field of 'graphql' must be a string.")?;
+        envelope.insert("operationName".to_string(), Value::from(operation_name));
+    }
+
+    Ok(serde_json::to_string(&envelope)?)
+}
+
+/// Parses the optional `response` field of an HTTP job.
+///
+/// Parameters:
+/// - `value`: JSON value of the HTTP job.
+///
+/// Returns:
+/// The constructed `HttpJobResponse` instance. If the `response` field is missing, the default
+/// response settings are returned.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let json_value = json!({
+///     "response": {
+///         "save_to": "./out/{{job_name}}-{{now}}.json",
+///         "max_response_bytes": 1048576,
+///         "log_response": "truncated",
+///         "log_headers": true
+///     }
+/// });
+///
+/// let response = get_http_job_response(&json_value)?;
+/// println!("Save to: {:?}", response.save_to);
+/// ```
+fn get_http_job_response(value: &Value) -> Result<HttpJobResponse, Box<dyn Error>> {
+    let response = match value.get("response") {
+        Some(r) => r,
+        None => return Ok(HttpJobResponse::default())
+    };
+
+    let save_to = response.get("save_to")
+        .map(|s| s.as_str().ok_or("The 'save_to' field must be a string."))
+        .transpose()?
+        .map(|s| s.to_string());
+
+    let max_response_bytes = response.get("max_response_bytes")
+        .map(|m| m.as_u64().ok_or("The 'max_response_bytes' field must be a non-negative integer."))
+        .transpose()?
+        .unwrap_or(DEFAULT_MAX_RESPONSE_BYTES);
+
+    let log_response = match response.get("log_response") {
+        Some(l) => l.as_str()
+            .and_then(ResponseLogging::parse)
+            .ok_or("The 'log_response' field must be one of 'none', 'status', 'truncated' or 'full'.")?,
+        None => ResponseLogging::default()
+    };
+
+    let log_response_bytes = response.get("log_response_bytes")
+        .map(|l| l.as_u64().filter(|l| *l > 0).ok_or("The 'log_response_bytes' field must be a positive integer."))
+        .transpose()?
+        .map(|l| l as usize)
+        .unwrap_or(DEFAULT_LOG_RESPONSE_BYTES);
+
+    let log_headers = response.get("log_headers")
+        .map(|l| l.as_bool().ok_or("The 'log_headers' field must be a boolean."))
+        .transpose()?
+        .unwrap_or(false);
+
+    let discard_body = response.get("discard_body")
+        .map(|d| d.as_bool().ok_or("The 'discard_body' field must be a boolean."))
+        .transpose()?
+        .unwrap_or(false);
+    if discard_body && save_to.is_some() {
+        return Err("The 'save_to' and 'discard_body' fields of 'response' can't be set together.".into());
+    }
+
+    let decompress = response.get("decompress")
+        .map(|d| d.as_bool().ok_or("The 'decompress' field must be a boolean."))
+        .transpose()?
+        .unwrap_or(false);
+
+    Ok(HttpJobResponse::new(save_to, max_response_bytes, log_response, log_response_bytes, log_headers, discard_body, decompress))
+}
+
+/// Parses the optional `headers` object of the given JSON value into a `HeaderMap`.
+///
+/// Parameters:
+/// - `value`: JSON value containing the `headers` field.
+///
+/// Returns:
+/// The parsed headers, or `None` if the `headers` field is missing or not an object.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let json_value = json!({
+///     "headers": {
+///         "Authorization": "Bearer token"
+///     }
+/// });
+///
+/// let headers = get_headers(&json_value)?;
+/// println!("Headers: {:?}", headers);
+/// ```
+pub fn get_headers(value: &Value) -> Result<Option<HeaderMap>, Box<dyn Error>> {
+    value.get("headers")
         .and_then(|h| h.as_object())
         .map(|map| {
             let mut header_map = HeaderMap::new();
@@ -124,15 +811,5 @@ fn get_http_job_request(value: &Value) -> Result<HttpJobRequest, Box<dyn Error>>
             }
             Ok(header_map)
         })
-        .transpose();
-
-    let body = request.get("body")
-        .and_then(|b| b.as_object())
-        .map(|body| {
-            serde_json::to_string(body)
-                .map_err(|_| format!("Error parsing request body."))
-        })
-        .transpose();
-
-    Ok(HttpJobRequest::new(url, method, headers?, body?))
+        .transpose()
 }
\ No newline at end of file
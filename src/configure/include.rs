@@ -0,0 +1,180 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde_json::{Map, Value};
+
+use crate::configure::migrations::migrate;
+use crate::configure::sops::{decrypt_document, is_encrypted};
+
+/// Resolves the `include` directives of a 'jobs' document, recursively.
+///
+/// `include` is a path or an array of paths, relative to the directory of the including file.
+/// A path may be a glob pattern like `./teams/*.yaml`, whose matches are included in
+/// alphabetical order. Each included file is JSON or YAML by its extension, is migrated to the
+/// current version on its own and may include other files.
+///
+/// The included documents are merged in order, then the including document is merged on top:
+/// arrays such as `http_jobs` are concatenated, objects such as `notifications` are merged
+/// field by field, and other values of the including document override the included ones. A
+/// file included more than once, e.g. by two team files, is only merged the first time.
+///
+/// # Arguments
+///
+/// * `value` - The migrated document of the file, replaced with the merged document.
+/// * `file` - The path of the file.
+///
+/// # Errors
+///
+/// Returns an error if `include` is not a path or an array of paths, an included file doesn't
+/// exist or cannot be parsed, or the files include each other in a cycle.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let mut value = json!({ "include": ["./common/auth.yaml", "./teams/*.yaml"], "timezone": "UTC" });
+/// resolve_includes(&mut value, Path::new("./jobs.yaml"))?;
+/// ```
+pub fn resolve_includes(value: &mut Value, file: &Path) -> Result<(), Box<dyn Error>> {
+    let canonical = fs::canonicalize(file)
+        .map_err(|e| format!("Failed to resolve the file '{}': {}", file.display(), e))?;
+    let mut chain = vec![(canonical.clone(), file.display().to_string())];
+    let mut included = HashSet::from([canonical]);
+
+    resolve(value, file, &mut chain, &mut included)
+}
+
+/// Resolves the `include` directive of a document.
+///
+/// # Arguments
+///
+/// * `chain` - The canonical and displayed paths of the files including this one, and itself.
+/// * `included` - The canonical paths of the files included so far.
+fn resolve(
+    value: &mut Value,
+    file: &Path,
+    chain: &mut Vec<(PathBuf, String)>,
+    included: &mut HashSet<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    let include = match value.as_object_mut().and_then(|o| o.remove("include")) {
+        Some(i) => i,
+        None => return Ok(())
+    };
+    let patterns: Vec<&str> = match &include {
+        Value::String(pattern) => vec![pattern.as_str()],
+        Value::Array(patterns) => patterns.iter()
+            .map(|p| p.as_str().ok_or(format!("The 'include' field of '{}' must be a path or an array of paths.", file.display())))
+            .collect::<Result<_, _>>()?,
+        _ => return Err(format!("The 'include' field of '{}' must be a path or an array of paths.", file.display()).into())
+    };
+
+    let base = file.parent().unwrap_or(Path::new("."));
+    let mut merged = Value::Object(Map::new());
+
+    for pattern in patterns {
+        for path in expand_pattern(base, pattern)? {
+            let canonical = fs::canonicalize(&path)
+                .map_err(|e| format!("Failed to resolve the included file '{}': {}", path.display(), e))?;
+
+            if chain.iter().any(|(c, _)| *c == canonical) {
+                let cycle = chain.iter()
+                    .map(|(_, display)| display.clone())
+                    .chain([path.display().to_string()])
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                return Err(format!("Include cycle detected: {}", cycle).into());
+            }
+            if !included.insert(canonical.clone()) {
+                continue;
+            }
+
+            let mut document = read_document(&path)?;
+            chain.push((canonical, path.display().to_string()));
+            resolve(&mut document, &path, chain, included)?;
+            chain.pop();
+
+            merge(&mut merged, document);
+        }
+    }
+
+    let own = std::mem::replace(value, merged);
+    merge(value, own);
+
+    Ok(())
+}
+
+/// Expands an include path relative to a directory into the paths of the files to include.
+///
+/// A glob pattern may match no file, a plain path must exist.
+fn expand_pattern(base: &Path, pattern: &str) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    // Collecting the components drops the `.` components of joined relative paths.
+    let path: PathBuf = base.join(pattern).components().collect();
+
+    if !pattern.contains(['*', '?', '[']) {
+        if !path.is_file() {
+            return Err(format!("The included file '{}' doesn't exist.", path.display()).into());
+        }
+        return Ok(vec![path]);
+    }
+
+    let pattern = path.to_str().ok_or(format!("The include pattern '{}' is not valid UTF-8.", path.display()))?;
+    let paths = glob::glob(pattern)
+        .map_err(|e| format!("Invalid include pattern '{}': {}", pattern, e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to expand the include pattern '{}': {}", pattern, e))?;
+
+    Ok(paths.into_iter().filter(|p| p.is_file()).collect())
+}
+
+/// Reads, parses and migrates an included file.
+///
+/// Files ending with `.yaml` or `.yml` are parsed as YAML, other files as JSON. A file
+/// encrypted with SOPS is decrypted, see `decrypt_document`.
+fn read_document(path: &Path) -> Result<Value, Box<dyn Error>> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("An error occurred while reading the file '{}': {}", path.display(), e))?;
+
+    let is_yaml = path.extension().is_some_and(|e| e == "yaml" || e == "yml");
+    let mut document: Value = if is_yaml {
+        serde_yaml::from_str(&content)
+            .map_err(|e| format!("An error occurred while parsing the '{}' file: {}", path.display(), e))?
+    } else {
+        serde_json::from_str(&content)
+            .map_err(|e| format!("An error occurred while parsing the '{}' file: {}", path.display(), e))?
+    };
+    if is_encrypted(&document) {
+        document = decrypt_document(&content)
+            .map_err(|e| format!("Failed to decrypt the '{}' file: {}", path.display(), e))?;
+    }
+
+    let migrations = migrate(&mut document)
+        .map_err(|e| format!("{} ({})", e, path.display()))?;
+    for (from, description) in migrations {
+        println!("Migrated the included file '{}' from version {} to {}: {}.", path.display(), from, from + 1, description);
+    }
+
+    Ok(document)
+}
+
+/// Merges a document into another.
+///
+/// Arrays are concatenated and objects are merged field by field, any other value of `overlay`
+/// replaces the value of `base`.
+fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Array(base), Value::Array(overlay)) => base.extend(overlay),
+        (base, overlay) => *base = overlay,
+    }
+}
\ No newline at end of file
@@ -0,0 +1,83 @@
+use std::error::Error;
+use serde_json::Value;
+
+use crate::configure::groups::{get_job_group, get_job_priority};
+use crate::configure::hooks::get_job_hooks;
+use crate::configure::notifications::get_job_notifications;
+use crate::configure::tags::get_job_tags;
+use crate::models::catch_up::CatchUp;
+use crate::models::heartbeat::Heartbeat;
+use crate::models::job_options::JobOptions;
+
+/// Parses the options shared by the jobs of every type.
+///
+/// # Arguments
+///
+/// * `it` - The JSON value of the job.
+///
+/// # Returns
+///
+/// The `JobOptions` of the job, the defaults for the missing fields.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'max_runs' field is not a positive integer.
+/// * The 'heartbeat' field has no 'url' string, or its 'on_failure' field is not a boolean.
+/// * The 'catch_up' field is not an object, or its 'limit' field is not a positive integer.
+/// * The 'notifications', 'on_success', 'on_failure', 'tags', 'group' or 'priority' field is
+///   invalid.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "name": "postgres", "max_runs": 10, "catch_up": { "limit": 3 } });
+/// let options = get_job_options(&value)?;
+/// assert_eq!(options.max_runs, Some(10));
+/// ```
+pub fn get_job_options(it: &Value) -> Result<JobOptions, Box<dyn Error>> {
+    let dry_run = it.get("dry_run")
+        .and_then(|d| d.as_bool())
+        .unwrap_or(false);
+
+    let max_runs = it.get("max_runs")
+        .map(|m| m.as_u64().filter(|m| *m > 0).ok_or("The 'max_runs' field must be a positive integer."))
+        .transpose()?;
+
+    let catch_up = it.get("catch_up")
+        .map(|c| {
+            if !c.is_object() {
+                return Err("The 'catch_up' field must be an object.".into());
+            }
+            let max_runs = c.get("limit")
+                .map(|l| l.as_u64().filter(|l| *l > 0).ok_or("The 'limit' field of 'catch_up' must be a positive integer."))
+                .transpose()?
+                .unwrap_or(1);
+            Ok::<_, Box<dyn Error>>(CatchUp::new(max_runs as u32))
+        })
+        .transpose()?;
+
+    let heartbeat = it.get("heartbeat")
+        .map(|h| {
+            let url = h.get("url")
+                .and_then(|u| u.as_str())
+                .ok_or("The 'url' field of 'heartbeat' is required and must be a string.")?;
+            let ping_on_failure = h.get("on_failure")
+                .map(|p| p.as_bool().ok_or("The 'on_failure' field of 'heartbeat' must be a boolean."))
+                .transpose()?
+                .unwrap_or(false);
+            Ok::<_, Box<dyn Error>>(Heartbeat::new(url.to_string(), ping_on_failure))
+        })
+        .transpose()?;
+
+    let notifications = get_job_notifications(it)?;
+    let hooks = get_job_hooks(it)?;
+    let tags = get_job_tags(it)?;
+    let group = get_job_group(it)?;
+    let priority = get_job_priority(it)?;
+
+    Ok(JobOptions::new(dry_run, max_runs, catch_up, heartbeat, notifications, hooks, tags, group, priority))
+}
\ No newline at end of file
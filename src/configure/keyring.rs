@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Mutex;
+use std::thread;
+use keyring::Entry;
+use once_cell::sync::Lazy;
+use serde_json::Value;
+
+use crate::utils::redact_util::register_secret;
+
+/// The start of a reference to a secret of the OS keyring, e.g. `${keyring:rjob/github}`.
+const KEYRING_REFERENCE: &str = "${keyring:";
+
+/// The secrets read from the keyring so far, by reference, so each one is only read once, e.g.
+/// when the macOS Keychain asks the user to allow the access.
+static SECRETS: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Replaces the references to secrets of the OS keyring in the strings of a 'jobs' document.
+///
+/// A reference `${keyring:SERVICE/ACCOUNT}` is replaced with the password of the service and
+/// account in the keyring of the platform: the Secret Service on Linux, e.g. GNOME Keyring or
+/// KWallet, the Keychain on macOS and the Credential Manager on Windows. A string may hold
+/// several references next to other text, e.g. `Bearer ${keyring:rjob/github}`. The secrets
+/// are registered with `register_secret`, so they are redacted from the logs.
+///
+/// # Arguments
+///
+/// * `value` - The document or job value, whose strings are replaced in place.
+///
+/// # Errors
+///
+/// Returns an error if a reference is malformed, or its secret is not in the keyring or cannot
+/// be read.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let mut value = json!({ "headers": { "Authorization": "Bearer ${keyring:rjob/github}" } });
+/// resolve_keyring_references(&mut value)?;
+/// ```
+pub fn resolve_keyring_references(value: &mut Value) -> Result<(), Box<dyn Error>> {
+    match value {
+        Value::String(s) if s.contains(KEYRING_REFERENCE) => *s = resolve_string(s)?,
+        Value::Array(items) => items.iter_mut().try_for_each(resolve_keyring_references)?,
+        Value::Object(object) => object.values_mut().try_for_each(resolve_keyring_references)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replaces the keyring references of a string.
+fn resolve_string(value: &str) -> Result<String, Box<dyn Error>> {
+    let mut resolved = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find(KEYRING_REFERENCE) {
+        let reference = &rest[start + KEYRING_REFERENCE.len()..];
+        let end = reference.find('}')
+            .ok_or(format!("The keyring reference in '{}' has no closing '}}'.", value))?;
+
+        resolved.push_str(&rest[..start]);
+        resolved.push_str(&get_secret(&reference[..end])?);
+        rest = &reference[end + 1..];
+    }
+
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+/// Reads the secret of a `SERVICE/ACCOUNT` reference from the keyring, or from the secrets
+/// read before.
+fn get_secret(reference: &str) -> Result<String, Box<dyn Error>> {
+    if let Some(secret) = SECRETS.lock().unwrap().get(reference) {
+        return Ok(secret.clone());
+    }
+
+    let (service, account) = reference.split_once('/')
+        .filter(|(service, account)| !service.is_empty() && !account.is_empty())
+        .ok_or(format!("Invalid keyring reference '{}{}}}', expected '{}SERVICE/ACCOUNT}}'.", KEYRING_REFERENCE, reference, KEYRING_REFERENCE))?;
+    // Read on a thread of its own, as the Secret Service client blocks on a runtime of its own,
+    // which cannot be started on a thread of the runtime of the scheduler.
+    let secret = thread::scope(|scope| scope.spawn(|| Entry::new(service, account)?.get_password()).join())
+        .map_err(|_| "The OS keyring client panicked.")?
+        .map_err(|e| match e {
+            keyring::Error::NoEntry => format!("No secret of the service '{}' and the account '{}' in the OS keyring.", service, account),
+            e => format!("Failed to read the secret of the service '{}' and the account '{}' from the OS keyring: {}", service, account, e),
+        })?;
+
+    register_secret(&secret);
+    SECRETS.lock().unwrap().insert(reference.to_string(), secret.clone());
+    Ok(secret)
+}
\ No newline at end of file
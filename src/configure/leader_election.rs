@@ -0,0 +1,69 @@
+use std::error::Error;
+use std::time::Duration;
+use serde_json::Value;
+use crate::models::distributed_lock::RedisServer;
+use crate::models::leader_election::{DEFAULT_LEADER_KEY, DEFAULT_LEADER_LEASE, LeaderElection};
+use crate::utils::duration_util::parse_duration;
+
+/// The shortest lease of the leader, so the lease is renewed at most once per second.
+const MIN_LEADER_LEASE: Duration = Duration::from_secs(3);
+
+/// Parses the optional `leader_election` section of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The election of the instances of the scheduler, or `None` if the section is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'leader_election' field is not an object.
+/// * The 'redis' field is missing or is not a valid `redis://` URL.
+/// * The 'key' field is not a non-empty string.
+/// * The 'lease' field is not a duration of at least 3 seconds.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "leader_election": { "redis": "redis://10.0.0.5:6379", "lease": "15s" } });
+/// let leader_election = get_leader_election(&value)?;
+/// println!("Leader election: {:?}", leader_election);
+/// ```
+pub fn get_leader_election(value: &Value) -> Result<Option<LeaderElection>, Box<dyn Error>> {
+    let election = match value.get("leader_election") {
+        Some(e) => e,
+        None => return Ok(None)
+    };
+    if !election.is_object() {
+        return Err("The 'leader_election' field must be an object.".into());
+    }
+
+    let redis = election.get("redis")
+        .and_then(|r| r.as_str())
+        .ok_or("The 'redis' field of the leader election is required and must be a string.")?;
+    let redis = RedisServer::parse(redis)?;
+
+    let key = match election.get("key") {
+        Some(k) => k.as_str()
+            .filter(|k| !k.is_empty())
+            .ok_or("The 'key' field of the leader election must be a non-empty string.")?,
+        None => DEFAULT_LEADER_KEY
+    };
+
+    let lease = match election.get("lease") {
+        Some(l) => l.as_str()
+            .and_then(parse_duration)
+            .filter(|l| *l >= MIN_LEADER_LEASE)
+            .ok_or("The 'lease' field of the leader election must be a duration of at least '3s'.")?,
+        None => DEFAULT_LEADER_LEASE
+    };
+
+    Ok(Some(LeaderElection::new(redis, key.to_string(), lease)))
+}
\ No newline at end of file
@@ -0,0 +1,139 @@
+use std::error::Error;
+use serde_json::{Map, Value};
+
+/// The version of the format of the 'jobs' file read by the parser.
+pub const CURRENT_CONFIG_VERSION: u64 = 2;
+
+/// The version of a 'jobs' file without a `version` field, the format before it was introduced.
+const UNVERSIONED_CONFIG_VERSION: u64 = 1;
+
+/// An upgrade of the 'jobs' file from one version to the next.
+struct Migration {
+    /// The version the migration upgrades from, to `from + 1`.
+    from: u64,
+    /// A short description of the change, logged when the migration is applied.
+    description: &'static str,
+    apply: fn(&mut Value) -> Result<(), Box<dyn Error>>,
+}
+
+/// The migrations in order, one for every version before `CURRENT_CONFIG_VERSION`.
+const MIGRATIONS: [Migration; 1] = [
+    Migration {
+        from: 1,
+        description: "moved 'heartbeat_url' and 'heartbeat_on_failure' of HTTP jobs into 'heartbeat', and 'catch_up_limit' into 'catch_up'",
+        apply: migrate_heartbeat_and_catch_up,
+    },
+];
+
+/// Upgrades a 'jobs' document to `CURRENT_CONFIG_VERSION`.
+///
+/// The `version` field of the document tells which migrations to apply, a document without it
+/// is version 1. The migrations are applied in order and the `version` field is set to the
+/// current version, so older files keep working when the format changes.
+///
+/// # Arguments
+///
+/// * `value` - The parsed 'jobs' document, upgraded in place.
+///
+/// # Returns
+///
+/// The descriptions of the applied migrations with the version they upgraded from, empty if
+/// the document already was at the current version.
+///
+/// # Errors
+///
+/// Returns an error if the `version` field is not a positive integer, is newer than the current
+/// version, or a migration fails.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let mut value = json!({ "http_jobs": [{ "name": "sync", "heartbeat_url": "https://hc-ping.com/abc" }] });
+/// let applied = migrate(&mut value)?;
+/// assert_eq!(value["version"], 2);
+/// assert_eq!(value["http_jobs"][0]["heartbeat"]["url"], "https://hc-ping.com/abc");
+/// ```
+pub fn migrate(value: &mut Value) -> Result<Vec<(u64, &'static str)>, Box<dyn Error>> {
+    let object = value.as_object_mut().ok_or("The 'jobs' file must contain an object.")?;
+
+    let version = match object.get("version") {
+        Some(v) => v.as_u64()
+            .filter(|v| *v > 0)
+            .ok_or("The 'version' field must be a positive integer.")?,
+        None => UNVERSIONED_CONFIG_VERSION
+    };
+    if version > CURRENT_CONFIG_VERSION {
+        return Err(format!(
+            "The 'jobs' file has version {}, but this rjob only supports up to version {}. Please upgrade rjob.",
+            version, CURRENT_CONFIG_VERSION
+        ).into());
+    }
+
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.from >= version) {
+        (migration.apply)(value)
+            .map_err(|e| format!("Failed to migrate the 'jobs' file from version {}: {}", migration.from, e))?;
+        applied.push((migration.from, migration.description));
+    }
+
+    if let Some(object) = value.as_object_mut() {
+        object.insert("version".to_string(), Value::from(CURRENT_CONFIG_VERSION));
+    }
+
+    Ok(applied)
+}
+
+/// Migrates version 1 to 2.
+///
+/// `heartbeat_url` and `heartbeat_on_failure` become the `url` and `on_failure` fields of a
+/// `heartbeat` object. `catch_up: true` with the optional `catch_up_limit` becomes a
+/// `catch_up` object with a `limit`, and `catch_up: false` is removed.
+fn migrate_heartbeat_and_catch_up(value: &mut Value) -> Result<(), Box<dyn Error>> {
+    for_each_job(value, "http_jobs", |job| {
+        let url = job.remove("heartbeat_url");
+        let on_failure = job.remove("heartbeat_on_failure");
+        if let Some(url) = url {
+            let mut heartbeat = Map::new();
+            heartbeat.insert("url".to_string(), url);
+            if let Some(on_failure) = on_failure {
+                heartbeat.insert("on_failure".to_string(), on_failure);
+            }
+            job.insert("heartbeat".to_string(), Value::Object(heartbeat));
+        }
+
+        let limit = job.remove("catch_up_limit");
+        match job.remove("catch_up") {
+            Some(Value::Bool(true)) => {
+                let mut catch_up = Map::new();
+                if let Some(limit) = limit {
+                    catch_up.insert("limit".to_string(), limit);
+                }
+                job.insert("catch_up".to_string(), Value::Object(catch_up));
+            }
+            Some(Value::Bool(false)) | None => {}
+            Some(_) => return Err("The 'catch_up' field must be a boolean.".into())
+        }
+
+        Ok(())
+    })
+}
+
+/// Applies a change to every job object of an array of the document.
+///
+/// Elements that are not objects are left for the parser to report.
+fn for_each_job<F>(value: &mut Value, field: &str, mut change: F) -> Result<(), Box<dyn Error>>
+where
+    F: FnMut(&mut Map<String, Value>) -> Result<(), Box<dyn Error>>,
+{
+    let Some(jobs) = value.get_mut(field).and_then(|j| j.as_array_mut()) else {
+        return Ok(());
+    };
+
+    for job in jobs.iter_mut().filter_map(|j| j.as_object_mut()) {
+        change(job)?;
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,61 +1,340 @@
 use std::error::Error;
 use std::{fs, process};
+use std::net::SocketAddr;
 use std::path::Path;
 use std::str::FromStr;
+use std::sync::{Arc, RwLock};
 use chrono_tz::Tz;
+use reqwest::header::{HeaderName, HeaderValue};
 use once_cell::sync::Lazy;
 
 use serde_json::Value;
-use crate::configure::http_jobs::get_http_jobs;
+use crate::configure::http_jobs::{get_http_job, get_http_jobs, get_user_agent};
+use crate::configure::notifications::get_notifications;
+use crate::configure::defaults::{apply_defaults, get_default_headers, get_defaults};
+use crate::configure::groups::{get_groups, validate_job_groups};
+use crate::configure::hooks::validate_hook_targets;
+use crate::configure::include::resolve_includes;
+use crate::configure::keyring::resolve_keyring_references;
+use crate::configure::leader_election::get_leader_election;
+use crate::configure::run_archive::get_run_archive;
+use crate::configure::status_file::get_status_file;
+use crate::configure::watchdog::get_watchdog;
+use crate::configure::dns_cache::get_dns_cache;
+use crate::configure::migrations::migrate;
+use crate::configure::backup_jobs::get_backup_jobs;
+use crate::configure::blackouts::get_blackouts;
+use crate::configure::config_source::get_source_document;
+use crate::configure::distributed_lock::get_distributed_lock;
+use crate::configure::cleanup_jobs::get_cleanup_jobs;
+use crate::configure::db_jobs::get_db_jobs;
+use crate::configure::dns_jobs::get_dns_jobs;
+use crate::configure::s3_jobs::get_s3_jobs;
+use crate::configure::sftp_jobs::get_sftp_jobs;
+use crate::configure::sharding::get_sharding;
+use crate::configure::sops::{decrypt_document, is_encrypted};
+use crate::configure::tcp_jobs::get_tcp_jobs;
+use crate::configure::websocket_jobs::get_websocket_jobs;
+use crate::configure::rate_limits::{get_max_requests_per_second, get_rate_limits};
+use crate::configure::templates::{extend_job, get_templates};
+use crate::models::config_source::ConfigSource;
+use crate::models::correlation_header::{CorrelationHeader, DEFAULT_CORRELATION_VALUE};
+use crate::models::http_job::HttpJob;
 use crate::models::jobs::Jobs;
+use crate::models::on_invalid_job::OnInvalidJob;
+use crate::models::run_id_format::RunIdFormat;
+use crate::models::run_queue::{DEFAULT_RUN_QUEUE_SIZE, RunQueue, WhenFull};
+use crate::models::telemetry::Telemetry;
+use crate::utils::redact_util::{register_secret, set_sensitive_headers};
 
+mod blackouts;
+pub mod config_source;
+mod defaults;
+mod distributed_lock;
+mod dns_cache;
+mod groups;
+mod holidays;
+mod hooks;
 mod http_jobs;
+mod include;
+mod job_options;
+mod keyring;
+mod leader_election;
+mod migrations;
+mod notifications;
+mod rate_limits;
+pub mod reload;
+mod run_archive;
+mod schedules;
+mod sharding;
+mod sops;
+mod status_file;
+mod tags;
+mod templates;
+mod watchdog;
+pub mod schema;
+mod backup_jobs;
+mod cleanup_jobs;
+mod db_jobs;
+mod dns_jobs;
+mod s3_jobs;
+mod sftp_jobs;
+mod tcp_jobs;
+mod websocket_jobs;
+
+/// The 'jobs' files searched in the current directory, in order.
+const JOBS_FILES: [&str; 3] = ["./jobs.json", "./jobs.yaml", "./jobs.yml"];
+
+/// The state file used when no `state_file` is configured.
+const DEFAULT_STATE_FILE: &str = "./rjob_state.json";
+
+/// The user agent of HTTP requests when none is configured.
+const DEFAULT_USER_AGENT: &str = "rjob";
 
 /// The lazy-initialized `Jobs` instance.
 ///
 /// This static variable holds the lazily initialized `Jobs` instance using the `Lazy` type
 /// from the `once_cell` crate. The `Jobs` instance is initialized by calling the `init_read_jobs`
 /// function. The initialization is performed lazily, meaning that the `init_read_jobs` function
-/// is only called the first time the `JOBS` variable is accessed.
-static JOBS: Lazy<Jobs> = Lazy::new(|| init_read_jobs());
+/// is only called the first time the `JOBS` variable is accessed. It is replaced when the
+/// configuration is reloaded, see `set_jobs`.
+static JOBS: Lazy<RwLock<Arc<Jobs>>> = Lazy::new(|| RwLock::new(Arc::new(init_read_jobs())));
 
-/// Returns a reference to the initialized `Jobs` instance.
+/// Returns the initialized `Jobs` instance.
 ///
-/// This function returns a reference to the lazily initialized `Jobs` instance. The instance is
-/// created and initialized by the `init_read_jobs` function. Subsequent calls to this function
-/// will return a reference to the same `Jobs` instance without re-initializing it.
+/// This function returns the lazily initialized `Jobs` instance. The instance is created and
+/// initialized by the `init_read_jobs` function. Subsequent calls to this function will return
+/// the same `Jobs` instance without re-initializing it, until the configuration is reloaded.
+/// A caller holding the instance keeps the configuration it started with.
 ///
 /// # Returns
 ///
-/// A reference to the initialized `Jobs` instance.
-pub fn get_jobs() -> &'static Jobs {
-    &JOBS
+/// The initialized `Jobs` instance.
+pub fn get_jobs() -> Arc<Jobs> {
+    JOBS.read().unwrap().clone()
+}
+
+/// Replaces the `Jobs` instance returned by `get_jobs` with a reloaded configuration.
+///
+/// The previous instance is dropped once the runs in progress holding it end.
+///
+/// # Arguments
+///
+/// * `jobs` - The reloaded configuration, see `read_jobs`.
+fn set_jobs(jobs: Jobs) {
+    *JOBS.write().unwrap() = Arc::new(jobs);
 }
 
-/// Initializes and returns the `Jobs` instance by reading the configuration.
+/// Parses an array of jobs of the configuration, one element at a time.
+///
+/// The template a job extends and then the `defaults` of the configuration are applied before
+/// the job is parsed, so an unknown template makes the job invalid.
+///
+/// With `OnInvalidJob::Fail` the first invalid job aborts the parse. With `OnInvalidJob::Skip`
+/// every invalid job is reported on stderr with its name or position and the error, and the
+/// valid jobs are returned.
+///
+/// # Arguments
 ///
-/// This function reads the configuration, parses the timezone and HTTP jobs,
-/// and returns a fully initialized `Jobs` instance. If any errors occur during
-/// the process, appropriate error messages are printed to stderr and the program
-/// exits with a non-zero status code.
+/// * `value` - The JSON configuration value.
+/// * `field` - The name of the array, e.g. `tcp_jobs`.
+/// * `on_invalid_job` - Whether to fail or skip when a job is invalid.
+/// * `get_job` - Parses a single element of the array.
 ///
 /// # Returns
 ///
-/// The initialized `Jobs` instance.
+/// The parsed jobs, empty if the field is missing.
+///
+/// # Errors
+///
+/// Returns an error if the field is not an array, or if a job is invalid and `on_invalid_job`
+/// is `OnInvalidJob::Fail`.
+fn get_job_array<T>(
+    value: &Value,
+    field: &str,
+    on_invalid_job: OnInvalidJob,
+    get_job: fn(&Value) -> Result<T, Box<dyn Error>>,
+) -> Result<Vec<T>, Box<dyn Error>> {
+    let jobs_val = match value.get(field) {
+        Some(j) => j.as_array().ok_or(format!("The '{}' field must be an array in the JSON configuration.", field))?,
+        None => return Ok(vec![])
+    };
+
+    let templates = get_templates(value)?;
+    let defaults = get_defaults(value)?;
+    let mut jobs = Vec::new();
+
+    for (i, it) in jobs_val.iter().enumerate() {
+        let job = extend_job(it, &templates).and_then(|mut job| {
+            apply_defaults(&mut job, &defaults, field);
+            get_job(&job)
+        });
+        match job {
+            Ok(job) => jobs.push(job),
+            Err(e) if on_invalid_job == OnInvalidJob::Skip => {
+                let job = match it.get("name").and_then(|n| n.as_str()) {
+                    Some(name) => format!("'{}'", name),
+                    None => format!("#{}", i + 1)
+                };
+                eprintln!("Skipping invalid job {} of '{}': {}", job, field, e);
+            }
+            Err(e) => return Err(e)
+        }
+    }
+
+    Ok(jobs)
+}
+
+/// Parses an HTTP job with the same schema as an element of the 'http_jobs' array of the
+/// configuration.
+///
+/// The job may extend one of the templates of the configuration, and the `defaults` of the
+/// configuration apply to it. Its keyring references are replaced by the secrets, see
+/// `resolve_keyring_references`.
 ///
-/// # Panics
+/// # Arguments
 ///
-/// This function can panic under the following conditions:
+/// * `value` - The JSON value of the job.
+///
+/// # Errors
 ///
-/// * Failed to read the configure file.
-/// * Failed to parse the timezone field or the timezone is invalid.
-/// * Failed to parse the HTTP jobs.
+/// Returns an error if a field of the job is missing or invalid.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
 ///
+/// let value = json!({ "name": "users2", "cron": "*/5 * * * * ?", "request": { "url": "https://reqres.in/api/users/2" } });
+/// let http_job = parse_http_job(&value)?;
+/// ```
+pub fn parse_http_job(value: &Value) -> Result<HttpJob, Box<dyn Error>> {
+    let mut job = extend_job(value, &get_jobs().templates)?;
+    apply_defaults(&mut job, &get_jobs().defaults, "http_jobs");
+    resolve_keyring_references(&mut job)?;
+    let http_job = get_http_job(&job)?;
+
+    if let Some(group) = http_job.options.group.as_ref().filter(|g| !get_jobs().groups.contains_key(*g)) {
+        return Err(format!("The group '{}' doesn't exist.", group).into());
+    }
+
+    Ok(http_job)
+}
+
+/// Applies a change to the 'http_jobs' array of the 'jobs' file and writes the file back.
+///
+/// The file keeps its format (JSON or YAML) and is migrated to the current version, so the
+/// job values use the current format. Comments and formatting of a YAML file are not preserved.
+///
+/// # Arguments
+///
+/// * `update` - The change to apply to the array of job values.
+///
+/// # Errors
+///
+/// Returns an error if the 'jobs' file cannot be read, parsed or written, if it is encrypted with
+/// SOPS, or if the configuration is read from a configuration source instead, see
+/// `load_config_source`.
+///
+/// # Examples
+///
+/// ```
+/// update_jobs_file(|jobs| {
+///     jobs.retain(|job| job.get("name").and_then(|n| n.as_str()) != Some("login"));
+/// })?;
+/// ```
+pub fn update_jobs_file<F: FnOnce(&mut Vec<Value>)>(update: F) -> Result<(), Box<dyn Error>> {
+    if let Some(document) = get_source_document() {
+        return Err(format!("The configuration is read from {}, update it there instead.", document.source).into());
+    }
+    let file = get_jobs_file_path()?;
+    let content = read_file(file)?;
+    let is_json = file.ends_with(".json");
+
+    let mut value: Value = if is_json {
+        serde_json::from_str(&content)?
+    } else {
+        serde_yaml::from_str(&content)?
+    };
+    if is_encrypted(&value) {
+        return Err(format!("The file '{}' is encrypted with SOPS, edit it with sops instead.", file).into());
+    }
+    migrate(&mut value)?;
+
+    let jobs = value.get_mut("http_jobs")
+        .and_then(|jobs| jobs.as_array_mut())
+        .ok_or("The 'http_jobs' field must be an array in the JSON configuration.")?;
+    update(jobs);
+
+    let content = if is_json {
+        serde_json::to_string_pretty(&value)?
+    } else {
+        serde_yaml::to_string(&value)?
+    };
+    fs::write(file, content)
+        .map_err(|e| format!("An error occurred while writing the file '{}': {}", file, e))?;
+
+    Ok(())
+}
+
+/// Initializes and returns the `Jobs` instance by reading the configuration at startup.
+///
+/// This function reads the 'jobs' file, or the document of the configuration source, and
+/// parses it with `read_jobs`. If any errors occur during the process, the error message is
+/// printed to stderr and the program exits with a non-zero status code.
+///
+/// # Returns
+///
+/// The initialized `Jobs` instance.
 fn init_read_jobs() -> Jobs {
-    let value = get_value().unwrap_or_else(|e| {
-        eprintln!("Failed to read configure file: {}", e);
+    let source = get_source_document().map(|document| document.source);
+    let jobs = match get_jobs_file_content() {
+        Ok(content) => read_jobs(content, source.as_ref()),
+        Err(e) => Err(format!("Failed to read configure file: {}", e).into())
+    };
+
+    jobs.unwrap_or_else(|e| {
+        eprintln!("{}", e);
         process::exit(1);
-    });
+    })
+}
+
+/// Parses a configuration document into a `Jobs` instance.
+///
+/// This function parses the timezone, the global settings and the jobs of every type, and
+/// checks the targets of the hooks and the groups of the jobs. With `on_invalid_job: skip`,
+/// the invalid jobs are reported on stderr and left out.
+///
+/// # Arguments
+///
+/// * `content` - The content of the 'jobs' file or of the configuration source.
+/// * `source` - The configuration source the content was read from, or `None` for the 'jobs'
+///   file.
+///
+/// # Returns
+///
+/// The parsed `Jobs` instance.
+///
+/// # Errors
+///
+/// Returns an error if:
+///
+/// * The document cannot be parsed, see `get_value`.
+/// * A global setting, such as the rate limits, the run queue, the notifications, the admin
+///   server or the telemetry settings, is invalid.
+/// * A job is invalid and `on_invalid_job` is `fail`, or the document has no jobs.
+/// * A hook triggers an unknown job, or a job is in an unknown group, and `on_invalid_job`
+///   is `fail`.
+///
+/// # Examples
+///
+/// ```
+/// let content = FileContent::from(fs::read_to_string("./jobs.yaml")?, "./jobs.yaml");
+/// let jobs = read_jobs(content, None)?;
+/// println!("Jobs: {}", jobs.all_jobs().len());
+/// ```
+fn read_jobs(content: FileContent, source: Option<&ConfigSource>) -> Result<Jobs, Box<dyn Error>> {
+    let value = get_value(content, source).map_err(|e| format!("Failed to read configure file: {}", e))?;
 
     // Parse timezone
     let timezone = value
@@ -70,45 +349,496 @@ fn init_read_jobs() -> Jobs {
         Tz::UTC
     });
 
+    // Parse rate limits
+    let rate_limits = get_rate_limits(&value).map_err(|e| format!("Failed to parse rate limits: {}", e))?;
+    let max_requests_per_second = get_max_requests_per_second(&value).map_err(|e| format!("Failed to parse rate limits: {}", e))?;
+
+    // Parse groups
+    let groups = get_groups(&value).map_err(|e| format!("Failed to parse groups: {}", e))?;
+
+    // Parse run queue
+    let run_queue = get_run_queue(&value).map_err(|e| format!("Failed to parse run queue: {}", e))?;
+
+    // Parse blackouts
+    let blackouts = get_blackouts(&value, "the configuration").map_err(|e| format!("Failed to parse blackouts: {}", e))?;
+
+    // Parse distributed lock
+    let distributed_lock = get_distributed_lock(&value).map_err(|e| format!("Failed to parse distributed lock: {}", e))?;
+
+    // Parse leader election
+    let leader_election = get_leader_election(&value).map_err(|e| format!("Failed to parse leader election: {}", e))?;
+
+    // Parse sharding
+    let sharding = get_sharding(&value).map_err(|e| format!("Failed to parse sharding: {}", e))?;
+
+    // Parse dead-letter file
+    let dead_letter_file = value
+        .get("dead_letter_file")
+        .and_then(|f| f.as_str())
+        .map(|f| f.to_string());
+
+    // Parse run archive
+    let run_archive = get_run_archive(&value).map_err(|e| format!("Failed to parse run archive: {}", e))?;
+
+    // Parse state file
+    let state_file = value
+        .get("state_file")
+        .and_then(|f| f.as_str())
+        .unwrap_or(DEFAULT_STATE_FILE)
+        .to_string();
+
+    // Parse status file
+    let status_file = get_status_file(&value).map_err(|e| format!("Failed to parse status file: {}", e))?;
+
+    // Parse watchdog
+    let watchdog = get_watchdog(&value).map_err(|e| format!("Failed to parse watchdog: {}", e))?;
+
+    // Parse DNS cache
+    let dns_cache = get_dns_cache(&value).map_err(|e| format!("Failed to parse DNS cache: {}", e))?;
+
+    // Parse notifications
+    let notifications = get_notifications(&value).map_err(|e| format!("Failed to parse notifications: {}", e))?;
+
+    // Parse admin server
+    let admin_address = get_admin_address(&value).map_err(|e| format!("Failed to parse admin server: {}", e))?;
+    let admin_token = get_admin_token(&value).map_err(|e| format!("Failed to parse admin server: {}", e))?;
+
+    // Parse telemetry
+    let telemetry = get_telemetry(&value).map_err(|e| format!("Failed to parse telemetry: {}", e))?;
+
+    // Parse correlation header
+    let correlation_header = get_correlation_header(&value).map_err(|e| format!("Failed to parse correlation header: {}", e))?;
+
+    // Parse sensitive headers
+    let sensitive_headers = get_sensitive_headers(&value).map_err(|e| format!("Failed to parse sensitive headers: {}", e))?;
+    set_sensitive_headers(sensitive_headers);
+
+    // Parse user agent
+    let user_agent = get_user_agent(&value).map_err(|e| format!("Failed to parse user agent: {}", e))?.unwrap_or(DEFAULT_USER_AGENT.to_string());
+
+    // Parse run id format
+    let run_id_format = match value.get("run_id_format") {
+        Some(f) => f.as_str().and_then(RunIdFormat::parse).ok_or("Failed to parse run id format: The 'run_id_format' field must be one of 'uuid', 'uuidv7', 'ulid' or 'sequence'.")?,
+        None => RunIdFormat::default()
+    };
+
+    // Parse invalid job handling
+    let on_invalid_job = match value.get("on_invalid_job") {
+        Some(o) => o.as_str().and_then(OnInvalidJob::parse).ok_or("Failed to parse invalid job handling: The 'on_invalid_job' field must be one of 'fail' or 'skip'.")?,
+        None => OnInvalidJob::default()
+    };
+
+    // Parse templates
+    let templates = get_templates(&value).map_err(|e| format!("Failed to parse templates: {}", e))?;
+
+    // Parse defaults
+    let defaults = get_defaults(&value).map_err(|e| format!("Failed to parse defaults: {}", e))?;
+    let default_headers = get_default_headers(&value).map_err(|e| format!("Failed to parse defaults: {}", e))?;
+
     let mut job_count = 0;
 
+    // Parse WebSocket jobs
+    let websocket_jobs = get_websocket_jobs(&value, on_invalid_job).map_err(|e| format!("Failed to parse WebSocket jobs: {}", e))?;
+    job_count += websocket_jobs.len();
+
+    // Parse TCP jobs
+    let tcp_jobs = get_tcp_jobs(&value, on_invalid_job).map_err(|e| format!("Failed to parse TCP jobs: {}", e))?;
+    job_count += tcp_jobs.len();
+
+    // Parse DNS jobs
+    let dns_jobs = get_dns_jobs(&value, on_invalid_job).map_err(|e| format!("Failed to parse DNS jobs: {}", e))?;
+    job_count += dns_jobs.len();
+
+    // Parse database jobs
+    let db_jobs = get_db_jobs(&value, on_invalid_job).map_err(|e| format!("Failed to parse database jobs: {}", e))?;
+    job_count += db_jobs.len();
+
+    // Parse S3 jobs
+    let s3_jobs = get_s3_jobs(&value, on_invalid_job).map_err(|e| format!("Failed to parse S3 jobs: {}", e))?;
+    job_count += s3_jobs.len();
+
+    // Parse SFTP jobs
+    let sftp_jobs = get_sftp_jobs(&value, on_invalid_job).map_err(|e| format!("Failed to parse SFTP jobs: {}", e))?;
+    job_count += sftp_jobs.len();
+
+    // Parse cleanup jobs
+    let cleanup_jobs = get_cleanup_jobs(&value, on_invalid_job).map_err(|e| format!("Failed to parse cleanup jobs: {}", e))?;
+    job_count += cleanup_jobs.len();
+
+    // Parse backup jobs
+    let backup_jobs = get_backup_jobs(&value, on_invalid_job).map_err(|e| format!("Failed to parse backup jobs: {}", e))?;
+    job_count += backup_jobs.len();
+
     // Parse HTTP jobs
-    let http_jobs = get_http_jobs(value)
-        .and_then(|jobs| {
-            job_count += jobs.len();
-            Ok(jobs)
-        })
-        .unwrap_or(vec![]);
+    let http_jobs = get_http_jobs(&value, on_invalid_job).map_err(|e| format!("Failed to parse HTTP jobs: {}", e))?;
+    job_count += http_jobs.len();
 
     if job_count == 0 {
-        eprintln!("No jobs found in the 'jobs' file.");
-        process::exit(1);
+        return Err("No jobs found in the 'jobs' file.".into());
+    }
+
+    let jobs = Jobs::new(
+        timezone,
+        http_jobs,
+        websocket_jobs,
+        tcp_jobs,
+        dns_jobs,
+        db_jobs,
+        s3_jobs,
+        sftp_jobs,
+        cleanup_jobs,
+        backup_jobs,
+        rate_limits,
+        max_requests_per_second,
+        groups,
+        run_queue,
+        blackouts,
+        distributed_lock,
+        leader_election,
+        sharding,
+        dead_letter_file,
+        run_archive,
+        state_file,
+        status_file,
+        watchdog,
+        dns_cache,
+        notifications,
+        admin_address,
+        admin_token,
+        telemetry,
+        correlation_header,
+        user_agent,
+        run_id_format,
+        templates,
+        defaults,
+        default_headers,
+    );
+
+    // Check hook targets
+    if let Err(e) = validate_hook_targets(&jobs) {
+        if on_invalid_job == OnInvalidJob::Fail {
+            return Err(format!("Failed to parse hooks: {}", e).into());
+        }
+        eprintln!("Invalid hooks: {}", e);
+    }
+
+    // Check job groups
+    if let Err(e) = validate_job_groups(&jobs) {
+        if on_invalid_job == OnInvalidJob::Fail {
+            return Err(format!("Failed to parse groups: {}", e).into());
+        }
+        eprintln!("Invalid groups: {} Its runs are not limited.", e);
+    }
+
+    Ok(jobs)
+}
+
+/// Parses the optional `admin` section of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The socket address the admin server listens on, or `None` if the admin server is disabled.
+///
+/// # Errors
+///
+/// Returns an error if the 'address' field is missing or is not a valid socket address.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "admin": { "address": "127.0.0.1:9090" } });
+/// let address = get_admin_address(&value)?;
+/// println!("Admin address: {:?}", address);
+/// ```
+fn get_admin_address(value: &Value) -> Result<Option<SocketAddr>, Box<dyn Error>> {
+    let admin = match value.get("admin") {
+        Some(a) => a,
+        None => return Ok(None)
+    };
+
+    let address = admin.get("address")
+        .and_then(|a| a.as_str())
+        .ok_or("The 'address' field of the admin server is required and must be a string.")?;
+    let address = SocketAddr::from_str(address)
+        .map_err(|e| format!("Invalid admin server address '{}': {}", address, e))?;
+
+    Ok(Some(address))
+}
+
+/// Parses the optional `token` field of the `admin` section of the configuration.
+///
+/// The token is registered as a secret, so it is redacted from the logs.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The bearer token required by the endpoints of the admin server changing the scheduler, or
+/// `None` if no token is configured and these endpoints are disabled.
+///
+/// # Errors
+///
+/// Returns an error if the 'token' field is not a non-empty string.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "admin": { "address": "127.0.0.1:9090", "token": "s3cr3t" } });
+/// let token = get_admin_token(&value)?;
+/// assert_eq!(token.as_deref(), Some("s3cr3t"));
+/// ```
+fn get_admin_token(value: &Value) -> Result<Option<String>, Box<dyn Error>> {
+    let token = match value.get("admin").and_then(|admin| admin.get("token")) {
+        Some(t) => t.as_str()
+            .filter(|t| !t.trim().is_empty())
+            .ok_or("The 'token' field of the admin server must be a non-empty string.")?,
+        None => return Ok(None)
+    };
+    register_secret(token);
+
+    Ok(Some(token.to_string()))
+}
+
+/// Parses the optional `run_queue` section of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The execution capacity of the scheduler, or `None` if the section is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'run_queue' field is not an object.
+/// * The 'max_concurrent' field is missing or is not a positive integer.
+/// * The 'max_size' field is not a non-negative integer.
+/// * The 'when_full' field is not one of 'drop_oldest', 'drop_new' or 'block'.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "run_queue": { "max_concurrent": 16, "max_size": 100, "when_full": "drop_oldest" } });
+/// let run_queue = get_run_queue(&value)?;
+/// println!("Run queue: {:?}", run_queue);
+/// ```
+fn get_run_queue(value: &Value) -> Result<Option<RunQueue>, Box<dyn Error>> {
+    let run_queue = match value.get("run_queue") {
+        Some(q) => q,
+        None => return Ok(None)
+    };
+    if !run_queue.is_object() {
+        return Err("The 'run_queue' field must be an object.".into());
     }
 
-    Jobs::new(timezone, http_jobs)
+    let max_concurrent = run_queue.get("max_concurrent")
+        .and_then(|m| m.as_u64())
+        .filter(|m| *m > 0)
+        .ok_or("The 'max_concurrent' field of the run queue is required and must be a positive integer.")?;
+
+    let max_size = match run_queue.get("max_size") {
+        Some(m) => m.as_u64()
+            .ok_or("The 'max_size' field of the run queue must be a non-negative integer.")? as usize,
+        None => DEFAULT_RUN_QUEUE_SIZE
+    };
+
+    let when_full = match run_queue.get("when_full") {
+        Some(w) => w.as_str()
+            .and_then(WhenFull::parse)
+            .ok_or("The 'when_full' field of the run queue must be one of 'drop_oldest', 'drop_new' or 'block'.")?,
+        None => WhenFull::default()
+    };
+
+    Ok(Some(RunQueue::new(max_concurrent as usize, max_size, when_full)))
 }
 
-/// Retrieves the configuration from a file.
+/// Parses the optional `telemetry` section of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
 ///
-/// This function reads the content from the file and determines the file format based on the file extension.
-/// It supports JSON, YAML, and YML file formats.
+/// The OpenTelemetry export settings, or `None` if the section is missing.
+///
+/// # Errors
+///
+/// Returns an error if the 'otlp_endpoint' field is missing or a field is not a string.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "telemetry": { "otlp_endpoint": "http://localhost:4318/v1/traces" } });
+/// let telemetry = get_telemetry(&value)?;
+/// println!("Telemetry: {:?}", telemetry);
+/// ```
+fn get_telemetry(value: &Value) -> Result<Option<Telemetry>, Box<dyn Error>> {
+    let telemetry = match value.get("telemetry") {
+        Some(t) => t,
+        None => return Ok(None)
+    };
+
+    let otlp_endpoint = telemetry.get("otlp_endpoint")
+        .and_then(|e| e.as_str())
+        .ok_or("The 'otlp_endpoint' field of the telemetry is required and must be a string.")?
+        .to_string();
+
+    let service_name = match telemetry.get("service_name") {
+        Some(n) => n.as_str()
+            .ok_or("The 'service_name' field of the telemetry must be a string.")?
+            .to_string(),
+        None => "rjob".to_string()
+    };
+
+    Ok(Some(Telemetry::new(otlp_endpoint, service_name)))
+}
+
+/// Parses the optional `correlation_header` of the configuration.
+///
+/// The field is either a header name, in which case the run id is used as value, or an object
+/// with a `name` and a `value` template.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The correlation header, or `None` if the field is missing.
+///
+/// # Errors
+///
+/// Returns an error if the header name is missing or invalid, or if the value is not a string.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "correlation_header": { "name": "X-Request-Id", "value": "rjob-{{run_id}}" } });
+/// let header = get_correlation_header(&value)?;
+/// println!("Correlation header: {:?}", header);
+/// ```
+fn get_correlation_header(value: &Value) -> Result<Option<CorrelationHeader>, Box<dyn Error>> {
+    let header = match value.get("correlation_header") {
+        Some(h) => h,
+        None => return Ok(None)
+    };
+
+    let (name, template) = match header {
+        Value::String(name) => (name.as_str(), DEFAULT_CORRELATION_VALUE),
+        Value::Object(_) => {
+            let name = header.get("name")
+                .and_then(|n| n.as_str())
+                .ok_or("The 'name' field of the correlation header is required and must be a string.")?;
+            let template = match header.get("value") {
+                Some(v) => v.as_str().ok_or("The 'value' field of the correlation header must be a string.")?,
+                None => DEFAULT_CORRELATION_VALUE
+            };
+            (name, template)
+        }
+        _ => return Err("The 'correlation_header' field must be a header name or an object.".into())
+    };
+
+    HeaderName::from_str(name)
+        .map_err(|e| format!("Invalid correlation header name '{}': {}", name, e))?;
+    HeaderValue::from_str(template)
+        .map_err(|e| format!("Invalid correlation header value '{}': {}", template, e))?;
+
+    Ok(Some(CorrelationHeader::new(name.to_string(), template.to_string())))
+}
+
+/// Parses the optional `sensitive_headers` of the configuration.
+///
+/// The values of these headers are redacted from the logs, in addition to credential headers
+/// such as `Authorization`.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The header names, or an empty vector if the field is missing.
+///
+/// # Errors
+///
+/// Returns an error if the field is not an array of valid header names.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "sensitive_headers": ["X-Signature", "X-Tenant-Key"] });
+/// let headers = get_sensitive_headers(&value)?;
+/// assert_eq!(headers.len(), 2);
+/// ```
+fn get_sensitive_headers(value: &Value) -> Result<Vec<String>, Box<dyn Error>> {
+    let headers = match value.get("sensitive_headers") {
+        Some(h) => h.as_array().ok_or("The 'sensitive_headers' field must be an array of header names.")?,
+        None => return Ok(vec![])
+    };
+
+    headers.iter()
+        .map(|h| {
+            let name = h.as_str().ok_or("The 'sensitive_headers' field must be an array of header names.")?;
+            HeaderName::from_str(name)
+                .map_err(|e| format!("Invalid sensitive header name '{}': {}", name, e))?;
+            Ok(name.to_string())
+        })
+        .collect()
+}
+
+/// Retrieves the configuration from the content of a file.
+///
+/// The file format was determined from the file extension, see `FileContent::from`. It
+/// supports JSON, YAML, and YML file formats.
+///
+/// # Arguments
+///
+/// * `file_content` - The content of the 'jobs' file or of the configuration source.
+/// * `source` - The configuration source the content was read from, or `None` for the 'jobs'
+///   file, whose included files are read.
 ///
 /// # Errors
 ///
 /// This function may return an error if:
-/// - The file doesn't exist or cannot be read.
-/// - The file format is not supported.
-/// - There are multiple files with conflicting extensions.
+/// - The file is empty.
 /// - An error occurs while parsing the file content.
+/// - The file is encrypted with SOPS and cannot be decrypted, see `decrypt_document`.
+/// - The file has an unsupported version or cannot be migrated.
+/// - An included file cannot be read, or the files include each other in a cycle.
+/// - A configuration source uses `include`.
+/// - A secret referenced with `${keyring:SERVICE/ACCOUNT}` cannot be read from the OS keyring.
 ///
 /// # Returns
 ///
-/// The configuration value extracted from the file.
+/// The configuration value extracted from the file, migrated to the current version, merged
+/// with its included files and with its keyring references replaced by the secrets.
 ///
 /// # Examples
 ///
 /// ```
-/// match get_value() {
+/// match get_value(get_jobs_file_content()?, None) {
 ///     Ok(config) => {
 ///         // Use the configuration
 ///         println!("Configuration: {:?}", config);
@@ -118,19 +848,32 @@ fn init_read_jobs() -> Jobs {
 ///     },
 /// }
 /// ```
-fn get_value() -> Result<Value, Box<dyn Error>> {
-
-    let file_content = get_jobs_file_content()?;
-
-    let configure = match file_content {
-        FileContent::Json(content) => serde_json::from_str::<Value>(&content)
-            .map_err(|e| {format!("An error occurred while parsing the 'jobs.json' file: {}", e)})?,
-        FileContent::Yaml(content) => serde_yaml::from_str::<Value>(&content)
-            .map_err(|e| {format!("An error occurred while parsing the 'jobs.yaml' file: {}", e)})?,
-        FileContent::Yml(content) => serde_yaml::from_str::<Value>(&content)
-            .map_err(|e| {format!("An error occurred while parsing the 'jobs.yml' file: {}", e)})?,
+fn get_value(file_content: FileContent, source: Option<&ConfigSource>) -> Result<Value, Box<dyn Error>> {
+    let (mut configure, content) = match file_content {
+        FileContent::Json(content) => (serde_json::from_str::<Value>(&content)
+            .map_err(|e| {format!("An error occurred while parsing the 'jobs.json' file: {}", e)})?, content),
+        FileContent::Yaml(content) => (serde_yaml::from_str::<Value>(&content)
+            .map_err(|e| {format!("An error occurred while parsing the 'jobs.yaml' file: {}", e)})?, content),
+        FileContent::Yml(content) => (serde_yaml::from_str::<Value>(&content)
+            .map_err(|e| {format!("An error occurred while parsing the 'jobs.yml' file: {}", e)})?, content),
         FileContent::None => return Err("No 'jobs' file found.".into()),
     };
+    if is_encrypted(&configure) {
+        configure = decrypt_document(&content)
+            .map_err(|e| format!("Failed to decrypt the 'jobs' file: {}", e))?;
+    }
+
+    for (from, description) in migrate(&mut configure)? {
+        println!("Migrated the 'jobs' file from version {} to {}: {}.", from, from + 1, description);
+    }
+    match source {
+        Some(source) if configure.get("include").is_some() => {
+            return Err(format!("The 'include' field is not supported in the configuration read from {}.", source).into());
+        }
+        Some(_) => {}
+        None => resolve_includes(&mut configure, Path::new(get_jobs_file_path()?))?
+    }
+    resolve_keyring_references(&mut configure)?;
 
     Ok(configure)
 }
@@ -193,7 +936,10 @@ fn read_file(file_path: &str) -> Result<String, Box<dyn Error>> {
 /// }
 /// ```
 fn get_jobs_file_content() -> Result<FileContent, Box<dyn Error>> {
-    let files = ["./jobs.json", "./jobs.yaml", "./jobs.yml"];
+    if let Some(document) = get_source_document() {
+        return Ok(FileContent::from(document.content.clone(), &document.source.key));
+    }
+    let files = JOBS_FILES;
 
     let mut content: FileContent = FileContent::new_none();
     let mut count = 0;
@@ -215,6 +961,17 @@ fn get_jobs_file_content() -> Result<FileContent, Box<dyn Error>> {
     Ok(content)
 }
 
+/// Returns the path of the 'jobs' file in the current directory.
+///
+/// # Errors
+///
+/// Returns an error if no 'jobs' file is found.
+fn get_jobs_file_path() -> Result<&'static str, Box<dyn Error>> {
+    JOBS_FILES.into_iter()
+        .find(|file| fs::metadata(file).is_ok())
+        .ok_or("No 'jobs' file found.".into())
+}
+
 /// Represents the content of a file in different formats (JSON, YAML, YML).
 ///
 /// The `FileContent` enum has three variants, each corresponding to a specific file format.
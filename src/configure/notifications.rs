@@ -0,0 +1,272 @@
+use std::error::Error;
+use serde_json::Value;
+use crate::configure::http_jobs::get_headers;
+use crate::models::notifications::{EmailNotification, JobNotifications, Notifications, SlackNotification, SmtpSecurity, TelegramNotification, WebhookNotification};
+use crate::utils::duration_util::parse_duration;
+
+/// Parses the optional `notifications` section of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The configured notification channels. If the section is missing, no channel is configured.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'notifications' field is not an object.
+/// * The 'url' field of the webhook is missing or not a string.
+/// * Failed to parse the headers of the webhook.
+/// * The 'webhook_url' field of the Slack notification is missing or not a string.
+/// * The email notification is missing a required field or has an invalid field.
+/// * The 'bot_token' or 'chat_id' field of the Telegram notification is missing.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({
+///     "notifications": {
+///         "webhook": {
+///             "url": "https://hooks.example.com/rjob"
+///         }
+///     }
+/// });
+///
+/// let notifications = get_notifications(&value)?;
+/// println!("Webhook: {:?}", notifications.webhook);
+/// ```
+pub fn get_notifications(value: &Value) -> Result<Notifications, Box<dyn Error>> {
+    let notifications = match value.get("notifications") {
+        Some(n) if n.is_object() => n,
+        Some(_) => return Err("The 'notifications' field must be an object.".into()),
+        None => return Ok(Notifications::default())
+    };
+
+    let webhook = notifications.get("webhook")
+        .map(get_webhook_notification)
+        .transpose()?;
+
+    let slack = notifications.get("slack")
+        .map(get_slack_notification)
+        .transpose()?;
+
+    let email = notifications.get("email")
+        .map(get_email_notification)
+        .transpose()?;
+
+    let telegram = notifications.get("telegram")
+        .map(get_telegram_notification)
+        .transpose()?;
+
+    Ok(Notifications::new(webhook, slack, email, telegram))
+}
+
+/// Parses the `webhook` block of the `notifications` section.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value of the webhook block.
+///
+/// # Errors
+///
+/// Returns an error if the 'url' field is missing or the headers are invalid.
+fn get_webhook_notification(value: &Value) -> Result<WebhookNotification, Box<dyn Error>> {
+    let url = value.get("url")
+        .and_then(|u| u.as_str())
+        .ok_or("The 'url' field of the webhook notification is required and must be a string.")?
+        .to_string();
+
+    let headers = get_headers(value)?;
+
+    Ok(WebhookNotification::new(url, headers))
+}
+
+/// Parses the `slack` block of the `notifications` section.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value of the Slack block.
+///
+/// # Errors
+///
+/// Returns an error if the 'webhook_url' field is missing or a field has the wrong type.
+fn get_slack_notification(value: &Value) -> Result<SlackNotification, Box<dyn Error>> {
+    let webhook_url = value.get("webhook_url")
+        .and_then(|u| u.as_str())
+        .ok_or("The 'webhook_url' field of the Slack notification is required and must be a string.")?
+        .to_string();
+
+    let channel = get_optional_string(value, "channel")?;
+    let username = get_optional_string(value, "username")?;
+
+    Ok(SlackNotification::new(webhook_url, channel, username))
+}
+
+/// Parses the `telegram` block of the `notifications` section.
+///
+/// The chat id may be given as a number or a string (e.g. `@channelusername`).
+///
+/// # Arguments
+///
+/// * `value` - The JSON value of the Telegram block.
+///
+/// # Errors
+///
+/// Returns an error if the 'bot_token' or 'chat_id' field is missing or has the wrong type.
+fn get_telegram_notification(value: &Value) -> Result<TelegramNotification, Box<dyn Error>> {
+    let bot_token = value.get("bot_token")
+        .and_then(|t| t.as_str())
+        .ok_or("The 'bot_token' field of the Telegram notification is required and must be a string.")?
+        .to_string();
+
+    let chat_id = match value.get("chat_id") {
+        Some(Value::String(id)) => id.clone(),
+        Some(Value::Number(id)) => id.to_string(),
+        _ => return Err("The 'chat_id' field of the Telegram notification is required and must be a number or a string.".into())
+    };
+
+    Ok(TelegramNotification::new(bot_token, chat_id))
+}
+
+/// Parses the `email` block of the `notifications` section.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value of the email block.
+///
+/// # Errors
+///
+/// Returns an error if the 'host', 'from' or 'to' field is missing, or a field has the wrong type.
+fn get_email_notification(value: &Value) -> Result<EmailNotification, Box<dyn Error>> {
+    let host = value.get("host")
+        .and_then(|h| h.as_str())
+        .ok_or("The 'host' field of the email notification is required and must be a string.")?
+        .to_string();
+
+    let port = value.get("port")
+        .map(|p| {
+            p.as_u64()
+                .and_then(|p| u16::try_from(p).ok())
+                .ok_or("The 'port' field of the email notification must be a valid port number.")
+        })
+        .transpose()?;
+
+    let security = value.get("security")
+        .map(|s| {
+            s.as_str()
+                .and_then(SmtpSecurity::parse)
+                .ok_or("The 'security' field of the email notification must be one of 'starttls', 'tls' or 'none'.")
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    let username = get_optional_string(value, "username")?;
+    let password = get_optional_string(value, "password")?;
+
+    let from = value.get("from")
+        .and_then(|f| f.as_str())
+        .ok_or("The 'from' field of the email notification is required and must be a string.")?
+        .to_string();
+
+    let to = value.get("to")
+        .and_then(|t| t.as_array())
+        .ok_or("The 'to' field of the email notification is required and must be an array.")?
+        .iter()
+        .map(|t| {
+            t.as_str()
+                .map(|t| t.to_string())
+                .ok_or("The recipients of the email notification must be strings.")
+        })
+        .collect::<Result<Vec<String>, _>>()?;
+
+    let batch_window = value.get("batch_window")
+        .map(|b| {
+            b.as_str()
+                .and_then(parse_duration)
+                .ok_or("The 'batch_window' field of the email notification must be a duration like '5m'.")
+        })
+        .transpose()?;
+
+    Ok(EmailNotification {
+        host,
+        port,
+        security,
+        username,
+        password,
+        from,
+        to,
+        batch_window,
+    })
+}
+
+/// Parses the optional `notifications` block of a job, and its `alert_if_no_success_for` field.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value of the job.
+///
+/// # Returns
+///
+/// The notification settings of the job. If the block is missing, the global settings apply.
+///
+/// # Errors
+///
+/// Returns an error if the block is not an object, a field has the wrong type, or
+/// `alert_if_no_success_for` is not a duration.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({
+///     "alert_if_no_success_for": "6h",
+///     "notifications": {
+///         "slack_channel": "#billing-alerts"
+///     }
+/// });
+///
+/// let notifications = get_job_notifications(&value)?;
+/// println!("Slack channel: {:?}", notifications.slack_channel);
+/// ```
+pub fn get_job_notifications(value: &Value) -> Result<JobNotifications, Box<dyn Error>> {
+    let alert_if_no_success_for = value.get("alert_if_no_success_for")
+        .map(|a| {
+            a.as_str()
+                .and_then(parse_duration)
+                .filter(|d| !d.is_zero())
+                .ok_or("The 'alert_if_no_success_for' field of a job must be a duration like '6h'.")
+        })
+        .transpose()?;
+
+    let notifications = match value.get("notifications") {
+        Some(n) if n.is_object() => n,
+        Some(_) => return Err("The 'notifications' field of a job must be an object.".into()),
+        None => return Ok(JobNotifications::new(None, alert_if_no_success_for))
+    };
+
+    let slack_channel = get_optional_string(notifications, "slack_channel")?;
+
+    Ok(JobNotifications::new(slack_channel, alert_if_no_success_for))
+}
+
+/// Returns the value of an optional string field.
+///
+/// # Errors
+///
+/// Returns an error if the field is present but is not a string.
+fn get_optional_string(value: &Value, field: &str) -> Result<Option<String>, Box<dyn Error>> {
+    value.get(field)
+        .map(|v| {
+            v.as_str()
+                .map(|v| v.to_string())
+                .ok_or(format!("The '{}' field must be a string.", field).into())
+        })
+        .transpose()
+}
\ No newline at end of file
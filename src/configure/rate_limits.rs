@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::error::Error;
+use serde_json::Value;
+use crate::models::rate_limit::RateLimit;
+
+/// Parses the optional `rate_limits` section of the configuration.
+///
+/// The section maps host names to rates in the form `<requests>/<unit>`, where the unit is
+/// `s`, `m` or `h`.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// A map of host names to their rate limit. The map is empty if the section is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'rate_limits' field is not an object.
+/// * A rate is not a string or is malformed.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({
+///     "rate_limits": {
+///         "api.example.com": "5/s"
+///     }
+/// });
+///
+/// let rate_limits = get_rate_limits(&value)?;
+/// println!("Rate limits: {:?}", rate_limits);
+/// ```
+pub fn get_rate_limits(value: &Value) -> Result<HashMap<String, RateLimit>, Box<dyn Error>> {
+    let rate_limits_val = match value.get("rate_limits") {
+        Some(r) => r.as_object()
+            .ok_or("The 'rate_limits' field must be an object.")?,
+        None => return Ok(HashMap::new())
+    };
+
+    let mut rate_limits = HashMap::new();
+
+    for (host, rate) in rate_limits_val {
+        let rate = rate.as_str()
+            .and_then(RateLimit::parse)
+            .ok_or(format!("The rate limit of host '{}' must be a string like '5/s', '100/m' or '1000/h'.", host))?;
+        rate_limits.insert(host.to_lowercase(), rate);
+    }
+
+    Ok(rate_limits)
+}
+
+/// Parses the optional top-level `max_requests_per_second` field of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The maximum number of requests per second across all jobs, or `None` if the field is missing.
+///
+/// # Errors
+///
+/// Returns an error if the field is not a positive integer.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "max_requests_per_second": 20 });
+/// let max_requests_per_second = get_max_requests_per_second(&value)?;
+/// assert_eq!(max_requests_per_second, Some(20));
+/// ```
+pub fn get_max_requests_per_second(value: &Value) -> Result<Option<u32>, Box<dyn Error>> {
+    value.get("max_requests_per_second")
+        .map(|m| {
+            m.as_u64()
+                .filter(|m| *m > 0 && *m <= u32::MAX as u64)
+                .map(|m| m as u32)
+                .ok_or("The 'max_requests_per_second' field must be a positive integer.".into())
+        })
+        .transpose()
+}
\ No newline at end of file
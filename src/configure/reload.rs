@@ -0,0 +1,119 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::configure::config_source::{get_source_document, set_source_document, watch_config_source, SourceDocument};
+use crate::configure::{get_jobs_file_path, read_jobs, set_jobs, FileContent};
+use crate::scheduler::cron_scheduler::reschedule_jobs;
+use crate::scheduler::health::get_health;
+use crate::scheduler::systemd::notify_systemd;
+
+/// The interval between two checks of the 'jobs' file.
+///
+/// The kubelet updates mounted ConfigMaps about once a minute, so a few seconds are enough.
+const WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Reloads the scheduler in the background when its configuration changes.
+///
+/// With `--config`, the key of the configuration source is watched, see `watch_config_source`.
+/// Otherwise, the 'jobs' file is watched if it is a symbolic link, e.g. a key of a ConfigMap
+/// mounted by Kubernetes, see `watch_jobs_file`. A regular 'jobs' file is not watched.
+///
+/// # Examples
+///
+/// ```
+/// start_cron_scheduler(false, TagFilter::default()).await;
+/// watch_configuration();
+/// ```
+pub fn watch_configuration() {
+    if get_source_document().is_some() {
+        watch_config_source();
+    } else {
+        watch_jobs_file();
+    }
+}
+
+/// Watches a 'jobs' file that is a symbolic link, and reloads the scheduler when it changes.
+///
+/// Kubernetes projects each key of a mounted ConfigMap as a symbolic link through the `..data`
+/// link of the volume, and updates the ConfigMap by pointing `..data` to a new directory. The
+/// file itself is never modified, so its content is compared every `WATCH_INTERVAL` instead.
+/// A changed file reloads the scheduler like a changed document of a configuration source, see
+/// `reload_configuration`.
+fn watch_jobs_file() {
+    let Ok(file) = get_jobs_file_path() else {
+        return;
+    };
+    if !fs::symlink_metadata(file).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+        return;
+    }
+    let Ok((target, mut content)) = read_jobs_file(file) else {
+        return;
+    };
+    println!("Watching the jobs file, file: {}, target: {}", file, target.display());
+
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(WATCH_INTERVAL).await;
+
+            // The link may be dangling for a moment while it is swapped.
+            let Ok((target, new_content)) = read_jobs_file(file) else {
+                continue;
+            };
+            if new_content == content {
+                continue;
+            }
+            content = new_content;
+
+            println!("Jobs file changed, file: {}, target: {}", file, target.display());
+            reload_configuration(FileContent::from(content.clone(), file), None).await;
+        }
+    });
+}
+
+/// Reads the 'jobs' file with the path it links to.
+fn read_jobs_file(file: &str) -> std::io::Result<(PathBuf, String)> {
+    Ok((fs::canonicalize(file)?, fs::read_to_string(file)?))
+}
+
+/// Parses a changed configuration and reloads the scheduler with it.
+///
+/// The content the watcher read is parsed once, in the running process, see `read_jobs`: an
+/// invalid configuration is logged and ignored, the scheduler keeps running with the current
+/// one. A valid configuration replaces the current one and the jobs are scheduled again, see
+/// `reschedule_jobs`, so the runs in progress are not interrupted and keep the configuration
+/// they started with.
+///
+/// # Arguments
+///
+/// * `content` - The changed content of the 'jobs' file or of the configuration source.
+/// * `changed` - The changed document of the configuration source, or `None` if the 'jobs'
+///   file changed.
+///
+/// # Examples
+///
+/// ```
+/// println!("Jobs file changed, file: {}", file);
+/// reload_configuration(FileContent::from(content.clone(), file), None).await;
+/// ```
+pub(super) async fn reload_configuration(content: FileContent, changed: Option<SourceDocument>) {
+    let jobs = match read_jobs(content, changed.as_ref().map(|document| &document.source)) {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            println!("Configuration rejected, error: {}", e);
+            return;
+        }
+    };
+
+    println!("Reloading the configuration");
+    notify_systemd("RELOADING=1");
+    if let Some(document) = changed {
+        set_source_document(document);
+    }
+    set_jobs(jobs);
+    reschedule_jobs();
+
+    let registered_jobs = get_health().registered_jobs;
+    println!("Configuration reloaded, jobs: {}", registered_jobs);
+    notify_systemd(&format!("READY=1\nSTATUS=Scheduling {} jobs", registered_jobs));
+}
\ No newline at end of file
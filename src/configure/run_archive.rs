@@ -0,0 +1,66 @@
+use std::error::Error;
+use serde_json::Value;
+
+use crate::configure::s3_jobs::get_s3_storage;
+use crate::models::run_archive::{DEFAULT_ARCHIVE_KEY, DEFAULT_ARCHIVE_TIMEOUT, RunArchive};
+
+/// Parses the optional `run_archive` section of the configuration.
+///
+/// The credentials default to the `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` environment
+/// variables, as for S3 jobs.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The storage the runs are archived to, or `None` if the section is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'run_archive' field is not an object.
+/// * The 'endpoint' or 'bucket' field is missing, or the credentials are not set, see
+///   `get_s3_storage`.
+/// * The 'key' field is not a non-empty string.
+/// * The 'timeout' field is not a positive integer.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "run_archive": { "endpoint": "https://s3.us-east-1.amazonaws.com", "bucket": "rjob-runs" } });
+/// let run_archive = get_run_archive(&value)?;
+/// println!("Run archive: {:?}", run_archive);
+/// ```
+pub fn get_run_archive(value: &Value) -> Result<Option<RunArchive>, Box<dyn Error>> {
+    let run_archive = match value.get("run_archive") {
+        Some(r) => r,
+        None => return Ok(None)
+    };
+    if !run_archive.is_object() {
+        return Err("The 'run_archive' field must be an object.".into());
+    }
+
+    let storage = get_s3_storage(run_archive, "'run_archive'")?;
+
+    let key = match run_archive.get("key") {
+        Some(k) => k.as_str()
+            .map(|k| k.trim_start_matches('/'))
+            .filter(|k| !k.is_empty())
+            .ok_or("The 'key' field of 'run_archive' must be a non-empty string.")?,
+        None => DEFAULT_ARCHIVE_KEY
+    };
+
+    let timeout = match run_archive.get("timeout") {
+        Some(t) => t.as_u64()
+            .filter(|t| *t > 0)
+            .ok_or("The 'timeout' field of 'run_archive' must be a positive integer.")?,
+        None => DEFAULT_ARCHIVE_TIMEOUT
+    };
+
+    Ok(Some(RunArchive::new(storage, key.to_string(), timeout)))
+}
\ No newline at end of file
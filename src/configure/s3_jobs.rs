@@ -0,0 +1,192 @@
+use std::env;
+use std::error::Error;
+use serde_json::Value;
+
+use crate::configure::get_job_array;
+use crate::configure::job_options::get_job_options;
+use crate::configure::schedules::get_job_schedule;
+use crate::models::on_invalid_job::OnInvalidJob;
+use crate::models::s3_job::S3Job;
+use crate::models::s3_storage::S3Storage;
+use crate::models::transfer_operation::TransferOperation;
+use crate::utils::redact_util::register_secret;
+
+/// Parses the optional 's3_jobs' array of the configuration.
+///
+/// The credentials default to the `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` environment
+/// variables, so they can be kept out of the 'jobs' file.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+/// * `on_invalid_job` - Whether to fail or skip when a job is invalid.
+///
+/// # Returns
+///
+/// The list of S3 jobs, empty if the field is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 's3_jobs' field is not an array.
+/// * The 'name', 'cron', 'endpoint', 'bucket', 'key' or 'file' field is missing or not a string for any job.
+/// * The 'cron' field is not a valid cron expression for any job.
+/// * The 'operation' field is not 'upload' or 'download' for any job.
+/// * The 'endpoint' field does not start with `http://` or `https://` for any job.
+/// * The credentials are neither configured nor set in the environment for any job.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({
+///     "s3_jobs": [{
+///         "name": "nightly_export",
+///         "cron": "0 0 2 * * ?",
+///         "operation": "upload",
+///         "endpoint": "https://s3.us-east-1.amazonaws.com",
+///         "region": "us-east-1",
+///         "bucket": "exports",
+///         "key": "orders/{{date}}.csv",
+///         "file": "/var/exports/orders.csv"
+///     }]
+/// });
+/// let jobs = get_s3_jobs(&value, OnInvalidJob::Fail)?;
+/// ```
+pub fn get_s3_jobs(value: &Value, on_invalid_job: OnInvalidJob) -> Result<Vec<S3Job>, Box<dyn Error>> {
+    get_job_array(value, "s3_jobs", on_invalid_job, get_s3_job)
+}
+
+/// Parses a single job of the 's3_jobs' array, see `get_s3_jobs`.
+fn get_s3_job(it: &Value) -> Result<S3Job, Box<dyn Error>> {
+    let name = it.get("name")
+        .and_then(|n| n.as_str())
+        .ok_or("The 'name' field is missing or not a string.")?
+        .to_string();
+
+    let enable = it.get("enable")
+        .and_then(|e| e.as_bool())
+        .unwrap_or(true);
+
+    let schedule = get_job_schedule(it, &name)?;
+
+    let timeout = it.get("timeout")
+        .and_then(|t| t.as_u64())
+        .unwrap_or(60000);
+
+    let max_retry = it.get("max_retry")
+        .and_then(|m| m.as_u64())
+        .unwrap_or(3);
+
+    let operation = match it.get("operation").and_then(|o| o.as_str()) {
+        Some("upload") => TransferOperation::Upload,
+        Some("download") => TransferOperation::Download,
+        _ => return Err(format!("The 'operation' of the S3 job '{}' must be 'upload' or 'download'.", name).into())
+    };
+
+    let storage = get_s3_storage(it, &format!("the S3 job '{}'", name))?;
+    let key = get_string(it, "key")?
+        .trim_start_matches('/')
+        .to_string();
+    let file = get_string(it, "file")?;
+
+    let options = get_job_options(it)?;
+
+    Ok(S3Job {
+        name,
+        enable,
+        schedule,
+        timeout,
+        max_retry,
+        operation,
+        storage,
+        key,
+        file,
+        options,
+    })
+}
+
+/// Parses the endpoint, bucket and credentials of an S3 job or of the run archive.
+///
+/// The credentials default to the `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` environment
+/// variables.
+///
+/// # Arguments
+///
+/// * `it` - The JSON value of the job or section.
+/// * `owner` - The job or section, used in the error messages, e.g. `the S3 job 'export'`.
+///
+/// # Errors
+///
+/// Returns an error if the 'endpoint' or 'bucket' field is missing, the endpoint does not start
+/// with `http://` or `https://`, or the credentials are neither configured nor set in the
+/// environment.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "endpoint": "http://localhost:9000", "bucket": "runs", "access_key": "rjob", "secret_key": "secret" });
+/// let storage = get_s3_storage(&value, "'run_archive'")?;
+/// assert_eq!(storage.region, "us-east-1");
+/// ```
+pub fn get_s3_storage(it: &Value, owner: &str) -> Result<S3Storage, Box<dyn Error>> {
+    let endpoint = get_string(it, "endpoint")?
+        .trim_end_matches('/')
+        .to_string();
+    if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+        return Err(format!("The 'endpoint' of {} must start with http:// or https://.", owner).into());
+    }
+
+    let region = it.get("region")
+        .and_then(|r| r.as_str())
+        .unwrap_or("us-east-1")
+        .to_string();
+
+    let bucket = get_string(it, "bucket")?;
+
+    let access_key = get_credential(it, "access_key", "AWS_ACCESS_KEY_ID", owner)?;
+    let secret_key = get_credential(it, "secret_key", "AWS_SECRET_ACCESS_KEY", owner)?;
+
+    let path_style = it.get("path_style")
+        .and_then(|p| p.as_bool())
+        .unwrap_or(true);
+
+    Ok(S3Storage {
+        endpoint,
+        region,
+        bucket,
+        access_key,
+        secret_key,
+        path_style,
+    })
+}
+
+/// Reads a required string field of an S3 job.
+fn get_string(it: &Value, field: &str) -> Result<String, Box<dyn Error>> {
+    it.get(field)
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string())
+        .ok_or(format!("The '{}' field is required and must be a string.", field).into())
+}
+
+/// Reads a credential of an S3 job or of the run archive, falling back to the given
+/// environment variable.
+///
+/// A credential read from the environment is registered as a secret, so it is redacted from
+/// the logs.
+fn get_credential(it: &Value, field: &str, env_var: &str, owner: &str) -> Result<String, Box<dyn Error>> {
+    if let Some(value) = it.get(field) {
+        return value.as_str()
+            .map(|v| v.to_string())
+            .ok_or(format!("The '{}' field must be a string.", field).into());
+    }
+
+    let value = env::var(env_var)
+        .map_err(|_| format!("The '{}' of {} is missing and the {} environment variable is not set.", field, owner, env_var))?;
+    register_secret(&value);
+    Ok(value)
+}
\ No newline at end of file
@@ -0,0 +1,196 @@
+use std::error::Error;
+use chrono::{Duration, NaiveTime, Utc};
+use serde_json::Value;
+use crate::configure::blackouts::get_blackouts;
+use crate::configure::holidays::get_job_holidays;
+use crate::models::condition::Condition;
+use crate::models::job_schedule::{JobSchedule, SolarEvent};
+use crate::utils::cron_util::{normalize_cron, REBOOT, validate_cron};
+use crate::utils::duration_util::parse_duration;
+
+/// The fields scheduling a job, exactly one of which is set.
+const SCHEDULE_FIELDS: [&str; 3] = ["cron", "daily_between", "solar"];
+
+/// Parses the schedule of a job, its `cron`, `daily_between` or `solar` field.
+///
+/// The cron expression is normalized to the format of the scheduler, so a Unix crontab
+/// expression with 5 fields (`min hour day month weekday`) and the crontab macros such as
+/// `@daily` are accepted next to the 6 and 7-field formats with seconds and an optional year.
+/// The interpretation of a crontab expression is logged, since the same expression means
+/// something else in the two formats. `@reboot` runs the job once when the scheduler starts.
+///
+/// `daily_between` is a window of two times of day, `HH:MM` or `HH:MM:SS` in the configured
+/// timezone, e.g. `["02:00", "04:00"]`: the job runs once a day at a random time in the
+/// window, see `JobSchedule::daily_between`.
+///
+/// `solar` runs the job every day around the sunrise or the sunset at a location: its `event`
+/// is `sunrise` or `sunset`, optionally followed by an offset such as `+30m` or `-1h`, and its
+/// `latitude` and `longitude` are in degrees, positive north and east.
+///
+/// The fire times on the `holidays` of the job are skipped, see `get_job_holidays`, and the
+/// triggers during its `blackouts` are suppressed, see `get_blackouts`. With `run_on_start`, the
+/// job also runs once at startup. With an `initial_delay` such as `2m`, the job doesn't run
+/// before that time has passed since startup. A `when` expression such as
+/// `last_outcome == 'failure'` skips the triggers for which it is false, see `Condition`.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value of the job.
+/// * `name` - The name of the job, used in the log and the errors.
+///
+/// # Returns
+///
+/// The schedule of the job.
+///
+/// # Errors
+///
+/// Returns an error if:
+///
+/// * None or several of the fields are set.
+/// * The `cron` field is not a string, or is not a valid cron expression.
+/// * The `daily_between` field is not an array of two different times of day.
+/// * The `solar` field has an invalid event, or an invalid or missing location.
+/// * The `holidays`, `blackouts`, `run_on_start`, `initial_delay` or `when` field is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "name": "report", "cron": "30 2 * * 1-5" });
+/// let schedule = get_job_schedule(&value, "report")?;
+/// assert_eq!(schedule.expression, "0 30 2 * * 2,3,4,5,6");
+///
+/// let value = json!({ "name": "sync", "daily_between": ["02:00", "04:00"] });
+/// let schedule = get_job_schedule(&value, "sync")?;
+/// assert_eq!(schedule.expression, "daily_between 02:00-04:00");
+///
+/// let value = json!({ "name": "lights", "solar": { "event": "sunset+30m", "latitude": 48.8566, "longitude": 2.3522 } });
+/// let schedule = get_job_schedule(&value, "lights")?;
+/// assert_eq!(schedule.expression, "sunset+30m (48.8566, 2.3522)");
+/// ```
+pub fn get_job_schedule(value: &Value, name: &str) -> Result<JobSchedule, Box<dyn Error>> {
+    let fields: Vec<&str> = SCHEDULE_FIELDS.into_iter().filter(|field| value.get(field).is_some()).collect();
+
+    let schedule = match fields[..] {
+        ["cron"] => get_job_cron(&value["cron"], name)?,
+        ["daily_between"] => get_daily_window(&value["daily_between"], name)?,
+        ["solar"] => get_solar_schedule(&value["solar"], name)?,
+        [] => return Err("One of the 'cron', 'daily_between' or 'solar' fields is required.".into()),
+        _ => return Err(format!("The job '{}' has several schedules ({}), expected one of them.", name, fields.join(", ")).into()),
+    };
+
+    let schedule = match get_job_holidays(value, name)? {
+        Some(holidays) => schedule.with_holidays(holidays),
+        None => schedule
+    };
+    let blackouts = get_blackouts(value, &format!("job '{}'", name))?;
+    let run_on_start = match value.get("run_on_start") {
+        Some(r) => r.as_bool().ok_or(format!("The 'run_on_start' field of job '{}' must be a boolean.", name))?,
+        None => false
+    };
+
+    let initial_delay = match value.get("initial_delay") {
+        Some(d) => d.as_str()
+            .and_then(parse_duration)
+            .ok_or(format!("The 'initial_delay' field of job '{}' must be a duration like '2m'.", name))?,
+        None => std::time::Duration::ZERO
+    };
+    if Duration::from_std(initial_delay).ok().and_then(|delay| Utc::now().checked_add_signed(delay)).is_none() {
+        return Err(format!("The 'initial_delay' field of job '{}' is too large.", name).into());
+    }
+
+    let when = match value.get("when") {
+        Some(w) => {
+            let expression = w.as_str().ok_or(format!("The 'when' field of job '{}' must be a string.", name))?;
+            let condition = Condition::parse(expression)
+                .map_err(|e| format!("Invalid 'when' field of job '{}': {}", name, e))?;
+            Some(condition)
+        }
+        None => None
+    };
+
+    Ok(schedule.with_blackouts(blackouts).with_run_on_start(run_on_start).with_initial_delay(initial_delay).with_when(when))
+}
+
+/// Parses and normalizes the `cron` field of a job, see `get_job_schedule`.
+fn get_job_cron(value: &Value, name: &str) -> Result<JobSchedule, Box<dyn Error>> {
+    let cron = value.as_str()
+        .ok_or("The 'cron' field is not a string.")?;
+
+    let normalized = normalize_cron(cron)
+        .and_then(|normalized| validate_cron(&normalized).map(|_| normalized))
+        .map_err(|e| format!("Invalid cron expression '{}' of job '{}': {}", cron, name, e))?;
+
+    if cron.trim().starts_with('@') {
+        if normalized != REBOOT {
+            println!("Interpreted the cron macro '{}' of job '{}' as '{}'.", cron, name, normalized);
+        }
+    } else if normalized != cron {
+        println!("Interpreted the cron expression '{}' of job '{}' in crontab format (min hour day month weekday) as '{}', firing at second 0.",
+                 cron, name, normalized);
+    }
+
+    JobSchedule::cron(&normalized)
+}
+
+/// Parses the `daily_between` field of a job, see `get_job_schedule`.
+fn get_daily_window(value: &Value, name: &str) -> Result<JobSchedule, Box<dyn Error>> {
+    let invalid = || format!("The 'daily_between' field of job '{}' must be an array of two different times of day, e.g. [\"02:00\", \"04:00\"].", name);
+
+    let times = value.as_array()
+        .filter(|times| times.len() == 2)
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|time| time.as_str().and_then(parse_time_of_day).ok_or_else(invalid))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if times[0] == times[1] {
+        return Err(invalid().into());
+    }
+
+    Ok(JobSchedule::daily_between(times[0], times[1], name))
+}
+
+/// Parses the `solar` field of a job, see `get_job_schedule`.
+fn get_solar_schedule(value: &Value, name: &str) -> Result<JobSchedule, Box<dyn Error>> {
+    let solar = value.as_object()
+        .ok_or(format!("The 'solar' field of job '{}' must be an object.", name))?;
+
+    let event = solar.get("event")
+        .and_then(|e| e.as_str())
+        .ok_or(format!("The 'event' of the 'solar' field of job '{}' is required and must be a string.", name))?;
+    let (event, offset) = parse_solar_event(event)
+        .ok_or(format!("Invalid solar event '{}' of job '{}', expected 'sunrise' or 'sunset' with an optional offset, e.g. 'sunset+30m'.", event, name))?;
+
+    let latitude = solar.get("latitude")
+        .and_then(|l| l.as_f64())
+        .filter(|l| (-90.0..=90.0).contains(l))
+        .ok_or(format!("The 'latitude' of the 'solar' field of job '{}' is required and must be a number between -90 and 90.", name))?;
+    let longitude = solar.get("longitude")
+        .and_then(|l| l.as_f64())
+        .filter(|l| (-180.0..=180.0).contains(l))
+        .ok_or(format!("The 'longitude' of the 'solar' field of job '{}' is required and must be a number between -180 and 180.", name))?;
+
+    Ok(JobSchedule::solar(event, offset, latitude, longitude))
+}
+
+/// Parses a solar event with an optional offset, e.g. `sunrise`, `sunset+30m` or `sunrise-1h`.
+fn parse_solar_event(value: &str) -> Option<(SolarEvent, Duration)> {
+    let value = value.trim();
+    let Some(sign) = value.find(['+', '-']) else {
+        return Some((SolarEvent::parse(value)?, Duration::zero()));
+    };
+
+    let event = SolarEvent::parse(value[..sign].trim())?;
+    let offset = Duration::from_std(parse_duration(&value[sign + 1..])?).ok()?;
+
+    Some((event, if value[sign..].starts_with('-') { -offset } else { offset }))
+}
+
+/// Parses a time of day, `HH:MM` or `HH:MM:SS`.
+pub fn parse_time_of_day(time: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(time, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+        .ok()
+}
\ No newline at end of file
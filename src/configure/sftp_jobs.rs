@@ -0,0 +1,144 @@
+use std::error::Error;
+use serde_json::Value;
+
+use crate::configure::get_job_array;
+use crate::configure::job_options::get_job_options;
+use crate::configure::schedules::get_job_schedule;
+use crate::models::on_invalid_job::OnInvalidJob;
+use crate::models::sftp_job::{OverwritePolicy, SftpJob};
+use crate::models::transfer_operation::TransferOperation;
+
+/// Parses the optional 'sftp_jobs' array of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+/// * `on_invalid_job` - Whether to fail or skip when a job is invalid.
+///
+/// # Returns
+///
+/// The list of SFTP jobs, empty if the field is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'sftp_jobs' field is not an array.
+/// * The 'name', 'cron', 'host', 'username', 'private_key', 'remote_path' or 'local_path' field
+///   is missing or not a string for any job.
+/// * The 'cron' field is not a valid cron expression for any job.
+/// * The 'operation' field is not 'upload' or 'download' for any job.
+/// * The 'port' field is not a number between 1 and 65535 for any job.
+/// * The 'overwrite' field is not 'overwrite', 'skip' or 'fail' for any job.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({
+///     "sftp_jobs": [{
+///         "name": "partner_orders",
+///         "cron": "0 30 6 * * ?",
+///         "operation": "download",
+///         "host": "sftp.partner.com",
+///         "username": "acme",
+///         "private_key": "/etc/rjob/id_ed25519",
+///         "remote_path": "/outbox/orders-{{date}}.csv",
+///         "local_path": "/var/inbox/orders-{{date}}.csv",
+///         "overwrite": "skip"
+///     }]
+/// });
+/// let jobs = get_sftp_jobs(&value, OnInvalidJob::Fail)?;
+/// ```
+pub fn get_sftp_jobs(value: &Value, on_invalid_job: OnInvalidJob) -> Result<Vec<SftpJob>, Box<dyn Error>> {
+    get_job_array(value, "sftp_jobs", on_invalid_job, get_sftp_job)
+}
+
+/// Parses a single job of the 'sftp_jobs' array, see `get_sftp_jobs`.
+fn get_sftp_job(it: &Value) -> Result<SftpJob, Box<dyn Error>> {
+    let name = it.get("name")
+        .and_then(|n| n.as_str())
+        .ok_or("The 'name' field is missing or not a string.")?
+        .to_string();
+
+    let enable = it.get("enable")
+        .and_then(|e| e.as_bool())
+        .unwrap_or(true);
+
+    let schedule = get_job_schedule(it, &name)?;
+
+    let timeout = it.get("timeout")
+        .and_then(|t| t.as_u64())
+        .unwrap_or(60000);
+
+    let max_retry = it.get("max_retry")
+        .and_then(|m| m.as_u64())
+        .unwrap_or(3);
+
+    let operation = match it.get("operation").and_then(|o| o.as_str()) {
+        Some("upload") => TransferOperation::Upload,
+        Some("download") => TransferOperation::Download,
+        _ => return Err(format!("The 'operation' of the SFTP job '{}' must be 'upload' or 'download'.", name).into())
+    };
+
+    let host = get_string(it, "host")?;
+
+    let port = match it.get("port") {
+        Some(p) => p.as_u64()
+            .and_then(|p| u16::try_from(p).ok())
+            .filter(|p| *p != 0)
+            .ok_or(format!("The 'port' of the SFTP job '{}' must be a number between 1 and 65535.", name))?,
+        None => 22
+    };
+
+    let username = get_string(it, "username")?;
+    let private_key = get_string(it, "private_key")?;
+
+    let passphrase = it.get("passphrase")
+        .map(|p| p.as_str().map(|p| p.to_string()).ok_or("The 'passphrase' field must be a string."))
+        .transpose()?;
+
+    let known_hosts = it.get("known_hosts")
+        .map(|k| k.as_str().map(|k| k.to_string()).ok_or("The 'known_hosts' field must be a string."))
+        .transpose()?;
+
+    let remote_path = get_string(it, "remote_path")?;
+    let local_path = get_string(it, "local_path")?;
+
+    let overwrite = match it.get("overwrite").map(|o| o.as_str()) {
+        None | Some(Some("overwrite")) => OverwritePolicy::Overwrite,
+        Some(Some("skip")) => OverwritePolicy::Skip,
+        Some(Some("fail")) => OverwritePolicy::Fail,
+        _ => return Err(format!("The 'overwrite' of the SFTP job '{}' must be 'overwrite', 'skip' or 'fail'.", name).into())
+    };
+
+    let options = get_job_options(it)?;
+
+    Ok(SftpJob {
+        name,
+        enable,
+        schedule,
+        timeout,
+        max_retry,
+        operation,
+        host,
+        port,
+        username,
+        private_key,
+        passphrase,
+        known_hosts,
+        remote_path,
+        local_path,
+        overwrite,
+        options,
+    })
+}
+
+/// Reads a required string field of an SFTP job.
+fn get_string(it: &Value, field: &str) -> Result<String, Box<dyn Error>> {
+    it.get(field)
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string())
+        .ok_or(format!("The '{}' field is required and must be a string.", field).into())
+}
\ No newline at end of file
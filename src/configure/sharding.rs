@@ -0,0 +1,74 @@
+use std::error::Error;
+use std::time::Duration;
+use serde_json::Value;
+use crate::models::distributed_lock::RedisServer;
+use crate::models::sharding::{DEFAULT_MEMBER_LEASE, DEFAULT_MEMBERS_KEY, Sharding};
+use crate::utils::duration_util::parse_duration;
+
+/// The shortest lease of a member, so the lease is renewed at most once per second.
+const MIN_MEMBER_LEASE: Duration = Duration::from_secs(3);
+
+/// Parses the optional `sharding` section of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The partitioning of the jobs among the instances of the scheduler, or `None` if the section
+/// is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'sharding' field is not an object.
+/// * The 'redis' field is missing or is not a valid `redis://` URL.
+/// * The 'key' field is not a non-empty string.
+/// * The 'lease' field is not a duration of at least 3 seconds.
+/// * The 'leader_election' section is also configured.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "sharding": { "redis": "redis://10.0.0.5:6379", "lease": "15s" } });
+/// let sharding = get_sharding(&value)?;
+/// println!("Sharding: {:?}", sharding);
+/// ```
+pub fn get_sharding(value: &Value) -> Result<Option<Sharding>, Box<dyn Error>> {
+    let sharding = match value.get("sharding") {
+        Some(s) => s,
+        None => return Ok(None)
+    };
+    if !sharding.is_object() {
+        return Err("The 'sharding' field must be an object.".into());
+    }
+    if value.get("leader_election").is_some() {
+        return Err("The 'sharding' and 'leader_election' sections cannot be used together.".into());
+    }
+
+    let redis = sharding.get("redis")
+        .and_then(|r| r.as_str())
+        .ok_or("The 'redis' field of the sharding is required and must be a string.")?;
+    let redis = RedisServer::parse(redis)?;
+
+    let key = match sharding.get("key") {
+        Some(k) => k.as_str()
+            .filter(|k| !k.is_empty())
+            .ok_or("The 'key' field of the sharding must be a non-empty string.")?,
+        None => DEFAULT_MEMBERS_KEY
+    };
+
+    let lease = match sharding.get("lease") {
+        Some(l) => l.as_str()
+            .and_then(parse_duration)
+            .filter(|l| *l >= MIN_MEMBER_LEASE)
+            .ok_or("The 'lease' field of the sharding must be a duration of at least '3s'.")?,
+        None => DEFAULT_MEMBER_LEASE
+    };
+
+    Ok(Some(Sharding::new(redis, key.to_string(), lease)))
+}
\ No newline at end of file
@@ -0,0 +1,252 @@
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::Read;
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::aead::consts::U32;
+use aes_gcm::aes::Aes256;
+use aes_gcm::AesGcm;
+use age::armor::ArmoredReader;
+use age::{Decryptor, Identity, IdentityFile};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ring::digest::{Context, SHA512};
+use serde_json::{Map, Number, Value};
+
+/// The prefix of the values encrypted by SOPS.
+const ENCRYPTED_PREFIX: &str = "ENC[AES256_GCM,";
+
+/// AES-256-GCM with the 32-byte nonces used by SOPS.
+type SopsCipher = AesGcm<Aes256, U32>;
+
+/// Returns whether a 'jobs' document was encrypted by SOPS, i.e. has a `sops` section with a
+/// `mac`.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// assert!(!is_encrypted(&json!({ "timezone": "UTC" })));
+/// ```
+pub fn is_encrypted(value: &Value) -> bool {
+    value.get("sops").is_some_and(|sops| sops.get("mac").is_some())
+}
+
+/// Decrypts a 'jobs' document encrypted by SOPS with age.
+///
+/// The data key of the document is decrypted with an age identity, read from the
+/// `SOPS_AGE_KEY` environment variable, or from the file in `SOPS_AGE_KEY_FILE`, or else from
+/// `sops/age/keys.txt` in the user configuration directory, as SOPS does. Each `ENC[...]` value
+/// is then decrypted with the data key and its path in the document, and the MAC of the
+/// document is checked, so values moved, removed or edited without SOPS are detected. The values
+/// left unencrypted, e.g. with the `_unencrypted` suffix, are kept as they are.
+///
+/// # Arguments
+///
+/// * `content` - The content of the JSON or YAML document.
+///
+/// # Returns
+///
+/// The decrypted document, without its `sops` section.
+///
+/// # Errors
+///
+/// Returns an error if:
+///
+/// * The document has no age recipient, e.g. it was only encrypted with a cloud KMS.
+/// * No age identity is found, or none of them decrypts the data key.
+/// * A value cannot be decrypted.
+/// * The MAC of the document doesn't match.
+///
+/// # Examples
+///
+/// ```
+/// let content = fs::read_to_string("./jobs.yaml")?;
+/// let value = decrypt_document(&content)?;
+/// ```
+pub fn decrypt_document(content: &str) -> Result<Value, Box<dyn Error>> {
+    // Parsed as YAML, a superset of JSON, whose mappings keep the order of the keys the MAC
+    // depends on.
+    let document: serde_yaml::Value = serde_yaml::from_str(content)?;
+    let sops = document.get("sops").ok_or("The document has no 'sops' section.")?;
+
+    let key = get_data_key(sops)?;
+    let cipher = SopsCipher::new_from_slice(&key).map_err(|_| "The data key must be 32 bytes.")?;
+    let mac_only_encrypted = sops.get("mac_only_encrypted").and_then(|m| m.as_bool()).unwrap_or(false);
+
+    let mut hash = Context::new(&SHA512);
+    let mut path = Vec::new();
+    let value = decrypt_value(&document, &mut path, &cipher, mac_only_encrypted, &mut hash)?;
+
+    let last_modified = sops.get("lastmodified").and_then(|l| l.as_str()).ok_or("The 'sops' section has no 'lastmodified' field.")?;
+    let mac = sops.get("mac").and_then(|m| m.as_str()).ok_or("The 'sops' section has no 'mac' field.")?;
+    let (expected, _) = decrypt_string(mac, &cipher, last_modified)?;
+    let actual: String = hash.finish().as_ref().iter().map(|b| format!("{:02X}", b)).collect();
+    if expected != actual.as_bytes() {
+        let hint = if content.lines().any(|line| line.trim_start().starts_with("#ENC[")) {
+            " The comments of a YAML document are part of the MAC, remove them before encrypting it."
+        } else {
+            ""
+        };
+        return Err(format!("The MAC of the document doesn't match, it was modified without SOPS.{}", hint).into());
+    }
+
+    Ok(value)
+}
+
+/// Decrypts the data key of a document with the first age identity that can.
+fn get_data_key(sops: &serde_yaml::Value) -> Result<Vec<u8>, Box<dyn Error>> {
+    let recipients: Vec<&str> = sops.get("age")
+        .and_then(|age| age.as_sequence())
+        .map(|age| age.iter().filter_map(|r| r.get("enc").and_then(|enc| enc.as_str())).collect())
+        .unwrap_or_default();
+    if recipients.is_empty() {
+        return Err("The document has no age recipient, only age is supported.".into());
+    }
+
+    let identities = get_identities()?;
+    for enc in recipients {
+        let decryptor = Decryptor::new_buffered(ArmoredReader::new(enc.as_bytes()))?;
+        let Ok(mut reader) = decryptor.decrypt(identities.iter().map(|identity| identity.as_ref() as &dyn Identity)) else {
+            continue;
+        };
+        let mut key = Vec::new();
+        reader.read_to_end(&mut key)?;
+        return Ok(key);
+    }
+
+    Err("None of the age keys can decrypt the data key of the document.".into())
+}
+
+/// Reads the age identities of `SOPS_AGE_KEY`, `SOPS_AGE_KEY_FILE` or the default key file.
+fn get_identities() -> Result<Vec<Box<dyn Identity>>, Box<dyn Error>> {
+    let keys = match env::var("SOPS_AGE_KEY") {
+        Ok(keys) => keys,
+        Err(_) => {
+            let path = env::var("SOPS_AGE_KEY_FILE").ok()
+                .or_else(|| env::var("XDG_CONFIG_HOME").ok().map(|config| format!("{}/sops/age/keys.txt", config)))
+                .or_else(|| env::var("HOME").ok().map(|home| format!("{}/.config/sops/age/keys.txt", home)))
+                .ok_or("No age key found, set SOPS_AGE_KEY or SOPS_AGE_KEY_FILE.")?;
+            fs::read_to_string(&path)
+                .map_err(|e| format!("No age key found, set SOPS_AGE_KEY or SOPS_AGE_KEY_FILE ('{}': {}).", path, e))?
+        }
+    };
+
+    let identities = IdentityFile::from_buffer(keys.as_bytes())?.into_identities()?;
+    if identities.is_empty() {
+        return Err("No age key found, set SOPS_AGE_KEY or SOPS_AGE_KEY_FILE.".into());
+    }
+    Ok(identities)
+}
+
+/// Decrypts the values of a node of the document, and adds them to the hash of the MAC in the
+/// order of the document.
+///
+/// The path of a value, the keys of the mappings leading to it, is the additional data of its
+/// encryption. The items of a sequence share the path of the sequence.
+fn decrypt_value(
+    value: &serde_yaml::Value,
+    path: &mut Vec<String>,
+    cipher: &SopsCipher,
+    mac_only_encrypted: bool,
+    hash: &mut Context,
+) -> Result<Value, Box<dyn Error>> {
+    let (value, bytes) = match value {
+        serde_yaml::Value::Mapping(mapping) => {
+            let mut object = Map::new();
+            for (key, item) in mapping {
+                let key = key.as_str().ok_or("The keys of an encrypted document must be strings.")?;
+                if path.is_empty() && key == "sops" {
+                    continue;
+                }
+                path.push(key.to_string());
+                object.insert(key.to_string(), decrypt_value(item, path, cipher, mac_only_encrypted, hash)?);
+                path.pop();
+            }
+            return Ok(Value::Object(object));
+        }
+        serde_yaml::Value::Sequence(items) => {
+            let items = items.iter()
+                .map(|item| decrypt_value(item, path, cipher, mac_only_encrypted, hash))
+                .collect::<Result<Vec<Value>, Box<dyn Error>>>()?;
+            return Ok(Value::Array(items));
+        }
+        serde_yaml::Value::String(s) if s.starts_with(ENCRYPTED_PREFIX) => {
+            let additional_data = format!("{}:", path.join(":"));
+            let (plaintext, datatype) = decrypt_string(s, cipher, &additional_data)
+                .map_err(|e| format!("Failed to decrypt '{}': {}", path.join("."), e))?;
+            let value = parse_plaintext(plaintext, &datatype)
+                .map_err(|e| format!("Failed to decrypt '{}': {}", path.join("."), e))?;
+            hash.update(&mac_bytes(&value));
+            return Ok(value);
+        }
+        serde_yaml::Value::Tagged(tagged) => return decrypt_value(&tagged.value, path, cipher, mac_only_encrypted, hash),
+        value => {
+            let value = serde_json::to_value(value)?;
+            let bytes = mac_bytes(&value);
+            (value, bytes)
+        }
+    };
+
+    if !mac_only_encrypted {
+        hash.update(&bytes);
+    }
+    Ok(value)
+}
+
+/// Decrypts a value in the `ENC[AES256_GCM,data:...,iv:...,tag:...,type:...]` format.
+///
+/// # Returns
+///
+/// The plaintext and its type, e.g. `str` or `int`.
+fn decrypt_string(value: &str, cipher: &SopsCipher, additional_data: &str) -> Result<(Vec<u8>, String), Box<dyn Error>> {
+    let fields = value.strip_prefix(ENCRYPTED_PREFIX)
+        .and_then(|fields| fields.strip_suffix(']'))
+        .ok_or("Not a value encrypted by SOPS.")?;
+    let field = |name: &str| fields.split(',')
+        .find_map(|field| field.strip_prefix(name).and_then(|f| f.strip_prefix(':')))
+        .ok_or(format!("The encrypted value has no '{}'.", name));
+
+    let mut data = STANDARD.decode(field("data")?)?;
+    let iv = STANDARD.decode(field("iv")?)?;
+    data.extend(STANDARD.decode(field("tag")?)?);
+    if iv.len() != 32 {
+        return Err("The IV of the encrypted value must be 32 bytes.".into());
+    }
+
+    let plaintext = cipher.decrypt(iv.as_slice().into(), Payload { msg: &data, aad: additional_data.as_bytes() })
+        .map_err(|_| "Wrong data key, or the value was moved or modified.")?;
+    Ok((plaintext, field("type")?.to_string()))
+}
+
+/// Converts a decrypted value to its type, `str`, `int`, `float`, `bool` or `bytes`.
+fn parse_plaintext(plaintext: Vec<u8>, datatype: &str) -> Result<Value, Box<dyn Error>> {
+    let text = String::from_utf8(plaintext)?;
+    let value = match datatype {
+        "str" | "bytes" => Value::String(text),
+        "int" => Value::Number(text.parse::<i64>()?.into()),
+        "float" => Number::from_f64(text.parse()?).map(Value::Number).ok_or("Invalid float.")?,
+        "bool" => match text.to_lowercase().as_str() {
+            "true" | "t" | "1" => Value::Bool(true),
+            "false" | "f" | "0" => Value::Bool(false),
+            _ => return Err(format!("Invalid boolean '{}'.", text).into())
+        },
+        _ => return Err(format!("Unsupported type '{}'.", datatype).into())
+    };
+    Ok(value)
+}
+
+/// Returns the bytes of a value hashed into the MAC, formatted as SOPS does.
+fn mac_bytes(value: &Value) -> Vec<u8> {
+    match value {
+        Value::String(s) => s.as_bytes().to_vec(),
+        Value::Bool(true) => b"True".to_vec(),
+        Value::Bool(false) => b"False".to_vec(),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.to_string().into_bytes(),
+            None => n.as_f64().unwrap_or_default().to_string().into_bytes(),
+        },
+        _ => vec![]
+    }
+}
\ No newline at end of file
@@ -0,0 +1,60 @@
+use std::error::Error;
+use std::time::Duration;
+use serde_json::Value;
+use crate::models::status_file::{DEFAULT_STATUS_INTERVAL, StatusFile};
+use crate::utils::duration_util::parse_duration;
+
+/// The shortest interval between two snapshots of the status file.
+const MIN_STATUS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Parses the optional `status_file` section of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The status snapshot of the scheduler, or `None` if the section is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'status_file' field is not an object.
+/// * The 'path' field is missing or is not a non-empty string.
+/// * The 'interval' field is not a duration of at least 1 second.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "status_file": { "path": "./status.json", "interval": "10s" } });
+/// let status_file = get_status_file(&value)?;
+/// println!("Status file: {:?}", status_file);
+/// ```
+pub fn get_status_file(value: &Value) -> Result<Option<StatusFile>, Box<dyn Error>> {
+    let status_file = match value.get("status_file") {
+        Some(s) => s,
+        None => return Ok(None)
+    };
+    if !status_file.is_object() {
+        return Err("The 'status_file' field must be an object.".into());
+    }
+
+    let path = status_file.get("path")
+        .and_then(|p| p.as_str())
+        .filter(|p| !p.is_empty())
+        .ok_or("The 'path' field of the status file is required and must be a non-empty string.")?;
+
+    let interval = match status_file.get("interval") {
+        Some(i) => i.as_str()
+            .and_then(parse_duration)
+            .filter(|i| *i >= MIN_STATUS_INTERVAL)
+            .ok_or("The 'interval' field of the status file must be a duration of at least '1s'.")?,
+        None => DEFAULT_STATUS_INTERVAL
+    };
+
+    Ok(Some(StatusFile::new(path.to_string(), interval)))
+}
\ No newline at end of file
@@ -0,0 +1,44 @@
+use std::error::Error;
+use serde_json::Value;
+
+/// Parses the optional `tags` of a job.
+///
+/// The tags select the jobs an instance of the scheduler runs, see `--tags` and
+/// `--exclude-tags`.
+///
+/// # Arguments
+///
+/// * `value` - The JSON value of the job.
+///
+/// # Returns
+///
+/// The tags of the job, empty if the field is missing.
+///
+/// # Errors
+///
+/// Returns an error if the field is not an array of non-empty strings.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "tags": ["prod", "billing"] });
+/// let tags = get_job_tags(&value)?;
+/// assert_eq!(tags, vec!["prod", "billing"]);
+/// ```
+pub fn get_job_tags(value: &Value) -> Result<Vec<String>, Box<dyn Error>> {
+    let tags = match value.get("tags") {
+        Some(t) => t.as_array().ok_or("The 'tags' field must be an array of strings.")?,
+        None => return Ok(vec![])
+    };
+
+    tags.iter()
+        .map(|t| {
+            t.as_str()
+                .filter(|t| !t.trim().is_empty())
+                .map(|t| t.to_string())
+                .ok_or_else(|| "The 'tags' field must be an array of non-empty strings.".into())
+        })
+        .collect()
+}
\ No newline at end of file
@@ -0,0 +1,93 @@
+use std::error::Error;
+use serde_json::Value;
+
+use crate::configure::get_job_array;
+use crate::configure::job_options::get_job_options;
+use crate::configure::schedules::get_job_schedule;
+use crate::models::on_invalid_job::OnInvalidJob;
+use crate::models::tcp_job::TcpJob;
+
+/// Parses the optional 'tcp_jobs' array of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+/// * `on_invalid_job` - Whether to fail or skip when a job is invalid.
+///
+/// # Returns
+///
+/// The list of TCP jobs, empty if the field is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'tcp_jobs' field is not an array.
+/// * The 'name', 'cron' or 'host' field is missing or not a string for any job.
+/// * The 'cron' field is not a valid cron expression for any job.
+/// * The 'port' field is missing or not a number between 1 and 65535 for any job.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({
+///     "tcp_jobs": [{
+///         "name": "postgres",
+///         "cron": "0 * * * * ?",
+///         "host": "db.example.com",
+///         "port": 5432
+///     }]
+/// });
+/// let jobs = get_tcp_jobs(&value, OnInvalidJob::Fail)?;
+/// ```
+pub fn get_tcp_jobs(value: &Value, on_invalid_job: OnInvalidJob) -> Result<Vec<TcpJob>, Box<dyn Error>> {
+    get_job_array(value, "tcp_jobs", on_invalid_job, get_tcp_job)
+}
+
+/// Parses a single job of the 'tcp_jobs' array, see `get_tcp_jobs`.
+fn get_tcp_job(it: &Value) -> Result<TcpJob, Box<dyn Error>> {
+    let name = it.get("name")
+        .and_then(|n| n.as_str())
+        .ok_or("The 'name' field is missing or not a string.")?
+        .to_string();
+
+    let enable = it.get("enable")
+        .and_then(|e| e.as_bool())
+        .unwrap_or(true);
+
+    let schedule = get_job_schedule(it, &name)?;
+
+    let timeout = it.get("timeout")
+        .and_then(|t| t.as_u64())
+        .unwrap_or(5000);
+
+    let max_retry = it.get("max_retry")
+        .and_then(|m| m.as_u64())
+        .unwrap_or(3);
+
+    let host = it.get("host")
+        .and_then(|h| h.as_str())
+        .ok_or("The 'host' field is required and must be a string.")?
+        .to_string();
+
+    let port = it.get("port")
+        .and_then(|p| p.as_u64())
+        .and_then(|p| u16::try_from(p).ok())
+        .filter(|p| *p != 0)
+        .ok_or(format!("The 'port' of the TCP job '{}' is required and must be a number between 1 and 65535.", name))?;
+
+    let options = get_job_options(it)?;
+
+    Ok(TcpJob {
+        name,
+        enable,
+        schedule,
+        timeout,
+        max_retry,
+        host,
+        port,
+        options,
+    })
+}
\ No newline at end of file
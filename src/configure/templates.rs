@@ -0,0 +1,138 @@
+use std::error::Error;
+use serde_json::{Map, Value};
+
+/// Parses the optional `templates` section of the configuration.
+///
+/// Each template is an object with the fields of a job, and may extend another template.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The templates by name, empty if the field is missing.
+///
+/// # Errors
+///
+/// Returns an error if the field is not an object of objects, or a template extends an unknown
+/// template or itself in a cycle.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({
+///     "templates": {
+///         "api": { "max_retry": 5, "request": { "base_url": "https://api.example.com" } }
+///     }
+/// });
+/// let templates = get_templates(&value)?;
+/// ```
+pub fn get_templates(value: &Value) -> Result<Map<String, Value>, Box<dyn Error>> {
+    let templates = match value.get("templates") {
+        Some(t) => t.as_object().ok_or("The 'templates' field must be an object of templates.")?,
+        None => return Ok(Map::new())
+    };
+
+    for (name, template) in templates {
+        if !template.is_object() {
+            return Err(format!("The template '{}' must be an object.", name).into());
+        }
+        resolve_template(name, templates, &mut vec![])?;
+    }
+
+    Ok(templates.clone())
+}
+
+/// Applies the template a job extends to the job.
+///
+/// The fields of the job override the fields of the template. Objects such as `request` and
+/// `headers` are merged field by field, so a job only declares what differs from its template.
+/// Other values, including arrays, are replaced.
+///
+/// # Arguments
+///
+/// * `job` - The JSON value of the job.
+/// * `templates` - The templates by name, see `get_templates`.
+///
+/// # Returns
+///
+/// The job with its template applied, or a copy of the job if it doesn't extend a template.
+///
+/// # Errors
+///
+/// Returns an error if the `extends` field is not a string or names an unknown template.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let job = json!({ "name": "users", "cron": "0 * * * * ?", "extends": "api", "request": { "url": "/users" } });
+/// let job = extend_job(&job, &templates)?;
+/// ```
+pub fn extend_job(job: &Value, templates: &Map<String, Value>) -> Result<Value, Box<dyn Error>> {
+    let Some(extends) = job.get("extends") else {
+        return Ok(job.clone());
+    };
+    let name = extends.as_str().ok_or("The 'extends' field must be the name of a template.")?;
+
+    let mut extended = resolve_template(name, templates, &mut vec![])?;
+    let mut job = job.clone();
+    if let Some(job) = job.as_object_mut() {
+        job.remove("extends");
+    }
+    override_with(&mut extended, job);
+
+    Ok(extended)
+}
+
+/// Resolves a template and the templates it extends into a single object.
+///
+/// # Arguments
+///
+/// * `chain` - The names of the templates extending this one, to detect cycles.
+fn resolve_template(name: &str, templates: &Map<String, Value>, chain: &mut Vec<String>) -> Result<Value, Box<dyn Error>> {
+    let template = templates.get(name)
+        .ok_or(format!("The template '{}' doesn't exist.", name))?;
+
+    if chain.iter().any(|n| n == name) {
+        chain.push(name.to_string());
+        return Err(format!("The templates extend each other in a cycle: {}", chain.join(" -> ")).into());
+    }
+
+    let mut template = template.clone();
+    let Some(extends) = template.as_object_mut().and_then(|t| t.remove("extends")) else {
+        return Ok(template);
+    };
+    let parent = extends.as_str()
+        .ok_or(format!("The 'extends' field of the template '{}' must be the name of a template.", name))?;
+
+    chain.push(name.to_string());
+    let mut resolved = resolve_template(parent, templates, chain)?;
+    chain.pop();
+
+    override_with(&mut resolved, template);
+    Ok(resolved)
+}
+
+/// Overrides the fields of a value with the fields of another.
+///
+/// Objects are merged field by field, any other value of `overlay` replaces the value of `base`.
+fn override_with(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => override_with(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
\ No newline at end of file
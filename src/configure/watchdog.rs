@@ -0,0 +1,70 @@
+use std::error::Error;
+use std::time::Duration;
+use serde_json::Value;
+use crate::models::watchdog::{DEFAULT_WATCHDOG_TIMEOUT, Watchdog, WatchdogAction};
+use crate::utils::duration_util::parse_duration;
+
+/// The shortest timeout of the watchdog, two ticks of its heartbeat job.
+const MIN_WATCHDOG_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Parses the optional `watchdog` section of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+///
+/// # Returns
+///
+/// The watchdog of the scheduler, or `None` if the section is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'watchdog' field is not an object.
+/// * The 'timeout' field is not a duration of at least 2 minutes.
+/// * The 'action' field is not one of 'log', 'notify' or 'exit', or a non-empty array of them.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({ "watchdog": { "timeout": "5m", "action": ["notify", "exit"] } });
+/// let watchdog = get_watchdog(&value)?;
+/// println!("Watchdog: {:?}", watchdog);
+/// ```
+pub fn get_watchdog(value: &Value) -> Result<Option<Watchdog>, Box<dyn Error>> {
+    let watchdog = match value.get("watchdog") {
+        Some(w) => w,
+        None => return Ok(None)
+    };
+    if !watchdog.is_object() {
+        return Err("The 'watchdog' field must be an object.".into());
+    }
+
+    let timeout = match watchdog.get("timeout") {
+        Some(t) => t.as_str()
+            .and_then(parse_duration)
+            .filter(|t| *t >= MIN_WATCHDOG_TIMEOUT)
+            .ok_or("The 'timeout' field of the watchdog must be a duration of at least '2m'.")?,
+        None => DEFAULT_WATCHDOG_TIMEOUT
+    };
+
+    let actions = match watchdog.get("action") {
+        Some(Value::String(action)) => vec![parse_action(action)?],
+        Some(Value::Array(actions)) if !actions.is_empty() => actions.iter()
+            .map(|action| action.as_str().ok_or("The actions of the watchdog must be strings.".into()).and_then(parse_action))
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?,
+        Some(_) => return Err("The 'action' field of the watchdog must be a string or a non-empty array of strings.".into()),
+        None => vec![WatchdogAction::Log]
+    };
+
+    Ok(Some(Watchdog::new(timeout, actions)))
+}
+
+/// Parses an action of the watchdog.
+fn parse_action(action: &str) -> Result<WatchdogAction, Box<dyn Error>> {
+    WatchdogAction::parse(action)
+        .ok_or(format!("Invalid watchdog action '{}', expected 'log', 'notify' or 'exit'.", action).into())
+}
\ No newline at end of file
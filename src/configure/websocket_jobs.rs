@@ -0,0 +1,105 @@
+use std::error::Error;
+use serde_json::Value;
+
+use crate::configure::http_jobs::get_headers;
+use crate::configure::get_job_array;
+use crate::configure::job_options::get_job_options;
+use crate::configure::schedules::get_job_schedule;
+use crate::models::on_invalid_job::OnInvalidJob;
+use crate::models::websocket_job::WebSocketJob;
+
+/// Parses the optional 'websocket_jobs' array of the configuration.
+///
+/// # Arguments
+///
+/// * `value` - The JSON configuration value.
+/// * `on_invalid_job` - Whether to fail or skip when a job is invalid.
+///
+/// # Returns
+///
+/// The list of WebSocket jobs, empty if the field is missing.
+///
+/// # Errors
+///
+/// This function can return an error under the following conditions:
+///
+/// * The 'websocket_jobs' field is not an array.
+/// * The 'name', 'cron' or 'url' field is missing or not a string for any job.
+/// * The 'cron' field is not a valid cron expression for any job.
+/// * The 'url' field does not start with `ws://` or `wss://` for any job.
+/// * The 'message' or 'expect' field is not a string for any job.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({
+///     "websocket_jobs": [{
+///         "name": "echo",
+///         "cron": "0 */5 * * * ?",
+///         "url": "wss://echo.websocket.org",
+///         "message": "ping",
+///         "expect": "ping"
+///     }]
+/// });
+/// let jobs = get_websocket_jobs(&value, OnInvalidJob::Fail)?;
+/// ```
+pub fn get_websocket_jobs(value: &Value, on_invalid_job: OnInvalidJob) -> Result<Vec<WebSocketJob>, Box<dyn Error>> {
+    get_job_array(value, "websocket_jobs", on_invalid_job, get_websocket_job)
+}
+
+/// Parses a single job of the 'websocket_jobs' array, see `get_websocket_jobs`.
+fn get_websocket_job(it: &Value) -> Result<WebSocketJob, Box<dyn Error>> {
+    let name = it.get("name")
+        .and_then(|n| n.as_str())
+        .ok_or("The 'name' field is missing or not a string.")?
+        .to_string();
+
+    let enable = it.get("enable")
+        .and_then(|e| e.as_bool())
+        .unwrap_or(true);
+
+    let schedule = get_job_schedule(it, &name)?;
+
+    let timeout = it.get("timeout")
+        .and_then(|t| t.as_u64())
+        .unwrap_or(5000);
+
+    let max_retry = it.get("max_retry")
+        .and_then(|m| m.as_u64())
+        .unwrap_or(3);
+
+    let url = it.get("url")
+        .and_then(|u| u.as_str())
+        .ok_or("The 'url' field is required and must be a string.")?
+        .to_string();
+    if !url.starts_with("ws://") && !url.starts_with("wss://") {
+        return Err(format!("The 'url' of the WebSocket job '{}' must start with ws:// or wss://.", name).into());
+    }
+
+    let headers = get_headers(it)?;
+
+    let message = it.get("message")
+        .map(|m| m.as_str().map(|m| m.to_string()).ok_or("The 'message' field must be a string."))
+        .transpose()?;
+
+    let expect = it.get("expect")
+        .map(|e| e.as_str().map(|e| e.to_string()).ok_or("The 'expect' field must be a string."))
+        .transpose()?;
+
+    let options = get_job_options(it)?;
+
+    Ok(WebSocketJob {
+        name,
+        enable,
+        schedule,
+        timeout,
+        max_retry,
+        url,
+        headers,
+        message,
+        expect,
+        options,
+    })
+}
\ No newline at end of file
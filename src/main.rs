@@ -1,14 +1,170 @@
-use std::time::Duration;
+// The JSON Schema of the jobs file is built with a single `json!` invocation.
+#![recursion_limit = "256"]
 
-use crate::scheduler::cron_scheduler::start_cron_scheduler;
+use std::path::Path;
+use std::process;
+use clap::Parser;
 
+use crate::command::{Cli, Command};
+use crate::command::health::health;
+use crate::command::list::list;
+use crate::command::next::next;
+use crate::command::pause::pause;
+use crate::command::replay::replay;
+use crate::command::run::run;
+use crate::command::schema::schema;
+use crate::command::set::set;
+#[cfg(windows)]
+use crate::command::service::service;
+use crate::configure::config_source::load_config_source;
+use crate::configure::get_jobs;
+use crate::configure::reload::watch_configuration;
+use crate::models::tag_filter::TagFilter;
+use crate::scheduler::cron_scheduler::{start_cron_scheduler, stop_cron_scheduler, wait_for_shutdown_signal};
+#[cfg(unix)]
+use crate::scheduler::daemon::{daemonize, lock_pid_file, PidFile};
+use crate::scheduler::health::get_health;
+use crate::scheduler::run_log::open_run_log;
+use crate::scheduler::systemd::notify_systemd;
+use crate::server::start_admin_server;
+use crate::telemetry::init_telemetry;
+
+mod command;
 mod models;
+mod notifier;
 mod configure;
 mod scheduler;
+mod server;
+mod telemetry;
 mod utils;
 
+fn main() {
+    let cli = Cli::parse();
+
+    #[cfg(unix)]
+    let pid_file = if cli.command.is_none() { detach(&cli) } else { None };
+
+    execute(cli);
+
+    #[cfg(unix)]
+    if let Some(pid_file) = pid_file {
+        pid_file.remove();
+    }
+}
+
+/// Locks the PID file and runs the scheduler in the background, before the runtime starts.
+///
+/// The 'jobs' file is parsed before detaching, so its errors still reach the terminal.
+#[cfg(unix)]
+fn detach(cli: &Cli) -> Option<PidFile> {
+    let mut pid_file = cli.daemon.pid_file.as_deref().map(|path| lock_pid_file(path).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    }));
+
+    if cli.daemon.daemon {
+        if cli.config.is_none() {
+            get_jobs();
+        }
+        if let Err(err) = daemonize(cli.daemon.log_file.as_deref()) {
+            eprintln!("Failed to run in the background: {}", err);
+            process::exit(1);
+        }
+    }
+
+    if let Some(pid_file) = &mut pid_file {
+        if let Err(err) = pid_file.write_pid() {
+            eprintln!("Failed to write the PID file: {}", err);
+            process::exit(1);
+        }
+    }
+    pid_file
+}
+
+/// Runs the command of the command line, or the scheduler without a command.
 #[tokio::main]
-async fn main() {
-    start_cron_scheduler().await;
-    tokio::time::sleep(Duration::MAX).await;
+async fn execute(mut cli: Cli) {
+
+    if let Some(config) = &cli.config {
+        if let Err(err) = load_config_source(config).await {
+            eprintln!("Failed to read the configuration: {}", err);
+            process::exit(1);
+        }
+    }
+
+    match cli.command.take() {
+        Some(Command::Health(args)) => {
+            if !health(args).await {
+                process::exit(1);
+            }
+        }
+        Some(Command::List(args)) => list(args),
+        Some(Command::Next(args)) => next(args),
+        Some(Command::Pause(args)) => {
+            if !pause(args, true) {
+                process::exit(1);
+            }
+        }
+        Some(Command::Resume(args)) => {
+            if !pause(args, false) {
+                process::exit(1);
+            }
+        }
+        Some(Command::Replay(args)) => {
+            if !replay(args).await {
+                process::exit(1);
+            }
+        }
+        Some(Command::Run(args)) => {
+            if !run(args).await {
+                process::exit(1);
+            }
+        }
+        Some(Command::Schema(args)) => {
+            if !schema(args) {
+                process::exit(1);
+            }
+        }
+        Some(Command::Set(args)) => {
+            if !set(args) {
+                process::exit(1);
+            }
+        }
+        #[cfg(windows)]
+        Some(Command::Service(args)) => {
+            let start = start_scheduler(cli.dry_run, cli.tags.to_filter(), cli.run_log.as_deref());
+            if !service(args, &cli, start).await {
+                process::exit(1);
+            }
+        }
+        None => {
+            start_scheduler(cli.dry_run, cli.tags.to_filter(), cli.run_log.as_deref()).await;
+            notify_systemd(&format!("READY=1\nSTATUS=Scheduling {} jobs", get_health().registered_jobs));
+            wait_for_shutdown_signal().await;
+            notify_systemd("STOPPING=1");
+            stop_cron_scheduler().await;
+        }
+    }
+}
+
+/// Starts the scheduler with its telemetry, its run log, its admin server and the reload of its
+/// configuration.
+async fn start_scheduler(dry_run: bool, tag_filter: TagFilter, run_log: Option<&Path>) {
+    if let Some(telemetry) = &get_jobs().telemetry {
+        if let Err(err) = init_telemetry(telemetry) {
+            eprintln!("Failed to initialize telemetry: {}", err);
+            process::exit(1);
+        }
+    }
+    if let Some(path) = run_log {
+        if let Err(err) = open_run_log(path) {
+            eprintln!("Failed to open the run log: {}", err);
+            process::exit(1);
+        }
+    }
+    start_cron_scheduler(dry_run, tag_filter).await;
+    if let Some(address) = get_jobs().admin_address {
+        start_admin_server(address);
+    }
+    watch_configuration();
 }
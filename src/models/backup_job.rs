@@ -0,0 +1,27 @@
+use std::fmt::{Display, Formatter};
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+
+/// A scheduled backup of a local directory into a `.tar.gz` archive.
+///
+/// Each run archives `source` into `target_dir`, naming the archive after the `file_name`
+/// template, which supports the `{{job_name}}`, `{{run_id}}`, `{{now}}` and `{{date}}` variables.
+/// If `keep` is set, only the newest `keep` archives matching the template are kept.
+#[derive(Debug, Clone)]
+pub struct BackupJob {
+    pub name: String,
+    pub enable: bool,
+    pub schedule: JobSchedule,
+    pub source: String,
+    pub target_dir: String,
+    pub file_name: String,
+    pub keep: Option<usize>,
+    pub options: JobOptions,
+}
+
+impl Display for BackupJob {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "name: {}, enable: {}, cron: {}, source: {}, target_dir: {}, file_name: {}, keep: {:?}",
+               self.name, self.enable, self.schedule, self.source, self.target_dir, self.file_name, self.keep)
+    }
+}
\ No newline at end of file
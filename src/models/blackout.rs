@@ -0,0 +1,74 @@
+use std::fmt::{Display, Formatter};
+use chrono::{Datelike, NaiveDateTime, NaiveTime, Weekday};
+
+/// A period during which the scheduled runs are skipped, e.g. a deployment or a maintenance.
+///
+/// The times are local times in the configured timezone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blackout {
+    /// The name of the blackout, shown in the logs.
+    pub name: Option<String>,
+    pub window: BlackoutWindow,
+}
+
+/// The time range of a blackout.
+///
+/// - `Once`: From a date-time until another, e.g. for a planned deployment.
+/// - `Recurring`: Every day, or on some days of the week, from a time of day until another. A
+///   window whose `end` is before its `start` spans midnight, and belongs to the day it starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlackoutWindow {
+    Once { start: NaiveDateTime, end: NaiveDateTime },
+    Recurring { start: NaiveTime, end: NaiveTime, weekdays: Vec<Weekday> },
+}
+
+impl Blackout {
+
+    /// Returns whether a local time is within the blackout, its end excluded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let blackout = Blackout {
+    ///     name: None,
+    ///     window: BlackoutWindow::Recurring { start: NaiveTime::from_hms_opt(23, 0, 0).unwrap(), end: NaiveTime::from_hms_opt(1, 0, 0).unwrap(), weekdays: vec![] },
+    /// };
+    /// assert!(blackout.contains(NaiveDate::from_ymd_opt(2026, 11, 1).unwrap().and_hms_opt(0, 30, 0).unwrap()));
+    /// ```
+    pub fn contains(&self, time: NaiveDateTime) -> bool {
+        match &self.window {
+            BlackoutWindow::Once { start, end } => *start <= time && time < *end,
+            BlackoutWindow::Recurring { start, end, weekdays } => {
+                let on_day = |weekday: Weekday| weekdays.is_empty() || weekdays.contains(&weekday);
+                let weekday = time.weekday();
+                let clock = time.time();
+
+                if start < end {
+                    on_day(weekday) && *start <= clock && clock < *end
+                } else {
+                    (on_day(weekday) && *start <= clock) || (on_day(weekday.pred()) && clock < *end)
+                }
+            }
+        }
+    }
+}
+
+impl Display for Blackout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let window = match &self.window {
+            BlackoutWindow::Once { start, end } =>
+                format!("{} - {}", start.format("%Y-%m-%d %H:%M:%S"), end.format("%Y-%m-%d %H:%M:%S")),
+            BlackoutWindow::Recurring { start, end, weekdays } if weekdays.is_empty() =>
+                format!("{}-{} every day", start.format("%H:%M:%S"), end.format("%H:%M:%S")),
+            BlackoutWindow::Recurring { start, end, weekdays } => {
+                let weekdays = weekdays.iter().map(|w| w.to_string()).collect::<Vec<_>>().join(", ");
+                format!("{}-{} on {}", start.format("%H:%M:%S"), end.format("%H:%M:%S"), weekdays)
+            }
+        };
+
+        match &self.name {
+            Some(name) => write!(f, "{} ({})", name, window),
+            None => write!(f, "{}", window),
+        }
+    }
+}
\ No newline at end of file
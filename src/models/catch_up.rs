@@ -0,0 +1,16 @@
+/// The catch-up policy of a job for schedule ticks missed while rjob was not running.
+///
+/// On startup, the ticks between the last successful run of the job and now are counted and the
+/// job is run once per missed tick, at most `max_runs` times.
+#[derive(Debug, Clone)]
+pub struct CatchUp {
+    pub max_runs: u32,
+}
+
+impl CatchUp {
+    pub fn new(max_runs: u32) -> Self {
+        CatchUp {
+            max_runs,
+        }
+    }
+}
\ No newline at end of file
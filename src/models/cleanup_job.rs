@@ -0,0 +1,46 @@
+use std::fmt::{Display, Formatter};
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+
+/// What a cleanup job does with the files it matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CleanupAction {
+    /// Deletes the files.
+    Delete,
+    /// Moves the files into the given directory, keeping their path relative to the cleaned directory.
+    Archive(String),
+}
+
+impl Display for CleanupAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CleanupAction::Delete => write!(f, "delete"),
+            CleanupAction::Archive(dir) => write!(f, "archive to {}", dir),
+        }
+    }
+}
+
+/// A scheduled cleanup of old files in a local directory.
+///
+/// Each run matches the file names in `directory`, and in its subdirectories if `recursive` is
+/// set, against `pattern`, and applies `action` to the matching files last modified more than
+/// `older_than_days` days ago.
+#[derive(Debug, Clone)]
+pub struct CleanupJob {
+    pub name: String,
+    pub enable: bool,
+    pub schedule: JobSchedule,
+    pub directory: String,
+    pub pattern: String,
+    pub recursive: bool,
+    pub older_than_days: u64,
+    pub action: CleanupAction,
+    pub options: JobOptions,
+}
+
+impl Display for CleanupJob {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "name: {}, enable: {}, dry_run: {}, cron: {}, directory: {}, pattern: {}, recursive: {}, older_than_days: {}, action: {}",
+               self.name, self.enable, self.options.dry_run, self.schedule, self.directory, self.pattern, self.recursive, self.older_than_days, self.action)
+    }
+}
\ No newline at end of file
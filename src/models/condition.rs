@@ -0,0 +1,416 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// The variables a condition can read, next to the `env.NAME` and `kv.NAME` variables.
+pub const CONDITION_VARIABLES: [&str; 9] = [
+    "weekday", "day", "month", "year", "hour", "minute", "last_day_of_month", "last_outcome", "consecutive_failures",
+];
+
+/// The `when` expression of a job, evaluated by the timer before each triggered run.
+///
+/// An expression compares variables and literals with `==`, `!=`, `<`, `<=`, `>` and `>=`,
+/// and combines the comparisons with `&&`, `||`, `!` and parentheses, e.g.
+/// `last_outcome == 'failure' && weekday != 'sun'`. A variable or a literal on its own is
+/// true unless it is `false`, `null`, `0` or an empty string.
+#[derive(Debug, Clone)]
+pub struct Condition {
+    /// The expression as configured, shown in the logs.
+    pub expression: String,
+    root: Expr,
+}
+
+/// The value of a variable or a literal of a condition.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Literal(ConditionValue),
+    Variable(String),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, &'static str, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Text(String),
+    Identifier(String),
+    Operator(&'static str),
+}
+
+/// The operators of a condition, the longer ones first so `<=` is not read as `<`.
+const OPERATORS: [&str; 11] = ["==", "!=", "<=", ">=", "&&", "||", "<", ">", "!", "(", ")"];
+
+impl Condition {
+
+    /// Parses a `when` expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - The expression, e.g. `!last_day_of_month && kv.maintenance != 'on'`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expression is not valid, or uses an unknown variable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let condition = Condition::parse("last_outcome == 'failure'")?;
+    /// assert!(condition.evaluate(|_| ConditionValue::Text("failure".to_string()))?);
+    /// ```
+    pub fn parse(expression: &str) -> Result<Self, Box<dyn Error>> {
+        let tokens = tokenize(expression)?;
+        let mut parser = Parser { tokens, position: 0 };
+        let root = parser.parse_or()?;
+        if let Some(token) = parser.tokens.get(parser.position) {
+            return Err(format!("Unexpected {} in the expression '{}'.", token, expression).into());
+        }
+
+        Ok(Condition { expression: expression.to_string(), root })
+    }
+
+    /// Evaluates the condition with the values of its variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `lookup` - Returns the value of a variable, given its name.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the job should run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if two values cannot be ordered, e.g. a number and a string that is not
+    /// a number.
+    pub fn evaluate<F: Fn(&str) -> ConditionValue>(&self, lookup: F) -> Result<bool, Box<dyn Error>> {
+        Ok(evaluate(&self.root, &lookup)?.is_truthy())
+    }
+
+    /// Returns whether the condition reads a variable, or a variable starting with a prefix
+    /// such as `kv.`, so the values that are expensive to get are only read when needed.
+    pub fn uses(&self, name: &str) -> bool {
+        uses(&self.root, name)
+    }
+}
+
+impl Display for Condition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+impl ConditionValue {
+
+    /// Returns whether the value counts as true on its own.
+    fn is_truthy(&self) -> bool {
+        match self {
+            ConditionValue::Null => false,
+            ConditionValue::Bool(b) => *b,
+            ConditionValue::Number(n) => *n != 0.0,
+            ConditionValue::Text(t) => !t.is_empty(),
+        }
+    }
+
+    /// Returns the value as a number, a string holding a number included, e.g. a `kv` value.
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            ConditionValue::Number(n) => Some(*n),
+            ConditionValue::Text(t) => t.trim().parse().ok(),
+            _ => None
+        }
+    }
+}
+
+impl Display for ConditionValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConditionValue::Null => write!(f, "null"),
+            ConditionValue::Bool(b) => write!(f, "{}", b),
+            ConditionValue::Number(n) => write!(f, "{}", n),
+            ConditionValue::Text(t) => write!(f, "'{}'", t),
+        }
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(n) => write!(f, "'{}'", n),
+            Token::Text(t) => write!(f, "string '{}'", t),
+            Token::Identifier(name) => write!(f, "'{}'", name),
+            Token::Operator(op) => write!(f, "'{}'", op),
+        }
+    }
+}
+
+/// Splits an expression into numbers, quoted strings, identifiers and operators.
+fn tokenize(expression: &str) -> Result<Vec<Token>, Box<dyn Error>> {
+    let mut tokens = Vec::new();
+    let mut rest = expression.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        let len = if c == '\'' || c == '"' {
+            let end = rest[1..].find(c)
+                .ok_or(format!("Unterminated string in the expression '{}'.", expression))?;
+            tokens.push(Token::Text(rest[1..end + 1].to_string()));
+            end + 2
+        } else if c.is_ascii_digit() {
+            let len = rest.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(rest.len());
+            let number = rest[..len].parse()
+                .map_err(|_| format!("Invalid number '{}' in the expression '{}'.", &rest[..len], expression))?;
+            tokens.push(Token::Number(number));
+            len
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let len = rest.find(|c: char| !c.is_ascii_alphanumeric() && c != '_' && c != '.').unwrap_or(rest.len());
+            tokens.push(Token::Identifier(rest[..len].to_string()));
+            len
+        } else {
+            let op = OPERATORS.into_iter().find(|op| rest.starts_with(op))
+                .ok_or(format!("Unexpected character '{}' in the expression '{}'.", c, expression))?;
+            tokens.push(Token::Operator(op));
+            op.len()
+        };
+        rest = rest[len..].trim_start();
+    }
+
+    Ok(tokens)
+}
+
+/// A recursive descent parser of the tokens of an expression, `||` binding the loosest.
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+
+    /// Consumes the next token if it is the operator `op`.
+    fn eat(&mut self, op: &str) -> bool {
+        let matches = matches!(self.tokens.get(self.position), Some(Token::Operator(o)) if *o == op);
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, Box<dyn Error>> {
+        let mut left = self.parse_and()?;
+        while self.eat("||") {
+            left = Expr::Or(Box::new(left), Box::new(self.parse_and()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, Box<dyn Error>> {
+        let mut left = self.parse_not()?;
+        while self.eat("&&") {
+            left = Expr::And(Box::new(left), Box::new(self.parse_not()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, Box<dyn Error>> {
+        if self.eat("!") {
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, Box<dyn Error>> {
+        let left = self.parse_operand()?;
+        let op = match self.tokens.get(self.position) {
+            Some(Token::Operator(op)) if ["==", "!=", "<", "<=", ">", ">="].contains(op) => *op,
+            _ => return Ok(left)
+        };
+        self.position += 1;
+        Ok(Expr::Compare(Box::new(left), op, Box::new(self.parse_operand()?)))
+    }
+
+    fn parse_operand(&mut self) -> Result<Expr, Box<dyn Error>> {
+        if self.eat("(") {
+            let expr = self.parse_or()?;
+            if !self.eat(")") {
+                return Err("Missing ')' in the expression.".into());
+            }
+            return Ok(expr);
+        }
+
+        let token = self.tokens.get(self.position).cloned().ok_or("Unexpected end of the expression.")?;
+        self.position += 1;
+        match token {
+            Token::Number(n) => Ok(Expr::Literal(ConditionValue::Number(n))),
+            Token::Text(t) => Ok(Expr::Literal(ConditionValue::Text(t))),
+            Token::Identifier(name) => match name.as_str() {
+                "true" => Ok(Expr::Literal(ConditionValue::Bool(true))),
+                "false" => Ok(Expr::Literal(ConditionValue::Bool(false))),
+                "null" => Ok(Expr::Literal(ConditionValue::Null)),
+                _ if is_variable(&name) => Ok(Expr::Variable(name)),
+                _ => Err(format!("Unknown variable '{}', expected one of {}, 'env.NAME' or 'kv.NAME'.", name, CONDITION_VARIABLES.join(", ")).into())
+            },
+            Token::Operator(_) => Err(format!("Unexpected {}.", token).into()),
+        }
+    }
+}
+
+/// Returns whether a name is a variable of a condition.
+fn is_variable(name: &str) -> bool {
+    CONDITION_VARIABLES.contains(&name)
+        || ["env.", "kv."].iter().any(|prefix| name.strip_prefix(prefix).is_some_and(|rest| !rest.is_empty()))
+}
+
+fn uses(expr: &Expr, name: &str) -> bool {
+    match expr {
+        Expr::Literal(_) => false,
+        Expr::Variable(variable) => variable.starts_with(name),
+        Expr::Not(e) => uses(e, name),
+        Expr::And(l, r) | Expr::Or(l, r) | Expr::Compare(l, _, r) => uses(l, name) || uses(r, name),
+    }
+}
+
+fn evaluate(expr: &Expr, lookup: &dyn Fn(&str) -> ConditionValue) -> Result<ConditionValue, Box<dyn Error>> {
+    let value = match expr {
+        Expr::Literal(value) => value.clone(),
+        Expr::Variable(name) => lookup(name),
+        Expr::Not(e) => ConditionValue::Bool(!evaluate(e, lookup)?.is_truthy()),
+        Expr::And(l, r) => ConditionValue::Bool(evaluate(l, lookup)?.is_truthy() && evaluate(r, lookup)?.is_truthy()),
+        Expr::Or(l, r) => ConditionValue::Bool(evaluate(l, lookup)?.is_truthy() || evaluate(r, lookup)?.is_truthy()),
+        Expr::Compare(l, op, r) => ConditionValue::Bool(compare(&evaluate(l, lookup)?, op, &evaluate(r, lookup)?)?),
+    };
+    Ok(value)
+}
+
+/// Compares two values.
+///
+/// A number is compared with a string holding a number as numbers, since the `kv` and `env`
+/// values are strings. `==` and `!=` compare any values, the other operators only numbers or
+/// strings.
+fn compare(left: &ConditionValue, op: &str, right: &ConditionValue) -> Result<bool, Box<dyn Error>> {
+    let numeric = matches!(left, ConditionValue::Number(_)) || matches!(right, ConditionValue::Number(_));
+    let ordering = match (left, right) {
+        (ConditionValue::Text(l), ConditionValue::Text(r)) => Some(l.cmp(r)),
+        _ if numeric => left.as_number().zip(right.as_number()).and_then(|(l, r)| l.partial_cmp(&r)),
+        _ => None
+    };
+
+    match (op, ordering) {
+        ("==", Some(o)) => Ok(o == Ordering::Equal),
+        ("!=", Some(o)) => Ok(o != Ordering::Equal),
+        ("==", None) => Ok(left == right),
+        ("!=", None) => Ok(left != right),
+        ("<", Some(o)) => Ok(o == Ordering::Less),
+        ("<=", Some(o)) => Ok(o != Ordering::Greater),
+        (">", Some(o)) => Ok(o == Ordering::Greater),
+        (">=", Some(o)) => Ok(o != Ordering::Less),
+        _ => Err(format!("Cannot compare {} and {} with '{}'.", left, right, op).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Evaluates an expression with `weekday` set to `mon`, `kv.count` to the string `10` and
+    /// every other variable unset.
+    fn eval(expression: &str) -> Result<bool, Box<dyn Error>> {
+        Condition::parse(expression)?.evaluate(|name| match name {
+            "weekday" => ConditionValue::Text("mon".to_string()),
+            "kv.count" => ConditionValue::Text("10".to_string()),
+            "consecutive_failures" => ConditionValue::Number(3.0),
+            _ => ConditionValue::Null
+        })
+    }
+
+    #[test]
+    fn evaluates_doc_example() {
+        let condition = Condition::parse("last_outcome == 'failure'").unwrap();
+        assert!(condition.evaluate(|_| ConditionValue::Text("failure".to_string())).unwrap());
+        assert!(!condition.evaluate(|_| ConditionValue::Text("success".to_string())).unwrap());
+    }
+
+    #[test]
+    fn tokenizes_longest_operator_first() {
+        let tokens = tokenize("consecutive_failures<=3&&!kv.x").unwrap();
+        assert_eq!(tokens, vec![
+            Token::Identifier("consecutive_failures".to_string()),
+            Token::Operator("<="),
+            Token::Number(3.0),
+            Token::Operator("&&"),
+            Token::Operator("!"),
+            Token::Identifier("kv.x".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn tokenizes_quoted_strings_as_text() {
+        assert_eq!(tokenize("'10' \"a b\"").unwrap(), vec![Token::Text("10".to_string()), Token::Text("a b".to_string())]);
+        assert!(tokenize("weekday == 'mon").is_err());
+        assert!(tokenize("weekday = 'mon'").is_err());
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert!(eval("true || false && false").unwrap());
+        assert!(!eval("(true || false) && false").unwrap());
+    }
+
+    #[test]
+    fn not_binds_tighter_than_and_and_looser_than_comparison() {
+        assert!(!eval("!false && false").unwrap());
+        assert!(eval("!weekday == 'sun'").unwrap());
+    }
+
+    #[test]
+    fn compares_numbers_with_numeric_strings_as_numbers() {
+        assert!(eval("kv.count > 5").unwrap());
+        assert!(eval("kv.count == 10").unwrap());
+        assert!(eval("consecutive_failures >= '3'").unwrap());
+    }
+
+    #[test]
+    fn compares_quoted_values_as_strings() {
+        assert!(!eval("kv.count > '5'").unwrap());
+        assert!(!eval("kv.count == '10.0'").unwrap());
+        assert!(eval("weekday != 'sun'").unwrap());
+    }
+
+    #[test]
+    fn compares_unset_variables_with_null() {
+        assert!(eval("kv.missing == null").unwrap());
+        assert!(!eval("kv.missing").unwrap());
+        assert!(eval("kv.missing != 'on'").unwrap());
+    }
+
+    #[test]
+    fn rejects_ordering_values_that_are_not_numbers() {
+        assert!(eval("weekday < 3").is_err());
+        assert!(eval("kv.missing > 1").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert!(Condition::parse("hour > ").is_err());
+        assert!(Condition::parse("(hour > 8").is_err());
+        assert!(Condition::parse("hour > 8 9").is_err());
+        assert!(Condition::parse("uptime > 8").is_err());
+        assert!(Condition::parse("kv. == 'on'").is_err());
+    }
+
+    #[test]
+    fn reports_the_variables_it_reads() {
+        let condition = Condition::parse("!last_day_of_month && kv.maintenance != 'on'").unwrap();
+        assert!(condition.uses("kv."));
+        assert!(condition.uses("last_day_of_month"));
+        assert!(!condition.uses("env."));
+    }
+}
\ No newline at end of file
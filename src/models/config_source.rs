@@ -0,0 +1,100 @@
+use std::fmt::{Display, Formatter};
+use reqwest::Url;
+
+/// The key-value store holding the jobs document.
+///
+/// - `Consul`: A key of the Consul KV store, read with the HTTP API.
+/// - `Etcd`: A key of etcd, read with the JSON gateway of the v3 API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigBackend {
+    Consul,
+    Etcd,
+}
+
+impl ConfigBackend {
+
+    /// Returns the port of the HTTP API of the backend, when the URL doesn't have one.
+    pub fn default_port(&self) -> u16 {
+        match self {
+            ConfigBackend::Consul => 8500,
+            ConfigBackend::Etcd => 2379,
+        }
+    }
+}
+
+impl Display for ConfigBackend {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let backend = match self {
+            ConfigBackend::Consul => "consul",
+            ConfigBackend::Etcd => "etcd",
+        };
+        write!(f, "{}", backend)
+    }
+}
+
+/// The key of a Consul or etcd store the jobs document is read from, instead of the 'jobs' file.
+#[derive(Debug, Clone)]
+pub struct ConfigSource {
+    pub backend: ConfigBackend,
+    /// The base URL of the HTTP API, e.g. `http://127.0.0.1:8500`.
+    pub api_url: Url,
+    /// The key holding the document, e.g. `rjob/jobs.yaml`.
+    pub key: String,
+}
+
+impl ConfigSource {
+
+    /// Parses a source URL of the form `consul://host[:port]/key` or `etcd://host[:port]/key`.
+    ///
+    /// The API is called over HTTPS with the `consul+https` and `etcd+https` schemes. The format
+    /// of the document is given by the extension of the key, `.yaml` or `.yml` for YAML and JSON
+    /// otherwise, as for the 'jobs' file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not a URL with one of these schemes, a host and a key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let source = ConfigSource::parse("consul://127.0.0.1:8500/rjob/jobs.yaml")?;
+    /// assert_eq!(source.key, "rjob/jobs.yaml");
+    /// ```
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let url = Url::parse(value).map_err(|e| format!("Invalid configuration source '{}': {}", value, e))?;
+        let (backend, scheme) = match url.scheme() {
+            "consul" => (ConfigBackend::Consul, "http"),
+            "consul+https" => (ConfigBackend::Consul, "https"),
+            "etcd" => (ConfigBackend::Etcd, "http"),
+            "etcd+https" => (ConfigBackend::Etcd, "https"),
+            _ => return Err(format!("Invalid configuration source '{}': the scheme must be 'consul' or 'etcd'.", value))
+        };
+
+        let host = url.host_str()
+            .filter(|h| !h.is_empty())
+            .ok_or(format!("Invalid configuration source '{}': the host is missing.", value))?;
+        let key = url.path().trim_start_matches('/');
+        if key.is_empty() {
+            return Err(format!("Invalid configuration source '{}': the key is missing.", value));
+        }
+
+        let port = url.port().unwrap_or(backend.default_port());
+        let api_url = Url::parse(&format!("{}://{}:{}", scheme, host, port))
+            .map_err(|e| format!("Invalid configuration source '{}': {}", value, e))?;
+
+        Ok(ConfigSource {
+            backend,
+            api_url,
+            key: key.to_string(),
+        })
+    }
+}
+
+impl Display for ConfigSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let host = self.api_url.host_str().unwrap_or_default();
+        let port = self.api_url.port_or_known_default().unwrap_or(self.backend.default_port());
+        let tls = if self.api_url.scheme() == "https" { "+https" } else { "" };
+        write!(f, "{}{}://{}:{}/{}", self.backend, tls, host, port, self.key)
+    }
+}
\ No newline at end of file
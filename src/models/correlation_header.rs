@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+use crate::utils::template_util::render_template;
+
+/// The default value template of the correlation header.
+pub const DEFAULT_CORRELATION_VALUE: &str = "{{run_id}}";
+
+/// A header injected on every outgoing job request to correlate server-side logs with the run.
+///
+/// The value is a template supporting the `{{run_id}}` and `{{job_name}}` placeholders.
+#[derive(Debug, Clone)]
+pub struct CorrelationHeader {
+    pub name: String,
+    pub value: String,
+}
+
+impl CorrelationHeader {
+    pub fn new(name: String, value: String) -> Self {
+        CorrelationHeader {
+            name,
+            value,
+        }
+    }
+
+    /// Renders the header value for a run.
+    ///
+    /// # Arguments
+    ///
+    /// * `run_id` - The identifier of the run.
+    /// * `job_name` - The name of the job.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let header = CorrelationHeader::new("X-Request-Id".to_string(), "rjob-{{run_id}}".to_string());
+    /// assert_eq!(header.render_value("abc", "users"), "rjob-abc");
+    /// ```
+    pub fn render_value(&self, run_id: &str, job_name: &str) -> String {
+        let mut vars = HashMap::new();
+        vars.insert("run_id", run_id.to_string());
+        vars.insert("job_name", job_name.to_string());
+        render_template(&self.value, &vars)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,26 @@
+use std::fmt::{Display, Formatter};
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+use crate::utils::redact_util::redact_url;
+
+/// A scheduled SQL statement.
+///
+/// Each run connects to the Postgres or MySQL database of `dsn` and executes `statement`.
+#[derive(Debug, Clone)]
+pub struct DbJob {
+    pub name: String,
+    pub enable: bool,
+    pub schedule: JobSchedule,
+    pub timeout: u64,
+    pub max_retry: u64,
+    pub dsn: String,
+    pub statement: String,
+    pub options: JobOptions,
+}
+
+impl Display for DbJob {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "name: {}, enable: {}, cron: {}, timeout: {}, max_retry: {}, dsn: {}, statement: {}",
+               self.name, self.enable, self.schedule, self.timeout, self.max_retry, redact_url(&self.dsn), self.statement)
+    }
+}
\ No newline at end of file
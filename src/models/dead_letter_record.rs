@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::{Deserialize, Serialize};
+
+use crate::models::http_job_request::{BodyCompression, HttpJobRequest, HttpVersion, JSON_CONTENT_TYPE};
+
+/// A record of a job run that failed after all of its attempts, written to the dead-letter file.
+///
+/// The record contains a snapshot of the request so that it can be sent again with `rjob replay`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetterRecord {
+    pub job_name: String,
+    pub run_id: String,
+    pub time: String,
+    pub timeout: u64,
+    pub attempts: u64,
+    pub status: Option<u16>,
+    pub error: Option<String>,
+    pub request: DeadLetterRequest,
+}
+
+/// A serializable snapshot of an `HttpJobRequest`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetterRequest {
+    pub url: String,
+    pub method: String,
+    pub headers: Option<BTreeMap<String, String>>,
+    /// The default headers the request removes.
+    #[serde(default)]
+    pub removed_headers: Vec<String>,
+    pub body: Option<String>,
+    #[serde(default = "default_content_type")]
+    pub content_type: Option<String>,
+    pub http_version: String,
+    pub compress_body: Option<String>,
+    #[serde(default)]
+    pub graphql: bool,
+    pub host: Option<String>,
+    pub server_name: Option<String>,
+    pub accept_encoding: Option<Vec<String>>,
+}
+
+impl DeadLetterRequest {
+
+    /// Takes a snapshot of the given request.
+    ///
+    /// Header values that are not valid UTF-8 are converted lossily.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let snapshot = DeadLetterRequest::from_request(&http_job.request);
+    /// println!("URL: {}", snapshot.url);
+    /// ```
+    pub fn from_request(request: &HttpJobRequest) -> Self {
+        let headers = request.headers.as_ref().map(|headers| {
+            headers.iter()
+                .map(|(k, v)| (k.to_string(), String::from_utf8_lossy(v.as_bytes()).to_string()))
+                .collect()
+        });
+
+        DeadLetterRequest {
+            url: request.url.clone(),
+            method: request.method.clone(),
+            headers,
+            removed_headers: request.removed_headers.iter().map(|h| h.to_string()).collect(),
+            body: request.body.clone(),
+            content_type: request.content_type.clone(),
+            http_version: request.http_version.to_string(),
+            compress_body: request.compress_body.map(|c| c.to_string()),
+            graphql: request.graphql,
+            host: request.host.clone(),
+            server_name: request.server_name.clone(),
+            accept_encoding: request.accept_encoding.clone(),
+        }
+    }
+
+    /// Converts the snapshot back into an `HttpJobRequest`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a header name or value is invalid, or the compression is not supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let request = record.request.to_request()?;
+    /// println!("Method: {}", request.method);
+    /// ```
+    pub fn to_request(&self) -> Result<HttpJobRequest, Box<dyn Error>> {
+        let headers = match &self.headers {
+            Some(map) => {
+                let mut header_map = HeaderMap::new();
+                for (k, v) in map {
+                    header_map.append(HeaderName::try_from(k)?, HeaderValue::try_from(v)?);
+                }
+                Some(header_map)
+            }
+            None => None
+        };
+
+        let removed_headers = self.removed_headers.iter()
+            .map(HeaderName::try_from)
+            .collect::<Result<_, _>>()?;
+
+        let http_version = HttpVersion::parse(&self.http_version).unwrap_or_default();
+
+        let compress_body = self.compress_body.as_ref()
+            .map(|c| BodyCompression::parse(c).ok_or(format!("Unsupported body compression '{}'.", c)))
+            .transpose()?;
+
+        Ok(HttpJobRequest {
+            url: self.url.clone(),
+            method: self.method.clone(),
+            headers,
+            removed_headers,
+            body: self.body.clone(),
+            content_type: self.content_type.clone(),
+            http_version,
+            compress_body,
+            graphql: self.graphql,
+            host: self.host.clone(),
+            server_name: self.server_name.clone(),
+            accept_encoding: self.accept_encoding.clone(),
+        })
+    }
+}
+
+/// The content type of records written before it was recorded, when every body was JSON.
+fn default_content_type() -> Option<String> {
+    Some(JSON_CONTENT_TYPE.to_string())
+}
\ No newline at end of file
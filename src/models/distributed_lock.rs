@@ -0,0 +1,170 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+use reqwest::Url;
+
+/// The port of a Redis server, when its URL doesn't have one.
+pub const DEFAULT_REDIS_PORT: u16 = 6379;
+
+/// How long the lock of a run is kept, by default.
+pub const DEFAULT_LOCK_TTL: Duration = Duration::from_secs(600);
+
+/// The prefix of the keys of the locks, by default.
+pub const DEFAULT_LOCK_KEY_PREFIX: &str = "rjob:lock";
+
+/// What to do with a triggered run when the lock server cannot be reached.
+///
+/// - `Skip`: Skip the run, so that no run happens twice, at the risk of missing it.
+/// - `Run`: Run the job anyway, at the risk of running it on several instances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnLockError {
+    #[default]
+    Skip,
+    Run,
+}
+
+impl OnLockError {
+
+    /// Parses the value of the `on_error` field.
+    ///
+    /// Returns `None` if the value is not one of `skip` or `run`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let on_error = OnLockError::parse("run");
+    /// assert_eq!(on_error, Some(OnLockError::Run));
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "skip" => Some(OnLockError::Skip),
+            "run" => Some(OnLockError::Run),
+            _ => None
+        }
+    }
+}
+
+impl Display for OnLockError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let on_error = match self {
+            OnLockError::Skip => "skip",
+            OnLockError::Run => "run",
+        };
+        write!(f, "{}", on_error)
+    }
+}
+
+/// The address and credentials of a Redis server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedisServer {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub db: u32,
+}
+
+impl RedisServer {
+
+    /// Parses a Redis URL of the form `redis://[[username]:password@]host[:port][/db]`.
+    ///
+    /// Special characters of the username and password are percent-encoded, e.g. `%40` for `@`.
+    /// TLS (`rediss://`) is not supported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not a `redis://` URL with a host, or if the database is
+    /// not a number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let server = RedisServer::parse("redis://:secret@10.0.0.5:6380/2")?;
+    /// assert_eq!(server.port, 6380);
+    /// assert_eq!(server.db, 2);
+    /// ```
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let url = Url::parse(value).map_err(|e| format!("Invalid Redis URL '{}': {}", value, e))?;
+        if url.scheme() != "redis" {
+            return Err(format!("Invalid Redis URL '{}': the scheme must be 'redis'.", value));
+        }
+
+        let host = url.host_str()
+            .filter(|h| !h.is_empty())
+            .ok_or(format!("Invalid Redis URL '{}': the host is missing.", value))?
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .to_string();
+        let db = match url.path().trim_start_matches('/') {
+            "" => 0,
+            db => db.parse().map_err(|_| format!("Invalid Redis URL '{}': the database must be a number.", value))?
+        };
+
+        Ok(RedisServer {
+            host,
+            port: url.port().unwrap_or(DEFAULT_REDIS_PORT),
+            username: Some(url.username()).filter(|u| !u.is_empty()).map(decode_component),
+            password: url.password().map(decode_component),
+            db,
+        })
+    }
+}
+
+impl Display for RedisServer {
+    /// Formats the server as a URL without its password, for the logs.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.host.contains(':') {
+            write!(f, "redis://[{}]:{}/{}", self.host, self.port, self.db)
+        } else {
+            write!(f, "redis://{}:{}/{}", self.host, self.port, self.db)
+        }
+    }
+}
+
+/// The lock shared by the instances of the scheduler, so each fire time of a job runs once.
+///
+/// When several instances run the same configuration for high availability, each instance
+/// acquires the lock of a triggered run before starting it. The lock is keyed by the job name
+/// and the scheduled fire time, so only the first instance runs it and the others skip it. The
+/// lock is not released when the run ends, it expires after `ttl`, so an instance firing late
+/// doesn't run the same fire time again.
+#[derive(Debug, Clone)]
+pub struct DistributedLock {
+    pub redis: RedisServer,
+    pub ttl: Duration,
+    pub key_prefix: String,
+    pub on_error: OnLockError,
+}
+
+impl DistributedLock {
+    pub fn new(redis: RedisServer, ttl: Duration, key_prefix: String, on_error: OnLockError) -> Self {
+        DistributedLock {
+            redis,
+            ttl,
+            key_prefix,
+            on_error,
+        }
+    }
+}
+
+/// Decodes the percent-encoded characters of the username or password of a URL.
+fn decode_component(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(byte) if bytes[i] == b'%' => {
+                decoded.push(byte);
+                i += 3;
+            }
+            _ => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
\ No newline at end of file
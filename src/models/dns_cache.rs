@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+/// The time the addresses of a host name are cached for, by default.
+pub const DEFAULT_DNS_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// The cache of the DNS lookups of the HTTP clients.
+///
+/// The addresses of a host name are reused for `ttl` after they were resolved, instead of
+/// asking the system resolver again for every request, see `CachingResolver`.
+#[derive(Debug, Clone, Copy)]
+pub struct DnsCache {
+    pub ttl: Duration,
+}
+
+impl DnsCache {
+    pub fn new(ttl: Duration) -> Self {
+        DnsCache {
+            ttl,
+        }
+    }
+}
\ No newline at end of file
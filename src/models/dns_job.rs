@@ -0,0 +1,39 @@
+use std::fmt::{Display, Formatter};
+use std::net::SocketAddr;
+use hickory_resolver::proto::rr::RecordType;
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+
+/// A scheduled DNS query.
+///
+/// Each run resolves the `record_type` records of `query`, against `resolver` if set or the
+/// system resolver otherwise. If `expect` is set, the returned values must match it exactly,
+/// in any order.
+#[derive(Debug, Clone)]
+pub struct DnsJob {
+    pub name: String,
+    pub enable: bool,
+    pub schedule: JobSchedule,
+    pub timeout: u64,
+    pub max_retry: u64,
+    pub query: String,
+    pub record_type: RecordType,
+    pub resolver: Option<SocketAddr>,
+    pub expect: Option<Vec<String>>,
+    pub options: JobOptions,
+}
+
+impl Display for DnsJob {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let resolver = match &self.resolver {
+            Some(r) => r.to_string(),
+            None => "system".to_string()
+        };
+        let expect = match &self.expect {
+            Some(e) => e.join(", "),
+            None => "None".to_string()
+        };
+        write!(f, "name: {}, enable: {}, cron: {}, timeout: {}, max_retry: {}, query: {}, record_type: {}, resolver: {}, expect: [{}]",
+               self.name, self.enable, self.schedule, self.timeout, self.max_retry, self.query, self.record_type, resolver, expect)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+/// A dead-man's-switch monitor (such as healthchecks.io) pinged after the runs of a job.
+///
+/// The `url` is pinged when a run succeeds. If `ping_on_failure` is set, `<url>/fail` is pinged
+/// when a run fails.
+#[derive(Debug, Clone)]
+pub struct Heartbeat {
+    pub url: String,
+    pub ping_on_failure: bool,
+}
+
+impl Heartbeat {
+    pub fn new(url: String, ping_on_failure: bool) -> Self {
+        Heartbeat {
+            url,
+            ping_on_failure,
+        }
+    }
+
+    /// Returns the URL to ping for a run with the given result, or `None` if nothing is pinged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let heartbeat = Heartbeat::new("https://hc-ping.com/uuid".to_string(), true);
+    /// assert_eq!(heartbeat.ping_url(false), Some("https://hc-ping.com/uuid/fail".to_string()));
+    /// ```
+    pub fn ping_url(&self, success: bool) -> Option<String> {
+        if success {
+            Some(self.url.clone())
+        } else if self.ping_on_failure {
+            Some(format!("{}/fail", self.url.trim_end_matches('/')))
+        } else {
+            None
+        }
+    }
+}
\ No newline at end of file
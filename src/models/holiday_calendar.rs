@@ -0,0 +1,67 @@
+use std::collections::BTreeSet;
+use chrono::{Datelike, NaiveDate};
+
+/// The holidays on which the runs of a job are skipped.
+///
+/// The calendar holds single dates, from an inline list or the events of an iCal file, and the
+/// events of an iCal file recurring every year, such as Christmas.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HolidayCalendar {
+    pub dates: BTreeSet<NaiveDate>,
+    pub yearly: Vec<YearlyHoliday>,
+}
+
+/// A holiday recurring every year, from an iCal event with `RRULE:FREQ=YEARLY`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YearlyHoliday {
+    /// The first day of the first occurrence.
+    pub start: NaiveDate,
+    /// The number of days of each occurrence, at least 1.
+    pub days: u32,
+    /// The number of occurrences, unlimited if `None`.
+    pub count: Option<u32>,
+    /// The last day an occurrence may start, unlimited if `None`.
+    pub until: Option<NaiveDate>,
+}
+
+impl HolidayCalendar {
+
+    /// Returns whether a date is a holiday of the calendar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let christmas = NaiveDate::from_ymd_opt(2026, 12, 25).unwrap();
+    /// let calendar = HolidayCalendar { dates: BTreeSet::from([christmas]), yearly: vec![] };
+    /// assert!(calendar.is_holiday(christmas));
+    /// ```
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.dates.contains(&date) || self.yearly.iter().any(|holiday| holiday.contains(date))
+    }
+}
+
+impl YearlyHoliday {
+
+    /// Returns whether a date falls on an occurrence of the holiday.
+    fn contains(&self, date: NaiveDate) -> bool {
+        // An occurrence starting at the end of the year before may last into the year of `date`.
+        [date.year() - 1, date.year()].into_iter().any(|year| {
+            let Some(start) = self.occurrence(year) else {
+                return false;
+            };
+            start <= date && (date - start).num_days() < self.days as i64
+        })
+    }
+
+    /// Returns the first day of the occurrence of a year, if there is one.
+    ///
+    /// An occurrence on February 29 only happens in leap years.
+    fn occurrence(&self, year: i32) -> Option<NaiveDate> {
+        let index = u32::try_from(year - self.start.year()).ok()?;
+        if self.count.is_some_and(|count| index >= count) {
+            return None;
+        }
+
+        self.start.with_year(year).filter(|start| self.until.is_none_or(|until| *start <= until))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+use std::fmt::{Display, Formatter};
+
+/// An action run after a job run completes.
+///
+/// - `Notify`: Send the outcome to the configured notification channels.
+/// - `TriggerJob`: Start the job with the given name right away.
+/// - `Command`: Run a shell command, with the outcome in `RJOB_*` environment variables.
+/// - `Webhook`: Post the outcome as JSON to the given URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookAction {
+    Notify,
+    TriggerJob(String),
+    Command(String),
+    Webhook(String),
+}
+
+impl Display for HookAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HookAction::Notify => write!(f, "notify"),
+            HookAction::TriggerJob(name) => write!(f, "trigger_job: {}", name),
+            HookAction::Command(command) => write!(f, "command: {}", command),
+            HookAction::Webhook(url) => write!(f, "webhook: {}", url),
+        }
+    }
+}
+
+/// The hook actions of a job, keyed on the outcome of a run.
+#[derive(Debug, Clone, Default)]
+pub struct JobHooks {
+    pub on_success: Vec<HookAction>,
+    pub on_failure: Vec<HookAction>,
+}
+
+impl JobHooks {
+    pub fn new(on_success: Vec<HookAction>, on_failure: Vec<HookAction>) -> Self {
+        JobHooks {
+            on_success,
+            on_failure,
+        }
+    }
+
+    /// Returns the actions to run for a run with the given outcome.
+    pub fn actions(&self, success: bool) -> &[HookAction] {
+        if success {
+            &self.on_success
+        } else {
+            &self.on_failure
+        }
+    }
+}
\ No newline at end of file
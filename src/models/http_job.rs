@@ -1,32 +1,195 @@
 use std::fmt::{Display, Formatter};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
 use crate::models::http_job_request::HttpJobRequest;
+use crate::models::http_job_response::HttpJobResponse;
+use crate::models::http_job_step::HttpJobStep;
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+use crate::models::tls::TlsOptions;
 
 #[derive(Debug, Clone)]
 pub struct HttpJob {
     pub name: String,
     pub enable: bool,
-    pub cron: String,
+    pub schedule: JobSchedule,
     pub timeout: u64,
+    /// Bounds establishing the connection, in milliseconds, separately from `timeout`.
+    pub connect_timeout_ms: Option<u64>,
+    /// Bounds the whole run, including all retries, in milliseconds.
+    pub execution_timeout: Option<u64>,
+    /// The IP version used to connect to the host of the requests.
+    pub ip_version: IpVersion,
+    /// The local IP address the outgoing connections are bound to.
+    pub local_address: Option<IpAddr>,
+    /// The time an idle connection is kept in the pool of the client.
+    pub pool_idle_timeout: Option<Duration>,
+    /// The maximum number of idle connections kept in the pool per host.
+    pub pool_max_idle_per_host: Option<usize>,
+    /// The interval of the TCP keep-alive probes of the connections.
+    pub tcp_keepalive: Option<Duration>,
+    /// The TLS versions the connections may negotiate.
+    pub tls: TlsOptions,
     pub max_retry: u64,
+    /// The delay before each retry, in milliseconds.
+    pub retry_delay_ms: u64,
+    /// Stops retrying once this much time has elapsed since the first attempt.
+    pub retry_budget: Option<Duration>,
+    /// Whether a POST or PATCH request is retried after a failure that may have reached the
+    /// server, such as a timeout.
+    pub retry_non_idempotent: bool,
+    /// Overrides the global user agent for this job.
+    pub user_agent: Option<String>,
+    /// The request of the job, or the request of the first step of a pipeline.
     pub request: HttpJobRequest,
+    /// The steps of the request pipeline of the job, sent in order as a single run, empty if the
+    /// job sends a single request.
+    pub steps: Vec<HttpJobStep>,
+    /// Sent once when the run fails after all retries of `request`.
+    pub fallback_request: Option<HttpJobRequest>,
+    pub response: HttpJobResponse,
+    pub options: JobOptions,
+    /// The URL the result document of each run is posted to.
+    pub report_to: Option<String>,
 }
 
 impl HttpJob {
-    pub fn new(name: String, enable: bool, cron: String, timeout: u64, max_retry: u64, request: HttpJobRequest) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: String,
+        enable: bool,
+        schedule: JobSchedule,
+        timeout: u64,
+        connect_timeout_ms: Option<u64>,
+        execution_timeout: Option<u64>,
+        ip_version: IpVersion,
+        local_address: Option<IpAddr>,
+        pool_idle_timeout: Option<Duration>,
+        pool_max_idle_per_host: Option<usize>,
+        tcp_keepalive: Option<Duration>,
+        tls: TlsOptions,
+        max_retry: u64,
+        retry_delay_ms: u64,
+        retry_budget: Option<Duration>,
+        retry_non_idempotent: bool,
+        user_agent: Option<String>,
+        request: HttpJobRequest,
+        steps: Vec<HttpJobStep>,
+        fallback_request: Option<HttpJobRequest>,
+        response: HttpJobResponse,
+        options: JobOptions,
+        report_to: Option<String>,
+    ) -> Self {
         HttpJob {
             name,
             enable,
-            cron,
+            schedule,
             timeout,
+            connect_timeout_ms,
+            execution_timeout,
+            ip_version,
+            local_address,
+            pool_idle_timeout,
+            pool_max_idle_per_host,
+            tcp_keepalive,
+            tls,
             max_retry,
+            retry_delay_ms,
+            retry_budget,
+            retry_non_idempotent,
+            user_agent,
             request,
+            steps,
+            fallback_request,
+            response,
+            options,
+            report_to,
         }
     }
 }
 
 impl Display for HttpJob {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "name: {}, enable: {}, cron: {}, timeout: {}, max_retry: {}, request: [{}]",
-               self.name, self.enable, self.cron, self.timeout, self.max_retry, self.request)
+        let fallback_request = match &self.fallback_request {
+            Some(r) => format!("[{}]", r),
+            None => "None".to_string()
+        };
+        let steps = match self.steps.is_empty() {
+            true => "None".to_string(),
+            false => self.steps.iter().map(|s| format!("[{}]", s)).collect::<Vec<_>>().join(", ")
+        };
+        write!(f, "name: {}, enable: {}, dry_run: {}, cron: {}, timeout: {}, connect_timeout_ms: {:?}, execution_timeout: {:?}, ip_version: {}, local_address: {:?}, pool_idle_timeout: {:?}, pool_max_idle_per_host: {:?}, tcp_keepalive: {:?}, tls: [{}], max_retry: {}, retry_delay_ms: {}, retry_budget: {:?}, retry_non_idempotent: {}, max_runs: {:?}, user_agent: {:?}, request: [{}], steps: {}, fallback_request: {}, response: [{}], report_to: {:?}",
+               self.name, self.enable, self.options.dry_run, self.schedule, self.timeout, self.connect_timeout_ms, self.execution_timeout, self.ip_version, self.local_address, self.pool_idle_timeout, self.pool_max_idle_per_host, self.tcp_keepalive, self.tls, self.max_retry, self.retry_delay_ms, self.retry_budget, self.retry_non_idempotent, self.options.max_runs, self.user_agent, self.request, steps, fallback_request, self.response, self.report_to)
+    }
+}
+
+/// The IP version of the connections of an HTTP job.
+///
+/// - `Any`: Connect over IPv4 or IPv6, whichever the host resolves to first.
+/// - `V4`: Only connect to the IPv4 addresses of the host.
+/// - `V6`: Only connect to the IPv6 addresses of the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum IpVersion {
+    #[default]
+    Any,
+    V4,
+    V6,
+}
+
+impl IpVersion {
+
+    /// Parses the value of the `ip_version` field.
+    ///
+    /// Returns `None` if the value is not one of `v4`, `v6` or `any`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(IpVersion::parse("v6"), Some(IpVersion::V6));
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "any" => Some(IpVersion::Any),
+            "v4" => Some(IpVersion::V4),
+            "v6" => Some(IpVersion::V6),
+            _ => None
+        }
+    }
+
+    /// Returns the unspecified local address of the IP version, binding a connection to it
+    /// restricts the connection to that version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(IpVersion::V4.unspecified_address(), Some(IpAddr::V4(Ipv4Addr::UNSPECIFIED)));
+    /// assert_eq!(IpVersion::Any.unspecified_address(), None);
+    /// ```
+    pub fn unspecified_address(&self) -> Option<IpAddr> {
+        match self {
+            IpVersion::Any => None,
+            IpVersion::V4 => Some(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+            IpVersion::V6 => Some(IpAddr::V6(Ipv6Addr::UNSPECIFIED)),
+        }
+    }
+
+    /// Returns whether an IP address is of this version.
+    pub fn matches(&self, address: &IpAddr) -> bool {
+        match self {
+            IpVersion::Any => true,
+            IpVersion::V4 => address.is_ipv4(),
+            IpVersion::V6 => address.is_ipv6(),
+        }
+    }
+}
+
+impl Display for IpVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let version = match self {
+            IpVersion::Any => "any",
+            IpVersion::V4 => "v4",
+            IpVersion::V6 => "v6",
+        };
+        write!(f, "{}", version)
     }
 }
\ No newline at end of file
@@ -1,21 +1,102 @@
+use std::borrow::Cow;
 use std::fmt::{Debug, Display, Formatter};
-use reqwest::header::HeaderMap;
+use std::net::IpAddr;
+use reqwest::header::{HeaderMap, HeaderName};
+use reqwest::Url;
+use crate::utils::redact_util::redact_headers;
+
+/// The content type of JSON and GraphQL bodies.
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+
+/// The content type of plain text bodies.
+pub const TEXT_CONTENT_TYPE: &str = "text/plain; charset=utf-8";
+
+/// The content encodings of the responses the client can decompress, and `identity`.
+pub const DECODABLE_ENCODINGS: [&str; 4] = ["gzip", "deflate", "br", "identity"];
 
 #[derive(Debug, Clone)]
 pub struct HttpJobRequest {
     pub url: String,
     pub method: String,
     pub headers: Option<HeaderMap>,
+    /// The default headers the request removes, set to `null` in its headers.
+    pub removed_headers: Vec<HeaderName>,
     pub body: Option<String>,
+    /// The `Content-Type` of the body, unless the headers already set one.
+    pub content_type: Option<String>,
+    pub http_version: HttpVersion,
+    pub compress_body: Option<BodyCompression>,
+    /// Whether the body is a GraphQL request, whose response fails the run if it has errors.
+    pub graphql: bool,
+    /// The `Host` header of the request, instead of the host of the URL.
+    pub host: Option<String>,
+    /// The server name of the request, sent with TLS (SNI) and verified against the certificate,
+    /// while the connection is made to the IP address of the URL.
+    pub server_name: Option<String>,
+    /// The content encodings advertised in the `Accept-Encoding` header, `identity` if empty.
+    pub accept_encoding: Option<Vec<String>>,
 }
 
 impl HttpJobRequest {
-    pub fn new(url: String, method: String, headers: Option<HeaderMap>, body: Option<String>) -> Self {
-        HttpJobRequest {
-            url,
-            method,
-            headers,
-            body,
+
+    /// Returns the URL the request is sent to.
+    ///
+    /// With a `server_name`, the host of the URL is replaced by the server name, so that the
+    /// client uses it for TLS and as the default `Host` header. The client connects to the IP
+    /// address of the configured URL instead, see `connect_address`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let resp = client.request(Method::GET, request.target_url().as_ref()).send().await?;
+    /// ```
+    pub fn target_url(&self) -> Cow<'_, str> {
+        let Some(server_name) = &self.server_name else {
+            return Cow::Borrowed(&self.url);
+        };
+
+        let Ok(mut url) = Url::parse(&self.url) else {
+            return Cow::Borrowed(&self.url);
+        };
+        match url.set_host(Some(server_name)) {
+            Ok(()) => Cow::Owned(url.to_string()),
+            Err(_) => Cow::Borrowed(&self.url)
+        }
+    }
+
+    /// Returns the IP address the server name of the request connects to, the host of its URL.
+    ///
+    /// Returns `None` without a `server_name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// if let Some(address) = request.connect_address() {
+    ///     println!("Connecting to: {}", address);
+    /// }
+    /// ```
+    pub fn connect_address(&self) -> Option<IpAddr> {
+        self.server_name.as_ref()?;
+
+        // IPv6 addresses are enclosed in brackets in URLs.
+        let url = Url::parse(&self.url).ok()?;
+        url.host_str()?.trim_start_matches('[').trim_end_matches(']').parse().ok()
+    }
+
+    /// Returns the value of the `Accept-Encoding` header of the request, or `None` if the
+    /// request has no `accept_encoding`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// if let Some(accept_encoding) = request.accept_encoding_header() {
+    ///     request_builder = request_builder.header(ACCEPT_ENCODING, accept_encoding);
+    /// }
+    /// ```
+    pub fn accept_encoding_header(&self) -> Option<String> {
+        match self.accept_encoding.as_deref()? {
+            [] => Some("identity".to_string()),
+            encodings => Some(encodings.join(", "))
         }
     }
 }
@@ -23,17 +104,134 @@ impl HttpJobRequest {
 impl Display for HttpJobRequest {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let headers = match &self.headers {
-            Some(h) => format!("{:?}", h),
+            Some(h) => redact_headers(h),
             None => "None".to_string()
         };
         let body = match &self.body {
             Some(b) => b,
             None => "None"
         };
-        write!(f, "url: {}, method: {}, headers: {}, body: {}",
+        let content_type = match &self.content_type {
+            Some(c) => c,
+            None => "None"
+        };
+        let compress_body = match &self.compress_body {
+            Some(c) => c.to_string(),
+            None => "None".to_string()
+        };
+        let host = match &self.host {
+            Some(h) => h,
+            None => "None"
+        };
+        let server_name = match &self.server_name {
+            Some(s) => s,
+            None => "None"
+        };
+        let accept_encoding = match &self.accept_encoding {
+            Some(a) => format!("{:?}", a),
+            None => "None".to_string()
+        };
+        write!(f, "url: {}, method: {}, headers: {}, body: {}, content_type: {}, http_version: {}, compress_body: {}, graphql: {}, host: {}, server_name: {}, accept_encoding: {}",
                self.url,
                self.method,
                headers,
-               body)
+               body,
+               content_type,
+               self.http_version,
+               compress_body,
+               self.graphql,
+               host,
+               server_name,
+               accept_encoding)
+    }
+}
+
+/// The HTTP protocol version used to send a job's request.
+///
+/// - `Auto`: Let the client negotiate the version (HTTP/2 via ALPN when available, otherwise HTTP/1.1).
+/// - `Http1`: Only use HTTP/1.1.
+/// - `Http2`: Require HTTP/2, negotiated via ALPN.
+/// - `Http2PriorKnowledge`: Speak HTTP/2 directly without negotiation (e.g. h2c backends).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HttpVersion {
+    #[default]
+    Auto,
+    Http1,
+    Http2,
+    Http2PriorKnowledge,
+}
+
+impl HttpVersion {
+
+    /// Parses the value of the `http_version` field.
+    ///
+    /// Returns `None` if the value is not one of `http1`, `http2` or `http2-prior-knowledge`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let version = HttpVersion::parse("http2-prior-knowledge");
+    /// assert_eq!(version, Some(HttpVersion::Http2PriorKnowledge));
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "http1" => Some(HttpVersion::Http1),
+            "http2" => Some(HttpVersion::Http2),
+            "http2-prior-knowledge" => Some(HttpVersion::Http2PriorKnowledge),
+            _ => None
+        }
+    }
+}
+
+impl Display for HttpVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let version = match self {
+            HttpVersion::Auto => "auto",
+            HttpVersion::Http1 => "http1",
+            HttpVersion::Http2 => "http2",
+            HttpVersion::Http2PriorKnowledge => "http2-prior-knowledge",
+        };
+        write!(f, "{}", version)
+    }
+}
+
+/// The compression applied to the outgoing request body.
+///
+/// - `Gzip`: Compress the body with gzip and send it with `Content-Encoding: gzip`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyCompression {
+    Gzip,
+}
+
+impl BodyCompression {
+
+    /// Parses the value of the `compress_body` field.
+    ///
+    /// Returns `None` if the compression is not supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let compression = BodyCompression::parse("gzip");
+    /// assert_eq!(compression, Some(BodyCompression::Gzip));
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "gzip" => Some(BodyCompression::Gzip),
+            _ => None
+        }
+    }
+
+    /// Returns the value of the `Content-Encoding` header for this compression.
+    pub fn content_encoding(&self) -> &'static str {
+        match self {
+            BodyCompression::Gzip => "gzip",
+        }
+    }
+}
+
+impl Display for BodyCompression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.content_encoding())
     }
 }
\ No newline at end of file
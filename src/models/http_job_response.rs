@@ -0,0 +1,102 @@
+use std::fmt::{Display, Formatter};
+
+/// The default maximum number of response bytes kept in memory (10 MiB).
+pub const DEFAULT_MAX_RESPONSE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// The default number of response bytes logged when the response is logged truncated.
+pub const DEFAULT_LOG_RESPONSE_BYTES: usize = 1024;
+
+#[derive(Debug, Clone)]
+pub struct HttpJobResponse {
+    pub save_to: Option<String>,
+    pub max_response_bytes: u64,
+    pub log_response: ResponseLogging,
+    pub log_response_bytes: usize,
+    pub log_headers: bool,
+    /// Whether the body is read to the end and dropped instead of being kept in memory.
+    pub discard_body: bool,
+    /// Whether compressed responses are decompressed, and the encodings the client can
+    /// decompress advertised unless the request sets its own.
+    pub decompress: bool,
+}
+
+impl HttpJobResponse {
+    pub fn new(save_to: Option<String>, max_response_bytes: u64, log_response: ResponseLogging, log_response_bytes: usize, log_headers: bool, discard_body: bool, decompress: bool) -> Self {
+        HttpJobResponse {
+            save_to,
+            max_response_bytes,
+            log_response,
+            log_response_bytes,
+            log_headers,
+            discard_body,
+            decompress,
+        }
+    }
+}
+
+impl Default for HttpJobResponse {
+    fn default() -> Self {
+        HttpJobResponse::new(None, DEFAULT_MAX_RESPONSE_BYTES, ResponseLogging::default(), DEFAULT_LOG_RESPONSE_BYTES, false, false, false)
+    }
+}
+
+impl Display for HttpJobResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let save_to = match &self.save_to {
+            Some(s) => s,
+            None => "None"
+        };
+        write!(f, "save_to: {}, max_response_bytes: {}, log_response: {}, log_response_bytes: {}, log_headers: {}, discard_body: {}, decompress: {}",
+               save_to, self.max_response_bytes, self.log_response, self.log_response_bytes, self.log_headers, self.discard_body, self.decompress)
+    }
+}
+
+/// How much of the response body is written to the log.
+///
+/// - `None`: Do not log the response body, e.g. for responses with sensitive data.
+/// - `Status`: Log only the status and the size of the body.
+/// - `Truncated`: Log at most `log_response_bytes` bytes of the body.
+/// - `Full`: Log the whole body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResponseLogging {
+    None,
+    Status,
+    Truncated,
+    #[default]
+    Full,
+}
+
+impl ResponseLogging {
+
+    /// Parses the value of the `log_response` field.
+    ///
+    /// Returns `None` if the value is not one of `none`, `status`, `truncated` or `full`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let logging = ResponseLogging::parse("truncated");
+    /// assert_eq!(logging, Some(ResponseLogging::Truncated));
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "none" => Some(ResponseLogging::None),
+            "status" => Some(ResponseLogging::Status),
+            "truncated" => Some(ResponseLogging::Truncated),
+            "full" => Some(ResponseLogging::Full),
+            _ => None
+        }
+    }
+}
+
+impl Display for ResponseLogging {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let logging = match self {
+            ResponseLogging::None => "none",
+            ResponseLogging::Status => "status",
+            ResponseLogging::Truncated => "truncated",
+            ResponseLogging::Full => "full",
+        };
+        write!(f, "{}", logging)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use reqwest::header::{HeaderMap, HeaderName};
+use serde_json::Value;
+
+use crate::models::http_job_request::HttpJobRequest;
+use crate::utils::template_util::render_template;
+
+/// A step of the request pipeline of an HTTP job.
+///
+/// The steps of a pipeline are sent in order within a single run. The values a step extracts
+/// from its response are available to the requests of the following steps as `{{name}}`.
+#[derive(Debug, Clone)]
+pub struct HttpJobStep {
+    pub name: String,
+    pub request: HttpJobRequest,
+    /// The variables extracted from the response of the step, in the order they were declared.
+    pub extract: Vec<(String, Extraction)>,
+}
+
+impl HttpJobStep {
+    pub fn new(name: String, request: HttpJobRequest, extract: Vec<(String, Extraction)>) -> Self {
+        HttpJobStep {
+            name,
+            request,
+            extract,
+        }
+    }
+
+    /// Renders the request of the step with the variables of the run.
+    ///
+    /// The `{{name}}` placeholders of the URL, the header values, the `host` and the body are
+    /// replaced, see `render_template`. In a JSON body, the values are escaped as JSON strings,
+    /// so a placeholder is written inside the quotes of a string, e.g. `"Bearer {{token}}"`.
+    ///
+    /// # Arguments
+    ///
+    /// * `vars` - The variables of the run and the values extracted by the previous steps.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a rendered header value is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let request = step.render_request(&vars)?;
+    /// println!("URL: {}", request.url);
+    /// ```
+    pub fn render_request(&self, vars: &HashMap<&str, String>) -> Result<HttpJobRequest, String> {
+        let request = &self.request;
+        let mut rendered = request.clone();
+        rendered.url = render_template(&request.url, vars);
+        rendered.host = request.host.as_ref().map(|host| render_template(host, vars));
+
+        if let Some(headers) = &request.headers {
+            let mut rendered_headers = HeaderMap::new();
+            for (name, value) in headers {
+                let value = match value.to_str() {
+                    Ok(value) => render_template(value, vars).parse()
+                        .map_err(|_| format!("Invalid value of the '{}' header after rendering.", name))?,
+                    Err(_) => value.clone()
+                };
+                rendered_headers.append(name, value);
+            }
+            rendered.headers = Some(rendered_headers);
+        }
+
+        let json_body = request.content_type.as_deref().is_some_and(|c| c.contains("json"));
+        rendered.body = request.body.as_ref().map(|body| {
+            if !json_body {
+                return render_template(body, vars);
+            }
+            let escaped: HashMap<&str, String> = vars.iter()
+                .map(|(name, value)| {
+                    let json = Value::String(value.clone()).to_string();
+                    (*name, json[1..json.len() - 1].to_string())
+                })
+                .collect();
+            render_template(body, &escaped)
+        });
+
+        Ok(rendered)
+    }
+}
+
+impl Display for HttpJobStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let extract = self.extract.iter()
+            .map(|(name, extraction)| format!("{}: {}", name, extraction))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "name: {}, request: [{}], extract: [{}]", self.name, self.request, extract)
+    }
+}
+
+/// Where a pipeline step extracts a variable from its response.
+///
+/// - `Body`: The whole body, as text.
+/// - `JsonPointer`: The value at a JSON pointer of the JSON body, e.g. `/data/token`.
+/// - `Header`: The value of a response header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Extraction {
+    Body,
+    JsonPointer(String),
+    Header(HeaderName),
+}
+
+impl Extraction {
+
+    /// Parses the source of an extracted variable: `body`, a JSON pointer starting with `/`, or
+    /// `header:` followed by the name of a header.
+    ///
+    /// Returns `None` if the source is none of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(Extraction::parse("/data/token"), Some(Extraction::JsonPointer("/data/token".to_string())));
+    /// assert_eq!(Extraction::parse("header:Location"), Some(Extraction::Header(LOCATION)));
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        if value == "body" {
+            return Some(Extraction::Body);
+        }
+        if value.starts_with('/') {
+            return Some(Extraction::JsonPointer(value.to_string()));
+        }
+        let name = value.strip_prefix("header:")?;
+        HeaderName::try_from(name.trim()).ok().map(Extraction::Header)
+    }
+
+    /// Extracts the value from a response.
+    ///
+    /// A JSON string is extracted as is, any other JSON value as its JSON text.
+    ///
+    /// # Arguments
+    ///
+    /// * `headers` - The headers of the response.
+    /// * `body` - The body of the response.
+    ///
+    /// # Returns
+    ///
+    /// The extracted value, or `None` if the response has no such value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let token = Extraction::parse("/token").unwrap().extract(&headers, br#"{"token":"abc"}"#);
+    /// assert_eq!(token, Some("abc".to_string()));
+    /// ```
+    pub fn extract(&self, headers: &HeaderMap, body: &[u8]) -> Option<String> {
+        match self {
+            Extraction::Body => String::from_utf8(body.to_vec()).ok(),
+            Extraction::JsonPointer(pointer) => {
+                let json: Value = serde_json::from_slice(body).ok()?;
+                match json.pointer(pointer)? {
+                    Value::String(text) => Some(text.clone()),
+                    value => Some(value.to_string())
+                }
+            }
+            Extraction::Header(name) => headers.get(name)?.to_str().ok().map(|v| v.to_string())
+        }
+    }
+}
+
+impl Display for Extraction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Extraction::Body => write!(f, "body"),
+            Extraction::JsonPointer(pointer) => write!(f, "{}", pointer),
+            Extraction::Header(name) => write!(f, "header:{}", name),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+use crate::models::run_outcome::RunOutcome;
+
+/// The future of a run of a job, see `Job`.
+pub type JobFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+/// A job of the configuration, of any type.
+///
+/// The scheduler runs every job the same way, see `start_job`: it skips the run of a paused job,
+/// counts it towards `max_runs`, waits for the group of the job, then records the outcome, sends
+/// the notifications, runs the hooks and pings the heartbeat. A job type only executes the run.
+pub trait Job: Display + Send + Sync + 'static {
+    /// Returns the type of the job as it appears in the logs, e.g. `TCP`.
+    fn kind(&self) -> &'static str;
+
+    fn name(&self) -> &str;
+
+    fn enable(&self) -> bool;
+
+    fn schedule(&self) -> &JobSchedule;
+
+    fn options(&self) -> &JobOptions;
+
+    /// Returns the maximum time a run of the job may take, including its hooks, or `None` if
+    /// the run is not limited.
+    fn execution_timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Executes a run of the job and returns its outcome.
+    ///
+    /// # Arguments
+    ///
+    /// * `uuid` - The identifier of the run.
+    /// * `local_time` - The start time of the run, used as log prefix.
+    fn execute(self: Arc<Self>, uuid: String, local_time: String) -> JobFuture<RunOutcome>;
+
+    /// Logs what a run of the job would do, instead of executing it, in dry-run mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `uuid` - The identifier of the run.
+    /// * `local_time` - The start time of the run, used as log prefix.
+    fn dry_run(self: Arc<Self>, uuid: String, local_time: String) -> JobFuture<()> {
+        Box::pin(async move {
+            println!("{} {} Dry run, job name: {}, not executed", uuid, local_time, self.name());
+        })
+    }
+}
\ No newline at end of file
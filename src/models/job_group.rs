@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+/// The waiting time after which a waiting run gains one priority level, by default.
+pub const DEFAULT_PRIORITY_AGING: Duration = Duration::from_secs(30);
+
+/// A group of related jobs, e.g. all jobs calling one fragile backend.
+///
+/// At most `max_concurrent` runs of the jobs of the group are in progress at the same time,
+/// the other runs wait for one of them to end. The waiting run with the highest `priority`
+/// starts first, and a run gains one priority level for every `priority_aging` it waits, so
+/// low-priority runs are not starved by a steady stream of high-priority ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JobGroup {
+    pub max_concurrent: usize,
+    pub priority_aging: Duration,
+}
+
+impl JobGroup {
+    pub fn new(max_concurrent: usize, priority_aging: Duration) -> Self {
+        JobGroup {
+            max_concurrent,
+            priority_aging,
+        }
+    }
+}
\ No newline at end of file
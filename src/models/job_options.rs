@@ -0,0 +1,52 @@
+use crate::models::catch_up::CatchUp;
+use crate::models::heartbeat::Heartbeat;
+use crate::models::hooks::JobHooks;
+use crate::models::notifications::JobNotifications;
+
+/// The options shared by the jobs of every type.
+///
+/// They are applied by the scheduler around each run, whatever the job executes.
+#[derive(Debug, Clone)]
+pub struct JobOptions {
+    /// Whether the runs of the job are only logged instead of executed.
+    pub dry_run: bool,
+    /// The number of runs after which the job stops, or `None` if it runs forever.
+    pub max_runs: Option<u64>,
+    pub catch_up: Option<CatchUp>,
+    pub heartbeat: Option<Heartbeat>,
+    pub notifications: JobNotifications,
+    pub hooks: JobHooks,
+    /// The tags selecting the instances of the scheduler that run the job.
+    pub tags: Vec<String>,
+    /// The group limiting the concurrent runs of the job with related jobs.
+    pub group: Option<String>,
+    /// The priority of the runs of the job when they wait for their group, higher first.
+    pub priority: i64,
+}
+
+impl JobOptions {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        dry_run: bool,
+        max_runs: Option<u64>,
+        catch_up: Option<CatchUp>,
+        heartbeat: Option<Heartbeat>,
+        notifications: JobNotifications,
+        hooks: JobHooks,
+        tags: Vec<String>,
+        group: Option<String>,
+        priority: i64,
+    ) -> Self {
+        JobOptions {
+            dry_run,
+            max_runs,
+            catch_up,
+            heartbeat,
+            notifications,
+            hooks,
+            tags,
+            group,
+            priority,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,412 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::Arc;
+use chrono::{DateTime, Datelike, Days, Duration, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use cron::Schedule;
+
+use crate::models::blackout::Blackout;
+use crate::models::condition::Condition;
+use crate::models::holiday_calendar::HolidayCalendar;
+use crate::utils::cron_util::{describe_cron, is_reboot};
+use crate::utils::duration_util::format_duration;
+use crate::utils::hash_util::{fnv1a, mix};
+use crate::utils::solar_util::get_sunrise_sunset;
+
+/// The number of days in a row without a solar event after which a solar schedule is
+/// considered to never fire again, e.g. at a pole.
+const MAX_DAYS_WITHOUT_SOLAR_EVENT: u32 = 366;
+
+/// The schedule of a job, i.e. when the timer fires it.
+///
+/// A job is scheduled with a cron expression, see `JobSchedule::cron`, with a daily window in
+/// which it fires at a random time, see `JobSchedule::daily_between`, or relative to the
+/// sunrise or the sunset at a location, see `JobSchedule::solar`. The fire times falling on a
+/// holiday of the job are skipped, see `JobSchedule::with_holidays`, and its triggers are
+/// suppressed during its blackouts, see `JobSchedule::with_blackouts`, or when its `when`
+/// condition is false, see `JobSchedule::with_when`.
+#[derive(Debug, Clone)]
+pub struct JobSchedule {
+    /// The schedule as shown in the logs and the `list` command, e.g. `0 30 2 * * ?` or
+    /// `daily_between 02:00-04:00`, or `sunset+30m (48.8566, 2.3522)`.
+    pub expression: String,
+    kind: ScheduleKind,
+    holidays: Option<Arc<HolidayCalendar>>,
+    /// The blackouts of the job, next to the global ones, checked by the timer.
+    pub blackouts: Vec<Blackout>,
+    /// Whether the job also runs once when its trigger is added, i.e. at startup.
+    pub run_on_start: bool,
+    /// The time after startup before which the job doesn't run.
+    pub initial_delay: std::time::Duration,
+    /// The condition checked by the timer before each triggered run, see `Condition`.
+    pub when: Option<Condition>,
+}
+
+#[derive(Debug, Clone)]
+enum ScheduleKind {
+    /// A cron expression in the format of the scheduler.
+    Cron(Box<Schedule>),
+    /// The `@reboot` macro, which has no fire times but runs the job once at startup.
+    Reboot,
+    /// A random time of each day between `start` and `end`, drawn from `seed` and the date.
+    DailyBetween { start: NaiveTime, end: NaiveTime, seed: u64 },
+    /// The sunrise or the sunset of each day at a location, shifted by `offset`.
+    Solar { event: SolarEvent, offset: Duration, latitude: f64, longitude: f64 },
+}
+
+/// A solar event a job can be scheduled around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolarEvent {
+    Sunrise,
+    Sunset,
+}
+
+impl SolarEvent {
+
+    /// Parses the name of a solar event, `sunrise` or `sunset`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(SolarEvent::parse("sunset"), Some(SolarEvent::Sunset));
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "sunrise" => Some(SolarEvent::Sunrise),
+            "sunset" => Some(SolarEvent::Sunset),
+            _ => None
+        }
+    }
+}
+
+impl Display for SolarEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let event = match self {
+            SolarEvent::Sunrise => "sunrise",
+            SolarEvent::Sunset => "sunset",
+        };
+        write!(f, "{}", event)
+    }
+}
+
+impl JobSchedule {
+
+    /// Creates the schedule of a cron expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `cron` - The cron expression, normalized to the format of the scheduler, see
+    ///   `normalize_cron`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cron expression is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let schedule = JobSchedule::cron("0 30 2 * * ?")?;
+    /// assert_eq!(schedule.describe(), "every day at 02:30");
+    /// ```
+    pub fn cron(cron: &str) -> Result<Self, Box<dyn Error>> {
+        let kind = if is_reboot(cron) {
+            ScheduleKind::Reboot
+        } else {
+            ScheduleKind::Cron(Box::new(Schedule::from_str(cron)?))
+        };
+
+        Ok(JobSchedule { expression: cron.to_string(), kind, holidays: None, blackouts: vec![], run_on_start: false, initial_delay: std::time::Duration::ZERO, when: None })
+    }
+
+    /// Creates a schedule firing once a day, at a random time between `start` and `end`.
+    ///
+    /// The time of a day is drawn from the name of the job and the date. It changes from day
+    /// to day and differs between jobs sharing a window, which spreads their load, but it stays
+    /// the same when the scheduler restarts, so a restart neither repeats nor skips the run of
+    /// the day. A window whose `end` is before its `start` spans midnight.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The start of the window, included.
+    /// * `end` - The end of the window, excluded. Must differ from `start`.
+    /// * `job_name` - The name of the job, seeding the random times.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let start = NaiveTime::from_hms_opt(2, 0, 0).unwrap();
+    /// let end = NaiveTime::from_hms_opt(4, 0, 0).unwrap();
+    /// let schedule = JobSchedule::daily_between(start, end, "sync-users");
+    /// assert_eq!(schedule.expression, "daily_between 02:00-04:00");
+    /// ```
+    pub fn daily_between(start: NaiveTime, end: NaiveTime, job_name: &str) -> Self {
+        let seed = fnv1a(job_name.as_bytes());
+
+        JobSchedule {
+            expression: format!("daily_between {}-{}", format_time(start), format_time(end)),
+            kind: ScheduleKind::DailyBetween { start, end, seed },
+            holidays: None,
+            blackouts: vec![],
+            run_on_start: false,
+            initial_delay: std::time::Duration::ZERO,
+            when: None,
+        }
+    }
+
+    /// Creates a schedule firing every day at the sunrise or the sunset at a location.
+    ///
+    /// The solar events are computed for each day, see `get_sunrise_sunset`. No run is
+    /// scheduled on the days the sun doesn't rise or set, e.g. during the polar night.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The sunrise or the sunset.
+    /// * `offset` - The time between the event and the run, negative to run before the event.
+    /// * `latitude` - The latitude of the location in degrees, positive north of the equator.
+    /// * `longitude` - The longitude of the location in degrees, positive east of Greenwich.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let schedule = JobSchedule::solar(SolarEvent::Sunset, Duration::minutes(30), 48.8566, 2.3522);
+    /// assert_eq!(schedule.expression, "sunset+30m (48.8566, 2.3522)");
+    /// assert_eq!(schedule.describe(), "every day 30m after sunset at 48.8566, 2.3522");
+    /// ```
+    pub fn solar(event: SolarEvent, offset: Duration, latitude: f64, longitude: f64) -> Self {
+        let sign = if offset < Duration::zero() { "-" } else { "+" };
+        let offset_text = if offset.is_zero() { String::new() } else { format!("{}{}", sign, format_offset(offset)) };
+
+        JobSchedule {
+            expression: format!("{}{} ({}, {})", event, offset_text, latitude, longitude),
+            kind: ScheduleKind::Solar { event, offset, latitude, longitude },
+            holidays: None,
+            blackouts: vec![],
+            run_on_start: false,
+            initial_delay: std::time::Duration::ZERO,
+            when: None,
+        }
+    }
+
+    /// Skips the fire times falling on the holidays of a calendar.
+    ///
+    /// A fire time is skipped if its date in the configured timezone is a holiday. `@reboot`
+    /// has no fire times and is not affected.
+    ///
+    /// # Arguments
+    ///
+    /// * `holidays` - The holidays of the job.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let christmas = NaiveDate::from_ymd_opt(2026, 12, 25).unwrap();
+    /// let holidays = HolidayCalendar { dates: BTreeSet::from([christmas]), yearly: vec![] };
+    /// let schedule = JobSchedule::cron("0 0 8 * * MON-FRI")?.with_holidays(holidays);
+    /// assert_eq!(schedule.describe(), "at 08:00 on weekdays, except on holidays");
+    /// ```
+    pub fn with_holidays(mut self, holidays: HolidayCalendar) -> Self {
+        self.holidays = Some(Arc::new(holidays));
+        self
+    }
+
+    /// Suppresses the triggers of the schedule during blackouts.
+    ///
+    /// Unlike holidays, the fire times during a blackout are kept, and the timer logs the runs
+    /// it skips, see `fire_due_triggers`.
+    ///
+    /// # Arguments
+    ///
+    /// * `blackouts` - The blackouts of the job.
+    pub fn with_blackouts(mut self, blackouts: Vec<Blackout>) -> Self {
+        self.blackouts = blackouts;
+        self
+    }
+
+    /// Runs the job once when its trigger is added, in addition to its fire times.
+    ///
+    /// # Arguments
+    ///
+    /// * `run_on_start` - Whether the job runs at startup.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let schedule = JobSchedule::cron("0 0 * * * ?")?.with_run_on_start(true);
+    /// assert_eq!(schedule.describe(), "every hour at minute 0, and once at startup");
+    /// ```
+    pub fn with_run_on_start(mut self, run_on_start: bool) -> Self {
+        self.run_on_start = run_on_start;
+        self
+    }
+
+    /// Delays the first run of the job after its trigger is added, i.e. after startup.
+    ///
+    /// The fire times during the delay are skipped. The run at startup of `@reboot` and
+    /// `run_on_start` happens at the end of the delay instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial_delay` - The time before the first run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let schedule = JobSchedule::cron("0 * * * * ?")?.with_initial_delay(Duration::from_secs(120));
+    /// ```
+    pub fn with_initial_delay(mut self, initial_delay: std::time::Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    /// Sets the condition of the job, its triggers are skipped when it is false.
+    ///
+    /// # Arguments
+    ///
+    /// * `when` - The condition evaluated at each fire time, or `None` to always run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let schedule = JobSchedule::cron("0 0 2 * * ?")?.with_when(Some(Condition::parse("!last_day_of_month")?));
+    /// ```
+    pub fn with_when(mut self, when: Option<Condition>) -> Self {
+        self.when = when;
+        self
+    }
+
+    /// Returns whether the schedule is `@reboot`, which runs the job once at startup.
+    pub fn is_reboot(&self) -> bool {
+        matches!(self.kind, ScheduleKind::Reboot)
+    }
+
+    /// Returns the fire times of the schedule after a time, in its timezone.
+    ///
+    /// `@reboot` has no fire times, and the fire times on holidays are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - The time after which fire times are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let now = Utc::now().with_timezone(&jobs.timezone);
+    /// let next = schedule.after(&now).next();
+    /// ```
+    pub fn after<'a>(&'a self, time: &DateTime<Tz>) -> Box<dyn Iterator<Item = DateTime<Tz>> + 'a> {
+        let fire_times = self.fire_times(time);
+
+        match &self.holidays {
+            Some(holidays) => Box::new(fire_times.filter(|fire| !holidays.is_holiday(fire.date_naive()))),
+            None => fire_times
+        }
+    }
+
+    /// Returns the fire times of the schedule after a time, holidays included.
+    fn fire_times<'a>(&'a self, time: &DateTime<Tz>) -> Box<dyn Iterator<Item = DateTime<Tz>> + 'a> {
+        match &self.kind {
+            ScheduleKind::Cron(schedule) => Box::new(schedule.after(time)),
+            ScheduleKind::Reboot => Box::new(std::iter::empty()),
+            ScheduleKind::DailyBetween { start, end, seed } => {
+                let (start, end, seed, time) = (*start, *end, *seed, *time);
+                // The window of the day before may span midnight and end after `time`.
+                let first_day = time.date_naive().pred_opt().unwrap_or(time.date_naive());
+
+                Box::new((0..)
+                    .map_while(move |days| first_day.checked_add_days(Days::new(days)))
+                    .filter_map(move |date| pick_time_between(date, start, end, seed, &time.timezone()))
+                    .filter(move |fire| *fire > time))
+            }
+            ScheduleKind::Solar { event, offset, latitude, longitude } => {
+                let (event, offset, latitude, longitude, time) = (*event, *offset, *latitude, *longitude, *time);
+                // The event of the day before may fire after `time` once shifted by the offset.
+                let first_day = (time.with_timezone(&Utc) - offset).date_naive().pred_opt().unwrap_or(time.date_naive());
+
+                Box::new((0..)
+                    .map_while(move |days| first_day.checked_add_days(Days::new(days)))
+                    .map(move |date| get_sunrise_sunset(date, latitude, longitude))
+                    // Ends the fire times when the sun stops rising or setting for good.
+                    .scan(0, |missing, events| {
+                        *missing = if events.is_some() { 0 } else { *missing + 1 };
+                        (*missing <= MAX_DAYS_WITHOUT_SOLAR_EVENT).then_some(events)
+                    })
+                    .flatten()
+                    .map(move |(sunrise, sunset)| {
+                        let at = if event == SolarEvent::Sunrise { sunrise } else { sunset };
+                        (at + offset).with_timezone(&time.timezone())
+                    })
+                    .filter(move |fire| *fire > time))
+            }
+        }
+    }
+
+    /// Renders the schedule in natural language, e.g. `every 5 minutes on weekdays`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let start = NaiveTime::from_hms_opt(2, 0, 0).unwrap();
+    /// let end = NaiveTime::from_hms_opt(4, 0, 0).unwrap();
+    /// let schedule = JobSchedule::daily_between(start, end, "sync-users");
+    /// assert_eq!(schedule.describe(), "every day at a random time between 02:00 and 04:00");
+    /// ```
+    pub fn describe(&self) -> String {
+        let description = match &self.kind {
+            ScheduleKind::DailyBetween { start, end, .. } =>
+                format!("every day at a random time between {} and {}", format_time(*start), format_time(*end)),
+            ScheduleKind::Solar { event, offset, latitude, longitude } => {
+                let when = if offset.is_zero() {
+                    format!("at {}", event)
+                } else if *offset < Duration::zero() {
+                    format!("{} before {}", format_offset(*offset), event)
+                } else {
+                    format!("{} after {}", format_offset(*offset), event)
+                };
+                format!("every day {} at {}, {}", when, latitude, longitude)
+            }
+            _ => describe_cron(&self.expression),
+        };
+
+        let description = match self.holidays {
+            Some(_) if !self.is_reboot() => format!("{}, except on holidays", description),
+            _ => description
+        };
+
+        if self.run_on_start && !self.is_reboot() {
+            format!("{}, and once at startup", description)
+        } else {
+            description
+        }
+    }
+}
+
+impl Display for JobSchedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.expression)
+    }
+}
+
+/// Picks the random fire time of a day in a window, or `None` if the start of the window
+/// doesn't exist on that day, e.g. during a daylight saving time gap.
+fn pick_time_between(date: NaiveDate, start: NaiveTime, end: NaiveTime, seed: u64, timezone: &Tz) -> Option<DateTime<Tz>> {
+    let window = (end - start).num_seconds().rem_euclid(86400) as u64;
+    let offset = mix(seed ^ date.num_days_from_ce() as u64) % window;
+    let start = timezone.from_local_datetime(&date.and_time(start)).earliest()?;
+
+    Some(start + Duration::seconds(offset as i64))
+}
+
+/// Formats the length of an offset, without its sign, e.g. `1h30m`.
+fn format_offset(offset: Duration) -> String {
+    format_duration(std::time::Duration::from_millis(offset.num_milliseconds().unsigned_abs()))
+}
+
+/// Formats a time of day as `HH:MM`, or `HH:MM:SS` if the second is not zero.
+fn format_time(time: NaiveTime) -> String {
+    if time.second() == 0 {
+        time.format("%H:%M").to_string()
+    } else {
+        time.format("%H:%M:%S").to_string()
+    }
+}
\ No newline at end of file
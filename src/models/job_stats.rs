@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use crate::models::scheduler_state::JobState;
+
+/// The rolling statistics of a job, computed over its last finished runs.
+#[derive(Debug, Clone, Copy)]
+pub struct JobStats {
+    /// The number of runs in the window.
+    pub runs: usize,
+    /// The share of successful runs in the window, between 0 and 1.
+    pub success_rate: f64,
+    pub average: Duration,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+    /// The number of failed runs since the last successful run, also outside of the window.
+    pub consecutive_failures: u64,
+}
+
+impl JobStats {
+    /// Computes the rolling statistics of a job from its persisted state.
+    ///
+    /// The percentiles use the nearest-rank method over the durations of the runs in the window.
+    ///
+    /// # Arguments
+    ///
+    /// * `job` - The persisted state of the job.
+    ///
+    /// # Returns
+    ///
+    /// The statistics of the job, or `None` if it has no finished run yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// if let Some(stats) = JobStats::from_state(&job_state) {
+    ///     println!("Success rate: {:.1}%, p95: {}ms", stats.success_rate * 100.0, stats.p95.as_millis());
+    /// }
+    /// ```
+    pub fn from_state(job: &JobState) -> Option<Self> {
+        if job.recent_runs.is_empty() {
+            return None;
+        }
+
+        let runs = job.recent_runs.len();
+        let successes = job.recent_runs.iter().filter(|run| run.success).count();
+        let mut durations: Vec<u64> = job.recent_runs.iter().map(|run| run.duration_ms).collect();
+        durations.sort_unstable();
+
+        let percentile = |p: usize| Duration::from_millis(durations[(p * runs).div_ceil(100).max(1) - 1]);
+
+        Some(JobStats {
+            runs,
+            success_rate: successes as f64 / runs as f64,
+            average: Duration::from_millis(durations.iter().sum::<u64>() / runs as u64),
+            p50: percentile(50),
+            p95: percentile(95),
+            max: Duration::from_millis(durations[runs - 1]),
+            consecutive_failures: job.consecutive_failures,
+        })
+    }
+}
\ No newline at end of file
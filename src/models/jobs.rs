@@ -1,17 +1,189 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
 use chrono_tz::Tz;
+use reqwest::header::HeaderMap;
+use serde_json::{Map, Value};
+use crate::models::backup_job::BackupJob;
+use crate::models::blackout::Blackout;
+use crate::models::cleanup_job::CleanupJob;
+use crate::models::correlation_header::CorrelationHeader;
+use crate::models::db_job::DbJob;
+use crate::models::distributed_lock::DistributedLock;
+use crate::models::dns_cache::DnsCache;
+use crate::models::dns_job::DnsJob;
 use crate::models::http_job::HttpJob;
+use crate::models::job::Job;
+use crate::models::job_group::JobGroup;
+use crate::models::leader_election::LeaderElection;
+use crate::models::status_file::StatusFile;
+use crate::models::notifications::Notifications;
+use crate::models::rate_limit::RateLimit;
+use crate::models::run_archive::RunArchive;
+use crate::models::run_id_format::RunIdFormat;
+use crate::models::run_queue::RunQueue;
+use crate::models::tcp_job::TcpJob;
+use crate::models::s3_job::S3Job;
+use crate::models::sftp_job::SftpJob;
+use crate::models::sharding::Sharding;
+use crate::models::telemetry::Telemetry;
+use crate::models::watchdog::Watchdog;
+use crate::models::websocket_job::WebSocketJob;
 
 #[derive(Debug, Clone)]
 pub struct Jobs {
     pub timezone: Tz,
-    pub http_jobs: Vec<HttpJob>
+    pub http_jobs: Vec<HttpJob>,
+    pub websocket_jobs: Vec<WebSocketJob>,
+    pub tcp_jobs: Vec<TcpJob>,
+    pub dns_jobs: Vec<DnsJob>,
+    pub db_jobs: Vec<DbJob>,
+    pub s3_jobs: Vec<S3Job>,
+    pub sftp_jobs: Vec<SftpJob>,
+    pub cleanup_jobs: Vec<CleanupJob>,
+    pub backup_jobs: Vec<BackupJob>,
+    pub rate_limits: HashMap<String, RateLimit>,
+    pub max_requests_per_second: Option<u32>,
+    /// The groups of related jobs by name, limiting their concurrent runs.
+    pub groups: HashMap<String, JobGroup>,
+    /// The execution capacity of the scheduler, or `None` if triggered runs are not limited.
+    pub run_queue: Option<RunQueue>,
+    /// The blackouts of all jobs, during which their triggers are suppressed.
+    pub blackouts: Vec<Blackout>,
+    /// The lock shared with the other instances of the scheduler, or `None` if the triggered
+    /// runs are not locked.
+    pub distributed_lock: Option<DistributedLock>,
+    /// The election of the instance firing the triggers, or `None` if every instance fires them.
+    pub leader_election: Option<LeaderElection>,
+    /// The partitioning of the jobs among the instances, or `None` if every instance fires
+    /// the triggers of all jobs.
+    pub sharding: Option<Sharding>,
+    pub dead_letter_file: Option<String>,
+    /// The storage the runs of the HTTP jobs are archived to, or `None` if they are not.
+    pub run_archive: Option<RunArchive>,
+    pub state_file: String,
+    /// The status snapshot written periodically, or `None` if no status file is written.
+    pub status_file: Option<StatusFile>,
+    /// The watchdog of the scheduler itself, or `None` if the ticks of the scheduler are not
+    /// watched.
+    pub watchdog: Option<Watchdog>,
+    /// The cache of the DNS lookups of the HTTP clients, or `None` if every request resolves
+    /// its host.
+    pub dns_cache: Option<DnsCache>,
+    pub notifications: Notifications,
+    pub admin_address: Option<SocketAddr>,
+    /// The bearer token required by the endpoints of the admin server changing the scheduler,
+    /// or `None` if they are disabled.
+    pub admin_token: Option<String>,
+    pub telemetry: Option<Telemetry>,
+    pub correlation_header: Option<CorrelationHeader>,
+    pub user_agent: String,
+    pub run_id_format: RunIdFormat,
+    /// The job templates by name, applied to jobs added through the admin server.
+    pub templates: Map<String, Value>,
+    /// The default job fields, applied to jobs added through the admin server.
+    pub defaults: Map<String, Value>,
+    /// The default headers of HTTP requests, merged with the headers of each request.
+    pub default_headers: HeaderMap,
 }
 
 impl Jobs {
-    pub fn new(timezone: Tz, http_jobs: Vec<HttpJob>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        timezone: Tz,
+        http_jobs: Vec<HttpJob>,
+        websocket_jobs: Vec<WebSocketJob>,
+        tcp_jobs: Vec<TcpJob>,
+        dns_jobs: Vec<DnsJob>,
+        db_jobs: Vec<DbJob>,
+        s3_jobs: Vec<S3Job>,
+        sftp_jobs: Vec<SftpJob>,
+        cleanup_jobs: Vec<CleanupJob>,
+        backup_jobs: Vec<BackupJob>,
+        rate_limits: HashMap<String, RateLimit>,
+        max_requests_per_second: Option<u32>,
+        groups: HashMap<String, JobGroup>,
+        run_queue: Option<RunQueue>,
+        blackouts: Vec<Blackout>,
+        distributed_lock: Option<DistributedLock>,
+        leader_election: Option<LeaderElection>,
+        sharding: Option<Sharding>,
+        dead_letter_file: Option<String>,
+        run_archive: Option<RunArchive>,
+        state_file: String,
+        status_file: Option<StatusFile>,
+        watchdog: Option<Watchdog>,
+        dns_cache: Option<DnsCache>,
+        notifications: Notifications,
+        admin_address: Option<SocketAddr>,
+        admin_token: Option<String>,
+        telemetry: Option<Telemetry>,
+        correlation_header: Option<CorrelationHeader>,
+        user_agent: String,
+        run_id_format: RunIdFormat,
+        templates: Map<String, Value>,
+        defaults: Map<String, Value>,
+        default_headers: HeaderMap,
+    ) -> Self {
         Jobs {
             timezone,
-            http_jobs
+            http_jobs,
+            websocket_jobs,
+            tcp_jobs,
+            dns_jobs,
+            db_jobs,
+            s3_jobs,
+            sftp_jobs,
+            cleanup_jobs,
+            backup_jobs,
+            rate_limits,
+            max_requests_per_second,
+            groups,
+            run_queue,
+            blackouts,
+            distributed_lock,
+            leader_election,
+            sharding,
+            dead_letter_file,
+            run_archive,
+            state_file,
+            status_file,
+            watchdog,
+            dns_cache,
+            notifications,
+            admin_address,
+            admin_token,
+            telemetry,
+            correlation_header,
+            user_agent,
+            run_id_format,
+            templates,
+            defaults,
+            default_headers,
         }
     }
+
+    /// Returns the jobs of every type, the HTTP jobs first and then the other types in the order
+    /// of the configuration sections.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let exists = get_jobs().all_jobs().iter().any(|job| job.name() == "login");
+    /// ```
+    pub fn all_jobs(&self) -> Vec<Arc<dyn Job>> {
+        let http_jobs = self.http_jobs.iter().map(|job| Arc::new(job.clone()) as Arc<dyn Job>);
+        let websocket_jobs = self.websocket_jobs.iter().map(|job| Arc::new(job.clone()) as Arc<dyn Job>);
+        let tcp_jobs = self.tcp_jobs.iter().map(|job| Arc::new(job.clone()) as Arc<dyn Job>);
+        let dns_jobs = self.dns_jobs.iter().map(|job| Arc::new(job.clone()) as Arc<dyn Job>);
+        let db_jobs = self.db_jobs.iter().map(|job| Arc::new(job.clone()) as Arc<dyn Job>);
+        let s3_jobs = self.s3_jobs.iter().map(|job| Arc::new(job.clone()) as Arc<dyn Job>);
+        let sftp_jobs = self.sftp_jobs.iter().map(|job| Arc::new(job.clone()) as Arc<dyn Job>);
+        let cleanup_jobs = self.cleanup_jobs.iter().map(|job| Arc::new(job.clone()) as Arc<dyn Job>);
+        let backup_jobs = self.backup_jobs.iter().map(|job| Arc::new(job.clone()) as Arc<dyn Job>);
+
+        http_jobs.chain(websocket_jobs).chain(tcp_jobs).chain(dns_jobs).chain(db_jobs)
+            .chain(s3_jobs).chain(sftp_jobs).chain(cleanup_jobs).chain(backup_jobs)
+            .collect()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,36 @@
+use std::time::Duration;
+use crate::models::distributed_lock::RedisServer;
+
+/// The lease of the leader, by default.
+pub const DEFAULT_LEADER_LEASE: Duration = Duration::from_secs(15);
+
+/// The Redis key holding the identifier of the leader, by default.
+pub const DEFAULT_LEADER_KEY: &str = "rjob:leader";
+
+/// The active/standby election of the instances of the scheduler.
+///
+/// The leader is the instance holding the `key` in Redis, which expires after `lease` unless
+/// the leader renews it. Only the leader fires the triggers of the jobs. The other instances
+/// stand by and try to take the key over, so one of them becomes the leader within `lease`
+/// when the leader disappears.
+#[derive(Debug, Clone)]
+pub struct LeaderElection {
+    pub redis: RedisServer,
+    pub key: String,
+    pub lease: Duration,
+}
+
+impl LeaderElection {
+    pub fn new(redis: RedisServer, key: String, lease: Duration) -> Self {
+        LeaderElection {
+            redis,
+            key,
+            lease,
+        }
+    }
+
+    /// Returns the interval between two renewals of the lease, or two attempts to take it over.
+    pub fn renew_interval(&self) -> Duration {
+        self.lease / 3
+    }
+}
\ No newline at end of file
@@ -1,3 +1,46 @@
 pub mod jobs;
+pub mod backup_job;
+pub mod blackout;
+pub mod catch_up;
+pub mod condition;
+pub mod cleanup_job;
+pub mod config_source;
+pub mod correlation_header;
 pub mod http_job;
-pub mod http_job_request;
\ No newline at end of file
+pub mod http_job_request;
+pub mod http_job_response;
+pub mod http_job_step;
+pub mod job;
+pub mod job_group;
+pub mod job_options;
+pub mod job_schedule;
+pub mod job_stats;
+pub mod leader_election;
+pub mod db_job;
+pub mod dead_letter_record;
+pub mod distributed_lock;
+pub mod dns_cache;
+pub mod dns_job;
+pub mod heartbeat;
+pub mod holiday_calendar;
+pub mod hooks;
+pub mod notifications;
+pub mod on_invalid_job;
+pub mod rate_limit;
+pub mod run_id_format;
+pub mod run_archive;
+pub mod run_outcome;
+pub mod run_queue;
+pub mod s3_job;
+pub mod s3_storage;
+pub mod scheduler_state;
+pub mod sftp_job;
+pub mod sharding;
+pub mod status_file;
+pub mod tag_filter;
+pub mod tcp_job;
+pub mod telemetry;
+pub mod tls;
+pub mod transfer_operation;
+pub mod watchdog;
+pub mod websocket_job;
\ No newline at end of file
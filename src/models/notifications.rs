@@ -0,0 +1,137 @@
+use std::time::Duration;
+use reqwest::header::HeaderMap;
+
+/// The notification channels that are alerted when a job fails or recovers.
+#[derive(Debug, Clone, Default)]
+pub struct Notifications {
+    pub webhook: Option<WebhookNotification>,
+    pub slack: Option<SlackNotification>,
+    pub email: Option<EmailNotification>,
+    pub telegram: Option<TelegramNotification>,
+}
+
+impl Notifications {
+    pub fn new(webhook: Option<WebhookNotification>, slack: Option<SlackNotification>, email: Option<EmailNotification>, telegram: Option<TelegramNotification>) -> Self {
+        Notifications {
+            webhook,
+            slack,
+            email,
+            telegram,
+        }
+    }
+}
+
+/// A webhook that receives a JSON payload when a job fails after all retries.
+#[derive(Debug, Clone)]
+pub struct WebhookNotification {
+    pub url: String,
+    pub headers: Option<HeaderMap>,
+}
+
+impl WebhookNotification {
+    pub fn new(url: String, headers: Option<HeaderMap>) -> Self {
+        WebhookNotification {
+            url,
+            headers,
+        }
+    }
+}
+
+/// A Slack incoming webhook that receives a message when a job fails after all retries and
+/// when it succeeds again.
+#[derive(Debug, Clone)]
+pub struct SlackNotification {
+    pub webhook_url: String,
+    pub channel: Option<String>,
+    pub username: Option<String>,
+}
+
+impl SlackNotification {
+    pub fn new(webhook_url: String, channel: Option<String>, username: Option<String>) -> Self {
+        SlackNotification {
+            webhook_url,
+            channel,
+            username,
+        }
+    }
+}
+
+/// A Telegram bot that sends a message to a chat when a job fails after all retries and when
+/// it succeeds again.
+#[derive(Debug, Clone)]
+pub struct TelegramNotification {
+    pub bot_token: String,
+    pub chat_id: String,
+}
+
+impl TelegramNotification {
+    pub fn new(bot_token: String, chat_id: String) -> Self {
+        TelegramNotification {
+            bot_token,
+            chat_id,
+        }
+    }
+}
+
+/// An SMTP server used to email a summary of failed job runs.
+///
+/// If `batch_window` is set, failures occurring within the window are sent together in one
+/// digest message instead of one email per failure.
+#[derive(Debug, Clone)]
+pub struct EmailNotification {
+    pub host: String,
+    pub port: Option<u16>,
+    pub security: SmtpSecurity,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+    pub batch_window: Option<Duration>,
+}
+
+/// The transport security used to connect to the SMTP server.
+///
+/// - `StartTls`: Connect in plain text and upgrade the connection with STARTTLS (port 587 by default).
+/// - `Tls`: Connect with implicit TLS (port 465 by default).
+/// - `None`: Don't encrypt the connection (port 25 by default).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SmtpSecurity {
+    #[default]
+    StartTls,
+    Tls,
+    None,
+}
+
+impl SmtpSecurity {
+
+    /// Parses the value of the `security` field.
+    ///
+    /// Returns `None` if the value is not one of `starttls`, `tls` or `none`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "starttls" => Some(SmtpSecurity::StartTls),
+            "tls" => Some(SmtpSecurity::Tls),
+            "none" => Some(SmtpSecurity::None),
+            _ => None
+        }
+    }
+}
+
+/// The notification settings of a single job, overriding the global `notifications` section.
+///
+/// With `alert_if_no_success_for`, an alert is sent to the global channels when the job has not
+/// run successfully within that window, see `start_freshness_check`.
+#[derive(Debug, Clone, Default)]
+pub struct JobNotifications {
+    pub slack_channel: Option<String>,
+    pub alert_if_no_success_for: Option<Duration>,
+}
+
+impl JobNotifications {
+    pub fn new(slack_channel: Option<String>, alert_if_no_success_for: Option<Duration>) -> Self {
+        JobNotifications {
+            slack_channel,
+            alert_if_no_success_for,
+        }
+    }
+}
\ No newline at end of file
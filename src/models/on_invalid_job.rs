@@ -0,0 +1,43 @@
+use std::fmt::{Display, Formatter};
+
+/// What to do with a job definition of the configuration that cannot be parsed.
+///
+/// - `Fail`: Abort loading the configuration.
+/// - `Skip`: Report the invalid job and keep running the valid ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnInvalidJob {
+    #[default]
+    Fail,
+    Skip,
+}
+
+impl OnInvalidJob {
+
+    /// Parses the value of the `on_invalid_job` field.
+    ///
+    /// Returns `None` if the value is not one of `fail` or `skip`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let on_invalid_job = OnInvalidJob::parse("skip");
+    /// assert_eq!(on_invalid_job, Some(OnInvalidJob::Skip));
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "fail" => Some(OnInvalidJob::Fail),
+            "skip" => Some(OnInvalidJob::Skip),
+            _ => None
+        }
+    }
+}
+
+impl Display for OnInvalidJob {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let on_invalid_job = match self {
+            OnInvalidJob::Fail => "fail",
+            OnInvalidJob::Skip => "skip",
+        };
+        write!(f, "{}", on_invalid_job)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,52 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// A request rate, such as `5/s`, expressed as a number of requests allowed per period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub period: Duration,
+}
+
+impl RateLimit {
+    pub fn new(requests: u32, period: Duration) -> Self {
+        RateLimit {
+            requests,
+            period,
+        }
+    }
+
+    /// Parses a rate in the form `<requests>/<unit>`, where the unit is `s`, `m` or `h`.
+    ///
+    /// Returns `None` if the value is malformed or the number of requests is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let rate = RateLimit::parse("5/s").unwrap();
+    /// assert_eq!(rate.requests, 5);
+    /// assert_eq!(rate.period, Duration::from_secs(1));
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        let (requests, unit) = value.split_once('/')?;
+        let requests = requests.trim().parse::<u32>().ok().filter(|r| *r > 0)?;
+        let period = match unit.trim() {
+            "s" => Duration::from_secs(1),
+            "m" => Duration::from_secs(60),
+            "h" => Duration::from_secs(3600),
+            _ => return None
+        };
+        Some(RateLimit::new(requests, period))
+    }
+}
+
+impl Display for RateLimit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let unit = match self.period.as_secs() {
+            60 => "m",
+            3600 => "h",
+            _ => "s"
+        };
+        write!(f, "{}/{}", self.requests, unit)
+    }
+}
\ No newline at end of file
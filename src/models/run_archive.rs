@@ -0,0 +1,29 @@
+use crate::models::s3_storage::S3Storage;
+
+/// The key of the archived records, by default.
+pub const DEFAULT_ARCHIVE_KEY: &str = "{{job_name}}/{{date}}/{{run_id}}.json";
+
+/// The timeout of the upload of an archived record, in milliseconds, by default.
+pub const DEFAULT_ARCHIVE_TIMEOUT: u64 = 30000;
+
+/// The S3-compatible storage the request/response record of each run of an HTTP job is
+/// written to, see `archive_run`.
+///
+/// `key` is a template supporting the `{{job_name}}`, `{{run_id}}`, `{{now}}` and `{{date}}`
+/// variables.
+#[derive(Debug, Clone)]
+pub struct RunArchive {
+    pub storage: S3Storage,
+    pub key: String,
+    pub timeout: u64,
+}
+
+impl RunArchive {
+    pub fn new(storage: S3Storage, key: String, timeout: u64) -> Self {
+        RunArchive {
+            storage,
+            key,
+            timeout,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+use std::fmt::{Display, Formatter};
+
+/// The format of the identifier generated for every job run.
+///
+/// - `Uuid`: A random UUID (version 4) without hyphens.
+/// - `UuidV7`: A time-ordered UUID (version 7) without hyphens.
+/// - `Ulid`: A time-ordered ULID, e.g. `01J9Z8Q4RJ6W1X2Y3Z4A5B6C7D`.
+/// - `Sequence`: A per-job sequence number persisted in the state file, e.g. `0000000042`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RunIdFormat {
+    #[default]
+    Uuid,
+    UuidV7,
+    Ulid,
+    Sequence,
+}
+
+impl RunIdFormat {
+
+    /// Parses the value of the `run_id_format` field.
+    ///
+    /// Returns `None` if the value is not one of `uuid`, `uuidv7`, `ulid` or `sequence`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let format = RunIdFormat::parse("ulid");
+    /// assert_eq!(format, Some(RunIdFormat::Ulid));
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "uuid" => Some(RunIdFormat::Uuid),
+            "uuidv7" => Some(RunIdFormat::UuidV7),
+            "ulid" => Some(RunIdFormat::Ulid),
+            "sequence" => Some(RunIdFormat::Sequence),
+            _ => None
+        }
+    }
+}
+
+impl Display for RunIdFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let format = match self {
+            RunIdFormat::Uuid => "uuid",
+            RunIdFormat::UuidV7 => "uuidv7",
+            RunIdFormat::Ulid => "ulid",
+            RunIdFormat::Sequence => "sequence",
+        };
+        write!(f, "{}", format)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+use std::fmt::{Display, Formatter};
+
+/// The outcome of a single run of a job, including all of its retry attempts.
+#[derive(Debug, Clone)]
+pub struct RunOutcome {
+    pub run_id: String,
+    pub job_name: String,
+    pub success: bool,
+    pub status: Option<u16>,
+    pub attempts: u64,
+    pub error: Option<String>,
+}
+
+impl RunOutcome {
+    pub fn new(run_id: String, job_name: String, success: bool, status: Option<u16>, attempts: u64, error: Option<String>) -> Self {
+        RunOutcome {
+            run_id,
+            job_name,
+            success,
+            status,
+            attempts,
+            error,
+        }
+    }
+}
+
+impl Display for RunOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let status = match &self.status {
+            Some(s) => s.to_string(),
+            None => "None".to_string()
+        };
+        let error = match &self.error {
+            Some(e) => e,
+            None => "None"
+        };
+        write!(f, "run_id: {}, job_name: {}, success: {}, status: {}, attempts: {}, error: {}",
+               self.run_id, self.job_name, self.success, status, self.attempts, error)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,72 @@
+use std::fmt::{Display, Formatter};
+
+/// The number of pending runs kept in the run queue, by default.
+pub const DEFAULT_RUN_QUEUE_SIZE: usize = 100;
+
+/// What to do with a triggered run when the run queue is full.
+///
+/// - `DropOldest`: Drop the run that waited the longest and queue the new one.
+/// - `DropNew`: Drop the new run.
+/// - `Block`: Hold back the timer until a run ends, delaying the triggers of all jobs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WhenFull {
+    DropOldest,
+    #[default]
+    DropNew,
+    Block,
+}
+
+impl WhenFull {
+
+    /// Parses the value of the `when_full` field.
+    ///
+    /// Returns `None` if the value is not one of `drop_oldest`, `drop_new` or `block`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let when_full = WhenFull::parse("drop_oldest");
+    /// assert_eq!(when_full, Some(WhenFull::DropOldest));
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "drop_oldest" => Some(WhenFull::DropOldest),
+            "drop_new" => Some(WhenFull::DropNew),
+            "block" => Some(WhenFull::Block),
+            _ => None
+        }
+    }
+}
+
+impl Display for WhenFull {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let when_full = match self {
+            WhenFull::DropOldest => "drop_oldest",
+            WhenFull::DropNew => "drop_new",
+            WhenFull::Block => "block",
+        };
+        write!(f, "{}", when_full)
+    }
+}
+
+/// The execution capacity of the scheduler.
+///
+/// At most `max_concurrent` triggered runs of all jobs are in progress at the same time. The
+/// other triggered runs wait in a queue of at most `max_size` runs, in trigger order, and
+/// `when_full` decides what happens to a run triggered while the queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunQueue {
+    pub max_concurrent: usize,
+    pub max_size: usize,
+    pub when_full: WhenFull,
+}
+
+impl RunQueue {
+    pub fn new(max_concurrent: usize, max_size: usize, when_full: WhenFull) -> Self {
+        RunQueue {
+            max_concurrent,
+            max_size,
+            when_full,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,30 @@
+use std::fmt::{Display, Formatter};
+use crate::models::s3_storage::S3Storage;
+use crate::models::transfer_operation::TransferOperation;
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+
+/// A scheduled transfer between a local file and an object of an S3-compatible bucket.
+///
+/// `key` and `file` are templates supporting the `{{job_name}}`, `{{run_id}}`, `{{now}}` and
+/// `{{date}}` variables.
+#[derive(Debug, Clone)]
+pub struct S3Job {
+    pub name: String,
+    pub enable: bool,
+    pub schedule: JobSchedule,
+    pub timeout: u64,
+    pub max_retry: u64,
+    pub operation: TransferOperation,
+    pub storage: S3Storage,
+    pub key: String,
+    pub file: String,
+    pub options: JobOptions,
+}
+
+impl Display for S3Job {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "name: {}, enable: {}, cron: {}, timeout: {}, max_retry: {}, operation: {}, {}, key: {}, file: {}",
+               self.name, self.enable, self.schedule, self.timeout, self.max_retry, self.operation, self.storage, self.key, self.file)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,19 @@
+use std::fmt::{Display, Formatter};
+
+/// The endpoint, bucket and credentials of an S3-compatible storage.
+#[derive(Debug, Clone)]
+pub struct S3Storage {
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key: String,
+    pub secret_key: String,
+    /// Whether objects are addressed as `endpoint/bucket/key` rather than `bucket.endpoint/key`.
+    pub path_style: bool,
+}
+
+impl Display for S3Storage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "endpoint: {}, region: {}, bucket: {}, path_style: {}", self.endpoint, self.region, self.bucket, self.path_style)
+    }
+}
\ No newline at end of file
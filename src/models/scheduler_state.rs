@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The state of the scheduler persisted across restarts, keyed by job name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchedulerState {
+    #[serde(default)]
+    pub jobs: BTreeMap<String, JobState>,
+
+    /// The values stored with the `set` command, read by the `when` conditions as `kv.NAME`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub values: BTreeMap<String, String>,
+}
+
+/// The persisted state of a job.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobState {
+    /// The fire time of the last successful run, or its start time if it was not started by
+    /// the timer, see `record_success`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_success: Option<DateTime<Utc>>,
+
+    /// The number of runs started, counted for jobs with `max_runs`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub runs: u64,
+
+    /// The start time of the last finished run, successful or not.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run: Option<DateTime<Utc>>,
+
+    /// The start time of the last failed run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure: Option<DateTime<Utc>>,
+
+    /// The number of successful runs.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub successes: u64,
+
+    /// The number of failed runs.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub failures: u64,
+
+    /// The number of failed runs since the last successful run.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub consecutive_failures: u64,
+
+    /// The last finished runs, oldest first, for the rolling statistics of the job.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent_runs: Vec<RecentRun>,
+
+    /// The last run sequence number, used by the `sequence` run id format.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub sequence: u64,
+
+    /// Whether the triggers of the job are skipped.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub paused: bool,
+}
+
+/// A finished run of a job, kept in the window of the rolling statistics.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RecentRun {
+    /// The time from the start of the run to its end, retries included, in milliseconds.
+    pub duration_ms: u64,
+    pub success: bool,
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
\ No newline at end of file
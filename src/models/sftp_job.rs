@@ -0,0 +1,61 @@
+use std::fmt::{Display, Formatter};
+use crate::models::transfer_operation::TransferOperation;
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+
+/// What an SFTP job does when the destination file already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverwritePolicy {
+    /// Replaces the existing file.
+    Overwrite,
+    /// Leaves the existing file and ends the run successfully.
+    Skip,
+    /// Leaves the existing file and fails the run.
+    Fail,
+}
+
+impl Display for OverwritePolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OverwritePolicy::Overwrite => write!(f, "overwrite"),
+            OverwritePolicy::Skip => write!(f, "skip"),
+            OverwritePolicy::Fail => write!(f, "fail"),
+        }
+    }
+}
+
+/// A scheduled transfer between a local file and a file of an SFTP server.
+///
+/// The session authenticates with the private key at `private_key`. If `known_hosts` is set, the
+/// host key of the server must be listed in it. `remote_path` and `local_path` are templates
+/// supporting the `{{job_name}}`, `{{run_id}}`, `{{now}}` and `{{date}}` variables.
+#[derive(Debug, Clone)]
+pub struct SftpJob {
+    pub name: String,
+    pub enable: bool,
+    pub schedule: JobSchedule,
+    pub timeout: u64,
+    pub max_retry: u64,
+    pub operation: TransferOperation,
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub private_key: String,
+    pub passphrase: Option<String>,
+    pub known_hosts: Option<String>,
+    pub remote_path: String,
+    pub local_path: String,
+    pub overwrite: OverwritePolicy,
+    pub options: JobOptions,
+}
+
+impl Display for SftpJob {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let known_hosts = match &self.known_hosts {
+            Some(k) => k,
+            None => "None"
+        };
+        write!(f, "name: {}, enable: {}, cron: {}, timeout: {}, max_retry: {}, operation: {}, host: {}, port: {}, username: {}, private_key: {}, known_hosts: {}, remote_path: {}, local_path: {}, overwrite: {}",
+               self.name, self.enable, self.schedule, self.timeout, self.max_retry, self.operation, self.host, self.port, self.username, self.private_key, known_hosts, self.remote_path, self.local_path, self.overwrite)
+    }
+}
\ No newline at end of file
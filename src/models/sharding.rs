@@ -0,0 +1,36 @@
+use std::time::Duration;
+use crate::models::distributed_lock::RedisServer;
+
+/// The lease of a member of the cluster, by default.
+pub const DEFAULT_MEMBER_LEASE: Duration = Duration::from_secs(15);
+
+/// The Redis key holding the members of the cluster, by default.
+pub const DEFAULT_MEMBERS_KEY: &str = "rjob:members";
+
+/// The partitioning of the jobs among the instances of the scheduler.
+///
+/// The members of the cluster are the instances registered in the sorted set `key` in Redis,
+/// each with a lease renewed every third of `lease`. Every member fires the triggers of its own
+/// share of the jobs only, the share of a member that stops renewing its lease is taken over by
+/// the other members.
+#[derive(Debug, Clone)]
+pub struct Sharding {
+    pub redis: RedisServer,
+    pub key: String,
+    pub lease: Duration,
+}
+
+impl Sharding {
+    pub fn new(redis: RedisServer, key: String, lease: Duration) -> Self {
+        Sharding {
+            redis,
+            key,
+            lease,
+        }
+    }
+
+    /// Returns the interval between two renewals of the lease of this member.
+    pub fn renew_interval(&self) -> Duration {
+        self.lease / 3
+    }
+}
\ No newline at end of file
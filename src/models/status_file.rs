@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+/// The interval between two snapshots of the status file, by default.
+pub const DEFAULT_STATUS_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The status snapshot of the scheduler, written periodically for external tooling.
+///
+/// The snapshot holds the last run, the last result, the consecutive failures and the next
+/// fire time of each job, see `start_status_file`.
+#[derive(Debug, Clone)]
+pub struct StatusFile {
+    pub path: String,
+    pub interval: Duration,
+}
+
+impl StatusFile {
+    pub fn new(path: String, interval: Duration) -> Self {
+        StatusFile {
+            path,
+            interval,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+/// The tags selecting the jobs an instance of the scheduler runs, given with `--tags` and
+/// `--exclude-tags`.
+///
+/// A job is selected if it has one of `tags`, or `tags` is empty, and none of `exclude_tags`.
+#[derive(Debug, Clone, Default)]
+pub struct TagFilter {
+    pub tags: Vec<String>,
+    pub exclude_tags: Vec<String>,
+}
+
+impl TagFilter {
+
+    pub fn new(tags: Vec<String>, exclude_tags: Vec<String>) -> Self {
+        TagFilter { tags, exclude_tags }
+    }
+
+    /// Returns whether a job with the given tags is selected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let filter = TagFilter::new(vec!["prod".to_string()], vec!["billing".to_string()]);
+    /// assert!(filter.matches(&["prod".to_string()]));
+    /// assert!(!filter.matches(&["prod".to_string(), "billing".to_string()]));
+    /// assert!(!filter.matches(&[]));
+    /// ```
+    pub fn matches(&self, job_tags: &[String]) -> bool {
+        let included = self.tags.is_empty() || self.tags.iter().any(|t| job_tags.contains(t));
+        let excluded = self.exclude_tags.iter().any(|t| job_tags.contains(t));
+
+        included && !excluded
+    }
+}
\ No newline at end of file
@@ -0,0 +1,25 @@
+use std::fmt::{Display, Formatter};
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+
+/// A scheduled check that a TCP port accepts connections.
+///
+/// Each run opens a connection to `host:port` and closes it as soon as it is established.
+#[derive(Debug, Clone)]
+pub struct TcpJob {
+    pub name: String,
+    pub enable: bool,
+    pub schedule: JobSchedule,
+    pub timeout: u64,
+    pub max_retry: u64,
+    pub host: String,
+    pub port: u16,
+    pub options: JobOptions,
+}
+
+impl Display for TcpJob {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "name: {}, enable: {}, cron: {}, timeout: {}, max_retry: {}, host: {}, port: {}",
+               self.name, self.enable, self.schedule, self.timeout, self.max_retry, self.host, self.port)
+    }
+}
\ No newline at end of file
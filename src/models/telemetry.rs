@@ -0,0 +1,18 @@
+/// The OpenTelemetry export settings.
+///
+/// When configured, every job run is exported as an OTLP span with one child span per request
+/// attempt, and a `traceparent` header is propagated on the outgoing requests.
+#[derive(Debug, Clone)]
+pub struct Telemetry {
+    pub otlp_endpoint: String,
+    pub service_name: String,
+}
+
+impl Telemetry {
+    pub fn new(otlp_endpoint: String, service_name: String) -> Self {
+        Telemetry {
+            otlp_endpoint,
+            service_name,
+        }
+    }
+}
\ No newline at end of file
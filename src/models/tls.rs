@@ -0,0 +1,102 @@
+use std::fmt::{Display, Formatter};
+
+/// A version of the TLS protocol.
+///
+/// TLS 1.0 and 1.1 are only implemented by the native TLS backend of the platform, not by
+/// rustls, see `TlsOptions::allows_legacy_versions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TlsVersion {
+    Tls1_0,
+    Tls1_1,
+    Tls1_2,
+    Tls1_3,
+}
+
+impl TlsVersion {
+
+    /// Parses a TLS version of the `tls` field.
+    ///
+    /// Returns `None` if the value is not one of `1.0`, `1.1`, `1.2` or `1.3`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let version = TlsVersion::parse("1.3");
+    /// assert_eq!(version, Some(TlsVersion::Tls1_3));
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "1.0" => Some(TlsVersion::Tls1_0),
+            "1.1" => Some(TlsVersion::Tls1_1),
+            "1.2" => Some(TlsVersion::Tls1_2),
+            "1.3" => Some(TlsVersion::Tls1_3),
+            _ => None
+        }
+    }
+
+    /// Returns the TLS version as the client builder expects it.
+    pub fn to_reqwest(self) -> reqwest::tls::Version {
+        match self {
+            TlsVersion::Tls1_0 => reqwest::tls::Version::TLS_1_0,
+            TlsVersion::Tls1_1 => reqwest::tls::Version::TLS_1_1,
+            TlsVersion::Tls1_2 => reqwest::tls::Version::TLS_1_2,
+            TlsVersion::Tls1_3 => reqwest::tls::Version::TLS_1_3,
+        }
+    }
+}
+
+impl Display for TlsVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let version = match self {
+            TlsVersion::Tls1_0 => "1.0",
+            TlsVersion::Tls1_1 => "1.1",
+            TlsVersion::Tls1_2 => "1.2",
+            TlsVersion::Tls1_3 => "1.3",
+        };
+        write!(f, "{}", version)
+    }
+}
+
+/// The TLS settings of the connections of an HTTP job.
+///
+/// A version left unset is not restricted beyond what the TLS backend supports.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TlsOptions {
+    pub min_version: Option<TlsVersion>,
+    pub max_version: Option<TlsVersion>,
+    /// The SHA-256 hashes of the public keys the server certificate chain must include one
+    /// of, none if the certificates are not pinned.
+    pub pinned_spki_sha256: Vec<[u8; 32]>,
+}
+
+impl TlsOptions {
+    pub fn new(min_version: Option<TlsVersion>, max_version: Option<TlsVersion>, pinned_spki_sha256: Vec<[u8; 32]>) -> Self {
+        TlsOptions {
+            min_version,
+            max_version,
+            pinned_spki_sha256,
+        }
+    }
+
+    /// Returns whether the versions allow TLS 1.0 or 1.1, which only the native TLS backend
+    /// implements, see `build_client`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let tls = TlsOptions::new(Some(TlsVersion::Tls1_0), None, Vec::new());
+    /// assert!(tls.allows_legacy_versions());
+    /// assert!(!TlsOptions::default().allows_legacy_versions());
+    /// ```
+    pub fn allows_legacy_versions(&self) -> bool {
+        let is_legacy = |version: Option<TlsVersion>| version.is_some_and(|version| version < TlsVersion::Tls1_2);
+        is_legacy(self.min_version) || is_legacy(self.max_version)
+    }
+}
+
+impl Display for TlsOptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let version = |v: Option<TlsVersion>| v.map_or("None".to_string(), |v| v.to_string());
+        write!(f, "min_version: {}, max_version: {}, pinned_spki_sha256: {}", version(self.min_version), version(self.max_version), self.pinned_spki_sha256.len())
+    }
+}
\ No newline at end of file
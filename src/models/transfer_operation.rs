@@ -0,0 +1,19 @@
+use std::fmt::{Display, Formatter};
+
+/// The direction of a file transfer job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferOperation {
+    /// Uploads the local file to the remote location.
+    Upload,
+    /// Downloads the remote file to the local path.
+    Download,
+}
+
+impl Display for TransferOperation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransferOperation::Upload => write!(f, "upload"),
+            TransferOperation::Download => write!(f, "download"),
+        }
+    }
+}
\ No newline at end of file
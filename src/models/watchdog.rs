@@ -0,0 +1,69 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// The time without a tick of the scheduler after which the watchdog acts, by default.
+pub const DEFAULT_WATCHDOG_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// What the watchdog does when the scheduler stopped ticking.
+///
+/// - `Log`: Log that the scheduler stalled.
+/// - `Notify`: Send an alert to the global notification channels.
+/// - `Exit`: Exit the process with status 1, so a supervisor restarts it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchdogAction {
+    Log,
+    Notify,
+    Exit,
+}
+
+impl WatchdogAction {
+
+    /// Parses an action of the `action` field.
+    ///
+    /// Returns `None` if the value is not one of `log`, `notify` or `exit`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let action = WatchdogAction::parse("exit");
+    /// assert_eq!(action, Some(WatchdogAction::Exit));
+    /// ```
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "log" => Some(WatchdogAction::Log),
+            "notify" => Some(WatchdogAction::Notify),
+            "exit" => Some(WatchdogAction::Exit),
+            _ => None
+        }
+    }
+}
+
+impl Display for WatchdogAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let action = match self {
+            WatchdogAction::Log => "log",
+            WatchdogAction::Notify => "notify",
+            WatchdogAction::Exit => "exit",
+        };
+        write!(f, "{}", action)
+    }
+}
+
+/// The watchdog of the scheduler itself.
+///
+/// An internal heartbeat job fires every minute on the timer of the scheduler. When it didn't
+/// fire for `timeout`, the watchdog runs its `actions`, see `start_watchdog`.
+#[derive(Debug, Clone)]
+pub struct Watchdog {
+    pub timeout: Duration,
+    pub actions: Vec<WatchdogAction>,
+}
+
+impl Watchdog {
+    pub fn new(timeout: Duration, actions: Vec<WatchdogAction>) -> Self {
+        Watchdog {
+            timeout,
+            actions,
+        }
+    }
+}
\ No newline at end of file
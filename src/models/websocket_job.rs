@@ -0,0 +1,37 @@
+use std::fmt::{Display, Formatter};
+use reqwest::header::HeaderMap;
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+
+/// A scheduled check of a WebSocket endpoint.
+///
+/// Each run opens a connection to `url`. If `message` is set, it is sent and the run waits for a
+/// response, which must contain `expect` if configured. Otherwise only the handshake is checked.
+#[derive(Debug, Clone)]
+pub struct WebSocketJob {
+    pub name: String,
+    pub enable: bool,
+    pub schedule: JobSchedule,
+    pub timeout: u64,
+    pub max_retry: u64,
+    pub url: String,
+    pub headers: Option<HeaderMap>,
+    pub message: Option<String>,
+    pub expect: Option<String>,
+    pub options: JobOptions,
+}
+
+impl Display for WebSocketJob {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let message = match &self.message {
+            Some(m) => m,
+            None => "None"
+        };
+        let expect = match &self.expect {
+            Some(e) => e,
+            None => "None"
+        };
+        write!(f, "name: {}, enable: {}, cron: {}, timeout: {}, max_retry: {}, url: {}, message: {}, expect: {}",
+               self.name, self.enable, self.schedule, self.timeout, self.max_retry, self.url, message, expect)
+    }
+}
\ No newline at end of file
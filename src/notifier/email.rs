@@ -0,0 +1,126 @@
+use std::error::Error;
+use std::sync::Mutex;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use lettre::transport::smtp::authentication::Credentials;
+use once_cell::sync::Lazy;
+
+use crate::configure::get_jobs;
+use crate::models::notifications::{EmailNotification, SmtpSecurity};
+use crate::models::run_outcome::RunOutcome;
+use crate::utils::datetime_util::get_local_datetime_in_timezone;
+
+/// The failed runs waiting to be sent in the next digest email.
+static PENDING_FAILURES: Lazy<Mutex<Vec<RunOutcome>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Emails the failure of a job run.
+///
+/// Without a `batch_window`, the email is sent immediately. Otherwise the failure is queued and
+/// the first failure of a window schedules a digest email containing every failure queued until
+/// the window elapses.
+///
+/// # Arguments
+///
+/// * `email` - The email configuration.
+/// * `outcome` - The outcome of the failed run.
+///
+/// # Errors
+///
+/// Returns an error if the email cannot be built or sent.
+///
+/// # Examples
+///
+/// ```
+/// send_email_failure(&email, &outcome).await?;
+/// ```
+pub async fn send_email_failure(email: &EmailNotification, outcome: &RunOutcome) -> Result<(), Box<dyn Error>> {
+    let batch_window = match email.batch_window {
+        Some(window) => window,
+        None => return send_email(email, std::slice::from_ref(outcome)).await
+    };
+
+    let first_of_window = {
+        let mut pending = PENDING_FAILURES.lock().unwrap();
+        pending.push(outcome.clone());
+        pending.len() == 1
+    };
+
+    if first_of_window {
+        let email = email.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(batch_window).await;
+
+            let failures = std::mem::take(&mut *PENDING_FAILURES.lock().unwrap());
+            if let Err(err) = send_email(&email, &failures).await {
+                let jobs = get_jobs();
+                let local_time = get_local_datetime_in_timezone(&jobs.timezone);
+                println!("{} Failed to send email digest of {} failure(s), error: {}", local_time, failures.len(), err);
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// Sends one email summarizing the given failed runs.
+///
+/// # Arguments
+///
+/// * `email` - The email configuration.
+/// * `failures` - The outcomes of the failed runs.
+///
+/// # Errors
+///
+/// Returns an error if an address is invalid or the SMTP server rejects the email.
+async fn send_email(email: &EmailNotification, failures: &[RunOutcome]) -> Result<(), Box<dyn Error>> {
+    let subject = match failures {
+        [outcome] => format!("[rjob] Job {} failed", outcome.job_name),
+        _ => format!("[rjob] {} job runs failed", failures.len())
+    };
+
+    let mut body = String::new();
+    for outcome in failures {
+        body.push_str(&format!(
+            "Job: {}\nRun id: {}\nAttempts: {}\nStatus: {}\nError: {}\n\n",
+            outcome.job_name,
+            outcome.run_id,
+            outcome.attempts,
+            outcome.status.map(|s| s.to_string()).unwrap_or("None".to_string()),
+            outcome.error.as_deref().unwrap_or("None")
+        ));
+    }
+
+    let mut builder = Message::builder()
+        .from(email.from.parse()?)
+        .subject(subject);
+    for to in &email.to {
+        builder = builder.to(to.parse()?);
+    }
+    let message = builder.body(body)?;
+
+    let transport = get_transport(email)?;
+    transport.send(message).await?;
+    Ok(())
+}
+
+/// Builds the SMTP transport described by the email configuration.
+///
+/// # Errors
+///
+/// Returns an error if the TLS parameters of the host cannot be built.
+fn get_transport(email: &EmailNotification) -> Result<AsyncSmtpTransport<Tokio1Executor>, Box<dyn Error>> {
+    let mut builder = match email.security {
+        SmtpSecurity::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&email.host)?,
+        SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&email.host)?,
+        SmtpSecurity::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&email.host),
+    };
+
+    if let Some(port) = email.port {
+        builder = builder.port(port);
+    }
+
+    if let (Some(username), Some(password)) = (&email.username, &email.password) {
+        builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+    }
+
+    Ok(builder.build())
+}
\ No newline at end of file
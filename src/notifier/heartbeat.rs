@@ -0,0 +1,45 @@
+use std::error::Error;
+
+use crate::models::heartbeat::Heartbeat;
+use crate::models::run_outcome::RunOutcome;
+use crate::notifier::get_notifier_client;
+
+/// Pings the heartbeat monitor of a job with the result of a run.
+///
+/// # Arguments
+///
+/// * `heartbeat` - The heartbeat configuration of the job.
+/// * `outcome` - The outcome of the run.
+///
+/// # Returns
+///
+/// The pinged URL, or `None` if the run result is not reported.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the monitor responds with a non-2xx status.
+///
+/// # Examples
+///
+/// ```
+/// if let Some(url) = send_heartbeat(&heartbeat, &outcome).await? {
+///     println!("Pinged {}", url);
+/// }
+/// ```
+pub async fn send_heartbeat(heartbeat: &Heartbeat, outcome: &RunOutcome) -> Result<Option<String>, Box<dyn Error>> {
+    let url = match heartbeat.ping_url(outcome.success) {
+        Some(url) => url,
+        None => return Ok(None)
+    };
+
+    let resp = get_notifier_client()?
+        .get(&url)
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Err(format!("The heartbeat monitor responded with http status {}", resp.status().as_u16()).into());
+    }
+
+    Ok(Some(url))
+}
\ No newline at end of file
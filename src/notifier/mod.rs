@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+use reqwest::Client;
+
+use crate::configure::get_jobs;
+use crate::models::http_job_request::HttpVersion;
+use crate::models::notifications::JobNotifications;
+use crate::models::run_outcome::RunOutcome;
+use crate::notifier::email::send_email_failure;
+use crate::notifier::slack::send_slack_message;
+use crate::notifier::telegram::send_telegram_message;
+use crate::notifier::webhook::send_webhook_event;
+use crate::scheduler::http_client::{ClientOptions, get_client};
+use crate::utils::datetime_util::get_local_datetime_in_timezone;
+
+pub mod email;
+pub mod heartbeat;
+pub mod report;
+pub mod slack;
+pub mod telegram;
+pub mod webhook;
+
+/// The timeout of the requests sent to notification channels, in milliseconds.
+const NOTIFIER_TIMEOUT: u64 = 10000;
+
+/// The names of the jobs whose last run failed.
+///
+/// A job is added when a run fails and removed when a run succeeds, which is when a recovery
+/// notification is sent.
+static FAILING_JOBS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// The kind of event a notification is sent for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationEvent {
+    /// The job failed after all retries.
+    Failure,
+    /// The job succeeded after one or more failed runs.
+    Recovery,
+    /// The job succeeded, sent only by an `on_success` hook.
+    Success,
+    /// The job has not run successfully within its `alert_if_no_success_for` window.
+    Stale,
+}
+
+/// Notifies the configured channels about the outcome of a job run.
+///
+/// A failed run triggers a failure notification. A successful run following a failed one
+/// triggers a recovery notification. Errors of individual channels are logged and don't
+/// prevent the other channels from being notified.
+///
+/// # Arguments
+///
+/// * `job_notifications` - The notification settings of the job.
+/// * `outcome` - The outcome of the run.
+///
+/// # Examples
+///
+/// ```
+/// notify_outcome(&job.options().notifications, &outcome).await;
+/// ```
+pub async fn notify_outcome(job_notifications: &JobNotifications, outcome: &RunOutcome) {
+    let event = {
+        let mut failing_jobs = FAILING_JOBS.lock().unwrap();
+        if !outcome.success {
+            failing_jobs.insert(outcome.job_name.clone());
+            NotificationEvent::Failure
+        } else if failing_jobs.remove(&outcome.job_name) {
+            NotificationEvent::Recovery
+        } else {
+            return;
+        }
+    };
+
+    send_notifications(job_notifications, event, outcome).await;
+}
+
+/// Sends a notification about a job run to the configured channels.
+///
+/// The webhook is notified about failures, successes and stale jobs, email is only sent for
+/// failures and stale jobs.
+/// Errors of individual channels are logged and don't prevent the other channels from being
+/// notified.
+///
+/// # Arguments
+///
+/// * `job_notifications` - The notification settings of the job.
+/// * `event` - The kind of event to notify about.
+/// * `outcome` - The outcome of the run.
+///
+/// # Examples
+///
+/// ```
+/// send_notifications(&job.options().notifications, NotificationEvent::Success, &outcome).await;
+/// ```
+pub async fn send_notifications(job_notifications: &JobNotifications, event: NotificationEvent, outcome: &RunOutcome) {
+    let jobs = get_jobs();
+    let notifications = &jobs.notifications;
+    let local_time = get_local_datetime_in_timezone(&jobs.timezone);
+
+    if let Some(webhook) = &notifications.webhook {
+        if event != NotificationEvent::Recovery {
+            if let Err(err) = send_webhook_event(&webhook.url, webhook.headers.as_ref(), event, outcome).await {
+                println!("{} {} Failed to send webhook notification, job name: {}, error: {}", outcome.run_id, local_time, outcome.job_name, err);
+            }
+        }
+    }
+
+    if let Some(slack) = &notifications.slack {
+        if let Err(err) = send_slack_message(slack, job_notifications, event, outcome).await {
+            println!("{} {} Failed to send Slack notification, job name: {}, error: {}", outcome.run_id, local_time, outcome.job_name, err);
+        }
+    }
+
+    if let Some(email) = &notifications.email {
+        if matches!(event, NotificationEvent::Failure | NotificationEvent::Stale) {
+            if let Err(err) = send_email_failure(email, outcome).await {
+                println!("{} {} Failed to send email notification, job name: {}, error: {}", outcome.run_id, local_time, outcome.job_name, err);
+            }
+        }
+    }
+
+    if let Some(telegram) = &notifications.telegram {
+        if let Err(err) = send_telegram_message(telegram, event, outcome).await {
+            println!("{} {} Failed to send Telegram notification, job name: {}, error: {}", outcome.run_id, local_time, outcome.job_name, err);
+        }
+    }
+}
+
+/// Returns the shared HTTP client used to send notifications.
+///
+/// # Errors
+///
+/// Returns an error if the client cannot be built.
+pub fn get_notifier_client() -> Result<Client, reqwest::Error> {
+    get_client(&ClientOptions::new(NOTIFIER_TIMEOUT, None, HttpVersion::Auto, get_jobs().user_agent.clone()))
+}
\ No newline at end of file
@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde::Serialize;
+
+use crate::models::http_job::HttpJob;
+use crate::models::run_outcome::RunOutcome;
+use crate::notifier::get_notifier_client;
+use crate::scheduler::cron_scheduler::ReceivedResponse;
+use crate::utils::redact_util::redact_secrets;
+
+/// The JSON result document posted to the `report_to` URL of a job.
+#[derive(Debug, Serialize)]
+struct RunReport<'a> {
+    job_name: &'a str,
+    run_id: &'a str,
+    success: bool,
+    status: Option<u16>,
+    attempts: u64,
+    error: Option<&'a str>,
+    started_at: String,
+    duration_ms: u64,
+    extracted: BTreeMap<&'a str, String>,
+    body: Option<String>,
+    body_truncated: bool,
+}
+
+/// Posts the result document of a run of an HTTP job to its `report_to` URL.
+///
+/// The document holds the outcome of the run, its start time and duration, the values extracted
+/// by the steps of its request pipeline, and the body of the final response, truncated to
+/// `log_response_bytes`. The body is `null` when the run received no successful response, or
+/// with `discard_body`. Registered secrets are redacted from the body and the extracted values.
+///
+/// # Arguments
+///
+/// * `http_job` - The HTTP job.
+/// * `url` - The `report_to` URL of the job.
+/// * `outcome` - The outcome of the run.
+/// * `response` - The final response of the run, if it succeeded.
+/// * `started_at` - The start time of the run.
+/// * `duration` - The duration of the run.
+/// * `timezone` - The configured timezone, used to format the start time.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the server responds with a non-2xx status.
+///
+/// # Examples
+///
+/// ```
+/// if let Some(url) = &http_job.report_to {
+///     send_run_report(http_job, url, &outcome, response.as_ref(), started_at, duration, timezone).await?;
+/// }
+/// ```
+pub async fn send_run_report(
+    http_job: &HttpJob,
+    url: &str,
+    outcome: &RunOutcome,
+    response: Option<&ReceivedResponse>,
+    started_at: DateTime<Utc>,
+    duration: Duration,
+    timezone: &Tz,
+) -> Result<(), Box<dyn Error>> {
+    let max_bytes = http_job.response.log_response_bytes;
+    let (body, body_truncated) = match response.filter(|_| !http_job.response.discard_body) {
+        Some(response) => {
+            let body = &response.body[..response.body.len().min(max_bytes)];
+            // A multi-byte character cut at the end of the body is rendered as U+FFFD.
+            (Some(redact_secrets(&String::from_utf8_lossy(body))), response.truncated || response.body.len() > max_bytes)
+        }
+        None => (None, false)
+    };
+    let extracted = response.map(|response| {
+        response.extracted.iter().map(|(name, value)| (name.as_str(), redact_secrets(value))).collect()
+    });
+
+    let report = RunReport {
+        job_name: &outcome.job_name,
+        run_id: &outcome.run_id,
+        success: outcome.success,
+        status: outcome.status,
+        attempts: outcome.attempts,
+        error: outcome.error.as_deref(),
+        started_at: started_at.with_timezone(timezone).to_rfc3339(),
+        duration_ms: duration.as_millis() as u64,
+        extracted: extracted.unwrap_or_default(),
+        body,
+        body_truncated,
+    };
+
+    let resp = get_notifier_client()?
+        .post(url)
+        .json(&report)
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Err(format!("The report URL responded with http status {}", resp.status().as_u16()).into());
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,86 @@
+use std::error::Error;
+use serde::Serialize;
+
+use crate::models::notifications::{JobNotifications, SlackNotification};
+use crate::models::run_outcome::RunOutcome;
+use crate::notifier::{get_notifier_client, NotificationEvent};
+
+/// The JSON payload posted to the Slack incoming webhook.
+#[derive(Debug, Serialize)]
+struct SlackPayload<'a> {
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<&'a str>,
+}
+
+/// Sends a failure, recovery, success or stale message of a job to Slack.
+///
+/// The channel configured on the job takes precedence over the channel of the `slack` block.
+///
+/// # Arguments
+///
+/// * `slack` - The Slack configuration.
+/// * `job_notifications` - The notification settings of the job.
+/// * `event` - Whether the job failed, recovered, succeeded or is stale.
+/// * `outcome` - The outcome of the run.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or Slack responds with a non-2xx status.
+///
+/// # Examples
+///
+/// ```
+/// send_slack_message(&slack, &job.options().notifications, NotificationEvent::Failure, &outcome).await?;
+/// ```
+pub async fn send_slack_message(slack: &SlackNotification, job_notifications: &JobNotifications, event: NotificationEvent, outcome: &RunOutcome) -> Result<(), Box<dyn Error>> {
+    let text = match event {
+        NotificationEvent::Failure => format!(
+            ":red_circle: Job *{}* failed after {} attempt(s).\nRun id: `{}`\nStatus: {}\nError: {}",
+            outcome.job_name,
+            outcome.attempts,
+            outcome.run_id,
+            outcome.status.map(|s| s.to_string()).unwrap_or("None".to_string()),
+            outcome.error.as_deref().unwrap_or("None")
+        ),
+        NotificationEvent::Recovery => format!(
+            ":large_green_circle: Job *{}* recovered.\nRun id: `{}`",
+            outcome.job_name,
+            outcome.run_id
+        ),
+        NotificationEvent::Success => format!(
+            ":white_check_mark: Job *{}* succeeded after {} attempt(s).\nRun id: `{}`",
+            outcome.job_name,
+            outcome.attempts,
+            outcome.run_id
+        ),
+        NotificationEvent::Stale => format!(
+            ":warning: Job *{}* is stale.\n{}",
+            outcome.job_name,
+            outcome.error.as_deref().unwrap_or("None")
+        ),
+    };
+
+    let channel = job_notifications.slack_channel.as_deref()
+        .or(slack.channel.as_deref());
+
+    let payload = SlackPayload {
+        text,
+        channel,
+        username: slack.username.as_deref(),
+    };
+
+    let resp = get_notifier_client()?
+        .post(&slack.webhook_url)
+        .json(&payload)
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Err(format!("Slack responded with http status {}", resp.status().as_u16()).into());
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+use std::error::Error;
+use serde::Serialize;
+
+use crate::models::notifications::TelegramNotification;
+use crate::models::run_outcome::RunOutcome;
+use crate::notifier::{get_notifier_client, NotificationEvent};
+
+/// The JSON payload of the Telegram Bot API `sendMessage` method.
+#[derive(Debug, Serialize)]
+struct TelegramPayload<'a> {
+    chat_id: &'a str,
+    text: String,
+}
+
+/// Sends a failure, recovery, success or stale message of a job to the Telegram chat.
+///
+/// # Arguments
+///
+/// * `telegram` - The Telegram configuration.
+/// * `event` - Whether the job failed, recovered, succeeded or is stale.
+/// * `outcome` - The outcome of the run.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the Bot API responds with a non-2xx status.
+///
+/// # Examples
+///
+/// ```
+/// send_telegram_message(&telegram, NotificationEvent::Failure, &outcome).await?;
+/// ```
+pub async fn send_telegram_message(telegram: &TelegramNotification, event: NotificationEvent, outcome: &RunOutcome) -> Result<(), Box<dyn Error>> {
+    let text = match event {
+        NotificationEvent::Failure => format!(
+            "❌ Job {} failed after {} attempt(s).\nRun id: {}\nStatus: {}\nError: {}",
+            outcome.job_name,
+            outcome.attempts,
+            outcome.run_id,
+            outcome.status.map(|s| s.to_string()).unwrap_or("None".to_string()),
+            outcome.error.as_deref().unwrap_or("None")
+        ),
+        NotificationEvent::Recovery => format!(
+            "✅ Job {} recovered.\nRun id: {}",
+            outcome.job_name,
+            outcome.run_id
+        ),
+        NotificationEvent::Success => format!(
+            "✅ Job {} succeeded after {} attempt(s).\nRun id: {}",
+            outcome.job_name,
+            outcome.attempts,
+            outcome.run_id
+        ),
+        NotificationEvent::Stale => format!(
+            "⚠️ Job {} is stale.\n{}",
+            outcome.job_name,
+            outcome.error.as_deref().unwrap_or("None")
+        ),
+    };
+
+    let payload = TelegramPayload {
+        chat_id: &telegram.chat_id,
+        text,
+    };
+
+    let url = format!("https://api.telegram.org/bot{}/sendMessage", telegram.bot_token);
+    let resp = get_notifier_client()?
+        .post(url)
+        .json(&payload)
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Err(format!("Telegram responded with http status {}", resp.status().as_u16()).into());
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,68 @@
+use std::error::Error;
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+
+use crate::models::run_outcome::RunOutcome;
+use crate::notifier::{get_notifier_client, NotificationEvent};
+
+/// The JSON payload posted to the webhook.
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    event: &'a str,
+    job_name: &'a str,
+    run_id: &'a str,
+    error: Option<&'a str>,
+    status: Option<u16>,
+    attempts: u64,
+}
+
+/// Posts an event of a job run to a webhook.
+///
+/// The same payload is posted by the global `webhook` notification channel and by the
+/// `webhook` hook action, with the `event` field set to `failure`, `recovery`, `success` or `stale`.
+///
+/// # Arguments
+///
+/// * `url` - The URL of the webhook.
+/// * `headers` - The extra headers to send, if any.
+/// * `event` - The kind of event to post.
+/// * `outcome` - The outcome of the run.
+///
+/// # Errors
+///
+/// Returns an error if the request fails or the webhook responds with a non-2xx status.
+///
+/// # Examples
+///
+/// ```
+/// send_webhook_event(&webhook.url, webhook.headers.as_ref(), NotificationEvent::Failure, &outcome).await?;
+/// ```
+pub async fn send_webhook_event(url: &str, headers: Option<&HeaderMap>, event: NotificationEvent, outcome: &RunOutcome) -> Result<(), Box<dyn Error>> {
+    let event = match event {
+        NotificationEvent::Failure => "failure",
+        NotificationEvent::Recovery => "recovery",
+        NotificationEvent::Success => "success",
+        NotificationEvent::Stale => "stale",
+    };
+    let payload = WebhookPayload {
+        event,
+        job_name: &outcome.job_name,
+        run_id: &outcome.run_id,
+        error: outcome.error.as_deref(),
+        status: outcome.status,
+        attempts: outcome.attempts,
+    };
+
+    let resp = get_notifier_client()?
+        .post(url)
+        .headers(headers.cloned().unwrap_or_default())
+        .json(&payload)
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        return Err(format!("The webhook responded with http status {}", resp.status().as_u16()).into());
+    }
+
+    Ok(())
+}
\ No newline at end of file
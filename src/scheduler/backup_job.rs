@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use glob::Pattern;
+
+use crate::configure::get_jobs;
+use crate::models::backup_job::BackupJob;
+use crate::models::job::{Job, JobFuture};
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+use crate::models::run_outcome::RunOutcome;
+use crate::utils::template_util::{get_run_vars, render_template};
+
+impl Job for BackupJob {
+    fn kind(&self) -> &'static str {
+        "Backup"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn enable(&self) -> bool {
+        self.enable
+    }
+
+    fn schedule(&self) -> &JobSchedule {
+        &self.schedule
+    }
+
+    fn options(&self) -> &JobOptions {
+        &self.options
+    }
+
+    fn execute(self: Arc<Self>, uuid: String, local_time: String) -> JobFuture<RunOutcome> {
+        Box::pin(async move { execute_backup_job(self, &uuid, &local_time).await })
+    }
+}
+
+/// Writes the archive of a backup job, then removes its old archives, and returns the outcome.
+///
+/// The archive is written on the blocking thread pool. Old archives are only removed after the
+/// new one was written successfully.
+///
+/// # Arguments
+///
+/// * `backup_job` - The backup job to run.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+///
+/// # Examples
+///
+/// ```
+/// let outcome = execute_backup_job(Arc::new(backup_job), &uuid, &local_time).await;
+/// println!("Success: {}", outcome.success);
+/// ```
+pub async fn execute_backup_job(backup_job: Arc<BackupJob>, uuid: &str, local_time: &str) -> RunOutcome {
+    let vars = get_run_vars(&backup_job.name, uuid, &get_jobs().timezone);
+    let archive = Path::new(&backup_job.target_dir).join(render_template(&backup_job.file_name, &vars));
+
+    let started = Instant::now();
+    let (job, path) = (backup_job.clone(), archive.clone());
+    let result = tokio::task::spawn_blocking(move || create_archive(&job.source, &path))
+        .await
+        .unwrap_or_else(|e| Err(io::Error::other(e.to_string())));
+
+    match result {
+        Ok(bytes) => {
+            println!("{} {} Backup success, job name: {}, archive: {}, bytes: {}, elapsed: {}ms",
+                     uuid, local_time, &backup_job.name, archive.display(), bytes, started.elapsed().as_millis());
+
+            if let Some(keep) = backup_job.keep {
+                for removed in remove_old_archives(&backup_job, keep) {
+                    match removed {
+                        Ok(path) => println!("{} {} Backup archive removed, job name: {}, archive: {}", uuid, local_time, &backup_job.name, path),
+                        Err(err) => println!("{} {} Failed to remove backup archive, job name: {}, error: {}", uuid, local_time, &backup_job.name, err),
+                    }
+                }
+            }
+
+            RunOutcome::new(uuid.to_string(), backup_job.name.clone(), true, None, 1, None)
+        }
+        Err(err) => {
+            let error = format!("An error occurred while archiving '{}' to '{}': {}", backup_job.source, archive.display(), err);
+            println!("{} {} Backup failed, job name: {}, error: {}", uuid, local_time, &backup_job.name, error);
+            RunOutcome::new(uuid.to_string(), backup_job.name.clone(), false, None, 1, Some(error))
+        }
+    }
+}
+
+/// Writes a directory into a `.tar.gz` archive.
+///
+/// The entries are stored under the name of the directory. Symbolic links are stored as links.
+/// The archive is written to a `.part` file first and renamed when complete.
+///
+/// # Returns
+///
+/// The size of the archive in bytes.
+fn create_archive(source: &str, archive: &Path) -> io::Result<u64> {
+    let source = Path::new(source);
+    let root = source.file_name()
+        .map(Path::new)
+        .unwrap_or(Path::new("."));
+
+    if let Some(parent) = archive.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut part = archive.as_os_str().to_owned();
+    part.push(".part");
+
+    let encoder = GzEncoder::new(File::create(&part)?, Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    builder.follow_symlinks(false);
+    builder.append_dir_all(root, source)?;
+    builder.into_inner()?.finish()?;
+
+    fs::rename(&part, archive)?;
+    Ok(fs::metadata(archive)?.len())
+}
+
+/// Removes the archives of a job beyond the newest `keep`.
+///
+/// The archives of a job are the files of its target directory matching its `file_name`
+/// template, with the run variables other than `{{job_name}}` matching anything.
+///
+/// # Returns
+///
+/// The result of each removal, with the path of the removed archive.
+fn remove_old_archives(backup_job: &BackupJob, keep: usize) -> Vec<Result<String, String>> {
+    let mut vars = HashMap::new();
+    vars.insert("job_name", Pattern::escape(&backup_job.name));
+    for name in ["run_id", "now", "date"] {
+        vars.insert(name, "*".to_string());
+    }
+    let pattern = match Pattern::new(&render_template(&Pattern::escape(&backup_job.file_name), &vars)) {
+        Ok(pattern) => pattern,
+        Err(err) => return vec![Err(err.to_string())]
+    };
+
+    let entries = match fs::read_dir(&backup_job.target_dir) {
+        Ok(entries) => entries,
+        Err(err) => return vec![Err(err.to_string())]
+    };
+
+    let mut archives: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .filter(|entry| entry.file_name().to_str().is_some_and(|name| pattern.matches(name)))
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .collect();
+
+    // Newest first, by name for equal times since timestamped names sort chronologically.
+    archives.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.cmp(&a.1)));
+
+    archives.into_iter()
+        .skip(keep)
+        .map(|(_, path)| fs::remove_file(&path)
+            .map(|_| path.display().to_string())
+            .map_err(|e| format!("{}: {}", path.display(), e)))
+        .collect()
+}
\ No newline at end of file
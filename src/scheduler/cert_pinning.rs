@@ -0,0 +1,213 @@
+use std::sync::Arc;
+use std::time::SystemTime;
+use ring::digest::{digest, SHA256};
+use rustls::client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier};
+use rustls::{Certificate, ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName, SupportedProtocolVersion};
+
+use crate::models::http_job_request::HttpVersion;
+use crate::models::tls::{TlsOptions, TlsVersion};
+
+/// The TLS versions the client supports, with the protocol version of rustls implementing them.
+const VERSIONS: [(TlsVersion, &SupportedProtocolVersion); 2] = [
+    (TlsVersion::Tls1_2, &rustls::version::TLS12),
+    (TlsVersion::Tls1_3, &rustls::version::TLS13),
+];
+
+/// Verifies the certificate chain of a server like the default verifier of the HTTP client,
+/// then checks that the public key of the server certificate is pinned.
+struct PinnedKeyVerifier {
+    inner: WebPkiVerifier,
+    /// The SHA-256 hashes of the pinned `SubjectPublicKeyInfo` structures.
+    pins: Vec<[u8; 32]>,
+}
+
+impl ServerCertVerifier for PinnedKeyVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified = self.inner.verify_server_cert(end_entity, intermediates, server_name, scts, ocsp_response, now)?;
+
+        // The intermediates are the certificates the server sent, not the verified path, so
+        // any server could send the pinned certificate along with its own.
+        let pinned = get_spki_sha256(&end_entity.0).is_some_and(|hash| self.pins.contains(&hash));
+        if !pinned {
+            return Err(rustls::Error::General("The public key of the server certificate doesn't match 'pinned_spki_sha256'".to_string()));
+        }
+
+        Ok(verified)
+    }
+}
+
+/// Builds the TLS configuration of a client whose connections are pinned to the
+/// `pinned_spki_sha256` of the TLS settings of a job.
+///
+/// The server certificate is first verified against the built-in root certificates, as by the
+/// default configuration of the client. The connection is then only accepted if the SHA-256
+/// hash of the public key of the server certificate is pinned. As the configuration replaces the one the client would build, it
+/// applies the TLS versions and the ALPN protocols of the job itself.
+///
+/// # Arguments
+///
+/// * `tls` - The TLS settings of the job, with at least one pin.
+/// * `http_version` - The HTTP version of the request, selecting the ALPN protocols.
+///
+/// # Examples
+///
+/// ```
+/// let builder = Client::builder().use_preconfigured_tls(build_pinned_tls_config(&options.tls, options.http_version));
+/// ```
+pub fn build_pinned_tls_config(tls: &TlsOptions, http_version: HttpVersion) -> ClientConfig {
+    let mut roots = RootCertStore::empty();
+    roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|trust_anchor| {
+        OwnedTrustAnchor::from_subject_spki_name_constraints(trust_anchor.subject, trust_anchor.spki, trust_anchor.name_constraints)
+    }));
+
+    let versions: Vec<&SupportedProtocolVersion> = VERSIONS.iter()
+        .filter(|(version, _)| tls.min_version.is_none_or(|min| *version >= min))
+        .filter(|(version, _)| tls.max_version.is_none_or(|max| *version <= max))
+        .map(|(_, version)| *version)
+        .collect();
+
+    let verifier = PinnedKeyVerifier {
+        inner: WebPkiVerifier::new(roots, None),
+        pins: tls.pinned_spki_sha256.clone(),
+    };
+    let mut config = ClientConfig::builder()
+        .with_safe_default_cipher_suites()
+        .with_safe_default_kx_groups()
+        .with_protocol_versions(&versions)
+        .expect("The TLS versions of a job are validated when the job is parsed.")
+        .with_custom_certificate_verifier(Arc::new(verifier))
+        .with_no_client_auth();
+
+    config.alpn_protocols = match http_version {
+        HttpVersion::Http1 => vec![b"http/1.1".to_vec()],
+        HttpVersion::Http2PriorKnowledge => vec![b"h2".to_vec()],
+        HttpVersion::Auto | HttpVersion::Http2 => vec![b"h2".to_vec(), b"http/1.1".to_vec()],
+    };
+    config
+}
+
+/// Returns the SHA-256 hash of the `SubjectPublicKeyInfo` of a DER encoded certificate, the
+/// value of `pinned_spki_sha256`.
+///
+/// Returns `None` if the certificate cannot be parsed.
+fn get_spki_sha256(cert: &[u8]) -> Option<[u8; 32]> {
+    // Certificate ::= SEQUENCE { tbsCertificate, signatureAlgorithm, signatureValue }
+    let (certificate, _) = read_der(cert)?;
+    let (tbs_certificate, _) = read_der(certificate.content)?;
+
+    // TBSCertificate ::= SEQUENCE { [0] version OPTIONAL, serialNumber, signature, issuer,
+    // validity, subject, subjectPublicKeyInfo, ... }
+    let (mut field, mut rest) = read_der(tbs_certificate.content)?;
+    if field.tag == 0xa0 {
+        (field, rest) = read_der(rest)?;
+    }
+    for _ in 0..5 {
+        (field, rest) = read_der(rest)?;
+    }
+
+    let mut hash = [0; 32];
+    hash.copy_from_slice(digest(&SHA256, field.encoded).as_ref());
+    Some(hash)
+}
+
+/// A DER encoded value.
+struct DerValue<'a> {
+    tag: u8,
+    /// The value, without its tag and length.
+    content: &'a [u8],
+    /// The whole encoding of the value, with its tag and length.
+    encoded: &'a [u8],
+}
+
+/// Reads the DER encoded value at the start of `input`, with a single-byte tag.
+///
+/// Returns the value and the bytes following it, or `None` if the encoding is invalid.
+fn read_der(input: &[u8]) -> Option<(DerValue<'_>, &[u8])> {
+    let tag = *input.first()?;
+    let first = *input.get(1)? as usize;
+
+    let (length, header) = if first < 0x80 {
+        (first, 2)
+    } else {
+        let octets = first & 0x7f;
+        if octets == 0 || octets > 4 {
+            return None;
+        }
+        let length = input.get(2..2 + octets)?.iter().fold(0, |length, byte| (length << 8) | *byte as usize);
+        (length, 2 + octets)
+    };
+
+    let end = header.checked_add(length)?;
+    let encoded = input.get(..end)?;
+    Some((DerValue { tag, content: &encoded[header..], encoded }, &input[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair};
+
+    use super::*;
+
+    /// A certificate authority and two server certificates for `example.com` it issued.
+    struct TestChain {
+        ca: Certificate,
+        pinned: Certificate,
+        other: Certificate,
+    }
+
+    fn build_chain() -> TestChain {
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+
+        let issue = || {
+            let key = KeyPair::generate().unwrap();
+            let params = CertificateParams::new(vec!["example.com".to_string()]).unwrap();
+            Certificate(params.signed_by(&key, &ca, &ca_key).unwrap().der().to_vec())
+        };
+        let pinned = issue();
+        let other = issue();
+
+        TestChain { ca: Certificate(ca.der().to_vec()), pinned, other }
+    }
+
+    fn verify(chain: &TestChain, end_entity: &Certificate, intermediates: &[Certificate]) -> Result<ServerCertVerified, rustls::Error> {
+        let mut roots = RootCertStore::empty();
+        roots.add(&chain.ca).unwrap();
+        let verifier = PinnedKeyVerifier {
+            inner: WebPkiVerifier::new(roots, None),
+            pins: vec![get_spki_sha256(&chain.pinned.0).unwrap()],
+        };
+
+        let server_name = ServerName::try_from("example.com").unwrap();
+        verifier.verify_server_cert(end_entity, intermediates, &server_name, &mut std::iter::empty(), &[], SystemTime::now())
+    }
+
+    #[test]
+    fn accepts_pinned_server_certificate() {
+        let chain = build_chain();
+        assert!(verify(&chain, &chain.pinned, &[]).is_ok());
+    }
+
+    #[test]
+    fn rejects_server_certificate_not_pinned() {
+        let chain = build_chain();
+        assert!(verify(&chain, &chain.other, &[]).is_err());
+    }
+
+    #[test]
+    fn rejects_pinned_certificate_sent_as_intermediate() {
+        let chain = build_chain();
+        let intermediates = [chain.pinned.clone()];
+        assert!(verify(&chain, &chain.other, &intermediates).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,187 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use glob::Pattern;
+
+use crate::models::cleanup_job::{CleanupAction, CleanupJob};
+use crate::models::job::{Job, JobFuture};
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+use crate::models::run_outcome::RunOutcome;
+
+/// The number of seconds in a day.
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// The totals of a cleanup run.
+#[derive(Debug, Default)]
+struct CleanupSummary {
+    matched: usize,
+    removed: usize,
+    bytes: u64,
+    errors: Vec<String>,
+}
+
+impl Job for CleanupJob {
+    fn kind(&self) -> &'static str {
+        "Cleanup"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn enable(&self) -> bool {
+        self.enable
+    }
+
+    fn schedule(&self) -> &JobSchedule {
+        &self.schedule
+    }
+
+    fn options(&self) -> &JobOptions {
+        &self.options
+    }
+
+    fn execute(self: Arc<Self>, uuid: String, local_time: String) -> JobFuture<RunOutcome> {
+        Box::pin(async move {
+            let summary = run_cleanup(self.clone(), false, &uuid, &local_time).await;
+            let success = summary.errors.is_empty();
+            let error = (!success).then(|| summary.errors.join("; "));
+            RunOutcome::new(uuid, self.name.clone(), success, None, 1, error)
+        })
+    }
+
+    fn dry_run(self: Arc<Self>, uuid: String, local_time: String) -> JobFuture<()> {
+        Box::pin(async move {
+            run_cleanup(self, true, &uuid, &local_time).await;
+        })
+    }
+}
+
+/// Runs a cleanup job on the blocking thread pool and logs a summary of the removed files.
+///
+/// In dry-run mode, either from `--dry-run` or the job configuration, the matching files are only
+/// logged.
+///
+/// # Arguments
+///
+/// * `cleanup_job` - The cleanup job to run.
+/// * `dry_run` - Whether to only log the matching files instead of removing them.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+async fn run_cleanup(cleanup_job: Arc<CleanupJob>, dry_run: bool, uuid: &str, local_time: &str) -> CleanupSummary {
+    let (job, run_id, time) = (cleanup_job.clone(), uuid.to_string(), local_time.to_string());
+    let summary = tokio::task::spawn_blocking(move || clean(&job, dry_run, &run_id, &time))
+        .await
+        .unwrap_or_else(|e| CleanupSummary { errors: vec![e.to_string()], ..Default::default() });
+
+    println!("{} {} Cleanup {}, job name: {}, matched: {}, {}: {}, bytes: {}, errors: {}",
+             uuid, local_time, if dry_run { "dry run" } else { "summary" }, &cleanup_job.name,
+             summary.matched, if dry_run { "would remove" } else { "removed" }, summary.removed, summary.bytes, summary.errors.len());
+
+    summary
+}
+
+/// Finds the files of a job that are due for cleanup and applies its action to them.
+fn clean(cleanup_job: &CleanupJob, dry_run: bool, uuid: &str, local_time: &str) -> CleanupSummary {
+    let mut summary = CleanupSummary::default();
+    let directory = Path::new(&cleanup_job.directory);
+
+    // Never descend into the archive directory, or archived files would be archived again.
+    let skip_dir = match &cleanup_job.action {
+        CleanupAction::Archive(dir) => fs::canonicalize(dir).ok(),
+        CleanupAction::Delete => None
+    };
+
+    let mut files = Vec::new();
+    if let Err(err) = collect_files(directory, cleanup_job.recursive, skip_dir.as_deref(), &mut files) {
+        summary.errors.push(format!("An error occurred while reading the directory '{}': {}", cleanup_job.directory, err));
+        return summary;
+    }
+
+    let pattern = Pattern::new(&cleanup_job.pattern).unwrap_or_default();
+    let cutoff = SystemTime::now() - Duration::from_secs(cleanup_job.older_than_days * SECONDS_PER_DAY);
+
+    for path in files {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        if !pattern.matches(name) {
+            continue;
+        }
+
+        let metadata = match fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                summary.errors.push(format!("{}: {}", path.display(), err));
+                continue;
+            }
+        };
+        if metadata.modified().map_or(true, |modified| modified > cutoff) {
+            continue;
+        }
+        summary.matched += 1;
+
+        if dry_run {
+            println!("{} {} Cleanup dry run, job name: {}, would {}: {}", uuid, local_time, &cleanup_job.name, cleanup_job.action, path.display());
+            summary.removed += 1;
+            summary.bytes += metadata.len();
+            continue;
+        }
+
+        match apply_action(&cleanup_job.action, directory, &path) {
+            Ok(_) => {
+                println!("{} {} Cleanup {}, job name: {}, file: {}", uuid, local_time, cleanup_job.action, &cleanup_job.name, path.display());
+                summary.removed += 1;
+                summary.bytes += metadata.len();
+            }
+            Err(err) => {
+                println!("{} {} Cleanup failed, job name: {}, file: {}, error: {}", uuid, local_time, &cleanup_job.name, path.display(), err);
+                summary.errors.push(format!("{}: {}", path.display(), err));
+            }
+        }
+    }
+
+    summary
+}
+
+/// Collects the regular files of a directory, and of its subdirectories if `recursive` is set.
+///
+/// Symbolic links are not followed.
+fn collect_files(dir: &Path, recursive: bool, skip_dir: Option<&Path>, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+
+        if file_type.is_file() {
+            files.push(path);
+        } else if file_type.is_dir() && recursive {
+            if skip_dir.is_some() && fs::canonicalize(&path).ok().as_deref() == skip_dir {
+                continue;
+            }
+            collect_files(&path, recursive, skip_dir, files)?;
+        }
+    }
+    Ok(())
+}
+
+/// Deletes a file, or moves it into the archive directory at the same relative path.
+fn apply_action(action: &CleanupAction, directory: &Path, path: &Path) -> io::Result<()> {
+    let archive_dir = match action {
+        CleanupAction::Delete => return fs::remove_file(path),
+        CleanupAction::Archive(dir) => Path::new(dir)
+    };
+
+    let target = archive_dir.join(path.strip_prefix(directory).unwrap_or(path));
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // Renaming fails across file systems, fall back to copying.
+    if fs::rename(path, &target).is_err() {
+        fs::copy(path, &target)?;
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
\ No newline at end of file
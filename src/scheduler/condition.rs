@@ -0,0 +1,71 @@
+use std::error::Error;
+use chrono::{DateTime, Datelike, Timelike};
+use chrono_tz::Tz;
+
+use crate::configure::get_jobs;
+use crate::models::condition::{Condition, ConditionValue};
+use crate::models::scheduler_state::SchedulerState;
+use crate::scheduler::state::read_state;
+
+/// Evaluates the `when` condition of a job at one of its fire times.
+///
+/// The time variables are those of the fire time in the configured timezone: `weekday` is
+/// `mon` to `sun`, and `last_day_of_month` is whether the next day is the first of a month.
+/// `last_outcome` is `success` or `failure` after the last finished run, and `null` if the job
+/// never ran. `consecutive_failures` counts the failed runs since the last successful one,
+/// `env.NAME` is an environment variable and `kv.NAME` a value stored with the `set` command,
+/// both `null` if unset. The state file is only read if the condition uses it, and on every
+/// call, so a value set by another process applies to the next trigger.
+///
+/// # Arguments
+///
+/// * `condition` - The condition of the job.
+/// * `job_name` - The name of the job, whose outcomes are read from the state file.
+/// * `time` - The fire time, in the configured timezone.
+///
+/// # Returns
+///
+/// `true` if the job should run.
+///
+/// # Errors
+///
+/// Returns an error if the state file cannot be read, or the values cannot be compared.
+///
+/// # Examples
+///
+/// ```
+/// let condition = Condition::parse("weekday != 'sun' && !last_day_of_month")?;
+/// let run = is_condition_met(&condition, "report", Utc::now().with_timezone(&get_jobs().timezone))?;
+/// ```
+pub fn is_condition_met(condition: &Condition, job_name: &str, time: DateTime<Tz>) -> Result<bool, Box<dyn Error>> {
+    let state = if ["last_outcome", "consecutive_failures", "kv."].iter().any(|name| condition.uses(name)) {
+        read_state(&get_jobs().state_file)?
+    } else {
+        SchedulerState::default()
+    };
+    let job = state.jobs.get(job_name);
+
+    condition.evaluate(|name| match name {
+        "weekday" => ConditionValue::Text(time.format("%a").to_string().to_lowercase()),
+        "day" => ConditionValue::Number(time.day() as f64),
+        "month" => ConditionValue::Number(time.month() as f64),
+        "year" => ConditionValue::Number(time.year() as f64),
+        "hour" => ConditionValue::Number(time.hour() as f64),
+        "minute" => ConditionValue::Number(time.minute() as f64),
+        "last_day_of_month" => ConditionValue::Bool(time.date_naive().succ_opt().is_some_and(|next| next.day() == 1)),
+        "last_outcome" => match job.and_then(|job| job.last_run.map(|run| job.last_failure == Some(run))) {
+            Some(true) => ConditionValue::Text("failure".to_string()),
+            Some(false) => ConditionValue::Text("success".to_string()),
+            None => ConditionValue::Null
+        },
+        "consecutive_failures" => ConditionValue::Number(job.map_or(0, |job| job.consecutive_failures) as f64),
+        _ => {
+            let value = match name.split_once('.') {
+                Some(("env", key)) => std::env::var(key).ok(),
+                Some(("kv", key)) => state.values.get(key).cloned(),
+                _ => None
+            };
+            value.map_or(ConditionValue::Null, ConditionValue::Text)
+        }
+    })
+}
\ No newline at end of file
@@ -1,123 +1,1301 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::future::Future;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
-use reqwest::{Method};
-use tokio_cron::{Job, Scheduler};
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode, Version};
+use reqwest::header::{ACCEPT_ENCODING, CONTENT_TYPE, HeaderMap, HOST, RETRY_AFTER};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use once_cell::sync::{Lazy, OnceCell};
+use opentelemetry::{Context, KeyValue};
+use opentelemetry::context::FutureExt;
+use opentelemetry::trace::{Span, Status, TraceContextExt, Tracer};
+use serde_json::Value;
+use tokio::time::Instant;
 use crate::configure::get_jobs;
 
+use crate::models::catch_up::CatchUp;
+use crate::models::dead_letter_record::{DeadLetterRecord, DeadLetterRequest};
 use crate::models::http_job::HttpJob;
-use crate::utils::datetime_util::{get_local_datetime_in_timezone};
-use crate::utils::uuid_util::generate_uuid_without_hyphens;
+use crate::models::http_job_request::{BodyCompression, HttpJobRequest, HttpVersion};
+use crate::models::http_job_response::{HttpJobResponse, ResponseLogging};
+use crate::models::http_job_step::HttpJobStep;
+use crate::models::job::{Job, JobFuture};
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+use crate::models::jobs::Jobs;
+use crate::models::run_outcome::RunOutcome;
+use crate::models::tag_filter::TagFilter;
+use crate::notifier::heartbeat::send_heartbeat;
+use crate::notifier::notify_outcome;
+use crate::notifier::report::send_run_report;
+use crate::scheduler::drift::{format_drift, get_scheduled_time, record_drift};
+use crate::scheduler::freshness::{start_freshness_check, watch_success};
+use crate::scheduler::group_limiter::acquire_group_permit;
+use crate::scheduler::hooks::run_hooks;
+use crate::scheduler::dead_letter::append_dead_letter;
+use crate::scheduler::dns_cache::flush_dns_cache;
+use crate::scheduler::health::start_heartbeat;
+use crate::scheduler::http_client::{ClientOptions, get_client};
+use crate::scheduler::leader_election::{is_leader, start_leader_election};
+use crate::scheduler::run_archive::archive_run;
+use crate::scheduler::registry::{deregister_job, get_registered_job_names, get_registration, register_job, set_trigger};
+use crate::scheduler::rate_limiter::{acquire_global_permit, acquire_host_permit};
+use crate::scheduler::run_queue::{submit_run, wait_for_runs};
+use crate::scheduler::sharding::{is_assigned, start_sharding};
+use crate::scheduler::timer::{add_trigger, start_timer, stop_timer};
+use crate::scheduler::watchdog::start_watchdog;
+use crate::scheduler::systemd::start_systemd_watchdog;
+use crate::scheduler::status::{record_outcome, start_status_file};
+use crate::scheduler::state::{claim_run, get_run_count, is_paused, read_state, record_success};
+use crate::telemetry::{get_trace_headers, get_tracer};
+use crate::utils::compress_util::gzip;
+use crate::utils::cron_util::get_missed_runs;
+use crate::utils::redact_util::{redact_headers, redact_secrets};
+use crate::utils::datetime_util::get_local_datetime_in_timezone;
+use crate::utils::template_util::{get_run_vars, render_template};
+use crate::utils::uuid_util::generate_run_id;
 
-/// Starts the cron scheduler for executing HTTP jobs.
+/// Whether every job only logs its request instead of sending it, as given to
+/// `start_cron_scheduler`.
+static DRY_RUN: AtomicBool = AtomicBool::new(false);
+
+/// Returns whether the scheduler was started with `--dry-run`.
+pub fn is_dry_run() -> bool {
+    DRY_RUN.load(Ordering::Relaxed)
+}
+
+/// The time the scheduler started, the initial delays of the jobs are counted from.
+static STARTED_AT: OnceCell<Instant> = OnceCell::new();
+
+/// The tags selecting the jobs to schedule, as given to `start_cron_scheduler`.
+static TAG_FILTER: Lazy<Mutex<TagFilter>> = Lazy::new(|| Mutex::new(TagFilter::default()));
+
+/// Returns whether a job is selected by the `--tags` and `--exclude-tags` of the scheduler.
+///
+/// A job that is not selected is logged and must not be scheduled.
+///
+/// # Arguments
+///
+/// * `name` - The name of the job.
+/// * `tags` - The tags of the job.
+///
+/// # Examples
+///
+/// ```
+/// if job.enable() && is_selected(job.name(), &job.options().tags) {
+///     // schedule the job
+/// }
+/// ```
+pub fn is_selected(name: &str, tags: &[String]) -> bool {
+    let selected = TAG_FILTER.lock().unwrap().matches(tags);
+    if !selected {
+        println!("Job not registered, job name: {}, not selected by tags: [{}]", name, tags.join(", "));
+    }
+    selected
+}
+
+/// Starts the cron scheduler for executing the jobs.
+///
+/// This function retrieves the jobs of every type and schedules them based on their cron
+/// expressions. Only enabled jobs are scheduled for execution.
+///
+/// # Arguments
+///
+/// * `dry_run` - Whether every job only logs its request instead of sending it.
+/// * `tag_filter` - The tags selecting the jobs to schedule, jobs that are not selected are
+///   skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// use tokio::runtime::Runtime;
+///
+/// let rt = Runtime::new().unwrap();
+/// rt.block_on(async {
+///     start_cron_scheduler(false, TagFilter::default()).await;
+/// });
+/// ```
+pub async fn start_cron_scheduler(dry_run: bool, tag_filter: TagFilter) {
+    let jobs = get_jobs();
+
+    let _ = STARTED_AT.set(Instant::now());
+    DRY_RUN.store(dry_run, Ordering::Relaxed);
+    *TAG_FILTER.lock().unwrap() = tag_filter;
+    start_leader_election().await;
+    start_sharding().await;
+    start_timer();
+    start_watchdog();
+    start_systemd_watchdog();
+    let scheduled_jobs = schedule_jobs(&jobs, false);
+
+    // The missed runs are caught up at startup only.
+    for job in scheduled_jobs {
+        if let Some(catch_up) = &job.options().catch_up {
+            catch_up_missed_runs(job.clone(), catch_up);
+        }
+    }
+
+    start_heartbeat();
+    start_status_file();
+    start_freshness_check();
+}
+
+/// Schedules the jobs of the configuration after it was reloaded, see `reload_configuration`.
+///
+/// The registered jobs that are no longer configured, enabled or selected are deregistered, the
+/// others are registered again with their new definition, which replaces their triggers. The
+/// runs in progress are not interrupted and end with the previous definition. Unlike at
+/// startup, the `@reboot` jobs and the `run_on_start` jobs don't run, the initial delays are
+/// still counted from startup, and the missed runs are not caught up.
+///
+/// # Examples
+///
+/// ```
+/// set_jobs(jobs);
+/// reschedule_jobs();
+/// ```
+pub fn reschedule_jobs() {
+    let scheduled_jobs = schedule_jobs(&get_jobs(), true);
+
+    for name in get_registered_job_names() {
+        if !scheduled_jobs.iter().any(|job| job.name() == name) && deregister_job(&name) {
+            println!("Job deregistered, job name: {}", name);
+        }
+    }
+}
+
+/// Registers the enabled and selected jobs of a configuration, except those that reached
+/// their `max_runs`.
+///
+/// # Arguments
+///
+/// * `jobs` - The configuration.
+/// * `reload` - Whether the configuration was reloaded, see `reschedule_jobs`.
+///
+/// # Returns
+///
+/// The registered jobs.
+fn schedule_jobs(jobs: &Jobs, reload: bool) -> Vec<Arc<dyn Job>> {
+    let mut scheduled_jobs = Vec::new();
+
+    for job in jobs.all_jobs().into_iter().filter(|job| job.enable() && is_selected(job.name(), &job.options().tags)) {
+        if has_reached_max_runs(job.as_ref()) {
+            println!("Job not registered, job name: {}, reached max runs: {}", job.name(), job.options().max_runs.unwrap_or_default());
+        } else {
+            let schedule = match reload {
+                true => get_reload_schedule(job.schedule()),
+                false => Some(job.schedule().clone())
+            };
+            add_job_trigger(job.clone(), schedule);
+            scheduled_jobs.push(job);
+        }
+    }
+
+    scheduled_jobs
+}
+
+/// Returns the schedule of a job registered again on a reload, or `None` for a `@reboot` job,
+/// which only runs at startup.
+///
+/// The job doesn't run right away with `run_on_start`, and its initial delay is what remains
+/// of it since startup.
+fn get_reload_schedule(schedule: &JobSchedule) -> Option<JobSchedule> {
+    if schedule.is_reboot() {
+        return None;
+    }
+
+    let mut schedule = schedule.clone();
+    let elapsed = STARTED_AT.get().map(|started_at| started_at.elapsed()).unwrap_or_default();
+    schedule.run_on_start = false;
+    schedule.initial_delay = schedule.initial_delay.saturating_sub(elapsed);
+    Some(schedule)
+}
+
+/// The time the runs in progress are given to end when the scheduler stops.
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Stops the cron scheduler cleanly.
+///
+/// No trigger fires anymore, and the runs in progress, including the runs waiting in the run
+/// queue, are given `SHUTDOWN_TIMEOUT` to end. The runs still in progress after the timeout
+/// are logged and cancelled when the program exits.
+///
+/// # Examples
+///
+/// ```
+/// wait_for_shutdown_signal().await;
+/// stop_cron_scheduler().await;
+/// ```
+pub async fn stop_cron_scheduler() {
+    stop_timer();
+    println!("Scheduler stopping, waiting for the runs in progress");
+
+    match wait_for_runs(SHUTDOWN_TIMEOUT).await {
+        0 => println!("Scheduler stopped"),
+        running => println!("Scheduler stopped, runs cancelled: {}", running)
+    }
+}
+
+/// Waits for a request to stop the program, `Ctrl-C` or, on Unix, `SIGTERM`.
+///
+/// # Examples
+///
+/// ```
+/// wait_for_shutdown_signal().await;
+/// stop_cron_scheduler().await;
+/// ```
+pub async fn wait_for_shutdown_signal() {
+    // Without a signal handler, the scheduler runs until it is killed.
+    let ctrl_c = async {
+        if tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+            tokio::select! {
+                _ = ctrl_c => {}
+                _ = terminate.recv() => {}
+            }
+            return;
+        }
+    }
+    ctrl_c.await;
+}
+
+/// Registers a job and adds its cron trigger to the timer.
+///
+/// A registered job with the same name is replaced. The trigger of a job is removed from the
+/// timer when the job is deregistered or replaced.
+///
+/// # Arguments
+///
+/// * `job` - The job to schedule.
+///
+/// # Examples
+///
+/// ```
+/// schedule_job(Arc::new(http_job));
+/// ```
+pub fn schedule_job(job: Arc<dyn Job>) {
+    let schedule = job.schedule().clone();
+    add_job_trigger(job, Some(schedule));
+}
+
+/// Registers a job and adds a trigger with the given schedule to the timer, or no trigger if
+/// the schedule is `None`.
+fn add_job_trigger(job: Arc<dyn Job>, schedule: Option<JobSchedule>) {
+    let name = job.name().to_string();
+    let id = register_job(job.clone());
+
+    if let Some(schedule) = schedule {
+        let registration = name.clone();
+        let trigger = add_trigger(&name, &schedule, move |scheduled_at| {
+            if let Some(job) = get_registration(&registration, id) {
+                submit_run(&registration, Some(scheduled_at), start_job(job));
+            }
+        });
+        set_trigger(&name, id, trigger);
+    }
+    watch_success(&name, &job.options().notifications);
+
+    println!("Job registered, job name: {}, cron: {} ({})", &name, job.schedule(), job.schedule().describe());
+}
+
+/// Returns whether a job with `max_runs` already ran that many times.
+///
+/// A state file that cannot be read is logged and treated as no runs.
+fn has_reached_max_runs(job: &dyn Job) -> bool {
+    let Some(max_runs) = job.options().max_runs else {
+        return false;
+    };
+
+    match get_run_count(job.name()) {
+        Ok(runs) => runs >= max_runs,
+        Err(err) => {
+            println!("Failed to read state file, job name: {}, error: {}", job.name(), err);
+            false
+        }
+    }
+}
+
+/// Runs a job for the schedule ticks missed since its last successful run.
+///
+/// The missed ticks are counted from the fire time of the last successful run, see
+/// `record_success`. Each missed run is submitted like a run of the timer, with the fire time it
+/// missed, see `submit_run`, so it goes through the run queue, the group of the job and the
+/// distributed lock. Nothing is run if the job never succeeded before, since there is no
+/// reference point to count missed ticks from, or if this instance is not the leader or the job
+/// is assigned to another instance.
+///
+/// # Arguments
+///
+/// * `job` - The job.
+/// * `catch_up` - The catch-up policy of the job.
+fn catch_up_missed_runs(job: Arc<dyn Job>, catch_up: &CatchUp) {
+    if !is_leader() || !is_assigned(job.name()) {
+        return;
+    }
+
+    let jobs = get_jobs();
+    let last_success = match read_state(&jobs.state_file) {
+        Ok(state) => state.jobs.get(job.name()).and_then(|job| job.last_success),
+        Err(err) => {
+            println!("Failed to read state file, job name: {}, error: {}", job.name(), err);
+            return;
+        }
+    };
+    let Some(last_success) = last_success else {
+        return;
+    };
+
+    let missed = get_missed_runs(job.schedule(), &jobs.timezone, last_success, catch_up.max_runs as usize);
+    if missed.is_empty() {
+        return;
+    }
+
+    println!("Catching up missed runs, job name: {}, last success: {}, runs: {}",
+             job.name(), last_success.with_timezone(&jobs.timezone), missed.len());
+    for scheduled_at in missed {
+        submit_run(job.name(), Some(scheduled_at), start_job(job.clone()));
+    }
+}
+
+/// Asynchronously runs a job of any type.
+///
+/// The run is skipped if the job is paused or already ran `max_runs` times, and waits for the
+/// group of the job. In dry-run mode, from `--dry-run` or the `dry_run` of the job, the run is
+/// only logged, see `Job::dry_run`. Otherwise the job is executed and its hooks are run, both
+/// within the execution timeout of the job, see `Job::execution_timeout`: a run that exceeds
+/// it fails with a timeout error. Then the outcome of the run is recorded, and the
+/// notifications and the heartbeat of the job are sent.
+///
+/// # Arguments
+///
+/// * `job` - The job to run.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// let tcp_job = get_jobs().tcp_jobs[0].clone();
+/// start_job(Arc::new(tcp_job)).await;
+/// ```
+pub async fn start_job(job: Arc<dyn Job>) {
+    let jobs = get_jobs();
+    let (name, kind, options) = (job.name(), job.kind(), job.options());
+    let dry_run = is_dry_run() || options.dry_run;
+    let uuid = generate_run_id(name);
+    let local_time = get_local_datetime_in_timezone(&jobs.timezone);
+
+    match is_paused(name).await {
+        Ok(true) => {
+            println!("{} {} {} job skipped, job name: {}, paused", uuid, local_time, kind, name);
+            return;
+        }
+        Ok(false) => {}
+        Err(err) => println!("{} {} Failed to read state file, job name: {}, error: {}", uuid, local_time, name, err)
+    }
+
+    if let Some(max_runs) = options.max_runs.filter(|_| !dry_run) {
+        match claim_run(name, max_runs) {
+            Ok(true) => {}
+            Ok(false) => {
+                println!("{} {} {} job skipped, job name: {}, reached max runs: {}", uuid, local_time, kind, name, max_runs);
+                return;
+            }
+            Err(err) => println!("{} {} Failed to update state file, job name: {}, error: {}", uuid, local_time, name, err)
+        }
+    }
+
+    let _permit = acquire_group_permit(options.group.as_deref(), options.priority, name, &uuid, &local_time).await;
+
+    let drift = record_drift();
+    println!("{} {} {} job start, job name: {}{}", uuid, local_time, kind, name, format_drift(drift));
+    println!("{} {} Job: [{}]", uuid, local_time, redact_secrets(&job.to_string()));
+
+    if dry_run {
+        job.clone().dry_run(uuid.clone(), local_time.clone()).await;
+        println!("{} {} {} job end, job name: {}\n", uuid, local_time, kind, name);
+        return;
+    }
+
+    let started_at = Utc::now();
+    let execution_timeout = job.execution_timeout();
+    let deadline = execution_timeout.map(|execution_timeout| Instant::now() + execution_timeout);
+    let mut outcome = job.clone().execute(uuid.clone(), local_time.clone()).await;
+
+    // The job enforces the deadline while it executes, the hooks get what is left of it.
+    let has_hooks = !options.hooks.actions(outcome.success).is_empty();
+    if has_hooks && run_until(deadline, run_hooks(&options.hooks, &options.notifications, &outcome)).await.is_none() {
+        let execution_timeout = execution_timeout.unwrap_or_default().as_millis();
+        println!("{} {} {} job timed out, job name: {}, execution timeout: {}ms, hooks not completed", uuid, local_time, kind, name, execution_timeout);
+        outcome = RunOutcome::new(uuid.clone(), name.to_string(), false, outcome.status, outcome.attempts, Some(format!("Execution timed out after {}ms", execution_timeout)));
+    }
+
+    if outcome.success {
+        if let Err(err) = record_success(name, get_scheduled_time().unwrap_or(started_at)) {
+            println!("{} {} Failed to update state file, job name: {}, error: {}", uuid, local_time, name, err);
+        }
+    }
+
+    record_outcome(&outcome, started_at);
+    notify_outcome(&options.notifications, &outcome).await;
+
+    if let Some(heartbeat) = &options.heartbeat {
+        match send_heartbeat(heartbeat, &outcome).await {
+            Ok(Some(url)) => println!("{} {} Heartbeat sent, job name: {}, url: {}", uuid, local_time, name, url),
+            Ok(None) => {}
+            Err(err) => println!("{} {} Failed to send heartbeat, job name: {}, error: {}", uuid, local_time, name, err)
+        }
+    }
+
+    println!("{} {} {} job end, job name: {}\n", uuid, local_time, kind, name);
+}
+
+impl Job for HttpJob {
+    fn kind(&self) -> &'static str {
+        "Http"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn enable(&self) -> bool {
+        self.enable
+    }
+
+    fn schedule(&self) -> &JobSchedule {
+        &self.schedule
+    }
+
+    fn options(&self) -> &JobOptions {
+        &self.options
+    }
+
+    fn execution_timeout(&self) -> Option<Duration> {
+        self.execution_timeout.map(Duration::from_millis)
+    }
+
+    fn execute(self: Arc<Self>, uuid: String, local_time: String) -> JobFuture<RunOutcome> {
+        Box::pin(async move { run_http_job(&self, &uuid, &local_time).await })
+    }
+
+    fn dry_run(self: Arc<Self>, uuid: String, local_time: String) -> JobFuture<()> {
+        Box::pin(async move {
+            if self.steps.is_empty() {
+                log_dry_run_request(&self, &self.request, &uuid, &local_time);
+            }
+            for step in &self.steps {
+                println!("{} {} Dry run step: {}, placeholders not rendered", uuid, local_time, &step.name);
+                log_dry_run_request(&self, &step.request, &uuid, &local_time);
+            }
+        })
+    }
+}
+
+/// Executes a run of an HTTP job in its trace span, then sends the run report, archives the run
+/// and writes the dead-letter record of a failed run.
+///
+/// # Arguments
+///
+/// * `http_job` - The HTTP job to run.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+///
+/// # Returns
+///
+/// The `RunOutcome` of the run.
+async fn run_http_job(http_job: &HttpJob, uuid: &str, local_time: &str) -> RunOutcome {
+    let jobs = get_jobs();
+    let timezone = &jobs.timezone;
+
+    let mut span = get_tracer().start(format!("job {}", http_job.name));
+    span.set_attribute(KeyValue::new("rjob.job.name", http_job.name.clone()));
+    span.set_attribute(KeyValue::new("rjob.run_id", uuid.to_string()));
+    let run_cx = Context::current_with_span(span);
+    let started = Instant::now();
+    let started_at = Utc::now();
+
+    let mut response = None;
+    let outcome = execute_http_job(http_job, uuid, local_time, timezone, &mut response)
+        .with_context(run_cx.clone())
+        .await;
+    let duration = started.elapsed();
+
+    let span = run_cx.span();
+    span.set_attribute(KeyValue::new("rjob.attempts", outcome.attempts as i64));
+    span.set_attribute(KeyValue::new("rjob.duration_ms", duration.as_millis() as i64));
+    if let Some(status) = outcome.status {
+        span.set_attribute(KeyValue::new("http.response.status_code", status as i64));
+    }
+    if let Some(error) = &outcome.error {
+        span.set_status(Status::error(error.clone()));
+    }
+    span.end();
+
+    if let Some(url) = &http_job.report_to {
+        match send_run_report(http_job, url, &outcome, response.as_ref(), started_at, duration, timezone).await {
+            Ok(_) => println!("{} {} Run report sent, job name: {}, url: {}", uuid, local_time, &http_job.name, redact_secrets(url)),
+            Err(err) => println!("{} {} Failed to send run report, job name: {}, error: {}", uuid, local_time, &http_job.name, err)
+        }
+    }
+
+    if let Some(run_archive) = &jobs.run_archive {
+        match archive_run(run_archive, http_job, &outcome, response.as_ref(), started_at, duration, timezone).await {
+            Ok(key) => println!("{} {} Run archived, job name: {}, bucket: {}, key: {}", uuid, local_time, &http_job.name, &run_archive.storage.bucket, key),
+            Err(err) => println!("{} {} Failed to archive run, job name: {}, error: {}", uuid, local_time, &http_job.name, err)
+        }
+    }
+
+    if !outcome.success {
+        // A step of a pipeline depends on the values extracted by the previous steps of the
+        // run, so it can't be replayed on its own.
+        if jobs.dead_letter_file.is_some() && !http_job.steps.is_empty() {
+            println!("{} {} Dead-letter record not written, job name: {}, a request pipeline can't be replayed", uuid, local_time, &http_job.name);
+        } else if let Some(path) = &jobs.dead_letter_file {
+            let record = DeadLetterRecord {
+                job_name: http_job.name.clone(),
+                run_id: uuid.to_string(),
+                time: local_time.to_string(),
+                timeout: http_job.timeout,
+                attempts: outcome.attempts,
+                status: outcome.status,
+                error: outcome.error.clone(),
+                request: DeadLetterRequest::from_request(&http_job.request),
+            };
+            match append_dead_letter(path, &record) {
+                Ok(_) => println!("{} {} Dead-letter record written, job name: {}, file: {}", uuid, local_time, &http_job.name, path),
+                Err(err) => println!("{} {} Failed to write dead-letter record, job name: {}, error: {}", uuid, local_time, &http_job.name, err)
+            }
+        }
+    }
+
+    outcome
+}
+
+/// Sends the request of an HTTP job, retrying on failure, and returns the outcome of the run.
+///
+/// A job with a request pipeline sends all of its steps at each attempt, see `send_pipeline`.
+/// The `execution_timeout` of the job covers the retries and the fallback request: a run that
+/// exceeds it fails with a timeout error, and the fallback request is then cancelled or not
+/// sent.
+///
+/// # Arguments
+///
+/// * `http_job` - The HTTP job to execute.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+/// * `timezone` - The configured timezone.
+/// * `response` - Set to the final response of the run if it succeeds, see `send_run_report`.
+///
+/// # Returns
+///
+/// The `RunOutcome` of the run.
+///
+/// # Examples
+///
+/// ```
+/// let outcome = execute_http_job(http_job, &uuid, &local_time, &Tz::UTC, &mut None).await;
+/// println!("Success: {}", outcome.success);
+/// ```
+pub async fn execute_http_job(http_job: &HttpJob, uuid: &str, local_time: &str, timezone: &Tz, response: &mut Option<ReceivedResponse>) -> RunOutcome {
+    let failed = |attempts: u64, status: Option<u16>, error: String| {
+        RunOutcome::new(uuid.to_string(), http_job.name.clone(), false, status, attempts, Some(error))
+    };
+
+    let requests = if http_job.steps.is_empty() {
+        let client = match get_client(&ClientOptions::from_job(http_job)) {
+            Ok(client) => client,
+            Err(err) => {
+                println!("{} {} Failed to create HTTP client, job name: {}, error: {}", uuid, local_time, &http_job.name, err);
+                return failed(0, None, err.to_string());
+            }
+        };
+
+        let body = match get_request_body(&http_job.request) {
+            Ok(body) => body,
+            Err(err) => {
+                println!("{} {} Failed to encode request body, job name: {}, error: {}", uuid, local_time, &http_job.name, err);
+                return failed(0, None, err.to_string());
+            }
+        };
+
+        RunRequests::Single(client, body)
+    } else {
+        RunRequests::Pipeline(&http_job.steps)
+    };
+
+    let execution_timeout = http_job.execution_timeout.unwrap_or_default();
+    let deadline = http_job.execution_timeout().map(|execution_timeout| Instant::now() + execution_timeout);
+    let mut attempts = 0;
+    let run = send_with_retries(http_job, &requests, uuid, local_time, timezone, &mut attempts, response);
+
+    let outcome = match run_until(deadline, run).await {
+        Some(outcome) => outcome,
+        None => {
+            println!("{} {} Http job timed out, job name: {}, execution timeout: {}ms, attempts: {}", uuid, local_time, &http_job.name, execution_timeout, attempts);
+            failed(attempts, None, format!("Execution timed out after {}ms", execution_timeout))
+        }
+    };
+
+    if let Some(fallback_request) = http_job.fallback_request.as_ref().filter(|_| !outcome.success) {
+        if run_until(deadline, send_fallback_request(http_job, fallback_request, uuid, local_time)).await.is_none() {
+            println!("{} {} Http job timed out, job name: {}, execution timeout: {}ms, fallback request not completed", uuid, local_time, &http_job.name, execution_timeout);
+            return failed(outcome.attempts, outcome.status, format!("Execution timed out after {}ms", execution_timeout));
+        }
+    }
+
+    outcome
+}
+
+/// Runs a step of a run until the deadline of the run, see `Job::execution_timeout`.
+///
+/// # Arguments
+///
+/// * `deadline` - The deadline of the run, or `None` if the run is not limited.
+/// * `future` - The step to run.
+///
+/// # Returns
+///
+/// The output of the step, or `None` if the deadline passed before the step completed. A step
+/// is not started once the deadline has passed.
+///
+/// # Examples
+///
+/// ```
+/// let deadline = job.execution_timeout().map(|execution_timeout| Instant::now() + execution_timeout);
+/// if run_until(deadline, run_hooks(&options.hooks, &options.notifications, &outcome)).await.is_none() {
+///     println!("Hooks not completed");
+/// }
+/// ```
+async fn run_until<F: Future>(deadline: Option<Instant>, future: F) -> Option<F::Output> {
+    match deadline {
+        Some(deadline) if Instant::now() >= deadline => None,
+        Some(deadline) => tokio::time::timeout_at(deadline, future).await.ok(),
+        None => Some(future.await)
+    }
+}
+
+/// Sends the `fallback_request` of an HTTP job once, after the primary request failed.
+///
+/// The result is only logged, under the run id of the failed run. The run stays failed even
+/// if the fallback request succeeds.
+///
+/// # Arguments
+///
+/// * `http_job` - The HTTP job.
+/// * `fallback_request` - The fallback request of the job.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+///
+/// # Examples
+///
+/// ```
+/// if let Some(fallback_request) = &http_job.fallback_request {
+///     send_fallback_request(http_job, fallback_request, &uuid, &local_time).await;
+/// }
+/// ```
+async fn send_fallback_request(http_job: &HttpJob, fallback_request: &HttpJobRequest, uuid: &str, local_time: &str) {
+    println!("{} {} Fallback request start, job name: {}, url: {}", uuid, local_time, &http_job.name, redact_secrets(&fallback_request.url));
+
+    let client = match get_client(&ClientOptions::for_request(http_job, fallback_request)) {
+        Ok(client) => client,
+        Err(err) => {
+            println!("{} {} Failed to create HTTP client, job name: {}, error: {}", uuid, local_time, &http_job.name, err);
+            return;
+        }
+    };
+
+    let body = match get_request_body(fallback_request) {
+        Ok(body) => body,
+        Err(err) => {
+            println!("{} {} Failed to encode fallback request body, job name: {}, error: {}", uuid, local_time, &http_job.name, err);
+            return;
+        }
+    };
+
+    acquire_host_permit(&fallback_request.url).await;
+    acquire_global_permit().await;
+
+    let request_builder = build_request(&client, fallback_request, body);
+    let resp = match apply_correlation_header(request_builder, uuid, &http_job.name).send().await {
+        Ok(resp) => resp,
+        Err(err) => {
+            println!("{} {} Fallback request failed, job name: {}, error: {}", uuid, local_time, &http_job.name, err);
+            return;
+        }
+    };
+
+    let status = resp.status();
+    match receive_response_body(resp, &http_job.response, uuid, local_time).await {
+        Ok((bytes, truncated)) => {
+            if status.is_success() {
+                println!("{} {} Fallback request success, job name: {}", uuid, local_time, &http_job.name);
+            } else {
+                println!("{} {} Fallback request failed, job name: {}, http status: {}", uuid, local_time, &http_job.name, status.as_u16());
+            }
+            log_response_body(&http_job.response, status.as_u16(), &bytes, truncated, uuid, local_time);
+        }
+        Err(err) => println!("{} {} Failed to read fallback response, job name: {}, error: {}", uuid, local_time, &http_job.name, err)
+    }
+}
+
+/// What an HTTP job sends at each attempt of a run.
+enum RunRequests<'a> {
+    /// The request of the job, with its client and encoded body.
+    Single(Client, Vec<u8>),
+    /// The steps of the request pipeline of the job.
+    Pipeline(&'a [HttpJobStep]),
+}
+
+/// A successful response of a request of a run.
+pub struct ReceivedResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    /// The body, truncated to `max_response_bytes`, or empty with `discard_body`.
+    pub body: Vec<u8>,
+    pub truncated: bool,
+    /// The values extracted by the steps of a request pipeline, in the order of the steps.
+    pub extracted: Vec<(String, String)>,
+}
+
+/// Why an attempt of a run failed, and whether the run is retried.
+enum AttemptFailure {
+    /// The request failed before a response was received, the run is retried.
+    Retryable(String),
+    /// The server responded with a 429 or 503 status and a `Retry-After` header, the run is
+    /// retried once the server asks for it.
+    Throttled { status: u16, error: String, retry_after: Duration },
+    /// The run fails without further attempts.
+    Final { status: Option<u16>, error: String },
+}
+
+impl AttemptFailure {
+
+    /// Prefixes the error with the name of the pipeline step that failed.
+    fn in_step(self, step: &str) -> Self {
+        match self {
+            AttemptFailure::Retryable(error) => AttemptFailure::Retryable(format!("Step '{}': {}", step, error)),
+            AttemptFailure::Throttled { status, error, retry_after } => {
+                AttemptFailure::Throttled { status, error: format!("Step '{}': {}", step, error), retry_after }
+            }
+            AttemptFailure::Final { status, error } => AttemptFailure::Final { status, error: format!("Step '{}': {}", step, error) },
+        }
+    }
+
+    /// Makes the failure final, so the run is not retried.
+    fn into_final(self) -> Self {
+        match self {
+            AttemptFailure::Retryable(error) => AttemptFailure::Final { status: None, error },
+            AttemptFailure::Throttled { status, error, .. } => AttemptFailure::Final { status: Some(status), error },
+            failure => failure,
+        }
+    }
+}
+
+/// Sends the requests of an HTTP job until they succeed, `max_retry` attempts were made or the
+/// `retry_budget` of the job is exhausted.
+///
+/// An attempt sends the request of the job, or all steps of its pipeline. A pipeline is retried
+/// as a whole, from its first step, so that a step never uses values extracted in an earlier
+/// attempt, such as an expired token.
+///
+/// Without `retry_non_idempotent`, a POST or PATCH request is only retried when it failed to
+/// connect, as any later failure, e.g. a timeout or a broken response, may happen after the
+/// server applied the request, and a retry would apply it twice. For the same reason, a
+/// pipeline is not retried once one of its POST or PATCH steps succeeded, see `send_pipeline`.
+///
+/// A response with status 429 or 503 and a `Retry-After` header is retried once the server
+/// asks for it, at the earliest after `retry_delay_ms`, rather than failing the run right away.
+/// If the wait would exceed the `retry_budget`, the run fails instead.
+///
+/// # Arguments
+///
+/// * `http_job` - The HTTP job to execute.
+/// * `requests` - What is sent at each attempt.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+/// * `timezone` - The configured timezone.
+/// * `attempts` - The number of attempts made, kept up to date so it is known even if the run
+///   is cancelled by the execution timeout.
+/// * `received` - Set to the final response of the run if it succeeds.
+///
+/// # Returns
+///
+/// The `RunOutcome` of the run.
+async fn send_with_retries(http_job: &HttpJob, requests: &RunRequests<'_>, uuid: &str, local_time: &str, timezone: &Tz, attempts: &mut u64, received: &mut Option<ReceivedResponse>) -> RunOutcome {
+    let failed = |attempts: u64, status: Option<u16>, error: String| {
+        RunOutcome::new(uuid.to_string(), http_job.name.clone(), false, status, attempts, Some(error))
+    };
+
+    let max_attempts = http_job.max_retry;
+    let mut last_error = String::from("No attempt was made.");
+    let started = Instant::now();
+
+    let retry_delay = Duration::from_millis(http_job.retry_delay_ms);
+    // The wait before the next attempt, longer than `retry_delay` after a `Retry-After`.
+    let mut next_delay = retry_delay;
+
+    while *attempts < max_attempts {
+        if *attempts > 0 {
+            // A retry that could only start after the budget is not attempted.
+            if let Some(retry_budget) = http_job.retry_budget {
+                if started.elapsed() + next_delay >= retry_budget {
+                    println!("{} {} Http job retry budget exhausted, job name: {}, retry budget: {}ms, attempts: {}/{}", uuid, local_time, &http_job.name, retry_budget.as_millis(), *attempts, max_attempts);
+                    break;
+                }
+            }
+            tokio::time::sleep(next_delay).await;
+            next_delay = retry_delay;
+        }
+
+        *attempts += 1;
+
+        let result = match requests {
+            RunRequests::Single(client, body) => send_request(http_job, client, &http_job.request, body.clone(), uuid, local_time, *attempts).await,
+            RunRequests::Pipeline(steps) => send_pipeline(http_job, steps, uuid, local_time, timezone, *attempts).await,
+        };
+
+        let response = match result {
+            Ok(response) => response,
+            Err(AttemptFailure::Retryable(error)) => {
+                last_error = error;
+                continue;
+            }
+            Err(AttemptFailure::Throttled { error, retry_after, .. }) if *attempts < max_attempts => {
+                next_delay = retry_delay.max(retry_after);
+                last_error = error;
+                println!("{} {} Http request throttled by server, job name: {}, retry after: {}ms. Retry attempt: {}/{}", uuid, local_time, &http_job.name, next_delay.as_millis(), *attempts, max_attempts);
+                continue;
+            }
+            Err(AttemptFailure::Throttled { status, error, .. }) => return failed(*attempts, Some(status), error),
+            Err(AttemptFailure::Final { status, error }) => return failed(*attempts, status, error),
+        };
+
+        println!("{} {} Http request success, job name: {}", uuid, local_time, &http_job.name);
+        log_response_body(&http_job.response, response.status, &response.body, response.truncated, uuid, local_time);
+
+        if let Some(save_to) = &http_job.response.save_to {
+            if response.truncated {
+                println!("{} {} Http response not saved because it exceeds max_response_bytes, job name: {}", uuid, local_time, &http_job.name);
+            } else {
+                match save_response_body(save_to, http_job, uuid, timezone, &response.body) {
+                    Ok(path) => println!("{} {} Http response saved, job name: {}, file: {}", uuid, local_time, &http_job.name, path),
+                    Err(err) => println!("{} {} Failed to save http response, job name: {}, error: {}", uuid, local_time, &http_job.name, err)
+                }
+            }
+        }
+
+        let status = response.status;
+        *received = Some(response);
+        return RunOutcome::new(uuid.to_string(), http_job.name.clone(), true, Some(status), *attempts, None);
+    }
+
+    failed(*attempts, None, last_error)
+}
+
+/// Sends the steps of the request pipeline of an HTTP job in order, as one attempt of a run.
 ///
-/// This function retrieves the HTTP jobs using the `get_http_jobs` function and schedules them
-/// based on their cron expressions. Only enabled jobs are scheduled for execution.
+/// Each step is rendered with the variables of the run and the values extracted by the previous
+/// steps, see `HttpJobStep::render_request`, and the values it extracts are added to them. The
+/// response of each step but the last is logged as the step succeeds. The attempt stops at the
+/// first step that fails, or that lacks a value to extract.
 ///
-/// # Examples
+/// Without `retry_non_idempotent`, the failure of a step following a successful POST or PATCH
+/// step is final, since retrying the pipeline from its first step would send that step again.
 ///
-/// ```rust
-/// use tokio::runtime::Runtime;
+/// # Arguments
 ///
-/// let rt = Runtime::new().unwrap();
-/// rt.block_on(async {
-///     start_cron_scheduler().await;
-/// });
-/// ```
-pub async fn start_cron_scheduler() {
-    let jobs = get_jobs();
-    let http_jobs = &jobs.http_jobs;
+/// * `http_job` - The HTTP job.
+/// * `steps` - The steps of the pipeline.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+/// * `timezone` - The configured timezone, used to render the date variables.
+/// * `attempt` - The number of the attempt, starting at 1.
+///
+/// # Returns
+///
+/// The response of the last step, with the values extracted by all steps.
+///
+/// # Errors
+///
+/// Returns the failure of the first step that failed, its error prefixed with the step name.
+async fn send_pipeline(http_job: &HttpJob, steps: &[HttpJobStep], uuid: &str, local_time: &str, timezone: &Tz, attempt: u64) -> Result<ReceivedResponse, AttemptFailure> {
+    let final_failure = |step: &HttpJobStep, status: Option<u16>, error: String| {
+        AttemptFailure::Final { status, error }.in_step(&step.name)
+    };
+
+    let mut vars: HashMap<&str, String> = get_run_vars(&http_job.name, uuid, timezone);
+    let mut extracted = Vec::new();
+    let mut last_response = None;
+    // The last POST or PATCH step that succeeded, after which the pipeline is not retried.
+    let mut applied_step: Option<&HttpJobStep> = None;
+
+    for (index, step) in steps.iter().enumerate() {
+        println!("{} {} Http pipeline step start, job name: {}, step: {} ({}/{})", uuid, local_time, &http_job.name, &step.name, index + 1, steps.len());
+
+        let request = step.render_request(&vars)
+            .map_err(|err| final_failure(step, None, err))?;
+        let client = get_client(&ClientOptions::for_request(http_job, &request))
+            .map_err(|err| final_failure(step, None, format!("Failed to create HTTP client: {}", err)))?;
+        let body = get_request_body(&request)
+            .map_err(|err| final_failure(step, None, format!("Failed to encode request body: {}", err)))?;
+
+        let response = match send_request(http_job, &client, &request, body, uuid, local_time, attempt).await {
+            Ok(response) => response,
+            Err(failure) => {
+                let failure = failure.in_step(&step.name);
+                return Err(match applied_step {
+                    Some(applied) if !matches!(failure, AttemptFailure::Final { .. }) => {
+                        println!("{} {} Http pipeline not retried, job name: {}, step: {}, method: {}, the step already reached the server, see retry_non_idempotent",
+                                 uuid, local_time, &http_job.name, &applied.name, applied.request.method.to_uppercase());
+                        failure.into_final()
+                    }
+                    _ => failure
+                });
+            }
+        };
+        if !http_job.retry_non_idempotent && !is_idempotent(&request.method) {
+            applied_step = Some(step);
+        }
 
-    let mut scheduler = Scheduler::new_in_timezone(jobs.timezone);
+        for (variable, extraction) in &step.extract {
+            let value = extraction.extract(&response.headers, &response.body).ok_or_else(|| {
+                println!("{} {} Http pipeline step failed, job name: {}, step: {}, no value to extract for '{}' at '{}'", uuid, local_time, &http_job.name, &step.name, variable, extraction);
+                final_failure(step, Some(response.status), format!("No value to extract for '{}' at '{}'", variable, extraction))
+            })?;
+            vars.insert(variable, value.clone());
+            extracted.push((variable.clone(), value));
+        }
 
-    for it in http_jobs {
-        if it.enable {
-            let job = Job::new_sync(&it.cron, move || {
-                tokio::spawn(start_http_job(it));
-            });
-            scheduler.add(job);
+        if index + 1 < steps.len() {
+            let extracted = step.extract.iter().map(|(variable, _)| variable.as_str()).collect::<Vec<_>>().join(", ");
+            println!("{} {} Http pipeline step success, job name: {}, step: {}, status: {}, extracted: [{}]", uuid, local_time, &http_job.name, &step.name, response.status, extracted);
+            log_response_body(&http_job.response, response.status, &response.body, response.truncated, uuid, local_time);
         }
+        last_response = Some(response);
     }
+
+    let mut last_response = last_response.expect("A pipeline has at least one step.");
+    last_response.extracted = extracted;
+    Ok(last_response)
 }
 
-/// Asynchronously starts an HTTP job by sending an HTTP request.
+/// Sends a request of an HTTP job once and reads its response.
+///
+/// The failures of the request are logged, and so is the body of a response with an error
+/// status. The body of a successful response is left to the caller.
 ///
 /// # Arguments
 ///
-/// * `http_job` - An `Arc`-wrapped `HttpJob` struct representing the job to be started.
+/// * `http_job` - The HTTP job.
+/// * `client` - The HTTP client used to send the request.
+/// * `request` - The request to send, the request of the job or of a pipeline step.
+/// * `body` - The encoded body, as returned by `get_request_body`.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+/// * `attempt` - The number of the attempt, starting at 1.
 ///
-/// # Examples
+/// # Returns
 ///
-/// ```rust
-/// use std::sync::Arc;
+/// The response, if its status is a success.
 ///
-/// let http_job = Arc::new(HttpJob {
-///     name: "Test job".to_string(),
-///     enable: true,
-///     cron: "*/5 * * * * * *".to_string(),
-///     request: HttpJobRequest {
-///         method: "GET".to_string(),
-///         url: "https://www.google.com".to_string(),
-///         headers: None,
-///         body: None
-///     }
-/// });
+/// # Errors
 ///
-/// start_http_job(http_job).await;
-/// ```
-async fn start_http_job(http_job: &HttpJob) {
-    let jobs = get_jobs();
-    let timezone = &jobs.timezone;
-    let uuid = generate_uuid_without_hyphens();
-    let local_time = get_local_datetime_in_timezone(timezone);
+/// Returns the failure of the attempt, see `AttemptFailure`.
+async fn send_request(http_job: &HttpJob, client: &Client, request: &HttpJobRequest, body: Vec<u8>, uuid: &str, local_time: &str, attempt: u64) -> Result<ReceivedResponse, AttemptFailure> {
+    let max_attempts = http_job.max_retry;
+    let retry_unsent_only = !http_job.retry_non_idempotent && !is_idempotent(&request.method);
+
+    let attempt_cx = Context::current_with_span(get_tracer().start(format!("attempt {}", attempt)));
+    let mut request_builder = build_request(client, request, body);
+    for (name, value) in get_trace_headers(&attempt_cx) {
+        request_builder = request_builder.header(name, value);
+    }
+    request_builder = apply_correlation_header(request_builder, uuid, &http_job.name);
+
+    if let Some(waited) = acquire_host_permit(&request.url).await {
+        if !waited.is_zero() {
+            println!("{} {} Http request throttled by host rate limit, job name: {}, waited: {}ms", uuid, local_time, &http_job.name, waited.as_millis());
+        }
+    }
 
-    println!("{} {} Http job start, job name: {}", uuid, local_time, &http_job.name);
-    println!("{} {} Job: [{}]", uuid, local_time, &http_job);
+    if let Some(waited) = acquire_global_permit().await {
+        if !waited.is_zero() {
+            println!("{} {} Http request throttled by global rate limit, job name: {}, waited: {}ms", uuid, local_time, &http_job.name, waited.as_millis());
+        }
+    }
 
-    let request = &http_job.request;
-    let method = get_method(&request.method);
-    let timeout = http_job.timeout.clone();
+    let resp = match request_builder.send().await {
+        Ok(resp) => resp,
+        Err(err) => {
+            println!("{} {} Http request failed, job name: {}, error: {}. Retry attempt: {}/{}", uuid, local_time, &http_job.name, err, attempt, max_attempts);
+            let error = err.to_string();
+            end_attempt_span(&attempt_cx, None, Some(&error));
+            if (err.is_connect() || err.is_timeout()) && flush_dns_cache(&request.url) {
+                println!("{} {} DNS cache flushed, job name: {}", uuid, local_time, &http_job.name);
+            }
+            if retry_unsent_only && !err.is_connect() {
+                log_not_retried(http_job, request, uuid, local_time);
+                return Err(AttemptFailure::Final { status: None, error });
+            }
+            return Err(AttemptFailure::Retryable(error));
+        }
+    };
 
-    let client = reqwest::Client::builder()
-        .user_agent("rjob")
-        .timeout(Duration::from_millis(timeout))
-        .build()
-        .expect("Failed to create HTTP client");
+    let status = resp.status();
+    if http_job.response.log_headers {
+        println!("{} {} Http response headers, job name: {}, headers: {}", uuid, local_time, &http_job.name, redact_headers(resp.headers()));
+    }
+    let headers = resp.headers().clone();
+    let retry_after = headers.get(RETRY_AFTER)
+        .filter(|_| matches!(status, StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE))
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_retry_after);
 
-    let mut attempts = 0;
-    let max_attempts = http_job.max_retry.clone();
+    let (bytes, truncated) = match receive_response_body(resp, &http_job.response, uuid, local_time).await {
+        Ok(body) => body,
+        Err(err) => {
+            println!("{} {} Failed to read http response, job name: {}, error: {}. Retry attempt: {}/{}", uuid, local_time, &http_job.name, err, attempt, max_attempts);
+            let error = err.to_string();
+            end_attempt_span(&attempt_cx, Some(status.as_u16()), Some(&error));
+            if retry_unsent_only {
+                log_not_retried(http_job, request, uuid, local_time);
+                return Err(AttemptFailure::Final { status: None, error });
+            }
+            return Err(AttemptFailure::Retryable(error));
+        }
+    };
+    if !status.is_success() {
+        println!("{} {} Http request failed, job name: {}, http status: {}", uuid, local_time, &http_job.name, status.as_u16());
+        log_response_body(&http_job.response, status.as_u16(), &bytes, truncated, uuid, local_time);
+        let error = format!("Http status {}", status.as_u16());
+        end_attempt_span(&attempt_cx, Some(status.as_u16()), Some(&error));
+        return Err(match retry_after {
+            Some(retry_after) => AttemptFailure::Throttled { status: status.as_u16(), error, retry_after },
+            None => AttemptFailure::Final { status: Some(status.as_u16()), error }
+        });
+    }
+
+    if request.graphql {
+        if let Err(error) = check_graphql_response(&bytes) {
+            println!("{} {} GraphQL request failed, job name: {}, error: {}", uuid, local_time, &http_job.name, error);
+            log_response_body(&http_job.response, status.as_u16(), &bytes, truncated, uuid, local_time);
+            end_attempt_span(&attempt_cx, Some(status.as_u16()), Some(&error));
+            return Err(AttemptFailure::Final { status: Some(status.as_u16()), error });
+        }
+    }
+
+    end_attempt_span(&attempt_cx, Some(status.as_u16()), None);
 
-    while attempts < max_attempts {
-        attempts += 1;
+    Ok(ReceivedResponse { status: status.as_u16(), headers, body: bytes, truncated, extracted: Vec::new() })
+}
 
-        let mut request_builder = client.request(method.clone(), &request.url)
-            .headers(request.headers.clone().unwrap_or_default())
-            .body(request.body.clone().unwrap_or_default());
+/// Logs that a failed request is not retried because its method is not idempotent.
+fn log_not_retried(http_job: &HttpJob, request: &HttpJobRequest, uuid: &str, local_time: &str) {
+    println!("{} {} Http request not retried, job name: {}, method: {}, the request may have reached the server, see retry_non_idempotent",
+             uuid, local_time, &http_job.name, request.method.to_uppercase());
+}
 
-        if let Some(_) = request.body {
-            request_builder = request_builder.header("Content-Type", "application/json");
+/// Logs the fully-rendered request of a job instead of sending it.
+///
+/// Credential headers are redacted. The body is logged before compression.
+///
+/// # Arguments
+///
+/// * `http_job` - The HTTP job.
+/// * `request` - The request of the job or of a step of its pipeline.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+fn log_dry_run_request(http_job: &HttpJob, request: &HttpJobRequest, uuid: &str, local_time: &str) {
+    let client = match get_client(&ClientOptions::for_request(http_job, request)) {
+        Ok(client) => client,
+        Err(err) => {
+            println!("{} {} Failed to create HTTP client, job name: {}, error: {}", uuid, local_time, &http_job.name, err);
+            return;
         }
+    };
 
-        let resp = match request_builder.send().await {
-            Ok(resp) => resp,
-            Err(err) => {
-                println!("{} {} Http request failed, job name: {}, error: {}. Retry attempt: {}/{}", uuid, local_time, &http_job.name, err, attempts, max_attempts);
-                continue;
-            }
-        };
+    let request_builder = build_request(&client, request, Vec::new());
+    let built = match apply_correlation_header(request_builder, uuid, &http_job.name).build() {
+        Ok(built) => built,
+        Err(err) => {
+            println!("{} {} Invalid http request, job name: {}, error: {}", uuid, local_time, &http_job.name, err);
+            return;
+        }
+    };
 
-        let status = resp.status();
-        let text = resp.text().await.unwrap();
+    println!("{} {} Dry run, request not sent, job name: {}", uuid, local_time, &http_job.name);
+    println!("{} {} Dry run request: {} {}", uuid, local_time, built.method(), redact_secrets(built.url().as_str()));
+    println!("{} {} Dry run headers: {}", uuid, local_time, redact_headers(built.headers()));
+    println!("{} {} Dry run body: {}", uuid, local_time, redact_secrets(request.body.as_deref().unwrap_or("None")));
+}
 
-        if status.is_success() {
-            println!("{} {} Http request success, job name: {}", uuid, local_time, &http_job.name);
-            println!("{} {} Http response: {}", uuid, local_time, text);
-        } else {
-            println!("{} {} Http request failed, job name: {}, http status: {}", uuid, local_time, &http_job.name, status.as_u16());
-            println!("{} {} Http response: {}", uuid, local_time, text);
+/// Adds the configured correlation header, if any, to a job request.
+///
+/// # Arguments
+///
+/// * `request_builder` - The request to add the header to.
+/// * `uuid` - The identifier of the current run.
+/// * `job_name` - The name of the job.
+fn apply_correlation_header(request_builder: RequestBuilder, uuid: &str, job_name: &str) -> RequestBuilder {
+    match &get_jobs().correlation_header {
+        Some(header) => request_builder.header(&header.name, header.render_value(uuid, job_name)),
+        None => request_builder
+    }
+}
+
+/// Records the result of a request attempt on its span and ends the span.
+///
+/// # Arguments
+///
+/// * `cx` - The context holding the span of the attempt.
+/// * `status` - The HTTP status of the response, if one was received.
+/// * `error` - The error of the attempt, if it failed.
+fn end_attempt_span(cx: &Context, status: Option<u16>, error: Option<&str>) {
+    let span = cx.span();
+    if let Some(status) = status {
+        span.set_attribute(KeyValue::new("http.response.status_code", status as i64));
+    }
+    if let Some(error) = error {
+        span.set_status(Status::error(error.to_string()));
+    }
+    span.end();
+}
+
+/// Builds the HTTP request of a job with the given client.
+///
+/// The method, headers and body of the request are applied, along with the HTTP version and
+/// the `Content-Type`/`Content-Encoding` headers of the body. The headers of the request are
+/// merged with the default headers of the configuration, see `merge_headers`. A `Content-Type`
+/// set in the headers takes precedence over the content type of the body. The request is sent
+/// to its `target_url`, with its `host` as the `Host` header and its `accept_encoding` as the
+/// `Accept-Encoding` header if set.
+///
+/// # Arguments
+///
+/// * `client` - The HTTP client used to send the request.
+/// * `request` - The request of the HTTP job.
+/// * `body` - The encoded body, as returned by `get_request_body`.
+///
+/// # Examples
+///
+/// ```
+/// let body = get_request_body(&http_job.request)?;
+/// let resp = build_request(&client, &http_job.request, body).send().await?;
+/// ```
+pub fn build_request(client: &Client, request: &HttpJobRequest, body: Vec<u8>) -> RequestBuilder {
+    let headers = merge_headers(&get_jobs().default_headers, request);
+    let has_content_type = headers.contains_key(CONTENT_TYPE);
+
+    let mut request_builder = client.request(get_method(&request.method), request.target_url().as_ref())
+        .headers(headers)
+        .body(body);
+
+    if let Some(host) = &request.host {
+        request_builder = request_builder.header(HOST, host);
+    }
+    if let Some(accept_encoding) = request.accept_encoding_header() {
+        request_builder = request_builder.header(ACCEPT_ENCODING, accept_encoding);
+    }
+
+    if request.http_version == HttpVersion::Http2 {
+        request_builder = request_builder.version(Version::HTTP_2);
+    }
+
+    if request.body.is_some() {
+        if let Some(content_type) = request.content_type.as_ref().filter(|_| !has_content_type) {
+            request_builder = request_builder.header(CONTENT_TYPE, content_type);
+        }
+
+        if let Some(compression) = &request.compress_body {
+            request_builder = request_builder.header("Content-Encoding", compression.content_encoding());
+        }
+    }
+
+    request_builder
+}
+
+/// Merges the default headers with the headers of a request.
+///
+/// A header of the request replaces every value of the default header of the same name, and
+/// the headers the request removes are left out. Header names are case-insensitive.
+///
+/// # Arguments
+///
+/// * `defaults` - The default headers of the configuration.
+/// * `request` - The request of the HTTP job.
+///
+/// # Examples
+///
+/// ```
+/// let headers = merge_headers(&get_jobs().default_headers, &http_job.request);
+/// ```
+pub fn merge_headers(defaults: &HeaderMap, request: &HttpJobRequest) -> HeaderMap {
+    let mut headers = defaults.clone();
+    for name in &request.removed_headers {
+        headers.remove(name);
+    }
+
+    if let Some(own) = &request.headers {
+        for name in own.keys() {
+            headers.remove(name);
+        }
+        for (name, value) in own {
+            headers.append(name, value.clone());
         }
-        break;
     }
 
-    println!("{} {} Http job end, job name: {}\n", uuid, local_time, &http_job.name);
+    headers
+}
+
+/// Checks the body of a GraphQL response for errors.
+///
+/// GraphQL servers usually report errors with a 200 status, in a non-empty `errors` array.
+///
+/// # Arguments
+///
+/// * `body` - The body of the response.
+///
+/// # Errors
+///
+/// Returns the messages of the errors, or an error if the body is not a JSON object.
+///
+/// # Examples
+///
+/// ```
+/// assert!(check_graphql_response(br#"{"data":{"user":null}}"#).is_ok());
+/// assert!(check_graphql_response(br#"{"errors":[{"message":"Not found"}]}"#).is_err());
+/// ```
+pub fn check_graphql_response(body: &[u8]) -> Result<(), String> {
+    let value: Value = serde_json::from_slice(body)
+        .map_err(|e| format!("The GraphQL response is not valid JSON: {}", e))?;
+
+    let errors = match value.get("errors").and_then(|e| e.as_array()) {
+        Some(errors) if !errors.is_empty() => errors,
+        _ => return Ok(())
+    };
+
+    let messages = errors.iter()
+        .map(|error| match error.get("message").and_then(|m| m.as_str()) {
+            Some(message) => message.to_string(),
+            None => error.to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    Err(format!("GraphQL errors: {}", messages))
 }
 
 /// Get the corresponding `Method` enum value for the given HTTP method string.
@@ -148,4 +1326,237 @@ fn get_method(method: &str) -> Method {
         "head" => Method::HEAD,
         _ => Method::GET
     }
+}
+
+/// Parses the value of a `Retry-After` header, either a number of seconds or an HTTP date.
+///
+/// A date in the past means no wait.
+///
+/// # Returns
+///
+/// The time to wait before retrying, or `None` if the value is invalid.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+/// assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), Some(Duration::ZERO));
+/// ```
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some((date.with_timezone(&Utc) - Utc::now()).to_std().unwrap_or_default())
+}
+
+/// Returns whether an HTTP method is idempotent, i.e. sending the request twice has the same
+/// effect as sending it once.
+///
+/// # Examples
+///
+/// ```
+/// assert!(is_idempotent("PUT"));
+/// assert!(!is_idempotent("post"));
+/// ```
+fn is_idempotent(method: &str) -> bool {
+    !matches!(get_method(method), Method::POST | Method::PATCH)
+}
+
+/// Returns the bytes to send as the body of the job's request.
+///
+/// The body is compressed if `compress_body` is configured on the request. A request without
+/// a body yields an empty vector.
+///
+/// # Arguments
+///
+/// * `request` - The request of the HTTP job.
+///
+/// # Errors
+///
+/// Returns an error if the body fails to be compressed.
+///
+/// # Examples
+///
+/// ```
+/// let body = get_request_body(&http_job.request)?;
+/// println!("Body size: {}", body.len());
+/// ```
+pub fn get_request_body(request: &HttpJobRequest) -> Result<Vec<u8>, Box<dyn Error>> {
+    let body = request.body.clone().unwrap_or_default().into_bytes();
+
+    match (&request.body, &request.compress_body) {
+        (Some(_), Some(BodyCompression::Gzip)) => Ok(gzip(&body)?),
+        _ => Ok(body)
+    }
+}
+
+/// Logs the body of a response according to the `log_response` setting of the job.
+///
+/// A discarded body is not logged, its size was logged when it was read, see
+/// `receive_response_body`.
+///
+/// # Arguments
+///
+/// * `response` - The response settings of the job.
+/// * `status` - The HTTP status of the response.
+/// * `body` - The body of the response, as returned by `read_response_body`.
+/// * `truncated` - Whether the body was truncated to `max_response_bytes`.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+///
+/// # Examples
+///
+/// ```
+/// let (body, truncated) = read_response_body(resp, http_job.response.max_response_bytes).await?;
+/// log_response_body(&http_job.response, 200, &body, truncated, &uuid, &local_time);
+/// ```
+fn log_response_body(response: &HttpJobResponse, status: u16, body: &[u8], truncated: bool, uuid: &str, local_time: &str) {
+    if response.discard_body {
+        return;
+    }
+    let (logged, truncated_mark) = match response.log_response {
+        ResponseLogging::None => return,
+        ResponseLogging::Status => {
+            let size = if truncated { format!("more than {}", body.len()) } else { body.len().to_string() };
+            println!("{} {} Http response: status {}, {} bytes", uuid, local_time, status, size);
+            return;
+        }
+        ResponseLogging::Truncated if body.len() > response.log_response_bytes => {
+            (&body[..response.log_response_bytes], format!(" ...[truncated to {} bytes]", response.log_response_bytes))
+        }
+        _ if truncated => (body, format!(" ...[truncated to {} bytes]", body.len())),
+        _ => (body, String::new())
+    };
+
+    // A multi-byte character cut at the end of the logged bytes is rendered as U+FFFD.
+    println!("{} {} Http response: {}{}", uuid, local_time, String::from_utf8_lossy(logged), truncated_mark);
+}
+
+/// Reads the body of a response according to the response settings of the job.
+///
+/// With `discard_body`, the body is read to the end chunk by chunk and each chunk is dropped
+/// right away, so a large download used as a health or latency check doesn't allocate its
+/// size. Only the size of the body is logged, and the returned body is empty. Otherwise, at most
+/// `max_response_bytes` bytes are kept, see `read_response_body`.
+///
+/// # Arguments
+///
+/// * `resp` - The response to read.
+/// * `response` - The response settings of the job.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+///
+/// # Returns
+///
+/// A tuple of the (possibly truncated) body and whether it was truncated.
+///
+/// # Errors
+///
+/// Returns an error if reading a chunk of the body fails.
+///
+/// # Examples
+///
+/// ```
+/// let (body, truncated) = receive_response_body(resp, &http_job.response, &uuid, &local_time).await?;
+/// log_response_body(&http_job.response, status.as_u16(), &body, truncated, &uuid, &local_time);
+/// ```
+async fn receive_response_body(mut resp: Response, response: &HttpJobResponse, uuid: &str, local_time: &str) -> Result<(Vec<u8>, bool), reqwest::Error> {
+    if !response.discard_body {
+        return read_response_body(resp, response.max_response_bytes).await;
+    }
+
+    let status = resp.status().as_u16();
+    let mut size = 0;
+    while let Some(chunk) = resp.chunk().await? {
+        size += chunk.len() as u64;
+    }
+    if response.log_response != ResponseLogging::None {
+        println!("{} {} Http response: status {}, {} bytes discarded", uuid, local_time, status, size);
+    }
+
+    Ok((Vec::new(), false))
+}
+
+/// Reads the body of a response, keeping at most `max_bytes` bytes in memory.
+///
+/// The body is consumed chunk by chunk. Once the limit is reached, the rest of the body is not
+/// read and the returned flag is set to `true`.
+///
+/// # Arguments
+///
+/// * `resp` - The response to read.
+/// * `max_bytes` - The maximum number of bytes to keep.
+///
+/// # Returns
+///
+/// A tuple of the (possibly truncated) body and whether it was truncated.
+///
+/// # Errors
+///
+/// Returns an error if reading a chunk of the body fails.
+///
+/// # Examples
+///
+/// ```
+/// let (body, truncated) = read_response_body(resp, 1024).await?;
+/// println!("Read {} bytes, truncated: {}", body.len(), truncated);
+/// ```
+async fn read_response_body(mut resp: Response, max_bytes: u64) -> Result<(Vec<u8>, bool), reqwest::Error> {
+    let max_bytes = max_bytes as usize;
+    let mut body = Vec::new();
+
+    while let Some(chunk) = resp.chunk().await? {
+        let remaining = max_bytes - body.len();
+        if chunk.len() > remaining {
+            body.extend_from_slice(&chunk[..remaining]);
+            return Ok((body, true));
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok((body, false))
+}
+
+/// Writes the response body of a job run to the file described by the `save_to` template.
+///
+/// The template supports the `{{job_name}}`, `{{run_id}}`, `{{now}}` and `{{date}}` variables.
+/// Missing parent directories are created.
+///
+/// # Arguments
+///
+/// * `save_to` - The path template of the file.
+/// * `http_job` - The HTTP job that produced the response.
+/// * `run_id` - The identifier of the current run.
+/// * `timezone` - The timezone used to render the date variables.
+/// * `body` - The response body.
+///
+/// # Returns
+///
+/// The path of the written file.
+///
+/// # Errors
+///
+/// Returns an error if the directories or the file cannot be written.
+///
+/// # Examples
+///
+/// ```
+/// let path = save_response_body("./out/{{job_name}}-{{now}}.json", http_job, &uuid, &Tz::UTC, b"{}")?;
+/// println!("Saved to: {}", path);
+/// ```
+fn save_response_body(save_to: &str, http_job: &HttpJob, run_id: &str, timezone: &Tz, body: &[u8]) -> Result<String, Box<dyn Error>> {
+    let vars = get_run_vars(&http_job.name, run_id, timezone);
+
+    let path = render_template(save_to, &vars);
+
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("An error occurred while creating the directory '{}': {}", parent.display(), e))?;
+    }
+    fs::write(&path, body)
+        .map_err(|e| format!("An error occurred while writing the file '{}': {}", path, e))?;
+
+    Ok(path)
 }
\ No newline at end of file
@@ -0,0 +1,131 @@
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// A PID file locked by this instance, until the program exits.
+///
+/// The lock, not the existence of the file, tells whether another instance runs, so a PID file
+/// left over by a killed instance doesn't prevent a new one from starting.
+pub struct PidFile {
+    file: File,
+    path: PathBuf,
+}
+
+impl PidFile {
+
+    /// Writes the process id of the current process to the PID file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be written.
+    pub fn write_pid(&mut self) -> io::Result<()> {
+        self.file.set_len(0)?;
+        self.file.seek(SeekFrom::Start(0))?;
+        writeln!(self.file, "{}", process::id())?;
+        self.file.sync_all()
+    }
+
+    /// Removes the PID file when the program stops cleanly.
+    pub fn remove(self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            println!("Failed to remove the PID file, file: {}, error: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Locks the PID file of the scheduler, creating it if needed.
+///
+/// The file is locked with `flock`, and the lock is inherited by the daemon when it detaches,
+/// see `daemonize`. The lock is held until the process exits.
+///
+/// # Arguments
+///
+/// * `path` - The path of the PID file, e.g. `/var/run/rjob.pid`.
+///
+/// # Errors
+///
+/// Returns an error with the process id of the other instance if another instance holds the
+/// lock, or if the file can't be opened.
+///
+/// # Examples
+///
+/// ```
+/// let mut pid_file = lock_pid_file(Path::new("/var/run/rjob.pid"))?;
+/// daemonize(None)?;
+/// pid_file.write_pid()?;
+/// ```
+pub fn lock_pid_file(path: &Path) -> Result<PidFile, Box<dyn Error>> {
+    let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)
+        .map_err(|e| format!("Failed to open the PID file '{}': {}", path.display(), e))?;
+
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+        let error = io::Error::last_os_error();
+        if error.kind() != io::ErrorKind::WouldBlock {
+            return Err(format!("Failed to lock the PID file '{}': {}", path.display(), error).into());
+        }
+        let mut pid = String::new();
+        let _ = file.read_to_string(&mut pid);
+        return Err(format!("Another instance of rjob is running, PID file: {}, pid: {}", path.display(), pid.trim()).into());
+    }
+
+    Ok(PidFile { file, path: path.to_path_buf() })
+}
+
+/// Detaches the program from its terminal and runs it in the background.
+///
+/// The program forks, the parent exits and the child starts a new session, then forks again so
+/// the daemon is not a session leader and can't acquire a terminal. The standard input of the
+/// daemon is `/dev/null`, its standard output and error go to the log file. The working
+/// directory is kept, as the 'jobs' file and the files it refers to are relative to it.
+///
+/// Must be called before the runtime starts, since only the calling thread survives a fork.
+///
+/// # Arguments
+///
+/// * `log_file` - The file the logs are appended to, or `None` to discard them.
+///
+/// # Errors
+///
+/// Returns an error if the log file can't be opened or the program can't be forked.
+///
+/// # Examples
+///
+/// ```
+/// daemonize(Some(Path::new("/var/log/rjob.log")))?;
+/// ```
+pub fn daemonize(log_file: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let output = match log_file {
+        Some(path) => OpenOptions::new().create(true).append(true).open(path)
+            .map_err(|e| format!("Failed to open the log file '{}': {}", path.display(), e))?,
+        None => OpenOptions::new().write(true).open("/dev/null")?
+    };
+    let input = File::open("/dev/null")?;
+    io::stdout().flush()?;
+
+    fork()?;
+    if unsafe { libc::setsid() } == -1 {
+        return Err(format!("Failed to start a new session: {}", io::Error::last_os_error()).into());
+    }
+    fork()?;
+
+    println!("Running in the background, pid: {}", process::id());
+    io::stdout().flush()?;
+    unsafe {
+        libc::dup2(input.as_raw_fd(), libc::STDIN_FILENO);
+        libc::dup2(output.as_raw_fd(), libc::STDOUT_FILENO);
+        libc::dup2(output.as_raw_fd(), libc::STDERR_FILENO);
+    }
+    Ok(())
+}
+
+/// Forks the program, the parent exits and the child continues.
+fn fork() -> Result<(), Box<dyn Error>> {
+    match unsafe { libc::fork() } {
+        -1 => Err(format!("Failed to fork: {}", io::Error::last_os_error()).into()),
+        0 => Ok(()),
+        _ => unsafe { libc::_exit(0) }
+    }
+}
\ No newline at end of file
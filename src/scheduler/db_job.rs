@@ -0,0 +1,104 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sqlx::{AnyConnection, Connection};
+
+use crate::models::db_job::DbJob;
+use crate::models::job::{Job, JobFuture};
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+use crate::models::run_outcome::RunOutcome;
+
+impl Job for DbJob {
+    fn kind(&self) -> &'static str {
+        "Database"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn enable(&self) -> bool {
+        self.enable
+    }
+
+    fn schedule(&self) -> &JobSchedule {
+        &self.schedule
+    }
+
+    fn options(&self) -> &JobOptions {
+        &self.options
+    }
+
+    fn execute(self: Arc<Self>, uuid: String, local_time: String) -> JobFuture<RunOutcome> {
+        Box::pin(async move { execute_db_job(&self, &uuid, &local_time).await })
+    }
+}
+
+/// Executes the statement of a job, retrying on failure, and returns the outcome.
+///
+/// Each attempt opens its own connection and is abandoned once the timeout of the job elapses.
+///
+/// # Arguments
+///
+/// * `db_job` - The database job to run.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+///
+/// # Examples
+///
+/// ```
+/// let outcome = execute_db_job(db_job, &uuid, &local_time).await;
+/// println!("Success: {}", outcome.success);
+/// ```
+pub async fn execute_db_job(db_job: &DbJob, uuid: &str, local_time: &str) -> RunOutcome {
+    sqlx::any::install_default_drivers();
+
+    let timeout = Duration::from_millis(db_job.timeout);
+    let mut attempts = 0;
+    let mut last_error = String::from("No attempt was made.");
+
+    while attempts < db_job.max_retry {
+        attempts += 1;
+
+        let started = Instant::now();
+        let result = match tokio::time::timeout(timeout, execute_statement(db_job)).await {
+            Ok(result) => result,
+            Err(_) => Err(format!("Timed out after {}ms", db_job.timeout))
+        };
+
+        match result {
+            Ok(rows_affected) => {
+                println!("{} {} Statement success, job name: {}, rows affected: {}, elapsed: {}ms",
+                         uuid, local_time, &db_job.name, rows_affected, started.elapsed().as_millis());
+                return RunOutcome::new(uuid.to_string(), db_job.name.clone(), true, None, attempts, None);
+            }
+            Err(err) => {
+                println!("{} {} Statement failed, job name: {}, error: {}. Retry attempt: {}/{}",
+                         uuid, local_time, &db_job.name, err, attempts, db_job.max_retry);
+                last_error = err;
+            }
+        }
+    }
+
+    RunOutcome::new(uuid.to_string(), db_job.name.clone(), false, None, attempts, Some(last_error))
+}
+
+/// Connects to the database of a job and executes its statement.
+///
+/// # Returns
+///
+/// The number of rows affected by the statement.
+async fn execute_statement(db_job: &DbJob) -> Result<u64, String> {
+    let mut connection = AnyConnection::connect(&db_job.dsn)
+        .await
+        .map_err(|e| format!("Failed to connect: {}", e))?;
+
+    let result = sqlx::query(&db_job.statement)
+        .execute(&mut connection)
+        .await
+        .map_err(|e| e.to_string());
+
+    let _ = connection.close().await;
+
+    result.map(|r| r.rows_affected())
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use crate::models::dead_letter_record::DeadLetterRecord;
+
+/// Appends a record to the dead-letter file.
+///
+/// The file uses the JSON Lines format, one record per line. It is created if it doesn't exist.
+///
+/// # Arguments
+///
+/// * `path` - The path of the dead-letter file.
+/// * `record` - The record to append.
+///
+/// # Errors
+///
+/// Returns an error if the record cannot be serialized or the file cannot be written.
+///
+/// # Examples
+///
+/// ```
+/// append_dead_letter("./dead_letter.jsonl", &record)?;
+/// ```
+pub fn append_dead_letter(path: &str, record: &DeadLetterRecord) -> Result<(), Box<dyn Error>> {
+    let mut line = serde_json::to_string(record)?;
+    line.push('\n');
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("An error occurred while opening the file '{}': {}", path, e))?;
+    file.write_all(line.as_bytes())
+        .map_err(|e| format!("An error occurred while writing the file '{}': {}", path, e))?;
+
+    Ok(())
+}
+
+/// Reads all records from the dead-letter file.
+///
+/// A missing file is treated as an empty dead-letter file.
+///
+/// # Arguments
+///
+/// * `path` - The path of the dead-letter file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or a line is not a valid record.
+///
+/// # Examples
+///
+/// ```
+/// let records = read_dead_letters("./dead_letter.jsonl")?;
+/// println!("{} records", records.len());
+/// ```
+pub fn read_dead_letters(path: &str) -> Result<Vec<DeadLetterRecord>, Box<dyn Error>> {
+    if !Path::new(path).exists() {
+        return Ok(vec![]);
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("An error occurred while reading the file '{}': {}", path, e))?;
+
+    content.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str::<DeadLetterRecord>(line)
+                .map_err(|e| format!("Invalid record at line {} of '{}': {}", i + 1, path, e).into())
+        })
+        .collect()
+}
+
+/// Replaces the content of the dead-letter file with the given records.
+///
+/// # Arguments
+///
+/// * `path` - The path of the dead-letter file.
+/// * `records` - The records to keep.
+///
+/// # Errors
+///
+/// Returns an error if a record cannot be serialized or the file cannot be written.
+///
+/// # Examples
+///
+/// ```
+/// write_dead_letters("./dead_letter.jsonl", &remaining)?;
+/// ```
+pub fn write_dead_letters(path: &str, records: &[DeadLetterRecord]) -> Result<(), Box<dyn Error>> {
+    let mut content = String::new();
+    for record in records {
+        content.push_str(&serde_json::to_string(record)?);
+        content.push('\n');
+    }
+
+    fs::write(path, content)
+        .map_err(|e| format!("An error occurred while writing the file '{}': {}", path, e).into())
+}
\ No newline at end of file
@@ -0,0 +1,134 @@
+use std::error::Error;
+use std::future::Future;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::configure::get_jobs;
+use crate::models::distributed_lock::{DistributedLock, OnLockError};
+use crate::scheduler::redis_client::{RedisConnection, RedisReply};
+
+/// The timeout of the connection to the lock server and of each command.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The connection to the lock server, opened on the first run and reopened after an error.
+static CONNECTION: Lazy<Mutex<Option<RedisConnection>>> = Lazy::new(|| Mutex::new(None));
+
+/// The identifier of this instance of the scheduler, see `get_instance_id`.
+static INSTANCE_ID: Lazy<String> = Lazy::new(|| Uuid::new_v4().to_string());
+
+/// Returns the identifier of this instance of the scheduler, random at each start.
+///
+/// The identifier is the value of the Redis keys held by the instance, the locks of its runs
+/// and the key of the leader, see `start_leader_election`.
+pub fn get_instance_id() -> &'static str {
+    INSTANCE_ID.as_str()
+}
+
+/// Runs a triggered run of a job if this instance acquires its lock, see `acquire_run_lock`.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job.
+/// * `scheduled_at` - The fire time of the trigger that started the run.
+/// * `run` - The run of the job.
+///
+/// # Examples
+///
+/// ```
+/// tokio::spawn(with_run_lock(job.name().to_string(), scheduled_at, start_job(job.clone())));
+/// ```
+pub async fn with_run_lock<F: Future<Output = ()>>(job_name: String, scheduled_at: DateTime<Utc>, run: F) {
+    if acquire_run_lock(&job_name, scheduled_at).await {
+        run.await;
+    }
+}
+
+/// Acquires the lock of the run of a job at a fire time, shared by all instances of the scheduler.
+///
+/// The lock is a Redis key made of the `key_prefix`, the job name and the fire time in UTC, set
+/// only if it doesn't exist yet and expiring after the `ttl` of the `distributed_lock` section.
+/// Only the instance that sets the key runs the job, so each fire time runs once across all the
+/// instances, as long as they fire within `ttl` of each other. When the lock server cannot be
+/// reached, `on_error` decides whether the run is skipped or runs anyway. Without a
+/// `distributed_lock` section every run is allowed.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job.
+/// * `scheduled_at` - The fire time of the trigger that started the run.
+///
+/// # Returns
+///
+/// `true` if this instance may run the job, `false` if the run is skipped.
+///
+/// # Examples
+///
+/// ```
+/// if acquire_run_lock("users", scheduled_at).await {
+///     start_job(job).await;
+/// }
+/// ```
+pub async fn acquire_run_lock(job_name: &str, scheduled_at: DateTime<Utc>) -> bool {
+    let Some(lock) = &get_jobs().distributed_lock else {
+        return true;
+    };
+    let key = format!("{}:{}:{}", lock.key_prefix, job_name, scheduled_at.format("%Y-%m-%dT%H:%M:%S%.3fZ"));
+    let local_time = scheduled_at.with_timezone(&get_jobs().timezone);
+
+    match set_lock(lock, &key).await {
+        Ok(true) => true,
+        Ok(false) => {
+            println!("Run skipped, job name: {}, scheduled at: {}, locked by another instance", job_name, local_time);
+            false
+        }
+        Err(e) if lock.on_error == OnLockError::Run => {
+            println!("Failed to acquire lock, job name: {}, scheduled at: {}, error: {}, running anyway", job_name, local_time, e);
+            true
+        }
+        Err(e) => {
+            println!("Run skipped, job name: {}, scheduled at: {}, failed to acquire lock: {}", job_name, local_time, e);
+            false
+        }
+    }
+}
+
+/// Sets the key of a lock if it doesn't exist, on the shared connection.
+///
+/// A failed command is retried once on a new connection, since the server may have closed an
+/// idle connection.
+///
+/// # Returns
+///
+/// `true` if the key was set, `false` if it already existed.
+async fn set_lock(lock: &DistributedLock, key: &str) -> Result<bool, Box<dyn Error>> {
+    let ttl = lock.ttl.as_millis().max(1).to_string();
+    let args = ["SET", key, INSTANCE_ID.as_str(), "NX", "PX", ttl.as_str()];
+    let mut connection = CONNECTION.lock().await;
+
+    let mut retried = false;
+    loop {
+        let redis = match connection.as_mut() {
+            Some(redis) => redis,
+            None => {
+                let redis = RedisConnection::connect(&lock.redis, LOCK_TIMEOUT).await?;
+                println!("Connected to lock server, address: {}, instance: {}", lock.redis, INSTANCE_ID.as_str());
+                connection.insert(redis)
+            }
+        };
+
+        match redis.command(&args).await {
+            Ok(reply) => return Ok(reply != RedisReply::Nil),
+            Err(e) if retried => {
+                *connection = None;
+                return Err(e);
+            }
+            Err(_) => {
+                *connection = None;
+                retried = true;
+            }
+        }
+    }
+}
\ No newline at end of file
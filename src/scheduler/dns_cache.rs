@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use tokio::time::Instant;
+
+use crate::configure::get_jobs;
+
+/// The addresses of the host names resolved by the HTTP clients, keyed by lowercase host name.
+static DNS_CACHE: Lazy<Mutex<HashMap<String, CachedAddrs>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// The resolved addresses of a host name and the time they expire.
+struct CachedAddrs {
+    addrs: Vec<SocketAddr>,
+    expires_at: Instant,
+}
+
+/// The DNS resolver of the HTTP clients, caching the lookups as configured by the `dns_cache`
+/// section.
+///
+/// Without a `dns_cache` section, every lookup goes to the system resolver, as with the default
+/// resolver of reqwest. The section is read on each lookup, so a reload takes effect on the
+/// shared clients too.
+///
+/// # Examples
+///
+/// ```
+/// let client = Client::builder().dns_resolver(Arc::new(CachingResolver)).build()?;
+/// ```
+pub struct CachingResolver;
+
+impl Resolve for CachingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_lowercase();
+        Box::pin(async move {
+            let addrs = resolve_host(host).await?;
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Removes the cached addresses of the host of a URL, so the next request resolves it again.
+///
+/// Called when a request fails to connect or times out, as the host may have moved to other
+/// addresses since it was resolved.
+///
+/// # Arguments
+///
+/// * `url` - The URL of the failed request.
+///
+/// # Returns
+///
+/// Whether addresses of the host were cached.
+///
+/// # Examples
+///
+/// ```
+/// if flush_dns_cache("https://api.example.com/users") {
+///     println!("DNS cache flushed");
+/// }
+/// ```
+pub fn flush_dns_cache(url: &str) -> bool {
+    let Some(host) = reqwest::Url::parse(url).ok().and_then(|url| url.host_str().map(|host| host.to_lowercase())) else {
+        return false;
+    };
+    DNS_CACHE.lock().unwrap().remove(&host).is_some()
+}
+
+/// Returns the addresses of a host name, from the cache if they were resolved less than `ttl`
+/// ago.
+///
+/// The port of the addresses is 0, the client sets the port of the URL.
+async fn resolve_host(host: String) -> Result<Vec<SocketAddr>, Box<dyn Error + Send + Sync>> {
+    let ttl = get_jobs().dns_cache.map(|dns_cache| dns_cache.ttl);
+
+    if ttl.is_some() {
+        let cache = DNS_CACHE.lock().unwrap();
+        if let Some(cached) = cache.get(&host).filter(|cached| cached.expires_at > Instant::now()) {
+            return Ok(cached.addrs.clone());
+        }
+    }
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+    if let Some(ttl) = ttl {
+        let cached = CachedAddrs { addrs: addrs.clone(), expires_at: Instant::now() + ttl };
+        DNS_CACHE.lock().unwrap().insert(host, cached);
+    }
+    Ok(addrs)
+}
\ No newline at end of file
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+use std::time::Duration;
+use hickory_resolver::TokioAsyncResolver;
+use hickory_resolver::config::{NameServerConfigGroup, ResolverConfig, ResolverOpts};
+use hickory_resolver::system_conf::read_system_conf;
+
+use crate::models::dns_job::DnsJob;
+use crate::models::job::{Job, JobFuture};
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+use crate::models::run_outcome::RunOutcome;
+
+impl Job for DnsJob {
+    fn kind(&self) -> &'static str {
+        "DNS"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn enable(&self) -> bool {
+        self.enable
+    }
+
+    fn schedule(&self) -> &JobSchedule {
+        &self.schedule
+    }
+
+    fn options(&self) -> &JobOptions {
+        &self.options
+    }
+
+    fn execute(self: Arc<Self>, uuid: String, local_time: String) -> JobFuture<RunOutcome> {
+        Box::pin(async move { execute_dns_job(&self, &uuid, &local_time).await })
+    }
+}
+
+/// Resolves the query of a job, retrying on failure, and returns the outcome.
+///
+/// # Arguments
+///
+/// * `dns_job` - The DNS job to run.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+///
+/// # Examples
+///
+/// ```
+/// let outcome = execute_dns_job(dns_job, &uuid, &local_time).await;
+/// println!("Success: {}", outcome.success);
+/// ```
+pub async fn execute_dns_job(dns_job: &DnsJob, uuid: &str, local_time: &str) -> RunOutcome {
+    let mut attempts = 0;
+    let mut last_error = String::from("No attempt was made.");
+
+    while attempts < dns_job.max_retry {
+        attempts += 1;
+
+        match check_dns(dns_job).await {
+            Ok(values) => {
+                println!("{} {} DNS query success, job name: {}, {} {}: {}",
+                         uuid, local_time, &dns_job.name, &dns_job.query, dns_job.record_type, values.join(", "));
+                return RunOutcome::new(uuid.to_string(), dns_job.name.clone(), true, None, attempts, None);
+            }
+            Err(err) => {
+                println!("{} {} DNS query failed, job name: {}, error: {}. Retry attempt: {}/{}",
+                         uuid, local_time, &dns_job.name, err, attempts, dns_job.max_retry);
+                last_error = err;
+            }
+        }
+    }
+
+    RunOutcome::new(uuid.to_string(), dns_job.name.clone(), false, None, attempts, Some(last_error))
+}
+
+/// Resolves the query of a job and compares the values with the expected ones.
+///
+/// Values are compared without the trailing dot of domain names.
+///
+/// # Returns
+///
+/// The sorted values of the returned records.
+///
+/// # Errors
+///
+/// Returns an error if the query fails or the values differ from the expected ones.
+async fn check_dns(dns_job: &DnsJob) -> Result<Vec<String>, String> {
+    let (config, mut options) = match dns_job.resolver {
+        Some(resolver) => {
+            let name_servers = NameServerConfigGroup::from_ips_clear(&[resolver.ip()], resolver.port(), true);
+            (ResolverConfig::from_parts(None, vec![], name_servers), ResolverOpts::default())
+        }
+        None => read_system_conf().map_err(|e| format!("Failed to read system resolver configuration: {}", e))?
+    };
+    options.timeout = Duration::from_millis(dns_job.timeout);
+    options.attempts = 0;
+    options.cache_size = 0;
+
+    let resolver = TokioAsyncResolver::tokio(config, options);
+    let lookup = resolver.lookup(dns_job.query.as_str(), dns_job.record_type)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut values: Vec<String> = lookup.iter()
+        .map(|record| record.to_string().trim_end_matches('.').to_string())
+        .collect();
+    values.sort();
+
+    if let Some(expect) = &dns_job.expect {
+        let mut expect: Vec<String> = expect.iter()
+            .map(|value| value.trim_end_matches('.').to_string())
+            .collect();
+        expect.sort();
+
+        if values != expect {
+            return Err(format!("Expected [{}], found [{}]", expect.join(", "), values.join(", ")));
+        }
+    }
+
+    Ok(values)
+}
\ No newline at end of file
@@ -0,0 +1,109 @@
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+
+/// The upper bounds of the buckets of the drift histogram, in seconds.
+pub const DRIFT_BUCKETS: [f64; 8] = [0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 30.0, 60.0];
+
+tokio::task_local! {
+    /// The fire time a run was scheduled at, set for the runs started by the timer.
+    static SCHEDULED_AT: DateTime<Utc>;
+}
+
+/// The drifts of the runs started so far.
+static DRIFT: Lazy<Mutex<DriftStats>> = Lazy::new(|| Mutex::new(DriftStats::default()));
+
+/// The histogram of the drifts of the scheduled runs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DriftStats {
+    /// The number of runs with a drift of at most each bound of `DRIFT_BUCKETS`.
+    pub buckets: [u64; DRIFT_BUCKETS.len()],
+    pub count: u64,
+    pub sum: Duration,
+    pub max: Duration,
+}
+
+/// Runs a job run with the fire time it was scheduled at, see `record_drift`.
+///
+/// # Arguments
+///
+/// * `scheduled_at` - The fire time of the trigger that started the run.
+/// * `run` - The run of the job.
+///
+/// # Examples
+///
+/// ```
+/// tokio::spawn(with_scheduled_time(scheduled_at, start_job(job.clone())));
+/// ```
+pub fn with_scheduled_time<F: Future>(scheduled_at: DateTime<Utc>, run: F) -> impl Future<Output = F::Output> {
+    SCHEDULED_AT.scope(scheduled_at, run)
+}
+
+/// Records the drift of the current run, the time between its scheduled fire time and now.
+///
+/// The drift adds up the lateness of the timer, the wait in the run queue and the wait for the
+/// group of the job, so it is called when the run actually starts. A catch-up run is scheduled
+/// at the fire time it missed, so its drift is how late it is. Runs started by a hook or the
+/// `run` command have no scheduled fire time and no drift.
+///
+/// # Returns
+///
+/// The drift of the run, or `None` if the run was not started by the timer.
+///
+/// # Examples
+///
+/// ```
+/// let drift = record_drift();
+/// println!("TCP job start, job name: {}{}", &tcp_job.name, format_drift(drift));
+/// ```
+pub fn record_drift() -> Option<Duration> {
+    let scheduled_at = get_scheduled_time()?;
+    let drift = (Utc::now() - scheduled_at).to_std().unwrap_or_default();
+
+    let mut stats = DRIFT.lock().unwrap();
+    for (bucket, bound) in stats.buckets.iter_mut().zip(DRIFT_BUCKETS) {
+        if drift.as_secs_f64() <= bound {
+            *bucket += 1;
+        }
+    }
+    stats.count += 1;
+    stats.sum += drift;
+    stats.max = stats.max.max(drift);
+
+    Some(drift)
+}
+
+/// Returns the fire time the current run was scheduled at, see `with_scheduled_time`.
+///
+/// # Returns
+///
+/// The fire time of the run, or `None` if the run was not started by the timer or a catch-up.
+pub fn get_scheduled_time() -> Option<DateTime<Utc>> {
+    SCHEDULED_AT.try_with(|at| *at).ok()
+}
+
+/// Formats the drift of a run as a suffix of its start log.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(format_drift(Some(Duration::from_millis(3))), ", drift: 3ms");
+/// assert_eq!(format_drift(None), "");
+/// ```
+pub fn format_drift(drift: Option<Duration>) -> String {
+    drift.map(|d| format!(", drift: {}ms", d.as_millis())).unwrap_or_default()
+}
+
+/// Returns the histogram of the drifts, for the metrics of the admin server.
+///
+/// # Examples
+///
+/// ```
+/// let stats = get_drift_stats();
+/// println!("Runs: {}, max drift: {:?}", stats.count, stats.max);
+/// ```
+pub fn get_drift_stats() -> DriftStats {
+    *DRIFT.lock().unwrap()
+}
\ No newline at end of file
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+
+use crate::configure::get_jobs;
+use crate::models::notifications::JobNotifications;
+use crate::models::run_outcome::RunOutcome;
+use crate::notifier::{send_notifications, NotificationEvent};
+use crate::scheduler::leader_election::is_leader;
+use crate::scheduler::sharding::is_assigned;
+use crate::scheduler::state::read_state;
+use crate::utils::datetime_util::get_local_datetime_in_timezone;
+use crate::utils::duration_util::format_duration;
+use crate::utils::uuid_util::generate_uuid_without_hyphens;
+
+/// The interval at which the jobs with `alert_if_no_success_for` are checked.
+const FRESHNESS_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The registered jobs with `alert_if_no_success_for`, keyed by job name.
+static WATCHED_JOBS: Lazy<Mutex<HashMap<String, WatchedJob>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A job checked for a successful run within its window.
+struct WatchedJob {
+    notifications: JobNotifications,
+    window: Duration,
+    /// The time the job was registered, the reference of a job that never succeeded.
+    registered_at: DateTime<Utc>,
+    /// The reference time the last alert was sent for, so a breach is only alerted once.
+    alerted_for: Option<DateTime<Utc>>,
+}
+
+/// Watches a registered job for a successful run within its `alert_if_no_success_for` window.
+///
+/// Registering a job again, e.g. when it is replaced through the admin server, restarts its
+/// watch, or ends it if the new job has no `alert_if_no_success_for`.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job.
+/// * `notifications` - The notification settings of the job.
+///
+/// # Examples
+///
+/// ```
+/// add_trigger(&it.name, &it.schedule, move |scheduled_at| { ... });
+/// watch_success(job.name(), &job.options().notifications);
+/// ```
+pub fn watch_success(job_name: &str, notifications: &JobNotifications) {
+    let Some(window) = notifications.alert_if_no_success_for else {
+        unwatch_success(job_name);
+        return;
+    };
+
+    WATCHED_JOBS.lock().unwrap().insert(job_name.to_string(), WatchedJob {
+        notifications: notifications.clone(),
+        window,
+        registered_at: Utc::now(),
+        alerted_for: None,
+    });
+}
+
+/// Stops watching a job, e.g. when it is removed from the running scheduler.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job.
+pub fn unwatch_success(job_name: &str) {
+    WATCHED_JOBS.lock().unwrap().remove(job_name);
+}
+
+/// Checks the watched jobs in the background every `FRESHNESS_CHECK_INTERVAL`.
+///
+/// A job is stale when its last successful run, as recorded in the state file, or its
+/// registration if it never succeeded since, is older than its `alert_if_no_success_for`
+/// window. This catches the jobs that keep failing as well as the jobs that silently stopped
+/// running, e.g. because their schedule never fires or their runs are dropped. A stale job is
+/// alerted once through the global notification channels with the `stale` event, and again
+/// only after a new successful run was followed by another breach. Paused jobs, jobs assigned
+/// to another instance of the cluster and all jobs of a standby instance are not checked.
+///
+/// # Examples
+///
+/// ```
+/// start_timer();
+/// start_freshness_check();
+/// ```
+pub fn start_freshness_check() {
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(FRESHNESS_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            check_freshness().await;
+        }
+    });
+}
+
+/// Alerts the watched jobs that had no successful run within their window.
+async fn check_freshness() {
+    if !is_leader() || WATCHED_JOBS.lock().unwrap().is_empty() {
+        return;
+    }
+
+    let jobs = get_jobs();
+    let state = match read_state(&jobs.state_file) {
+        Ok(state) => state,
+        Err(err) => {
+            println!("Failed to read state file for the freshness check, error: {}", err);
+            return;
+        }
+    };
+
+    let now = Utc::now();
+    let mut stale_jobs = Vec::new();
+    for (name, job) in WATCHED_JOBS.lock().unwrap().iter_mut().filter(|(name, _)| is_assigned(name)) {
+        let job_state = state.jobs.get(name);
+        if job_state.is_some_and(|job_state| job_state.paused) {
+            continue;
+        }
+
+        let last_success = job_state.and_then(|job_state| job_state.last_success);
+        let since = last_success.unwrap_or(job.registered_at);
+        let overdue = (now - since).to_std().is_ok_and(|elapsed| elapsed > job.window);
+        if overdue && job.alerted_for != Some(since) {
+            job.alerted_for = Some(since);
+            stale_jobs.push((name.clone(), job.notifications.clone(), job.window, last_success));
+        }
+    }
+
+    for (name, notifications, window, last_success) in stale_jobs {
+        let local_time = get_local_datetime_in_timezone(&jobs.timezone);
+        let last_success = last_success
+            .map(|time| time.with_timezone(&jobs.timezone).format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or("never".to_string());
+        println!("{} Job stale, job name: {}, no successful run for: {}, last success: {}", local_time, name, format_duration(window), last_success);
+
+        let error = format!("No successful run for {}, last success: {}", format_duration(window), last_success);
+
+        let outcome = RunOutcome::new(generate_uuid_without_hyphens(), name, false, None, 0, Some(error));
+        send_notifications(&notifications, NotificationEvent::Stale, &outcome).await;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use once_cell::sync::Lazy;
+use tokio::sync::oneshot;
+use tokio::time::Instant;
+
+use crate::configure::get_jobs;
+use crate::models::job_group::JobGroup;
+
+/// The limiters of the groups listed in the `groups` section, keyed by group name.
+static GROUP_LIMITERS: Lazy<HashMap<String, Arc<GroupLimiter>>> = Lazy::new(|| {
+    get_jobs().groups.iter()
+        .map(|(name, group)| (name.clone(), Arc::new(GroupLimiter::new(*group))))
+        .collect()
+});
+
+/// Limits the number of runs of the jobs of a group in progress at the same time.
+///
+/// Runs that cannot start wait in a queue. When a run ends, the waiting run with the highest
+/// priority starts, where a run gains one priority level for every `priority_aging` it waited.
+/// Runs with the same priority start in the order they arrived.
+pub struct GroupLimiter {
+    group: JobGroup,
+    state: Mutex<LimiterState>,
+}
+
+struct LimiterState {
+    running: usize,
+    waiting: Vec<Waiter>,
+}
+
+/// A run waiting for its group.
+struct Waiter {
+    priority: i64,
+    since: Instant,
+    /// Receives the permit of a run that ended.
+    start: oneshot::Sender<()>,
+}
+
+impl Waiter {
+
+    /// Returns the priority of the run, raised by the time it waited.
+    fn effective_priority(&self, now: Instant, priority_aging: Duration) -> i64 {
+        let levels = now.duration_since(self.since).as_millis() / priority_aging.as_millis().max(1);
+        self.priority.saturating_add(i64::try_from(levels).unwrap_or(i64::MAX))
+    }
+}
+
+/// The permission of a run to proceed, handed to the next waiting run when dropped.
+pub struct GroupPermit {
+    limiter: Arc<GroupLimiter>,
+}
+
+impl Drop for GroupPermit {
+    fn drop(&mut self) {
+        self.limiter.release();
+    }
+}
+
+impl GroupLimiter {
+    pub fn new(group: JobGroup) -> Self {
+        GroupLimiter {
+            group,
+            state: Mutex::new(LimiterState {
+                running: 0,
+                waiting: Vec::new(),
+            }),
+        }
+    }
+
+    /// Waits until the run may start.
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - The priority of the job, higher first.
+    ///
+    /// # Returns
+    ///
+    /// The permit of the run and the time spent waiting for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let limiter = Arc::new(GroupLimiter::new(JobGroup::new(1, DEFAULT_PRIORITY_AGING)));
+    /// let (permit, waited) = limiter.acquire(10).await;
+    /// println!("Waited: {:?}", waited);
+    /// ```
+    pub async fn acquire(self: &Arc<Self>, priority: i64) -> (GroupPermit, Duration) {
+        let start = Instant::now();
+
+        let receiver = {
+            let mut state = self.state.lock().unwrap();
+            if state.running < self.group.max_concurrent && state.waiting.is_empty() {
+                state.running += 1;
+                return (GroupPermit { limiter: self.clone() }, Duration::ZERO);
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            state.waiting.push(Waiter { priority, since: start, start: sender });
+            receiver
+        };
+
+        // The sender is only dropped with the limiter, which lives as long as the program.
+        let _ = receiver.await;
+        (GroupPermit { limiter: self.clone() }, start.elapsed())
+    }
+
+    /// Hands the permit of a run that ended to the waiting run with the highest priority.
+    ///
+    /// Waiters that gave up, e.g. because their task was cancelled, are skipped.
+    fn release(&self) {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+
+        while !state.waiting.is_empty() {
+            // The first of the waiters with the highest priority, as they are in arrival order.
+            let (next, _) = state.waiting.iter()
+                .enumerate()
+                .fold(None, |best: Option<(usize, i64)>, (i, waiter)| {
+                    let priority = waiter.effective_priority(now, self.group.priority_aging);
+                    match best {
+                        Some((_, best_priority)) if best_priority >= priority => best,
+                        _ => Some((i, priority))
+                    }
+                })
+                .unwrap();
+
+            let waiter = state.waiting.remove(next);
+            if waiter.start.send(()).is_ok() {
+                return;
+            }
+        }
+
+        state.running -= 1;
+    }
+}
+
+/// Waits until a run of a job of the given group may start.
+///
+/// Jobs without a group, or whose group is not configured, start immediately. A run that had
+/// to wait for other runs of the group is logged.
+///
+/// # Arguments
+///
+/// * `group` - The group of the job.
+/// * `priority` - The priority of the job, higher first.
+/// * `job_name` - The name of the job.
+/// * `uuid` - The identifier of the run.
+/// * `local_time` - The start time of the run, used as log prefix.
+///
+/// # Returns
+///
+/// The permit of the run, handed to the next waiting run when dropped, or `None` if the job
+/// has no group.
+///
+/// # Examples
+///
+/// ```
+/// let _permit = acquire_group_permit(options.group.as_deref(), options.priority, job.name(), &uuid, &local_time).await;
+/// ```
+pub async fn acquire_group_permit(group: Option<&str>, priority: i64, job_name: &str, uuid: &str, local_time: &str) -> Option<GroupPermit> {
+    let group = group?;
+    let limiter = GROUP_LIMITERS.get(group)?;
+
+    let (permit, waited) = limiter.acquire(priority).await;
+    if !waited.is_zero() {
+        println!("{} {} Job waited for its group, job name: {}, group: {}, priority: {}, waited: {}ms",
+                 uuid, local_time, job_name, group, priority, waited.as_millis());
+    }
+
+    Some(permit)
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use once_cell::sync::Lazy;
+use tokio::time::Instant;
+
+/// The interval at which the scheduler heartbeat ticks.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The time without a heartbeat tick after which the scheduler is considered stalled.
+const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The number of jobs registered with the scheduler.
+static REGISTERED_JOBS: AtomicUsize = AtomicUsize::new(0);
+
+/// The time of the last heartbeat tick, or `None` if the heartbeat was never started.
+static LAST_TICK: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
+
+/// A snapshot of the scheduler health.
+#[derive(Debug, Clone, Copy)]
+pub struct SchedulerHealth {
+    pub alive: bool,
+    pub registered_jobs: usize,
+    pub since_last_tick: Option<Duration>,
+}
+
+/// Records the number of jobs registered with the scheduler.
+pub fn set_registered_jobs(count: usize) {
+    REGISTERED_JOBS.store(count, Ordering::Relaxed);
+}
+
+/// Starts the heartbeat task of the scheduler.
+///
+/// The task runs on the same runtime as the jobs and records a tick every second, so a stalled
+/// runtime shows up as a heartbeat that stopped ticking.
+///
+/// # Examples
+///
+/// ```
+/// start_heartbeat();
+/// assert!(get_health().alive);
+/// ```
+pub fn start_heartbeat() {
+    *LAST_TICK.lock().unwrap() = Some(Instant::now());
+
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(HEARTBEAT_INTERVAL);
+        loop {
+            interval.tick().await;
+            *LAST_TICK.lock().unwrap() = Some(Instant::now());
+        }
+    });
+}
+
+/// Returns the current health of the scheduler.
+///
+/// The scheduler is alive if the heartbeat ticked within the last 30 seconds.
+///
+/// # Examples
+///
+/// ```
+/// let health = get_health();
+/// println!("Alive: {}, jobs: {}", health.alive, health.registered_jobs);
+/// ```
+pub fn get_health() -> SchedulerHealth {
+    let since_last_tick = LAST_TICK.lock().unwrap().map(|tick| tick.elapsed());
+
+    SchedulerHealth {
+        alive: since_last_tick.is_some_and(|elapsed| elapsed < HEARTBEAT_TIMEOUT),
+        registered_jobs: REGISTERED_JOBS.load(Ordering::Relaxed),
+        since_last_tick,
+    }
+}
\ No newline at end of file
@@ -0,0 +1,145 @@
+use std::error::Error;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+
+use crate::configure::get_jobs;
+use crate::models::hooks::{HookAction, JobHooks};
+use crate::models::notifications::JobNotifications;
+use crate::models::run_outcome::RunOutcome;
+use crate::notifier::{send_notifications, NotificationEvent};
+use crate::notifier::webhook::send_webhook_event;
+use crate::scheduler::cron_scheduler::start_job;
+use crate::scheduler::registry::get_registered_job;
+use crate::scheduler::run_queue::submit_run;
+use crate::utils::datetime_util::get_local_datetime_in_timezone;
+use crate::utils::redact_util::redact_secrets;
+
+/// The maximum time a hook command may run, in seconds.
+const HOOK_COMMAND_TIMEOUT: u64 = 300;
+
+/// Runs the hook actions of a job that match the outcome of a run.
+///
+/// The `on_success` actions run after a successful run and the `on_failure` actions after a
+/// failed run, in the order they are declared. Errors of individual actions are logged and don't
+/// prevent the other actions from running.
+///
+/// # Arguments
+///
+/// * `hooks` - The hooks of the job.
+/// * `job_notifications` - The notification settings of the job, used by `notify` actions.
+/// * `outcome` - The outcome of the run.
+///
+/// # Examples
+///
+/// ```
+/// run_hooks(&job.options().hooks, &job.options().notifications, &outcome).await;
+/// ```
+pub async fn run_hooks(hooks: &JobHooks, job_notifications: &JobNotifications, outcome: &RunOutcome) {
+    let actions = hooks.actions(outcome.success);
+    if actions.is_empty() {
+        return;
+    }
+
+    let jobs = get_jobs();
+    let hook = if outcome.success { "on_success" } else { "on_failure" };
+    let event = if outcome.success { NotificationEvent::Success } else { NotificationEvent::Failure };
+
+    for action in actions {
+        let local_time = get_local_datetime_in_timezone(&jobs.timezone);
+        println!("{} {} Running {} hook, job name: {}, action: {}", outcome.run_id, local_time, hook, outcome.job_name, redact_secrets(&action.to_string()));
+
+        let result = match action {
+            HookAction::Notify => {
+                send_notifications(job_notifications, event, outcome).await;
+                Ok(())
+            }
+            HookAction::TriggerJob(name) => trigger_job(name),
+            HookAction::Command(command) => run_command(command, outcome).await,
+            HookAction::Webhook(url) => send_webhook_event(url, None, event, outcome).await,
+        };
+
+        if let Err(err) = result {
+            let local_time = get_local_datetime_in_timezone(&jobs.timezone);
+            println!("{} {} Failed to run {} hook, job name: {}, action: {}, error: {}", outcome.run_id, local_time, hook, outcome.job_name, redact_secrets(&action.to_string()), err);
+        }
+    }
+}
+
+/// Starts a run of the job with the given name in the background.
+///
+/// The triggered run behaves like a scheduled run: it waits in the run queue when the scheduler
+/// is at capacity, it is skipped if the job is paused, and it runs its own hooks when it
+/// completes.
+///
+/// # Errors
+///
+/// Returns an error if no job with the name exists.
+fn trigger_job(name: &str) -> Result<(), Box<dyn Error>> {
+    // A job that is not registered, e.g. disabled or not selected by tags, can still be triggered.
+    let job = get_registered_job(name)
+        .or_else(|| get_jobs().all_jobs().into_iter().find(|job| job.name() == name))
+        .ok_or_else(|| format!("No job named '{}' exists.", name))?;
+
+    submit_run(name, None, start_job(job));
+    Ok(())
+}
+
+/// Runs a hook command with `sh -c`, or `cmd /C` on Windows, and logs its exit status and output.
+///
+/// The outcome of the run is passed in the `RJOB_JOB_NAME`, `RJOB_RUN_ID`, `RJOB_SUCCESS`,
+/// `RJOB_STATUS` and `RJOB_ERROR` environment variables. The command is killed if it runs
+/// longer than `HOOK_COMMAND_TIMEOUT`.
+///
+/// # Errors
+///
+/// Returns an error if the command cannot be started, times out or exits with a non-zero status.
+async fn run_command(command: &str, outcome: &RunOutcome) -> Result<(), Box<dyn Error>> {
+    let child = shell_command(command)
+        .env("RJOB_JOB_NAME", &outcome.job_name)
+        .env("RJOB_RUN_ID", &outcome.run_id)
+        .env("RJOB_SUCCESS", outcome.success.to_string())
+        .env("RJOB_STATUS", outcome.status.map(|s| s.to_string()).unwrap_or_default())
+        .env("RJOB_ERROR", outcome.error.as_deref().unwrap_or_default())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    let output = tokio::time::timeout(Duration::from_secs(HOOK_COMMAND_TIMEOUT), child.wait_with_output()).await
+        .map_err(|_| format!("The command timed out after {}s", HOOK_COMMAND_TIMEOUT))??;
+
+    let local_time = get_local_datetime_in_timezone(&get_jobs().timezone);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    println!("{} {} Hook command exited, job name: {}, status: {}, stdout: {}, stderr: {}",
+             outcome.run_id, local_time, outcome.job_name, output.status, redact_secrets(stdout.trim_end()), redact_secrets(stderr.trim_end()));
+
+    if !output.status.success() {
+        return Err(format!("The command exited with {}", output.status).into());
+    }
+
+    Ok(())
+}
+
+/// Returns the command running a hook command with the shell of the platform.
+#[cfg(unix)]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+/// Returns the command running a hook command with the shell of the platform.
+///
+/// The command is passed to `cmd` as is, since `cmd` doesn't unquote its arguments the way
+/// they are quoted for other programs.
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    use std::os::windows::process::CommandExt;
+
+    let mut shell = std::process::Command::new("cmd");
+    shell.arg("/C").raw_arg(command);
+    Command::from(shell)
+}
\ No newline at end of file
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use once_cell::sync::Lazy;
+use reqwest::{Client, ClientBuilder};
+
+use crate::configure::get_jobs;
+use crate::models::http_job::HttpJob;
+use crate::models::http_job_request::{HttpJobRequest, HttpVersion};
+use crate::models::tls::{TlsOptions, TlsVersion};
+use crate::scheduler::cert_pinning::build_pinned_tls_config;
+use crate::scheduler::dns_cache::CachingResolver;
+
+/// The HTTP clients shared between job runs, keyed by the settings they were built with.
+///
+/// Jobs with the same client settings reuse the same `Client`, and therefore share its
+/// connection pool, instead of building a new client (and performing new TLS handshakes)
+/// on every run.
+static CLIENTS: Lazy<Mutex<HashMap<ClientOptions, Client>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// The job settings that are applied when building an HTTP client.
+///
+/// Two jobs with equal `ClientOptions` share the same client.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClientOptions {
+    pub timeout: u64,
+    /// The timeout of establishing a connection, bounded by `timeout` only if not set.
+    pub connect_timeout: Option<u64>,
+    pub http_version: HttpVersion,
+    pub user_agent: String,
+    /// The local address the connections are bound to, which also restricts them to its IP
+    /// version.
+    pub local_address: Option<IpAddr>,
+    pub pool_idle_timeout: Option<Duration>,
+    pub pool_max_idle_per_host: Option<usize>,
+    pub tcp_keepalive: Option<Duration>,
+    pub tls: TlsOptions,
+    /// The server name of the request and the IP address it connects to instead of resolving
+    /// it.
+    pub connect_to: Option<(String, IpAddr)>,
+    /// Whether compressed responses are decompressed, see `DECODABLE_ENCODINGS`.
+    pub decompress: bool,
+}
+
+impl ClientOptions {
+    pub fn new(timeout: u64, connect_timeout: Option<u64>, http_version: HttpVersion, user_agent: String) -> Self {
+        ClientOptions {
+            timeout,
+            connect_timeout,
+            http_version,
+            user_agent,
+            local_address: None,
+            pool_idle_timeout: None,
+            pool_max_idle_per_host: None,
+            tcp_keepalive: None,
+            tls: TlsOptions::default(),
+            connect_to: None,
+            decompress: false,
+        }
+    }
+
+    /// Collects the client settings of the given HTTP job.
+    ///
+    /// The user agent of the job takes precedence over the global one. Without a `local_address`,
+    /// the connections of a job with an `ip_version` are bound to the unspecified address of that
+    /// version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let options = ClientOptions::from_job(&http_job);
+    /// println!("Timeout: {}", options.timeout);
+    /// ```
+    pub fn from_job(http_job: &HttpJob) -> Self {
+        ClientOptions::for_request(http_job, &http_job.request)
+    }
+
+    /// Collects the client settings of a request of the given HTTP job, such as its
+    /// `fallback_request`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let options = ClientOptions::for_request(&http_job, fallback_request);
+    /// println!("HTTP version: {}", options.http_version);
+    /// ```
+    pub fn for_request(http_job: &HttpJob, request: &HttpJobRequest) -> Self {
+        let user_agent = http_job.user_agent.clone()
+            .unwrap_or_else(|| get_jobs().user_agent.clone());
+        let local_address = http_job.local_address.or(http_job.ip_version.unspecified_address());
+        ClientOptions {
+            local_address,
+            pool_idle_timeout: http_job.pool_idle_timeout,
+            pool_max_idle_per_host: http_job.pool_max_idle_per_host,
+            tcp_keepalive: http_job.tcp_keepalive,
+            tls: http_job.tls.clone(),
+            connect_to: request.server_name.clone().zip(request.connect_address()),
+            decompress: http_job.response.decompress,
+            ..ClientOptions::new(http_job.timeout, http_job.connect_timeout_ms, request.http_version, user_agent)
+        }
+    }
+}
+
+/// Returns the shared HTTP client for the given settings, building it on first use.
+///
+/// # Arguments
+///
+/// * `options` - The client settings of the job.
+///
+/// # Errors
+///
+/// Returns an error if the client cannot be built.
+///
+/// # Examples
+///
+/// ```
+/// let client = get_client(&ClientOptions::from_job(&http_job))?;
+/// let resp = client.get("https://www.google.com").send().await?;
+/// ```
+pub fn get_client(options: &ClientOptions) -> Result<Client, reqwest::Error> {
+    let mut clients = CLIENTS.lock().unwrap();
+
+    if let Some(client) = clients.get(options) {
+        return Ok(client.clone());
+    }
+
+    let client = build_client(options)?;
+    clients.insert(options.clone(), client.clone());
+    Ok(client)
+}
+
+/// Builds a new HTTP client from the given settings.
+///
+/// The connect timeout lets a job fail fast on an unreachable host while `timeout` still
+/// leaves a slow endpoint time to respond. The settings of the connection pool left unset keep
+/// the defaults of reqwest: idle connections are closed after 90 seconds, their number per host
+/// is not limited, and TCP keep-alive is off. Host names are resolved through the DNS cache, see
+/// `CachingResolver`, except the server name of a request, which connects to the IP address of
+/// its URL. Responses are only decompressed if `decompress` is set, so that the size of a
+/// compressed body can be measured and saved as sent. The connections use rustls, except for
+/// the jobs that allow TLS 1.0 or 1.1, which use the native TLS backend of the platform, e.g.
+/// OpenSSL on Linux, see `TlsOptions::allows_legacy_versions`.
+///
+/// # Arguments
+///
+/// * `options` - The client settings of the job.
+///
+/// # Errors
+///
+/// Returns an error if the client cannot be built.
+fn build_client(options: &ClientOptions) -> Result<Client, reqwest::Error> {
+    let mut builder = Client::builder()
+        .user_agent(&options.user_agent)
+        .dns_resolver(Arc::new(CachingResolver))
+        .timeout(Duration::from_millis(options.timeout))
+        .gzip(options.decompress)
+        .deflate(options.decompress)
+        .brotli(options.decompress);
+    if let Some(connect_timeout) = options.connect_timeout {
+        builder = builder.connect_timeout(Duration::from_millis(connect_timeout));
+    }
+    if let Some(local_address) = options.local_address {
+        builder = builder.local_address(local_address);
+    }
+    if let Some(pool_idle_timeout) = options.pool_idle_timeout {
+        builder = builder.pool_idle_timeout(pool_idle_timeout);
+    }
+    if let Some(pool_max_idle_per_host) = options.pool_max_idle_per_host {
+        builder = builder.pool_max_idle_per_host(pool_max_idle_per_host);
+    }
+    if let Some(tcp_keepalive) = options.tcp_keepalive {
+        builder = builder.tcp_keepalive(tcp_keepalive);
+    }
+    let legacy = options.tls.allows_legacy_versions();
+    builder = if legacy { builder.use_native_tls() } else { builder.use_rustls_tls() };
+    if let Some(min_version) = options.tls.min_version {
+        builder = builder.min_tls_version(min_version.to_reqwest());
+    }
+    // The native TLS backend can't cap the version at TLS 1.3, which is its highest anyway.
+    if let Some(max_version) = options.tls.max_version.filter(|version| !legacy || *version < TlsVersion::Tls1_3) {
+        builder = builder.max_tls_version(max_version.to_reqwest());
+    }
+    if let Some((server_name, address)) = &options.connect_to {
+        // The port of the URL is used, not the one of the address.
+        builder = builder.resolve(server_name, SocketAddr::new(*address, 0));
+    }
+    if !options.tls.pinned_spki_sha256.is_empty() {
+        builder = builder.use_preconfigured_tls(build_pinned_tls_config(&options.tls, options.http_version));
+    }
+
+    apply_http_version(builder, options.http_version).build()
+}
+
+/// Configures the client builder according to the requested HTTP version.
+///
+/// `Http1` restricts the client to HTTP/1.1 and `Http2PriorKnowledge` makes it speak HTTP/2
+/// without negotiation. `Auto` and `Http2` leave the builder untouched, the latter is enforced
+/// on the request itself.
+///
+/// # Arguments
+///
+/// * `builder` - The client builder to configure.
+/// * `http_version` - The HTTP version configured for the job's request.
+///
+/// # Examples
+///
+/// ```
+/// let builder = apply_http_version(reqwest::Client::builder(), HttpVersion::Http1);
+/// let client = builder.build().unwrap();
+/// ```
+fn apply_http_version(builder: ClientBuilder, http_version: HttpVersion) -> ClientBuilder {
+    match http_version {
+        HttpVersion::Http1 => builder.http1_only(),
+        HttpVersion::Http2PriorKnowledge => builder.http2_prior_knowledge(),
+        HttpVersion::Auto | HttpVersion::Http2 => builder
+    }
+}
\ No newline at end of file
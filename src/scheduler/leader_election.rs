@@ -0,0 +1,146 @@
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::time::Instant;
+
+use crate::configure::get_jobs;
+use crate::models::leader_election::LeaderElection;
+use crate::scheduler::distributed_lock::get_instance_id;
+use crate::scheduler::redis_client::{RedisConnection, RedisReply};
+
+/// Extends the lease of the leader key, only if this instance still holds it.
+const RENEW_SCRIPT: &str = "if redis.call('GET', KEYS[1]) == ARGV[1] then return redis.call('PEXPIRE', KEYS[1], ARGV[2]) else return 0 end";
+
+/// Whether this instance is the elected leader.
+static LEADER: AtomicBool = AtomicBool::new(false);
+
+/// The state of the campaign of this instance for the leader key.
+struct Campaign {
+    connection: Option<RedisConnection>,
+    /// When the request that last took or renewed the lease was sent, while this instance is
+    /// the leader. The lease expires in Redis `lease` after it at the earliest.
+    renewed_at: Option<Instant>,
+}
+
+/// Returns whether this instance fires the triggers of the jobs.
+///
+/// Without a `leader_election` section every instance is a leader.
+///
+/// # Examples
+///
+/// ```
+/// if is_leader() {
+///     fire(scheduled_at);
+/// }
+/// ```
+pub fn is_leader() -> bool {
+    get_jobs().leader_election.is_none() || LEADER.load(Ordering::Relaxed)
+}
+
+/// Elects the leader among the instances of the scheduler, if a `leader_election` is configured.
+///
+/// The first round of the election is awaited, so the instance knows its role before the jobs
+/// are scheduled. The following rounds run in the background every third of the lease: the
+/// leader renews its lease, the standby instances try to take the leader key, which succeeds
+/// once the leader stopped renewing it. A leader that cannot renew its lease steps down a third
+/// of the lease before it expires, even if Redis doesn't reply, see `Campaign::run_round`, so
+/// two instances are never leaders at the same time as long as their clocks run at the same
+/// rate.
+///
+/// # Examples
+///
+/// ```
+/// start_leader_election().await;
+/// if !is_leader() {
+///     println!("Standing by.");
+/// }
+/// ```
+pub async fn start_leader_election() {
+    let Some(election) = get_jobs().leader_election.clone() else {
+        return;
+    };
+
+    let mut campaign = Campaign { connection: None, renewed_at: None };
+    campaign.run_round(&election).await;
+    if !is_leader() {
+        println!("Standing by, instance: {}, leader key: {}", get_instance_id(), election.key);
+    }
+
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(election.renew_interval()).await;
+            campaign.run_round(&election).await;
+        }
+    });
+}
+
+impl Campaign {
+
+    /// Takes or renews the lease, and updates the role of this instance.
+    ///
+    /// A round takes at most a third of the lease, and the round of a leader ends at the latest
+    /// a third of the lease before its lease expires. A leader whose lease is about to expire
+    /// steps down then, whether or not Redis replied.
+    async fn run_round(&mut self, election: &LeaderElection) {
+        let leader = LEADER.load(Ordering::Relaxed);
+        let started = Instant::now();
+        let mut deadline = started + election.renew_interval();
+        if let Some(renewed_at) = self.renewed_at.filter(|_| leader) {
+            deadline = deadline.min(renewed_at + election.lease - election.renew_interval());
+        }
+
+        let claimed = match tokio::time::timeout_at(deadline, self.claim(election, leader)).await {
+            Ok(claimed) => claimed,
+            Err(_) => Err("Redis did not reply in time.".into())
+        };
+        match claimed {
+            Ok(true) => {
+                self.renewed_at = Some(started);
+                if !leader {
+                    LEADER.store(true, Ordering::Relaxed);
+                    println!("Elected leader, instance: {}, leader key: {}", get_instance_id(), election.key);
+                }
+            }
+            Ok(false) if leader => {
+                self.step_down("the lease was taken over");
+            }
+            Ok(false) => {}
+            Err(e) => {
+                self.connection = None;
+                println!("Leader election failed, instance: {}, error: {}", get_instance_id(), e);
+                let expiring = self.renewed_at
+                    .is_none_or(|at| at.elapsed() + election.renew_interval() >= election.lease);
+                if leader && expiring {
+                    self.step_down("the lease could not be renewed");
+                }
+            }
+        }
+    }
+
+    /// Sets the leader key if it doesn't exist, or extends it if this instance is the leader.
+    ///
+    /// # Returns
+    ///
+    /// `true` if this instance holds the lease, `false` if another instance does.
+    async fn claim(&mut self, election: &LeaderElection, leader: bool) -> Result<bool, Box<dyn Error>> {
+        let redis = match self.connection.as_mut() {
+            Some(redis) => redis,
+            None => self.connection.insert(RedisConnection::connect(&election.redis, election.renew_interval()).await?)
+        };
+
+        let lease = election.lease.as_millis().to_string();
+        let reply = if leader {
+            redis.command(&["EVAL", RENEW_SCRIPT, "1", &election.key, get_instance_id(), &lease]).await?
+        } else {
+            redis.command(&["SET", &election.key, get_instance_id(), "NX", "PX", &lease]).await?
+        };
+
+        Ok(!matches!(reply, RedisReply::Nil | RedisReply::Integer(0)))
+    }
+
+    /// Stops firing the triggers of the jobs, the runs in progress go on.
+    fn step_down(&mut self, reason: &str) {
+        LEADER.store(false, Ordering::Relaxed);
+        self.renewed_at = None;
+        println!("Lost leadership, instance: {}, {}", get_instance_id(), reason);
+    }
+}
\ No newline at end of file
@@ -1 +1,37 @@
-pub mod cron_scheduler;
\ No newline at end of file
+pub mod backup_job;
+pub mod cert_pinning;
+pub mod cleanup_job;
+pub mod condition;
+pub mod cron_scheduler;
+#[cfg(unix)]
+pub mod daemon;
+pub mod db_job;
+pub mod dead_letter;
+pub mod distributed_lock;
+pub mod dns_cache;
+pub mod dns_job;
+pub mod drift;
+pub mod freshness;
+pub mod group_limiter;
+pub mod health;
+pub mod hooks;
+pub mod http_client;
+pub mod leader_election;
+pub mod rate_limiter;
+pub mod redis_client;
+pub mod registry;
+pub mod run_archive;
+pub mod run_log;
+pub mod run_queue;
+pub mod s3_client;
+pub mod s3_job;
+pub mod sftp_job;
+pub mod sharding;
+pub mod state;
+pub mod status;
+pub mod systemd;
+pub mod tcp_job;
+pub mod timer;
+pub mod watchdog;
+pub mod websocket_client;
+pub mod websocket_job;
\ No newline at end of file
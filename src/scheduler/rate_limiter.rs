@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use once_cell::sync::Lazy;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+use crate::configure::get_jobs;
+use crate::models::rate_limit::RateLimit;
+
+/// The rate limiters of the hosts listed in the `rate_limits` section, keyed by lowercase host name.
+///
+/// All jobs sending requests to the same host share one limiter, so the host is throttled
+/// collectively even when several schedules fire at the same time.
+static HOST_LIMITERS: Lazy<HashMap<String, RateLimiter>> = Lazy::new(|| {
+    get_jobs().rate_limits.iter()
+        .map(|(host, rate)| (host.to_lowercase(), RateLimiter::new(*rate)))
+        .collect()
+});
+
+/// The rate limiter applied to every request across all jobs, built from `max_requests_per_second`.
+static GLOBAL_LIMITER: Lazy<Option<RateLimiter>> = Lazy::new(|| {
+    get_jobs().max_requests_per_second
+        .map(|requests| RateLimiter::new(RateLimit::new(requests, Duration::from_secs(1))))
+});
+
+/// A token bucket limiting the number of requests sent within a period.
+///
+/// The bucket holds at most `requests` tokens and is refilled continuously, so bursts up to the
+/// configured number of requests are allowed before callers have to wait.
+pub struct RateLimiter {
+    capacity: f64,
+    tokens_per_sec: f64,
+    state: Mutex<BucketState>,
+}
+
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(rate: RateLimit) -> Self {
+        let capacity = rate.requests as f64;
+        RateLimiter {
+            capacity,
+            tokens_per_sec: capacity / rate.period.as_secs_f64(),
+            state: Mutex::new(BucketState {
+                tokens: capacity,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Waits until a token is available and takes it.
+    ///
+    /// # Returns
+    ///
+    /// The time spent waiting for the token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let limiter = RateLimiter::new(RateLimit::parse("5/s").unwrap());
+    /// let waited = limiter.acquire().await;
+    /// println!("Waited: {:?}", waited);
+    /// ```
+    pub async fn acquire(&self) -> Duration {
+        let start = Instant::now();
+
+        loop {
+            let wait = {
+                let mut state = self.state.lock().await;
+                let now = Instant::now();
+                let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+                state.tokens = (state.tokens + elapsed * self.tokens_per_sec).min(self.capacity);
+                state.last_refill = now;
+
+                if state.tokens >= 1.0 {
+                    state.tokens -= 1.0;
+                    return start.elapsed();
+                }
+
+                Duration::from_secs_f64((1.0 - state.tokens) / self.tokens_per_sec)
+            };
+
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Waits for the rate limiter of the host targeted by `url`, if the host has one.
+///
+/// URLs that cannot be parsed or whose host has no configured rate limit pass through
+/// immediately.
+///
+/// # Arguments
+///
+/// * `url` - The URL of the request about to be sent.
+///
+/// # Returns
+///
+/// The time spent waiting, or `None` if the host is not rate limited.
+///
+/// # Examples
+///
+/// ```
+/// if let Some(waited) = acquire_host_permit("https://api.example.com/users").await {
+///     println!("Throttled for {:?}", waited);
+/// }
+/// ```
+pub async fn acquire_host_permit(url: &str) -> Option<Duration> {
+    let url = reqwest::Url::parse(url).ok()?;
+    let host = url.host_str()?.to_lowercase();
+    let limiter = HOST_LIMITERS.get(&host)?;
+    Some(limiter.acquire().await)
+}
+
+/// Waits for the global rate limiter configured by `max_requests_per_second`, if any.
+///
+/// # Returns
+///
+/// The time spent waiting, or `None` if no global rate limit is configured.
+///
+/// # Examples
+///
+/// ```
+/// if let Some(waited) = acquire_global_permit().await {
+///     println!("Throttled for {:?}", waited);
+/// }
+/// ```
+pub async fn acquire_global_permit() -> Option<Duration> {
+    let limiter = GLOBAL_LIMITER.as_ref()?;
+    Some(limiter.acquire().await)
+}
\ No newline at end of file
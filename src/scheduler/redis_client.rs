@@ -0,0 +1,162 @@
+use std::error::Error;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+use crate::models::distributed_lock::RedisServer;
+
+/// The largest bulk string or array accepted from the server.
+const MAX_REPLY_LENGTH: usize = 16 * 1024 * 1024;
+
+/// The reading of a reply, boxed since the items of an array are read recursively.
+type ReplyFuture<'a> = Pin<Box<dyn Future<Output = Result<RedisReply, Box<dyn Error>>> + Send + 'a>>;
+
+/// A reply of the Redis server, in the RESP2 protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RedisReply {
+    Nil,
+    Status(String),
+    Integer(i64),
+    Bulk(Vec<u8>),
+    Array(Vec<RedisReply>),
+}
+
+/// A minimal client side of a Redis connection.
+///
+/// Only what the distributed lock needs is supported: authenticating, selecting the database
+/// and sending commands one at a time. Each command and the opening of the connection time out
+/// after the given timeout, the connection should be dropped then.
+pub struct RedisConnection {
+    stream: BufReader<TcpStream>,
+    timeout: Duration,
+}
+
+impl RedisConnection {
+    /// Opens a connection to a Redis server, authenticated and on the database of the server.
+    ///
+    /// # Arguments
+    ///
+    /// * `server` - The address and credentials of the server.
+    /// * `timeout` - The timeout of the connection and of each command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection fails or times out, or if the server rejects the
+    /// credentials or the database.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut redis = RedisConnection::connect(&RedisServer::parse("redis://127.0.0.1")?, Duration::from_secs(5)).await?;
+    /// let reply = redis.command(&["PING"]).await?;
+    /// ```
+    pub async fn connect(server: &RedisServer, timeout: Duration) -> Result<Self, Box<dyn Error>> {
+        let address = (server.host.as_str(), server.port);
+        let stream = tokio::time::timeout(timeout, TcpStream::connect(address)).await
+            .map_err(|_| format!("Connection to {} timed out.", server))??;
+        stream.set_nodelay(true)?;
+        let mut connection = RedisConnection { stream: BufReader::new(stream), timeout };
+
+        if let Some(password) = &server.password {
+            match &server.username {
+                Some(username) => connection.command(&["AUTH", username, password]).await?,
+                None => connection.command(&["AUTH", password]).await?
+            };
+        }
+        if server.db != 0 {
+            connection.command(&["SELECT", &server.db.to_string()]).await?;
+        }
+
+        Ok(connection)
+    }
+
+    /// Sends a command and reads its reply.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - The name and the arguments of the command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the command times out, the connection fails, the reply is invalid,
+    /// or the server replies with an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let reply = redis.command(&["SET", "rjob:lock:users", "1", "NX", "PX", "600000"]).await?;
+    /// let acquired = reply != RedisReply::Nil;
+    /// ```
+    pub async fn command(&mut self, args: &[&str]) -> Result<RedisReply, Box<dyn Error>> {
+        let mut request = format!("*{}\r\n", args.len()).into_bytes();
+        for arg in args {
+            request.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+            request.extend_from_slice(arg.as_bytes());
+            request.extend_from_slice(b"\r\n");
+        }
+
+        let timeout = self.timeout;
+        let exchange = async {
+            self.stream.get_mut().write_all(&request).await?;
+            self.read_reply().await
+        };
+        tokio::time::timeout(timeout, exchange).await
+            .map_err(|_| format!("Redis command {} timed out.", args.first().unwrap_or(&"")))?
+    }
+
+    /// Reads a reply, with the nested replies of an array.
+    fn read_reply(&mut self) -> ReplyFuture<'_> {
+        Box::pin(async move {
+            let line = self.read_line().await?;
+            // The first character may be multibyte in an invalid reply.
+            let mut chars = line.chars();
+            let kind = chars.next();
+            let rest = chars.as_str();
+
+            match kind {
+                Some('+') => Ok(RedisReply::Status(rest.to_string())),
+                Some('-') => Err(format!("Redis error: {}", rest).into()),
+                Some(':') => Ok(RedisReply::Integer(rest.parse().map_err(|_| format!("Invalid Redis integer '{}'.", rest))?)),
+                Some('$' | '*') => {
+                    let length: i64 = rest.parse().map_err(|_| format!("Invalid Redis length '{}'.", rest))?;
+                    if length < 0 {
+                        return Ok(RedisReply::Nil);
+                    }
+                    let length = length as usize;
+                    if length > MAX_REPLY_LENGTH {
+                        return Err(format!("Redis reply too large: {} items or bytes.", length).into());
+                    }
+
+                    if kind == Some('*') {
+                        let mut items = Vec::with_capacity(length.min(1024));
+                        for _ in 0..length {
+                            items.push(self.read_reply().await?);
+                        }
+                        return Ok(RedisReply::Array(items));
+                    }
+
+                    let mut bulk = vec![0; length + 2];
+                    self.stream.read_exact(&mut bulk).await?;
+                    bulk.truncate(length);
+                    Ok(RedisReply::Bulk(bulk))
+                }
+                _ => Err(format!("Invalid Redis reply '{}'.", line).into())
+            }
+        })
+    }
+
+    /// Reads a line of the reply, without its CRLF.
+    async fn read_line(&mut self) -> Result<String, Box<dyn Error>> {
+        let mut line = String::new();
+        if self.stream.read_line(&mut line).await? == 0 {
+            return Err("The Redis server closed the connection.".into());
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            return Err("Invalid empty Redis reply.".into());
+        }
+        Ok(line.to_string())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use once_cell::sync::Lazy;
+
+use crate::models::job::Job;
+use crate::scheduler::freshness::unwatch_success;
+use crate::scheduler::health::set_registered_jobs;
+use crate::scheduler::timer::remove_trigger;
+
+/// The jobs registered with the scheduler, keyed by job name.
+static REGISTERED_JOBS: Lazy<Mutex<HashMap<String, RegisteredJob>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// The identifier of the next registration.
+static NEXT_REGISTRATION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// A job registered with the scheduler.
+///
+/// Every registration gets a new identifier, so the cron trigger of a removed or replaced job
+/// can tell that it is stale. The trigger is removed from the timer with the job.
+struct RegisteredJob {
+    id: u64,
+    job: Arc<dyn Job>,
+    trigger: Option<u64>,
+}
+
+/// Registers a job and returns the identifier of the registration.
+///
+/// A job with the same name is replaced, and its trigger removed from the timer.
+///
+/// # Arguments
+///
+/// * `job` - The job to register.
+///
+/// # Examples
+///
+/// ```
+/// let id = register_job(Arc::new(tcp_job));
+/// assert!(get_registration(&name, id).is_some());
+/// ```
+pub fn register_job(job: Arc<dyn Job>) -> u64 {
+    let id = NEXT_REGISTRATION_ID.fetch_add(1, Ordering::Relaxed);
+    let mut jobs = REGISTERED_JOBS.lock().unwrap();
+    let replaced = jobs.insert(job.name().to_string(), RegisteredJob { id, job, trigger: None });
+    if let Some(trigger) = replaced.and_then(|job| job.trigger) {
+        remove_trigger(trigger);
+    }
+    set_registered_jobs(jobs.len());
+    id
+}
+
+/// Removes a job from the registered jobs, and its trigger from the timer.
+///
+/// # Arguments
+///
+/// * `name` - The name of the job.
+///
+/// # Returns
+///
+/// `true` if the job was registered.
+pub fn deregister_job(name: &str) -> bool {
+    let mut jobs = REGISTERED_JOBS.lock().unwrap();
+    let removed = jobs.remove(name);
+    if let Some(trigger) = removed.as_ref().and_then(|job| job.trigger) {
+        remove_trigger(trigger);
+    }
+    unwatch_success(name);
+    set_registered_jobs(jobs.len());
+    removed.is_some()
+}
+
+/// Sets the timer trigger of a registration.
+///
+/// The trigger is removed right away if the job was removed or registered again since.
+///
+/// # Arguments
+///
+/// * `name` - The name of the job.
+/// * `id` - The identifier returned by `register_job`.
+/// * `trigger` - The identifier returned by `add_trigger`.
+pub fn set_trigger(name: &str, id: u64, trigger: u64) {
+    let mut jobs = REGISTERED_JOBS.lock().unwrap();
+    match jobs.get_mut(name).filter(|job| job.id == id) {
+        Some(job) => job.trigger = Some(trigger),
+        None => remove_trigger(trigger)
+    }
+}
+
+/// Returns a registered job by name.
+///
+/// # Arguments
+///
+/// * `name` - The name of the job.
+pub fn get_registered_job(name: &str) -> Option<Arc<dyn Job>> {
+    REGISTERED_JOBS.lock().unwrap().get(name).map(|job| job.job.clone())
+}
+
+/// Returns the names of the registered jobs.
+pub fn get_registered_job_names() -> Vec<String> {
+    REGISTERED_JOBS.lock().unwrap().keys().cloned().collect()
+}
+
+/// Returns the job of a registration, or `None` if the job was removed or registered again since.
+///
+/// # Arguments
+///
+/// * `name` - The name of the job.
+/// * `id` - The identifier returned by `register_job`.
+pub fn get_registration(name: &str, id: u64) -> Option<Arc<dyn Job>> {
+    REGISTERED_JOBS.lock().unwrap()
+        .get(name)
+        .filter(|job| job.id == id)
+        .map(|job| job.job.clone())
+}
\ No newline at end of file
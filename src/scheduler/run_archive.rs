@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use reqwest::Method;
+use reqwest::header::{CONTENT_TYPE, HeaderMap};
+use serde::Serialize;
+
+use crate::configure::get_jobs;
+use crate::models::http_job::HttpJob;
+use crate::models::http_job_request::{HttpJobRequest, HttpVersion, JSON_CONTENT_TYPE};
+use crate::models::run_archive::RunArchive;
+use crate::models::run_outcome::RunOutcome;
+use crate::scheduler::cron_scheduler::{merge_headers, ReceivedResponse};
+use crate::scheduler::http_client::{ClientOptions, get_client};
+use crate::scheduler::s3_client::build_signed_request;
+use crate::utils::redact_util::{redact_header, redact_secrets};
+use crate::utils::template_util::{get_run_vars, render_template};
+
+/// The request/response record of a run, written as an object of the run archive.
+#[derive(Debug, Serialize)]
+struct RunRecord<'a> {
+    job_name: &'a str,
+    run_id: &'a str,
+    success: bool,
+    status: Option<u16>,
+    attempts: u64,
+    error: Option<&'a str>,
+    started_at: String,
+    duration_ms: u64,
+    requests: Vec<ArchivedRequest<'a>>,
+    response: Option<ArchivedResponse<'a>>,
+}
+
+/// The request of a job, or of a step of its pipeline, as it is archived.
+#[derive(Debug, Serialize)]
+struct ArchivedRequest<'a> {
+    step: Option<&'a str>,
+    method: String,
+    url: String,
+    headers: BTreeMap<String, String>,
+    body: Option<String>,
+}
+
+/// The final response of a successful run, as it is archived.
+#[derive(Debug, Serialize)]
+struct ArchivedResponse<'a> {
+    status: u16,
+    headers: BTreeMap<String, String>,
+    body: Option<String>,
+    body_truncated: bool,
+    extracted: BTreeMap<&'a str, String>,
+}
+
+/// Writes the request/response record of a run of an HTTP job to the run archive.
+///
+/// The record holds the outcome of the run, its start time and duration, the request of the
+/// job, or each step of its pipeline with its placeholders, and the final response if the run
+/// succeeded, with its headers, its body as read with `max_response_bytes` and the values
+/// extracted by the pipeline. Credential headers and registered secrets are redacted. The
+/// record is uploaded as a JSON object whose key is rendered from the `key` template.
+///
+/// # Arguments
+///
+/// * `run_archive` - The run archive of the configuration.
+/// * `http_job` - The HTTP job.
+/// * `outcome` - The outcome of the run.
+/// * `response` - The final response of the run, if it succeeded.
+/// * `started_at` - The start time of the run.
+/// * `duration` - The duration of the run.
+/// * `timezone` - The configured timezone, used to format the start time and render the key.
+///
+/// # Returns
+///
+/// The key of the written object.
+///
+/// # Errors
+///
+/// Returns an error if the upload fails or the storage responds with a non-2xx status.
+///
+/// # Examples
+///
+/// ```
+/// if let Some(run_archive) = &get_jobs().run_archive {
+///     let key = archive_run(run_archive, http_job, &outcome, response.as_ref(), started_at, duration, timezone).await?;
+///     println!("Run archived: {}", key);
+/// }
+/// ```
+pub async fn archive_run(
+    run_archive: &RunArchive,
+    http_job: &HttpJob,
+    outcome: &RunOutcome,
+    response: Option<&ReceivedResponse>,
+    started_at: DateTime<Utc>,
+    duration: Duration,
+    timezone: &Tz,
+) -> Result<String, Box<dyn Error>> {
+    let requests = match http_job.steps.is_empty() {
+        true => vec![archive_request(None, &http_job.request)],
+        false => http_job.steps.iter().map(|step| archive_request(Some(&step.name), &step.request)).collect()
+    };
+
+    let response = response.map(|response| ArchivedResponse {
+        status: response.status,
+        headers: archive_headers(&response.headers),
+        body: (!http_job.response.discard_body).then(|| redact_secrets(&String::from_utf8_lossy(&response.body))),
+        body_truncated: response.truncated,
+        extracted: response.extracted.iter().map(|(name, value)| (name.as_str(), redact_secrets(value))).collect(),
+    });
+
+    let record = RunRecord {
+        job_name: &outcome.job_name,
+        run_id: &outcome.run_id,
+        success: outcome.success,
+        status: outcome.status,
+        attempts: outcome.attempts,
+        error: outcome.error.as_deref(),
+        started_at: started_at.with_timezone(timezone).to_rfc3339(),
+        duration_ms: duration.as_millis() as u64,
+        requests,
+        response,
+    };
+
+    let vars = get_run_vars(&outcome.job_name, &outcome.run_id, timezone);
+    let key = render_template(&run_archive.key, &vars);
+
+    let options = ClientOptions::new(run_archive.timeout, None, HttpVersion::Auto, get_jobs().user_agent.clone());
+    let client = get_client(&options)?;
+    let request = build_signed_request(&client, &run_archive.storage, Method::PUT, &key, serde_json::to_vec(&record)?)?;
+    let resp = request
+        .header(CONTENT_TYPE, JSON_CONTENT_TYPE)
+        .send()
+        .await?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        return Err(format!("Unexpected status {}: {}", status, text).into());
+    }
+
+    Ok(key)
+}
+
+/// Returns the request of a job or of a step as it is archived, with the default headers of
+/// the configuration merged in.
+fn archive_request<'a>(step: Option<&'a str>, request: &HttpJobRequest) -> ArchivedRequest<'a> {
+    ArchivedRequest {
+        step,
+        method: request.method.to_uppercase(),
+        url: redact_secrets(&request.url),
+        headers: archive_headers(&merge_headers(&get_jobs().default_headers, request)),
+        body: request.body.as_deref().map(redact_secrets),
+    }
+}
+
+/// Returns the headers as they are archived, the values of a repeated header joined with `, `.
+fn archive_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    let mut archived: BTreeMap<String, String> = BTreeMap::new();
+    for (name, value) in headers {
+        let value = redact_secrets(&redact_header(name.as_str(), &String::from_utf8_lossy(value.as_bytes())));
+        archived.entry(name.to_string())
+            .and_modify(|values| {
+                values.push_str(", ");
+                values.push_str(&value);
+            })
+            .or_insert(value);
+    }
+    archived
+}
\ No newline at end of file
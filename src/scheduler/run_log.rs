@@ -0,0 +1,105 @@
+use std::error::Error;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use chrono::{DateTime, SecondsFormat, Utc};
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+
+use crate::models::run_outcome::RunOutcome;
+
+/// The run log of the `--run-log` option, or unset if the runs are not logged.
+static RUN_LOG: OnceCell<Mutex<File>> = OnceCell::new();
+
+/// The record of a completed run, one line of the run log.
+#[derive(Debug, Serialize)]
+struct RunRecord<'a> {
+    job_name: &'a str,
+    run_id: &'a str,
+    success: bool,
+    status: Option<u16>,
+    attempts: u64,
+    error: Option<&'a str>,
+    started_at: String,
+    finished_at: String,
+    duration_ms: u64,
+}
+
+/// Opens the run log, the file a record of each completed run is appended to.
+///
+/// The file uses the JSON Lines format, one record per line, and is created if it doesn't
+/// exist. It is opened once, so an unwritable path is reported when the scheduler starts.
+///
+/// # Arguments
+///
+/// * `path` - The path of the run log, e.g. `./runs.jsonl`.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened for appending.
+///
+/// # Examples
+///
+/// ```
+/// open_run_log(Path::new("./runs.jsonl"))?;
+/// ```
+pub fn open_run_log(path: &Path) -> Result<(), Box<dyn Error>> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("An error occurred while opening the file '{}': {}", path.display(), e))?;
+    RUN_LOG.set(Mutex::new(file)).map_err(|_| "The run log is already open.")?;
+    println!("Writing the run log, file: {}", path.display());
+    Ok(())
+}
+
+/// Appends the record of a completed run to the run log, if it is open.
+///
+/// The record holds the outcome of the run, its start and end times in UTC and its duration,
+/// retries included. It is written as a single line, so the records of concurrent runs are
+/// never interleaved.
+///
+/// # Arguments
+///
+/// * `outcome` - The outcome of the run.
+/// * `started_at` - The time the run started.
+/// * `duration` - The duration of the run.
+///
+/// # Errors
+///
+/// Returns an error if the record cannot be serialized or the file cannot be written.
+///
+/// # Examples
+///
+/// ```
+/// let duration = (Utc::now() - started_at).to_std().unwrap_or_default();
+/// append_run_record(&outcome, started_at, duration)?;
+/// ```
+pub fn append_run_record(outcome: &RunOutcome, started_at: DateTime<Utc>, duration: Duration) -> Result<(), Box<dyn Error>> {
+    let Some(run_log) = RUN_LOG.get() else {
+        return Ok(());
+    };
+
+    let finished_at = started_at + chrono::Duration::from_std(duration).unwrap_or_else(|_| chrono::Duration::zero());
+    let record = RunRecord {
+        job_name: &outcome.job_name,
+        run_id: &outcome.run_id,
+        success: outcome.success,
+        status: outcome.status,
+        attempts: outcome.attempts,
+        error: outcome.error.as_deref(),
+        started_at: started_at.to_rfc3339_opts(SecondsFormat::Millis, true),
+        finished_at: finished_at.to_rfc3339_opts(SecondsFormat::Millis, true),
+        duration_ms: duration.as_millis() as u64,
+    };
+
+    let mut line = serde_json::to_string(&record)?;
+    line.push('\n');
+    run_log.lock().unwrap().write_all(line.as_bytes())
+        .map_err(|e| format!("An error occurred while writing the run log: {}", e))?;
+
+    Ok(())
+}
\ No newline at end of file
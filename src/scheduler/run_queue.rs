@@ -0,0 +1,228 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use tokio::sync::Notify;
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::configure::get_jobs;
+use crate::models::run_queue::WhenFull;
+use crate::scheduler::distributed_lock::with_run_lock;
+use crate::scheduler::drift::with_scheduled_time;
+
+/// The runs in progress and the runs waiting to start.
+static RUN_QUEUE: Lazy<Mutex<QueueState>> = Lazy::new(|| Mutex::new(QueueState {
+    running: 0,
+    pending: VecDeque::new(),
+    max_depth: 0,
+    dropped: 0,
+}));
+
+/// Notified when a run ends, so a blocked timer can check for space in the queue again.
+static SPACE: Lazy<Notify> = Lazy::new(Notify::new);
+
+/// The run of a job, started once there is capacity.
+type Run = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+struct QueueState {
+    running: usize,
+    /// The triggered runs waiting to start, oldest first.
+    pending: VecDeque<PendingRun>,
+    /// The highest number of pending runs so far.
+    max_depth: usize,
+    /// The number of runs dropped because the queue was full.
+    dropped: u64,
+}
+
+/// A triggered run waiting for a run in progress to end.
+struct PendingRun {
+    job_name: String,
+    since: Instant,
+    run: Run,
+}
+
+/// A snapshot of the run queue.
+#[derive(Debug, Clone, Copy)]
+pub struct RunQueueStats {
+    pub running: usize,
+    pub depth: usize,
+    pub max_depth: usize,
+    pub dropped: u64,
+}
+
+/// Ends a run when dropped, also when the run panicked, and starts the next pending run.
+struct RunGuard;
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        let next = {
+            let mut state = RUN_QUEUE.lock().unwrap();
+            let next = state.pending.pop_front();
+            if next.is_none() {
+                state.running -= 1;
+            }
+            next
+        };
+        SPACE.notify_waiters();
+
+        if let Some(next) = next {
+            println!("Run dequeued, job name: {}, waited: {}ms", next.job_name, next.since.elapsed().as_millis());
+            spawn_run(next.run);
+        }
+    }
+}
+
+/// Submits a triggered run of a job.
+///
+/// Without a `run_queue` section the run starts right away. Otherwise, it starts if fewer than
+/// `max_concurrent` runs are in progress, and waits in the queue if not. When the queue already
+/// holds `max_size` runs, `when_full` decides which run is dropped: the oldest pending run with
+/// `drop_oldest`, the new run with `drop_new`. With `block` the run is queued anyway, and the
+/// timer holds back further triggers until there is space again, see `wait_for_space`.
+/// With a `distributed_lock` section, a triggered run only starts if this instance acquires
+/// its lock when the run leaves the queue, see `acquire_run_lock`.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job, used in the logs of the queue.
+/// * `scheduled_at` - The fire time of the trigger, or `None` if the run was not started by
+///   the timer. The drift of the run is measured from it, see `record_drift`, and the run is
+///   locked across instances by it.
+/// * `run` - The run of the job.
+///
+/// # Examples
+///
+/// ```
+/// submit_run(job.name(), Some(scheduled_at), start_job(job.clone()));
+/// ```
+pub fn submit_run<F>(job_name: &str, scheduled_at: Option<DateTime<Utc>>, run: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let run: Run = match scheduled_at {
+        Some(scheduled_at) => Box::pin(with_scheduled_time(scheduled_at, with_run_lock(job_name.to_string(), scheduled_at, run))),
+        None => Box::pin(run)
+    };
+    let run_queue = get_jobs().run_queue;
+    let max_concurrent = run_queue.map_or(usize::MAX, |q| q.max_concurrent);
+
+    let mut state = RUN_QUEUE.lock().unwrap();
+    if state.running < max_concurrent {
+        state.running += 1;
+        drop(state);
+        spawn_run(run);
+        return;
+    }
+
+    // Only reached with a run queue, as the number of runs is not limited otherwise.
+    let Some(run_queue) = run_queue else {
+        return;
+    };
+    if state.pending.len() >= run_queue.max_size {
+        match run_queue.when_full {
+            WhenFull::DropOldest if !state.pending.is_empty() => {
+                let oldest = state.pending.pop_front().unwrap();
+                state.dropped += 1;
+                println!("Run dropped, job name: {}, run queue full, waited: {}ms", oldest.job_name, oldest.since.elapsed().as_millis());
+            }
+            WhenFull::Block => {}
+            // With a `max_size` of 0, `drop_oldest` has no pending run to drop either.
+            _ => {
+                state.dropped += 1;
+                println!("Run dropped, job name: {}, run queue full", job_name);
+                return;
+            }
+        }
+    }
+
+    state.pending.push_back(PendingRun { job_name: job_name.to_string(), since: Instant::now(), run });
+    state.max_depth = state.max_depth.max(state.pending.len());
+    println!("Run queued, job name: {}, queue depth: {}", job_name, state.pending.len());
+}
+
+/// Waits until the run queue has space, if it is full and its `when_full` policy is `block`.
+///
+/// Returns immediately with any other policy, or without a `run_queue` section.
+///
+/// # Examples
+///
+/// ```
+/// wait_for_space().await;
+/// submit_run(job.name(), Some(scheduled_at), start_job(job.clone()));
+/// ```
+pub async fn wait_for_space() {
+    let Some(run_queue) = get_jobs().run_queue.filter(|q| q.when_full == WhenFull::Block) else {
+        return;
+    };
+
+    loop {
+        // Created before the check, so a run ending in between is not missed.
+        let notified = SPACE.notified();
+        if RUN_QUEUE.lock().unwrap().pending.len() < run_queue.max_size.max(1) {
+            return;
+        }
+        notified.await;
+    }
+}
+
+/// Waits until the runs in progress and the pending runs have ended, at most for a timeout.
+///
+/// # Arguments
+///
+/// * `timeout` - The maximum time to wait.
+///
+/// # Returns
+///
+/// The number of runs still in progress when the timeout elapsed, 0 if all runs ended.
+///
+/// # Examples
+///
+/// ```
+/// stop_timer();
+/// let running = wait_for_runs(Duration::from_secs(30)).await;
+/// ```
+pub async fn wait_for_runs(timeout: Duration) -> usize {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        // Created before the check, so a run ending in between is not missed.
+        let notified = SPACE.notified();
+        let running = RUN_QUEUE.lock().unwrap().running;
+        if running == 0 {
+            return 0;
+        }
+        if tokio::time::timeout_at(deadline, notified).await.is_err() {
+            return running;
+        }
+    }
+}
+
+/// Returns a snapshot of the run queue, for the metrics of the admin server.
+///
+/// # Examples
+///
+/// ```
+/// let stats = get_run_queue_stats();
+/// println!("Running: {}, pending: {}", stats.running, stats.depth);
+/// ```
+pub fn get_run_queue_stats() -> RunQueueStats {
+    let state = RUN_QUEUE.lock().unwrap();
+
+    RunQueueStats {
+        running: state.running,
+        depth: state.pending.len(),
+        max_depth: state.max_depth,
+        dropped: state.dropped,
+    }
+}
+
+/// Starts a run that holds one of the `max_concurrent` slots.
+fn spawn_run(run: Run) {
+    tokio::spawn(async move {
+        let _guard = RunGuard;
+        run.await;
+    });
+}
\ No newline at end of file
@@ -0,0 +1,110 @@
+use std::error::Error;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, Method, RequestBuilder, Url};
+use ring::{digest, hmac};
+
+use crate::models::s3_storage::S3Storage;
+
+/// The SHA-256 digest of an empty payload, in hex.
+const EMPTY_PAYLOAD_HASH: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+/// Builds a request for an object, signed with AWS Signature Version 4.
+///
+/// # Arguments
+///
+/// * `client` - The HTTP client.
+/// * `storage` - The storage providing the endpoint, bucket and credentials.
+/// * `method` - The HTTP method, `PUT` to upload or `GET` to download.
+/// * `key` - The rendered key of the object.
+/// * `body` - The payload of the request, empty for downloads.
+///
+/// # Errors
+///
+/// Returns an error if the object URL is invalid.
+///
+/// # Examples
+///
+/// ```
+/// let request = build_signed_request(&client, &s3_job.storage, Method::GET, "orders/2023-06-01.csv", Vec::new())?;
+/// let resp = request.send().await?;
+/// ```
+pub fn build_signed_request(client: &Client, storage: &S3Storage, method: Method, key: &str, body: Vec<u8>) -> Result<RequestBuilder, Box<dyn Error>> {
+    let url = get_object_url(storage, key)?;
+    let host = match url.port() {
+        Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+        None => url.host_str().unwrap_or_default().to_string()
+    };
+
+    let payload_hash = if body.is_empty() {
+        EMPTY_PAYLOAD_HASH.to_string()
+    } else {
+        hex(digest::digest(&digest::SHA256, &body).as_ref())
+    };
+
+    let now: DateTime<Utc> = Utc::now();
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let scope = format!("{}/{}/s3/aws4_request", date, storage.region);
+
+    let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+    let canonical_request = format!(
+        "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+        method, url.path(), host, payload_hash, amz_date, signed_headers, payload_hash
+    );
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date, scope, hex(digest::digest(&digest::SHA256, canonical_request.as_bytes()).as_ref())
+    );
+
+    let signing_key = [date.as_str(), storage.region.as_str(), "s3", "aws4_request"]
+        .iter()
+        .fold(format!("AWS4{}", storage.secret_key).into_bytes(), |key, part| hmac_sha256(&key, part.as_bytes()));
+    let signature = hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    let authorization = format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        storage.access_key, scope, signed_headers, signature
+    );
+
+    Ok(client.request(method, url)
+        .header("x-amz-content-sha256", payload_hash)
+        .header("x-amz-date", amz_date)
+        .header("authorization", authorization)
+        .body(body))
+}
+
+/// Returns the URL of an object, in path style (`endpoint/bucket/key`) or virtual-hosted style
+/// (`bucket.endpoint/key`).
+fn get_object_url(storage: &S3Storage, key: &str) -> Result<Url, Box<dyn Error>> {
+    let key = encode_key(key);
+
+    let url = if storage.path_style {
+        format!("{}/{}/{}", storage.endpoint, storage.bucket, key)
+    } else {
+        let (scheme, host) = storage.endpoint.split_once("://").unwrap_or(("https", &storage.endpoint));
+        format!("{}://{}.{}/{}", scheme, storage.bucket, host, key)
+    };
+
+    Url::parse(&url).map_err(|e| format!("Invalid object URL '{}': {}", url, e).into())
+}
+
+/// Percent-encodes an object key as required by the canonical request, keeping the `/` separators.
+fn encode_key(key: &str) -> String {
+    key.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Computes the HMAC-SHA256 of a message.
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+    hmac::sign(&key, message).as_ref().to_vec()
+}
+
+/// Encodes bytes as lowercase hex.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
\ No newline at end of file
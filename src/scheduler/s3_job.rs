@@ -0,0 +1,151 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+use reqwest::Method;
+use tokio::fs;
+
+use crate::configure::get_jobs;
+use crate::models::http_job_request::HttpVersion;
+use crate::models::job::{Job, JobFuture};
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+use crate::models::run_outcome::RunOutcome;
+use crate::models::s3_job::S3Job;
+use crate::models::transfer_operation::TransferOperation;
+use crate::scheduler::http_client::{ClientOptions, get_client};
+use crate::scheduler::s3_client::build_signed_request;
+use crate::utils::template_util::{get_run_vars, render_template};
+
+impl Job for S3Job {
+    fn kind(&self) -> &'static str {
+        "S3"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn enable(&self) -> bool {
+        self.enable
+    }
+
+    fn schedule(&self) -> &JobSchedule {
+        &self.schedule
+    }
+
+    fn options(&self) -> &JobOptions {
+        &self.options
+    }
+
+    fn execute(self: Arc<Self>, uuid: String, local_time: String) -> JobFuture<RunOutcome> {
+        Box::pin(async move { execute_s3_job(&self, &uuid, &local_time).await })
+    }
+}
+
+/// Transfers the file of a job, retrying on failure, and returns the outcome.
+///
+/// The key and file templates are rendered once per run, so all attempts target the same object.
+///
+/// # Arguments
+///
+/// * `s3_job` - The S3 job to run.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+///
+/// # Examples
+///
+/// ```
+/// let outcome = execute_s3_job(s3_job, &uuid, &local_time).await;
+/// println!("Success: {}", outcome.success);
+/// ```
+pub async fn execute_s3_job(s3_job: &S3Job, uuid: &str, local_time: &str) -> RunOutcome {
+    let vars = get_run_vars(&s3_job.name, uuid, &get_jobs().timezone);
+
+    let key = render_template(&s3_job.key, &vars);
+    let file = render_template(&s3_job.file, &vars);
+
+    let mut attempts = 0;
+    let mut last_error = String::from("No attempt was made.");
+
+    while attempts < s3_job.max_retry {
+        attempts += 1;
+
+        let started = Instant::now();
+        let result = match s3_job.operation {
+            TransferOperation::Upload => upload(s3_job, &key, &file).await,
+            TransferOperation::Download => download(s3_job, &key, &file).await,
+        };
+
+        match result {
+            Ok((status, bytes)) => {
+                println!("{} {} S3 {} success, job name: {}, bucket: {}, key: {}, file: {}, bytes: {}, elapsed: {}ms",
+                         uuid, local_time, s3_job.operation, &s3_job.name, &s3_job.storage.bucket, key, file, bytes, started.elapsed().as_millis());
+                return RunOutcome::new(uuid.to_string(), s3_job.name.clone(), true, Some(status), attempts, None);
+            }
+            Err(err) => {
+                println!("{} {} S3 {} failed, job name: {}, error: {}. Retry attempt: {}/{}",
+                         uuid, local_time, s3_job.operation, &s3_job.name, err, attempts, s3_job.max_retry);
+                last_error = err;
+            }
+        }
+    }
+
+    RunOutcome::new(uuid.to_string(), s3_job.name.clone(), false, None, attempts, Some(last_error))
+}
+
+/// Uploads a local file to an object.
+///
+/// # Returns
+///
+/// The response status and the number of bytes uploaded.
+async fn upload(s3_job: &S3Job, key: &str, file: &str) -> Result<(u16, usize), String> {
+    let body = fs::read(file)
+        .await
+        .map_err(|e| format!("An error occurred while reading the file '{}': {}", file, e))?;
+    let bytes = body.len();
+
+    let status = send(s3_job, Method::PUT, key, body).await?.status().as_u16();
+    Ok((status, bytes))
+}
+
+/// Downloads an object to a local file, creating missing parent directories.
+///
+/// # Returns
+///
+/// The response status and the number of bytes downloaded.
+async fn download(s3_job: &S3Job, key: &str, file: &str) -> Result<(u16, usize), String> {
+    let resp = send(s3_job, Method::GET, key, Vec::new()).await?;
+    let status = resp.status().as_u16();
+    let body = resp.bytes().await.map_err(|e| e.to_string())?;
+
+    if let Some(parent) = Path::new(file).parent() {
+        fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("An error occurred while creating the directory '{}': {}", parent.display(), e))?;
+    }
+    fs::write(file, &body)
+        .await
+        .map_err(|e| format!("An error occurred while writing the file '{}': {}", file, e))?;
+
+    Ok((status, body.len()))
+}
+
+/// Sends a signed request for an object and checks that the response status is successful.
+async fn send(s3_job: &S3Job, method: Method, key: &str, body: Vec<u8>) -> Result<reqwest::Response, String> {
+    let options = ClientOptions::new(s3_job.timeout, None, HttpVersion::Auto, get_jobs().user_agent.clone());
+    let client = get_client(&options).map_err(|e| e.to_string())?;
+
+    let resp = build_signed_request(&client, &s3_job.storage, method, key, body)
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        return Err(format!("Unexpected status {}: {}", status, text));
+    }
+
+    Ok(resp)
+}
\ No newline at end of file
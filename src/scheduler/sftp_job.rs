@@ -0,0 +1,218 @@
+use std::fs::{self, File};
+use std::io;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use ssh2::{CheckResult, KnownHostFileKind, Session};
+
+use crate::configure::get_jobs;
+use crate::models::job::{Job, JobFuture};
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+use crate::models::run_outcome::RunOutcome;
+use crate::models::sftp_job::{OverwritePolicy, SftpJob};
+use crate::models::transfer_operation::TransferOperation;
+use crate::utils::template_util::{get_run_vars, render_template};
+
+impl Job for SftpJob {
+    fn kind(&self) -> &'static str {
+        "SFTP"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn enable(&self) -> bool {
+        self.enable
+    }
+
+    fn schedule(&self) -> &JobSchedule {
+        &self.schedule
+    }
+
+    fn options(&self) -> &JobOptions {
+        &self.options
+    }
+
+    fn execute(self: Arc<Self>, uuid: String, local_time: String) -> JobFuture<RunOutcome> {
+        Box::pin(async move { execute_sftp_job(self, &uuid, &local_time).await })
+    }
+}
+
+/// Transfers the file of a job, retrying on failure, and returns the outcome.
+///
+/// The path templates are rendered once per run, so all attempts target the same files. The
+/// transfer runs on the blocking thread pool.
+///
+/// # Arguments
+///
+/// * `sftp_job` - The SFTP job to run.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+///
+/// # Examples
+///
+/// ```
+/// let outcome = execute_sftp_job(Arc::new(sftp_job), &uuid, &local_time).await;
+/// println!("Success: {}", outcome.success);
+/// ```
+pub async fn execute_sftp_job(sftp_job: Arc<SftpJob>, uuid: &str, local_time: &str) -> RunOutcome {
+    let vars = get_run_vars(&sftp_job.name, uuid, &get_jobs().timezone);
+    let remote_path = render_template(&sftp_job.remote_path, &vars);
+    let local_path = render_template(&sftp_job.local_path, &vars);
+
+    let mut attempts = 0;
+    let mut last_error = String::from("No attempt was made.");
+
+    while attempts < sftp_job.max_retry {
+        attempts += 1;
+
+        let started = Instant::now();
+        let (job, remote, local) = (sftp_job.clone(), remote_path.clone(), local_path.clone());
+        let result = tokio::task::spawn_blocking(move || transfer(&job, &remote, &local))
+            .await
+            .unwrap_or_else(|e| Err(e.to_string()));
+
+        match result {
+            Ok(Some(bytes)) => {
+                println!("{} {} SFTP {} success, job name: {}, remote: {}, local: {}, bytes: {}, elapsed: {}ms",
+                         uuid, local_time, sftp_job.operation, &sftp_job.name, remote_path, local_path, bytes, started.elapsed().as_millis());
+                return RunOutcome::new(uuid.to_string(), sftp_job.name.clone(), true, None, attempts, None);
+            }
+            Ok(None) => {
+                println!("{} {} SFTP {} skipped, job name: {}, destination exists", uuid, local_time, sftp_job.operation, &sftp_job.name);
+                return RunOutcome::new(uuid.to_string(), sftp_job.name.clone(), true, None, attempts, None);
+            }
+            Err(err) => {
+                println!("{} {} SFTP {} failed, job name: {}, error: {}. Retry attempt: {}/{}",
+                         uuid, local_time, sftp_job.operation, &sftp_job.name, err, attempts, sftp_job.max_retry);
+                last_error = err;
+            }
+        }
+    }
+
+    RunOutcome::new(uuid.to_string(), sftp_job.name.clone(), false, None, attempts, Some(last_error))
+}
+
+/// Connects to the server of a job and transfers a file in the direction of its operation.
+///
+/// The file is written to a `.part` file first and renamed when complete, so readers never see
+/// a partial file.
+///
+/// # Returns
+///
+/// The number of bytes transferred, or `None` if the destination exists and the overwrite policy
+/// is `skip`.
+fn transfer(sftp_job: &SftpJob, remote_path: &str, local_path: &str) -> Result<Option<u64>, String> {
+    let session = connect(sftp_job)?;
+    let sftp = session.sftp().map_err(|e| format!("Failed to start SFTP: {}", e))?;
+    let remote = Path::new(remote_path);
+    let local = Path::new(local_path);
+
+    match sftp_job.operation {
+        TransferOperation::Upload => {
+            let exists = sftp.stat(remote).is_ok();
+            if exists && !should_overwrite(sftp_job, remote_path)? {
+                return Ok(None);
+            }
+
+            let mut source = File::open(local)
+                .map_err(|e| format!("An error occurred while reading the file '{}': {}", local_path, e))?;
+            let part = format!("{}.part", remote_path);
+            let mut target = sftp.create(Path::new(&part))
+                .map_err(|e| format!("An error occurred while creating the remote file '{}': {}", part, e))?;
+            let bytes = io::copy(&mut source, &mut target).map_err(|e| e.to_string())?;
+            drop(target);
+
+            if exists {
+                sftp.unlink(remote).map_err(|e| format!("An error occurred while replacing the remote file '{}': {}", remote_path, e))?;
+            }
+            sftp.rename(Path::new(&part), remote, None)
+                .map_err(|e| format!("An error occurred while renaming the remote file '{}': {}", part, e))?;
+
+            Ok(Some(bytes))
+        }
+        TransferOperation::Download => {
+            if local.exists() && !should_overwrite(sftp_job, local_path)? {
+                return Ok(None);
+            }
+
+            let mut source = sftp.open(remote)
+                .map_err(|e| format!("An error occurred while opening the remote file '{}': {}", remote_path, e))?;
+            if let Some(parent) = local.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("An error occurred while creating the directory '{}': {}", parent.display(), e))?;
+            }
+            let part = format!("{}.part", local_path);
+            let mut target = File::create(&part)
+                .map_err(|e| format!("An error occurred while writing the file '{}': {}", part, e))?;
+            let bytes = io::copy(&mut source, &mut target).map_err(|e| e.to_string())?;
+            drop(target);
+
+            fs::rename(&part, local)
+                .map_err(|e| format!("An error occurred while renaming the file '{}': {}", part, e))?;
+
+            Ok(Some(bytes))
+        }
+    }
+}
+
+/// Applies the overwrite policy of a job to an existing destination.
+///
+/// # Returns
+///
+/// `true` if the destination is replaced, `false` if the transfer is skipped.
+fn should_overwrite(sftp_job: &SftpJob, destination: &str) -> Result<bool, String> {
+    match sftp_job.overwrite {
+        OverwritePolicy::Overwrite => Ok(true),
+        OverwritePolicy::Skip => Ok(false),
+        OverwritePolicy::Fail => Err(format!("The destination '{}' already exists.", destination)),
+    }
+}
+
+/// Opens an authenticated SSH session to the server of a job.
+///
+/// # Errors
+///
+/// Returns an error if the connection or the handshake fails, if the host key is not listed in
+/// the `known_hosts` file of the job, or if the key is rejected.
+fn connect(sftp_job: &SftpJob) -> Result<Session, String> {
+    let timeout = Duration::from_millis(sftp_job.timeout);
+    let address = (sftp_job.host.as_str(), sftp_job.port)
+        .to_socket_addrs()
+        .map_err(|e| format!("Failed to resolve '{}': {}", sftp_job.host, e))?
+        .next()
+        .ok_or(format!("No address found for '{}'", sftp_job.host))?;
+    let stream = TcpStream::connect_timeout(&address, timeout).map_err(|e| e.to_string())?;
+
+    let mut session = Session::new().map_err(|e| e.to_string())?;
+    session.set_timeout(u32::try_from(sftp_job.timeout).unwrap_or(u32::MAX));
+    session.set_tcp_stream(stream);
+    session.handshake().map_err(|e| format!("SSH handshake failed: {}", e))?;
+
+    if let Some(known_hosts) = &sftp_job.known_hosts {
+        verify_host_key(&session, sftp_job, known_hosts)?;
+    }
+
+    session.userauth_pubkey_file(&sftp_job.username, None, Path::new(&sftp_job.private_key), sftp_job.passphrase.as_deref())
+        .map_err(|e| format!("Authentication failed: {}", e))?;
+
+    Ok(session)
+}
+
+/// Checks the host key of the server against a `known_hosts` file.
+fn verify_host_key(session: &Session, sftp_job: &SftpJob, known_hosts: &str) -> Result<(), String> {
+    let mut hosts = session.known_hosts().map_err(|e| e.to_string())?;
+    hosts.read_file(Path::new(known_hosts), KnownHostFileKind::OpenSSH)
+        .map_err(|e| format!("An error occurred while reading the known hosts file '{}': {}", known_hosts, e))?;
+
+    let (key, _) = session.host_key().ok_or("The server sent no host key.")?;
+    match hosts.check_port(&sftp_job.host, sftp_job.port, key) {
+        CheckResult::Match => Ok(()),
+        CheckResult::Mismatch => Err(format!("The host key of '{}' does not match the known hosts file.", sftp_job.host)),
+        CheckResult::NotFound => Err(format!("The host '{}' is not in the known hosts file.", sftp_job.host)),
+        CheckResult::Failure => Err(format!("Failed to check the host key of '{}'.", sftp_job.host)),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,157 @@
+use std::error::Error;
+use std::sync::Mutex;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use tokio::time::Instant;
+
+use crate::configure::get_jobs;
+use crate::models::sharding::Sharding;
+use crate::scheduler::distributed_lock::get_instance_id;
+use crate::scheduler::redis_client::{RedisConnection, RedisReply};
+use crate::utils::hash_util::{fnv1a, mix};
+
+/// The identifiers of the members of the cluster, sorted, as of the last renewal of the lease.
+///
+/// Empty while this instance is not a member, e.g. when it cannot reach Redis.
+static MEMBERS: Lazy<Mutex<Vec<String>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// The state of the membership of this instance.
+struct Membership {
+    connection: Option<RedisConnection>,
+    /// When the lease of this member was last renewed.
+    renewed_at: Option<Instant>,
+}
+
+/// Returns whether this instance fires the triggers of a job.
+///
+/// The jobs are partitioned among the members of the cluster with rendezvous hashing, a form
+/// of consistent hashing: a job belongs to the member with the highest hash of the member and
+/// the job name. All members compute the same owner from the same members, and when a member
+/// joins or leaves only the jobs it gains or loses move. Without a `sharding` section every
+/// instance fires the triggers of all jobs.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job.
+///
+/// # Examples
+///
+/// ```
+/// if is_assigned(job.name()) {
+///     submit_run(job.name(), Some(scheduled_at), start_job(job.clone()));
+/// }
+/// ```
+pub fn is_assigned(job_name: &str) -> bool {
+    if get_jobs().sharding.is_none() {
+        return true;
+    }
+
+    let members = MEMBERS.lock().unwrap();
+    get_owner(&members, job_name) == Some(get_instance_id())
+}
+
+/// Returns the number of members of the cluster, for the metrics of the admin server.
+///
+/// # Examples
+///
+/// ```
+/// println!("Members: {}", get_member_count());
+/// ```
+pub fn get_member_count() -> usize {
+    MEMBERS.lock().unwrap().len()
+}
+
+/// Joins the cluster of instances sharing the jobs, if `sharding` is configured.
+///
+/// The first renewal of the lease is awaited, so the instance knows its share of the jobs
+/// before they are scheduled. The following renewals run in the background every third of the
+/// lease, and read the members again. The members whose lease expired are removed, so their
+/// jobs are rebalanced to the remaining members. A member that cannot renew its lease leaves
+/// the cluster before the lease expires, and fires no trigger until it can renew it again.
+///
+/// # Examples
+///
+/// ```
+/// start_sharding().await;
+/// println!("Members: {}", get_member_count());
+/// ```
+pub async fn start_sharding() {
+    let Some(sharding) = get_jobs().sharding.clone() else {
+        return;
+    };
+
+    let mut membership = Membership { connection: None, renewed_at: None };
+    membership.renew(&sharding).await;
+
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(sharding.renew_interval()).await;
+            membership.renew(&sharding).await;
+        }
+    });
+}
+
+impl Membership {
+
+    /// Renews the lease of this member and updates the members of the cluster.
+    async fn renew(&mut self, sharding: &Sharding) {
+        match self.read_members(sharding).await {
+            Ok(members) => {
+                self.renewed_at = Some(Instant::now());
+                let mut current = MEMBERS.lock().unwrap();
+                if *current != members {
+                    println!("Cluster members changed, instance: {}, members: {}", get_instance_id(), members.len());
+                    *current = members;
+                }
+            }
+            Err(e) => {
+                self.connection = None;
+                println!("Sharding failed, instance: {}, error: {}", get_instance_id(), e);
+                let expiring = self.renewed_at
+                    .is_none_or(|at| at.elapsed() + sharding.renew_interval() >= sharding.lease);
+                let mut current = MEMBERS.lock().unwrap();
+                if expiring && !current.is_empty() {
+                    current.clear();
+                    println!("Left the cluster, instance: {}, the lease could not be renewed", get_instance_id());
+                }
+            }
+        }
+    }
+
+    /// Renews the lease of this member, removes the expired members and reads the others.
+    ///
+    /// The members are a sorted set scored by the expiration time of their lease, in
+    /// milliseconds since the epoch, so the clocks of the instances must be in sync.
+    async fn read_members(&mut self, sharding: &Sharding) -> Result<Vec<String>, Box<dyn Error>> {
+        let redis = match self.connection.as_mut() {
+            Some(redis) => redis,
+            None => self.connection.insert(RedisConnection::connect(&sharding.redis, sharding.renew_interval()).await?)
+        };
+
+        let now = Utc::now().timestamp_millis();
+        let expires_at = (now + sharding.lease.as_millis() as i64).to_string();
+        redis.command(&["ZADD", &sharding.key, &expires_at, get_instance_id()]).await?;
+        redis.command(&["ZREMRANGEBYSCORE", &sharding.key, "-inf", &now.to_string()]).await?;
+
+        let RedisReply::Array(items) = redis.command(&["ZRANGE", &sharding.key, "0", "-1"]).await? else {
+            return Err("Invalid reply to ZRANGE, expected an array.".into());
+        };
+        let mut members = items.into_iter()
+            .map(|item| match item {
+                RedisReply::Bulk(member) => Ok(String::from_utf8_lossy(&member).into_owned()),
+                _ => Err("Invalid member in the reply to ZRANGE.")
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        members.sort();
+        Ok(members)
+    }
+}
+
+/// Returns the member a job belongs to, see `is_assigned`.
+fn get_owner<'a>(members: &'a [String], job_name: &str) -> Option<&'a str> {
+    let job_hash = fnv1a(job_name.as_bytes());
+
+    members.iter()
+        .max_by_key(|member| (mix(fnv1a(member.as_bytes()) ^ job_hash), member.as_str()))
+        .map(|member| member.as_str())
+}
\ No newline at end of file
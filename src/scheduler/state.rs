@@ -0,0 +1,360 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+
+use crate::configure::get_jobs;
+use crate::models::scheduler_state::{RecentRun, SchedulerState};
+
+/// The number of last runs of a job kept in the state file for its rolling statistics.
+pub const RECENT_RUNS: usize = 100;
+
+/// Serializes the updates of the state file between concurrently finishing jobs.
+static STATE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// The paused jobs as last read from the state file, or `None` if it was not read yet.
+static PAUSED_JOBS: Lazy<Mutex<Option<PausedJobs>>> = Lazy::new(|| Mutex::new(None));
+
+/// The names of the paused jobs, and the modification time of the state file they were read
+/// from, `None` if the file didn't exist.
+struct PausedJobs {
+    modified: Option<SystemTime>,
+    names: HashSet<String>,
+}
+
+/// Reads the scheduler state from the state file.
+///
+/// A missing file is treated as an empty state.
+///
+/// # Arguments
+///
+/// * `path` - The path of the state file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not a valid state.
+///
+/// # Examples
+///
+/// ```
+/// let state = read_state("./rjob_state.json")?;
+/// println!("{} jobs", state.jobs.len());
+/// ```
+pub fn read_state(path: &str) -> Result<SchedulerState, Box<dyn Error>> {
+    if !Path::new(path).exists() {
+        return Ok(SchedulerState::default());
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("An error occurred while reading the file '{}': {}", path, e))?;
+    let state = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid state file '{}': {}", path, e))?;
+
+    Ok(state)
+}
+
+/// Writes the scheduler state to the state file.
+///
+/// The state is written to a temporary file first and then renamed, so a crash never leaves a
+/// partially written state file behind.
+///
+/// # Arguments
+///
+/// * `path` - The path of the state file.
+/// * `state` - The state to write.
+///
+/// # Errors
+///
+/// Returns an error if the state cannot be serialized or the file cannot be written.
+pub fn write_state(path: &str, state: &SchedulerState) -> Result<(), Box<dyn Error>> {
+    let content = serde_json::to_string_pretty(state)?;
+    let tmp_path = format!("{}.tmp", path);
+
+    fs::write(&tmp_path, content)
+        .map_err(|e| format!("An error occurred while writing the file '{}': {}", tmp_path, e))?;
+    fs::rename(&tmp_path, path)
+        .map_err(|e| format!("An error occurred while writing the file '{}': {}", path, e))?;
+
+    Ok(())
+}
+
+/// Applies a change to the persisted state of the scheduler.
+///
+/// The update holds an exclusive lock on the `.lock` file next to the state file, so it is
+/// serialized with the updates of other processes, such as the `rjob pause` command changing
+/// the state of a running scheduler.
+///
+/// # Arguments
+///
+/// * `update` - The change to apply to the state.
+///
+/// # Errors
+///
+/// Returns an error if the state file cannot be read, written or locked.
+///
+/// # Examples
+///
+/// ```
+/// update_state(|state| {
+///     state.jobs.remove("old_job");
+/// })?;
+/// ```
+pub fn update_state<F: FnOnce(&mut SchedulerState)>(update: F) -> Result<(), Box<dyn Error>> {
+    let path = &get_jobs().state_file;
+
+    let _guard = STATE_LOCK.lock().unwrap();
+    let lock_path = format!("{}.lock", path);
+    let lock_file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .map_err(|e| format!("An error occurred while opening the file '{}': {}", lock_path, e))?;
+    // The lock is released when the file is closed.
+    lock_file.lock()
+        .map_err(|e| format!("An error occurred while locking the file '{}': {}", lock_path, e))?;
+
+    let mut state = read_state(path)?;
+    update(&mut state);
+    write_state(path, &state)
+}
+
+/// Counts a new run of a job, unless the job already reached its maximum number of runs.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job.
+/// * `max_runs` - The maximum number of runs of the job.
+///
+/// # Returns
+///
+/// `true` if the run was counted and may start, `false` if the job reached `max_runs`.
+///
+/// # Errors
+///
+/// Returns an error if the state file cannot be read or written.
+///
+/// # Examples
+///
+/// ```
+/// if !claim_run("backfill", 24)? {
+///     println!("The backfill job is done.");
+/// }
+/// ```
+pub fn claim_run(job_name: &str, max_runs: u64) -> Result<bool, Box<dyn Error>> {
+    let mut claimed = false;
+    update_state(|state| {
+        let job = state.jobs.entry(job_name.to_string()).or_default();
+        if job.runs < max_runs {
+            job.runs += 1;
+            claimed = true;
+        }
+    })?;
+    Ok(claimed)
+}
+
+/// Increments the run sequence number of a job.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job.
+///
+/// # Returns
+///
+/// The new sequence number, starting at 1.
+///
+/// # Errors
+///
+/// Returns an error if the state file cannot be read or written.
+///
+/// # Examples
+///
+/// ```
+/// let sequence = next_sequence("users2")?;
+/// println!("Run #{}", sequence);
+/// ```
+pub fn next_sequence(job_name: &str) -> Result<u64, Box<dyn Error>> {
+    let mut sequence = 0;
+    update_state(|state| {
+        let job = state.jobs.entry(job_name.to_string()).or_default();
+        job.sequence += 1;
+        sequence = job.sequence;
+    })?;
+    Ok(sequence)
+}
+
+/// Returns the number of runs a job started, as recorded in the state file.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job.
+///
+/// # Errors
+///
+/// Returns an error if the state file cannot be read.
+pub fn get_run_count(job_name: &str) -> Result<u64, Box<dyn Error>> {
+    let state = read_state(&get_jobs().state_file)?;
+    Ok(state.jobs.get(job_name).map(|job| job.runs).unwrap_or(0))
+}
+
+/// Pauses or resumes a job.
+///
+/// The triggers of a paused job are skipped until it is resumed.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job.
+/// * `paused` - `true` to pause the job, `false` to resume it.
+///
+/// # Errors
+///
+/// Returns an error if the state file cannot be read or written.
+///
+/// # Examples
+///
+/// ```
+/// set_paused("login", true)?;
+/// assert!(is_paused("login")?);
+/// ```
+pub fn set_paused(job_name: &str, paused: bool) -> Result<(), Box<dyn Error>> {
+    update_state(|state| {
+        state.jobs.entry(job_name.to_string()).or_default().paused = paused;
+    })
+}
+
+/// Returns whether a job is paused, as recorded in the state file.
+///
+/// The paused jobs are cached, and read again when the modification time of the state file
+/// changes, so a job paused by another process, such as the `rjob pause` command, is picked
+/// up by the running scheduler without reading the whole file at every trigger.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job.
+///
+/// # Errors
+///
+/// Returns an error if the state file cannot be read.
+///
+/// # Examples
+///
+/// ```
+/// if let Ok(true) = is_paused("login").await {
+///     println!("The login job is paused.");
+/// }
+/// ```
+pub async fn is_paused(job_name: &str) -> Result<bool, Box<dyn Error>> {
+    let path = &get_jobs().state_file;
+    let modified = match tokio::fs::metadata(path).await {
+        Ok(metadata) => Some(metadata.modified()?),
+        Err(e) if e.kind() == ErrorKind::NotFound => None,
+        Err(e) => return Err(format!("An error occurred while reading the file '{}': {}", path, e).into())
+    };
+
+    if let Some(paused_jobs) = PAUSED_JOBS.lock().unwrap().as_ref().filter(|paused_jobs| paused_jobs.modified == modified) {
+        return Ok(paused_jobs.names.contains(job_name));
+    }
+
+    let names: HashSet<String> = match modified {
+        Some(_) => {
+            let content = tokio::fs::read_to_string(path).await
+                .map_err(|e| format!("An error occurred while reading the file '{}': {}", path, e))?;
+            let state: SchedulerState = serde_json::from_str(&content)
+                .map_err(|e| format!("Invalid state file '{}': {}", path, e))?;
+            state.jobs.into_iter().filter(|(_, job)| job.paused).map(|(name, _)| name).collect()
+        }
+        None => HashSet::new()
+    };
+    let paused = names.contains(job_name);
+    *PAUSED_JOBS.lock().unwrap() = Some(PausedJobs { modified, names });
+
+    Ok(paused)
+}
+
+/// Counts a finished run of a job in its statistics, kept across restarts.
+///
+/// The run is also added to the window of the last `RECENT_RUNS` runs of the job, from which
+/// its rolling success rate and latencies are computed, see `JobStats`.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job.
+/// * `time` - The start time of the run.
+/// * `duration` - The time from the start of the run to its end, retries included.
+/// * `success` - Whether the run succeeded.
+///
+/// # Errors
+///
+/// Returns an error if the state file cannot be read or written.
+///
+/// # Examples
+///
+/// ```
+/// record_run("login", started_at, Duration::from_millis(120), outcome.success)?;
+/// ```
+pub fn record_run(job_name: &str, time: DateTime<Utc>, duration: Duration, success: bool) -> Result<(), Box<dyn Error>> {
+    update_state(|state| {
+        let job = state.jobs.entry(job_name.to_string()).or_default();
+        job.last_run = Some(time);
+        if success {
+            job.successes += 1;
+            job.consecutive_failures = 0;
+        } else {
+            job.failures += 1;
+            job.consecutive_failures += 1;
+            job.last_failure = Some(time);
+        }
+
+        job.recent_runs.push(RecentRun { duration_ms: duration.as_millis() as u64, success });
+        if job.recent_runs.len() > RECENT_RUNS {
+            job.recent_runs.drain(..job.recent_runs.len() - RECENT_RUNS);
+        }
+    })
+}
+
+/// Records the time of a successful run of a job.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job.
+/// * `time` - The fire time the run was scheduled at, or its start time if it was not started
+///   by the timer. The missed runs are counted from it, see `catch_up_missed_runs`.
+///
+/// # Errors
+///
+/// Returns an error if the state file cannot be read or written.
+pub fn record_success(job_name: &str, time: DateTime<Utc>) -> Result<(), Box<dyn Error>> {
+    update_state(|state| {
+        state.jobs.entry(job_name.to_string()).or_default().last_success = Some(time);
+    })
+}
+
+/// Stores a value read by the `when` conditions of the jobs as `kv.NAME`, or removes it.
+///
+/// # Arguments
+///
+/// * `name` - The name of the value.
+/// * `value` - The value, or `None` to remove it.
+///
+/// # Errors
+///
+/// Returns an error if the state file cannot be read or written.
+///
+/// # Examples
+///
+/// ```
+/// set_value("maintenance", Some("on"))?;
+/// ```
+pub fn set_value(name: &str, value: Option<&str>) -> Result<(), Box<dyn Error>> {
+    update_state(|state| {
+        match value {
+            Some(value) => state.values.insert(name.to_string(), value.to_string()),
+            None => state.values.remove(name)
+        };
+    })
+}
\ No newline at end of file
@@ -0,0 +1,128 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fs;
+use std::process;
+use std::sync::Mutex;
+use chrono::{DateTime, SecondsFormat, Utc};
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+
+use crate::configure::get_jobs;
+use crate::models::run_outcome::RunOutcome;
+use crate::scheduler::leader_election::is_leader;
+use crate::scheduler::run_log::append_run_record;
+use crate::scheduler::state::{read_state, record_run};
+use crate::scheduler::timer::get_next_fire_times;
+
+/// The outcome of the last run of each job that ran since the scheduler started.
+static LAST_OUTCOMES: Lazy<Mutex<HashMap<String, RunOutcome>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Records the outcome of a run of a job.
+///
+/// The run is counted in the statistics of the job in the state file, so the counters and the
+/// times of the last runs survive a restart, see `record_run`. The duration of the run is
+/// measured from `started_at` to now, retries included. The status code and the error
+/// of the last run are only kept in memory, for the status file. With `--run-log`, a record of
+/// the run is also appended to the run log, see `append_run_record`.
+///
+/// # Arguments
+///
+/// * `outcome` - The outcome of the run.
+/// * `started_at` - The time the run started.
+///
+/// # Examples
+///
+/// ```
+/// record_outcome(&outcome, started_at);
+/// notify_outcome(&job.options().notifications, &outcome).await;
+/// ```
+pub fn record_outcome(outcome: &RunOutcome, started_at: DateTime<Utc>) {
+    let duration = (Utc::now() - started_at).to_std().unwrap_or_default();
+    if let Err(err) = record_run(&outcome.job_name, started_at, duration, outcome.success) {
+        println!("{} Failed to update state file, job name: {}, error: {}", outcome.run_id, outcome.job_name, err);
+    }
+    if let Err(err) = append_run_record(outcome, started_at, duration) {
+        println!("{} Failed to write run log, job name: {}, error: {}", outcome.run_id, outcome.job_name, err);
+    }
+    LAST_OUTCOMES.lock().unwrap().insert(outcome.job_name.clone(), outcome.clone());
+}
+
+/// Writes the status file in the background every `interval`, if a `status_file` section is
+/// configured.
+///
+/// The snapshot lists the jobs scheduled on this instance and the jobs that ran since the
+/// scheduler started, with their next fire time, their last run and result, and their run
+/// statistics from the state file. The file is replaced atomically, so a reader never sees a
+/// partial snapshot.
+///
+/// # Examples
+///
+/// ```
+/// start_timer();
+/// start_status_file();
+/// ```
+pub fn start_status_file() {
+    let Some(status_file) = get_jobs().status_file.clone() else {
+        return;
+    };
+    println!("Writing the status file, file: {}, interval: {}s", status_file.path, status_file.interval.as_secs());
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(status_file.interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = write_status_file(&status_file.path) {
+                println!("Failed to write the status file, file: {}, error: {}", status_file.path, e);
+            }
+        }
+    });
+}
+
+/// Writes a snapshot of the status of the jobs to the status file.
+fn write_status_file(path: &str) -> Result<(), Box<dyn Error>> {
+    let state = read_state(&get_jobs().state_file)?;
+    let next_fire_times = get_next_fire_times();
+    let last_outcomes = LAST_OUTCOMES.lock().unwrap().clone();
+
+    let names = next_fire_times.keys().chain(last_outcomes.keys());
+    let jobs: BTreeMap<&String, Value> = names
+        .map(|name| {
+            let job = state.jobs.get(name).cloned().unwrap_or_default();
+            let outcome = last_outcomes.get(name);
+            let last_result = job.last_run.map(|_| if job.consecutive_failures == 0 { "success" } else { "failure" });
+
+            (name, json!({
+                "next_run": next_fire_times.get(name).copied().map(format_time),
+                "last_run": job.last_run.map(format_time),
+                "last_result": last_result,
+                "last_status": outcome.and_then(|o| o.status),
+                "last_error": outcome.and_then(|o| o.error.clone()),
+                "last_success": job.last_success.map(format_time),
+                "last_failure": job.last_failure.map(format_time),
+                "consecutive_failures": job.consecutive_failures,
+                "successes": job.successes,
+                "failures": job.failures,
+            }))
+        })
+        .collect();
+
+    let status = json!({
+        "updated_at": format_time(Utc::now()),
+        "pid": process::id(),
+        "leader": is_leader(),
+        "jobs": jobs,
+    });
+
+    let tmp_path = format!("{}.tmp", path);
+    fs::write(&tmp_path, serde_json::to_string_pretty(&status)?)
+        .map_err(|e| format!("An error occurred while writing the file '{}': {}", tmp_path, e))?;
+    fs::rename(&tmp_path, path)
+        .map_err(|e| format!("An error occurred while writing the file '{}': {}", path, e))?;
+
+    Ok(())
+}
+
+/// Formats a time of the status file, in UTC with milliseconds.
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
\ No newline at end of file
@@ -0,0 +1,136 @@
+use std::env;
+use std::io;
+use std::time::Duration;
+
+use crate::models::job_schedule::JobSchedule;
+use crate::scheduler::timer::add_internal_trigger;
+
+/// The environment variable holding the socket of the service manager, set by systemd for
+/// services with `Type=notify`.
+const NOTIFY_SOCKET_VARIABLE: &str = "NOTIFY_SOCKET";
+
+/// The environment variable holding the watchdog timeout in microseconds, set by systemd for
+/// services with `WatchdogSec=`.
+const WATCHDOG_USEC_VARIABLE: &str = "WATCHDOG_USEC";
+
+/// The environment variable holding the process expected to send the keep-alives, if any.
+const WATCHDOG_PID_VARIABLE: &str = "WATCHDOG_PID";
+
+/// The name of the internal job sending the watchdog keep-alives.
+const KEEP_ALIVE_JOB_NAME: &str = "rjob-systemd-watchdog";
+
+/// Sends a state change to systemd (`sd_notify`), e.g. `READY=1`.
+///
+/// Does nothing when rjob is not run by systemd with `Type=notify`, i.e. without a
+/// `NOTIFY_SOCKET`. A notification that cannot be sent is logged.
+///
+/// # Arguments
+///
+/// * `state` - The newline-separated assignments of the state, see `sd_notify(3)`.
+///
+/// # Examples
+///
+/// ```
+/// notify_systemd("READY=1\nSTATUS=Scheduling 12 jobs");
+/// ```
+pub fn notify_systemd(state: &str) {
+    let Some(socket) = env::var_os(NOTIFY_SOCKET_VARIABLE) else {
+        return;
+    };
+    let socket = socket.to_string_lossy().into_owned();
+
+    if let Err(e) = send_notification(&socket, state) {
+        println!("Failed to notify systemd, socket: {}, error: {}", socket, e);
+    }
+}
+
+/// Returns the interval of the watchdog keep-alives expected by systemd, half its timeout.
+///
+/// # Returns
+///
+/// The interval, or `None` if the watchdog of the service is disabled or expects the
+/// keep-alives of another process.
+///
+/// # Examples
+///
+/// ```
+/// if let Some(interval) = get_watchdog_interval() {
+///     println!("Watchdog keep-alive every {:?}", interval);
+/// }
+/// ```
+pub fn get_watchdog_interval() -> Option<Duration> {
+    if let Some(pid) = env::var(WATCHDOG_PID_VARIABLE).ok().and_then(|pid| pid.parse::<u32>().ok()) {
+        if pid != std::process::id() {
+            return None;
+        }
+    }
+
+    env::var(WATCHDOG_USEC_VARIABLE).ok()
+        .and_then(|usec| usec.parse::<u64>().ok())
+        .filter(|usec| *usec > 0)
+        .map(|usec| Duration::from_micros(usec) / 2)
+}
+
+/// Sends the watchdog keep-alives (`WATCHDOG=1`) expected by systemd (`WatchdogSec=`), so
+/// systemd restarts a stalled rjob.
+///
+/// The keep-alives are sent by an internal job on the timer shared by all jobs, see
+/// `add_internal_trigger`, at least every half of the watchdog timeout and at most every
+/// second. They stop when the timer loop stops firing, e.g. because it died, the runtime is
+/// blocked or the run queue holds back the timer, not only when the whole runtime is blocked.
+/// Does nothing when the watchdog of the service is disabled.
+///
+/// # Examples
+///
+/// ```
+/// start_timer();
+/// start_systemd_watchdog();
+/// ```
+pub fn start_systemd_watchdog() {
+    let Some(interval) = get_watchdog_interval() else {
+        return;
+    };
+
+    // The fire times of a step in seconds are at most the step apart, also across minutes.
+    let cron = match interval.as_secs().clamp(1, 60) {
+        60 => "0 * * * * *".to_string(),
+        seconds => format!("*/{} * * * * *", seconds)
+    };
+    let schedule = match JobSchedule::cron(&cron) {
+        Ok(schedule) => schedule,
+        Err(err) => {
+            println!("Failed to start the systemd watchdog, error: {}", err);
+            return;
+        }
+    };
+    add_internal_trigger(KEEP_ALIVE_JOB_NAME, &schedule, |_| notify_systemd("WATCHDOG=1"));
+
+    println!("Systemd watchdog enabled, keep-alive interval: {}ms, cron: {}", interval.as_millis(), cron);
+}
+
+/// Sends a datagram to the notification socket, a path or an abstract socket starting with `@`.
+#[cfg(unix)]
+fn send_notification(socket: &str, state: &str) -> io::Result<()> {
+    let datagram = std::os::unix::net::UnixDatagram::unbound()?;
+
+    if let Some(name) = socket.strip_prefix('@') {
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::linux::net::SocketAddrExt;
+            let address = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+            datagram.send_to_addr(state.as_bytes(), &address)?;
+            return Ok(());
+        }
+        #[cfg(not(target_os = "linux"))]
+        return Err(io::Error::new(io::ErrorKind::Unsupported, format!("abstract socket '@{}' not supported", name)));
+    }
+
+    datagram.send_to(state.as_bytes(), socket)?;
+    Ok(())
+}
+
+/// Sends a notification to systemd, which doesn't run on this platform.
+#[cfg(not(unix))]
+fn send_notification(socket: &str, _state: &str) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, format!("socket '{}' not supported", socket)))
+}
\ No newline at end of file
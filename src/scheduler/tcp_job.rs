@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+
+use crate::models::job::{Job, JobFuture};
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+use crate::models::run_outcome::RunOutcome;
+use crate::models::tcp_job::TcpJob;
+
+impl Job for TcpJob {
+    fn kind(&self) -> &'static str {
+        "TCP"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn enable(&self) -> bool {
+        self.enable
+    }
+
+    fn schedule(&self) -> &JobSchedule {
+        &self.schedule
+    }
+
+    fn options(&self) -> &JobOptions {
+        &self.options
+    }
+
+    fn execute(self: Arc<Self>, uuid: String, local_time: String) -> JobFuture<RunOutcome> {
+        Box::pin(async move { execute_tcp_job(&self, &uuid, &local_time).await })
+    }
+}
+
+/// Connects to the port of a job, retrying on failure, and returns the outcome.
+///
+/// The connect latency of the successful attempt is logged.
+///
+/// # Arguments
+///
+/// * `tcp_job` - The TCP job to run.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+///
+/// # Examples
+///
+/// ```
+/// let outcome = execute_tcp_job(tcp_job, &uuid, &local_time).await;
+/// println!("Success: {}", outcome.success);
+/// ```
+pub async fn execute_tcp_job(tcp_job: &TcpJob, uuid: &str, local_time: &str) -> RunOutcome {
+    let timeout = Duration::from_millis(tcp_job.timeout);
+    let address = (tcp_job.host.as_str(), tcp_job.port);
+    let mut attempts = 0;
+    let mut last_error = String::from("No attempt was made.");
+
+    while attempts < tcp_job.max_retry {
+        attempts += 1;
+
+        let started = Instant::now();
+        let result = match tokio::time::timeout(timeout, TcpStream::connect(address)).await {
+            Ok(result) => result.map_err(|e| e.to_string()),
+            Err(_) => Err(format!("Timed out after {}ms", tcp_job.timeout))
+        };
+
+        match result {
+            Ok(_) => {
+                println!("{} {} TCP connect success, job name: {}, address: {}:{}, latency: {}ms",
+                         uuid, local_time, &tcp_job.name, &tcp_job.host, tcp_job.port, started.elapsed().as_millis());
+                return RunOutcome::new(uuid.to_string(), tcp_job.name.clone(), true, None, attempts, None);
+            }
+            Err(err) => {
+                println!("{} {} TCP connect failed, job name: {}, address: {}:{}, error: {}. Retry attempt: {}/{}",
+                         uuid, local_time, &tcp_job.name, &tcp_job.host, tcp_job.port, err, attempts, tcp_job.max_retry);
+                last_error = err;
+            }
+        }
+    }
+
+    RunOutcome::new(uuid.to_string(), tcp_job.name.clone(), false, None, attempts, Some(last_error))
+}
\ No newline at end of file
@@ -0,0 +1,303 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use tokio::sync::Notify;
+
+use crate::configure::get_jobs;
+use crate::models::job_schedule::JobSchedule;
+use crate::scheduler::condition::is_condition_met;
+use crate::scheduler::leader_election::is_leader;
+use crate::scheduler::run_queue::wait_for_space;
+use crate::scheduler::sharding::is_assigned;
+
+/// The delay of a fire time after which the firing is logged as late.
+const DRIFT_WARNING: Duration = Duration::from_secs(1);
+
+/// The triggers of all scheduled jobs and their next fire times.
+static TIMER: Lazy<Mutex<TimerState>> = Lazy::new(|| Mutex::new(TimerState {
+    triggers: HashMap::new(),
+    queue: BinaryHeap::new(),
+}));
+
+/// Wakes the timer loop when a trigger is added, since it may fire before the next one.
+static WAKE: Lazy<Notify> = Lazy::new(Notify::new);
+
+/// Whether the timer loop was started.
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the timer loop was stopped, see `stop_timer`.
+static STOPPED: AtomicBool = AtomicBool::new(false);
+
+/// The identifier of the next trigger.
+static NEXT_TRIGGER_ID: AtomicU64 = AtomicU64::new(1);
+
+/// The triggers and a queue of their next fire times, earliest first.
+///
+/// A removed trigger keeps its entry in the queue until it is due, and is skipped then.
+struct TimerState {
+    triggers: HashMap<u64, Trigger>,
+    queue: BinaryHeap<Reverse<(DateTime<Utc>, u64)>>,
+}
+
+/// The schedule of a job and the action run at its fire times, given the fire time.
+///
+/// A `@reboot` trigger is fired once when it is added, then removed.
+struct Trigger {
+    job_name: String,
+    schedule: JobSchedule,
+    /// Whether the trigger belongs to the scheduler itself rather than to a configured job.
+    internal: bool,
+    fire: Arc<dyn Fn(DateTime<Utc>) + Send + Sync>,
+}
+
+/// Adds a trigger running an action at the fire times of the schedule of a job.
+///
+/// All triggers share one timer, a single task sleeping until the earliest fire time of all
+/// jobs, instead of one task per job. The schedule is evaluated in the configured timezone.
+/// A `@reboot` trigger fires once right away, i.e. when the scheduler starts, and so does the
+/// trigger of a job with `run_on_start`, before its first fire time. With an initial delay,
+/// the trigger doesn't fire before the delay has passed.
+/// The action should only submit the run of the job, so that it doesn't delay other triggers.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job, used in the logs of the timer.
+/// * `schedule` - The schedule of the job.
+/// * `fire` - The action run at each fire time, given the fire time it was scheduled at.
+///
+/// # Returns
+///
+/// The identifier of the trigger, see `remove_trigger`.
+///
+/// # Examples
+///
+/// ```
+/// let id = add_trigger(job.name(), job.schedule(), move |scheduled_at| {
+///     submit_run(job.name(), Some(scheduled_at), start_job(job.clone()));
+/// });
+/// ```
+pub fn add_trigger<F>(job_name: &str, schedule: &JobSchedule, fire: F) -> u64
+where
+    F: Fn(DateTime<Utc>) + Send + Sync + 'static,
+{
+    insert_trigger(job_name, schedule, false, Arc::new(fire))
+}
+
+/// Adds a trigger of the scheduler itself, such as the heartbeat job of the watchdog.
+///
+/// Unlike the trigger of a configured job, an internal trigger fires on every instance,
+/// whatever the leader election and the sharding, and also during blackouts. It is not listed
+/// by `get_next_fire_times`.
+///
+/// # Arguments
+///
+/// * `name` - The name of the internal job, used in the logs of the timer.
+/// * `schedule` - The schedule of the internal job.
+/// * `fire` - The action run at each fire time, given the fire time it was scheduled at.
+///
+/// # Returns
+///
+/// The identifier of the trigger, see `remove_trigger`.
+///
+/// # Examples
+///
+/// ```
+/// add_internal_trigger("rjob-watchdog", &JobSchedule::cron("0 * * * * *")?, |_| record_tick());
+/// ```
+pub fn add_internal_trigger<F>(name: &str, schedule: &JobSchedule, fire: F) -> u64
+where
+    F: Fn(DateTime<Utc>) + Send + Sync + 'static,
+{
+    insert_trigger(name, schedule, true, Arc::new(fire))
+}
+
+/// Adds a trigger to the timer and queues its first fire time.
+fn insert_trigger(job_name: &str, schedule: &JobSchedule, internal: bool, fire: Arc<dyn Fn(DateTime<Utc>) + Send + Sync>) -> u64 {
+    // The initial delay is checked when the configuration is loaded, a trigger starting past
+    // the supported dates never fires.
+    let start = chrono::Duration::from_std(schedule.initial_delay).ok()
+        .and_then(|delay| Utc::now().checked_add_signed(delay));
+    let next = match start {
+        Some(start) if schedule.is_reboot() || schedule.run_on_start => Some(start),
+        Some(start) => schedule.after(&start.with_timezone(&get_jobs().timezone)).next().map(|next| next.with_timezone(&Utc)),
+        None => None
+    };
+    let id = NEXT_TRIGGER_ID.fetch_add(1, Ordering::Relaxed);
+
+    let mut timer = TIMER.lock().unwrap();
+    if let Some(next) = next {
+        timer.queue.push(Reverse((next, id)));
+    }
+    timer.triggers.insert(id, Trigger { job_name: job_name.to_string(), schedule: schedule.clone(), internal, fire });
+    drop(timer);
+
+    WAKE.notify_one();
+    id
+}
+
+/// Removes a trigger, its action is not run anymore.
+///
+/// # Arguments
+///
+/// * `id` - The identifier returned by `add_trigger`.
+pub fn remove_trigger(id: u64) {
+    TIMER.lock().unwrap().triggers.remove(&id);
+}
+
+/// Starts the timer loop in the background, once.
+///
+/// The loop sleeps until the earliest fire time, runs the actions of the due triggers and
+/// queues their next fire times. While the run queue is full with the `block` policy, the loop
+/// waits for space before firing. A firing later than `DRIFT_WARNING`, e.g. because the runtime
+/// was busy, the host was suspended or the run queue blocked, is logged with its delay.
+///
+/// # Examples
+///
+/// ```
+/// start_timer();
+/// add_trigger(&name, &JobSchedule::cron("*/5 * * * * ?")?, |scheduled_at| println!("tick: {}", scheduled_at));
+/// ```
+pub fn start_timer() {
+    if STARTED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+
+    tokio::spawn(async {
+        while !STOPPED.load(Ordering::Relaxed) {
+            let next = TIMER.lock().unwrap().queue.peek().map(|Reverse((at, _))| *at);
+            let now = Utc::now();
+
+            match next {
+                Some(at) if at <= now => {
+                    wait_for_space().await;
+                    if !STOPPED.load(Ordering::Relaxed) {
+                        fire_due_triggers(Utc::now());
+                    }
+                }
+                Some(at) => {
+                    let sleep = (at - now).to_std().unwrap_or_default();
+                    tokio::select! {
+                        _ = tokio::time::sleep(sleep) => {}
+                        _ = WAKE.notified() => {}
+                    }
+                }
+                None => WAKE.notified().await
+            }
+        }
+    });
+}
+
+/// Returns the next fire time of each job with a trigger, the earliest if it has several.
+///
+/// The internal triggers of the scheduler are left out.
+///
+/// # Examples
+///
+/// ```
+/// for (job_name, next) in get_next_fire_times() {
+///     println!("{}: {}", job_name, next);
+/// }
+/// ```
+pub fn get_next_fire_times() -> HashMap<String, DateTime<Utc>> {
+    let timer = TIMER.lock().unwrap();
+    let mut next_fire_times: HashMap<String, DateTime<Utc>> = HashMap::new();
+
+    for Reverse((at, id)) in timer.queue.iter() {
+        let Some(trigger) = timer.triggers.get(id).filter(|trigger| !trigger.internal) else {
+            continue;
+        };
+        next_fire_times.entry(trigger.job_name.clone())
+            .and_modify(|next| *next = (*next).min(*at))
+            .or_insert(*at);
+    }
+    next_fire_times
+}
+
+/// Stops the timer loop, no trigger fires anymore.
+///
+/// # Examples
+///
+/// ```
+/// stop_timer();
+/// wait_for_runs(Duration::from_secs(30)).await;
+/// ```
+pub fn stop_timer() {
+    STOPPED.store(true, Ordering::Relaxed);
+    WAKE.notify_one();
+}
+
+/// Runs the actions of the triggers due at `now` and queues their next fire times.
+///
+/// Fire times missed while the timer was late are skipped, see `catch_up` for running them.
+/// A fire time during a global blackout or a blackout of the job is skipped and logged. On a
+/// standby instance of the leader election, or for a job assigned to another instance of the
+/// cluster, the fire times pass without firing. A fire time for which the `when` condition of
+/// the job is false, or cannot be evaluated, is skipped and logged too, see `is_condition_met`.
+/// Internal triggers always fire.
+fn fire_due_triggers(now: DateTime<Utc>) {
+    let jobs = get_jobs();
+    let leader = is_leader();
+    let timezone = jobs.timezone;
+    let mut due = Vec::new();
+
+    {
+        let mut timer = TIMER.lock().unwrap();
+        while let Some(Reverse((at, id))) = timer.queue.peek().copied() {
+            if at > now {
+                break;
+            }
+            timer.queue.pop();
+
+            let Some(trigger) = timer.triggers.get(&id) else {
+                continue;
+            };
+            let drift = (now - at).to_std().unwrap_or_default();
+            let local_time = at.with_timezone(&timezone).naive_local();
+            let blackout = jobs.blackouts.iter()
+                .chain(&trigger.schedule.blackouts)
+                .find(|blackout| !trigger.internal && blackout.contains(local_time))
+                .map(|blackout| blackout.to_string());
+            let when = trigger.schedule.when.clone().filter(|_| !trigger.internal);
+            due.push((trigger.job_name.clone(), trigger.internal, trigger.fire.clone(), at, drift, blackout, when));
+
+            let reboot = trigger.schedule.is_reboot();
+            let next = trigger.schedule.after(&now.with_timezone(&timezone)).next();
+            match next {
+                Some(next) => timer.queue.push(Reverse((next.with_timezone(&Utc), id))),
+                None if reboot => {
+                    timer.triggers.remove(&id);
+                }
+                None => {}
+            }
+        }
+    }
+
+    let due = due.into_iter().filter(|(job_name, internal, ..)| *internal || (leader && is_assigned(job_name)));
+    for (job_name, _, fire, at, drift, blackout, when) in due {
+        if let Some(blackout) = blackout {
+            println!("Run skipped, job name: {}, scheduled at: {}, blackout: {}", job_name, at.with_timezone(&timezone), blackout);
+            continue;
+        }
+        if let Some(when) = when {
+            match is_condition_met(&when, &job_name, at.with_timezone(&timezone)) {
+                Ok(true) => {}
+                Ok(false) => {
+                    println!("Run skipped, job name: {}, scheduled at: {}, when: {}", job_name, at.with_timezone(&timezone), when);
+                    continue;
+                }
+                Err(e) => {
+                    println!("Run skipped, job name: {}, scheduled at: {}, when: {}, error: {}", job_name, at.with_timezone(&timezone), when, e);
+                    continue;
+                }
+            }
+        }
+        if drift > DRIFT_WARNING {
+            println!("Job fired late, job name: {}, drift: {}ms", job_name, drift.as_millis());
+        }
+        fire(at);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,128 @@
+use std::process;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use once_cell::sync::Lazy;
+
+use crate::configure::get_jobs;
+use crate::models::job_schedule::JobSchedule;
+use crate::models::notifications::JobNotifications;
+use crate::models::run_outcome::RunOutcome;
+use crate::models::watchdog::{Watchdog, WatchdogAction};
+use crate::notifier::{send_notifications, NotificationEvent};
+use crate::scheduler::timer::add_internal_trigger;
+use crate::utils::datetime_util::get_local_datetime_in_timezone;
+use crate::utils::duration_util::format_duration;
+use crate::utils::uuid_util::generate_uuid_without_hyphens;
+
+/// The name of the internal heartbeat job of the watchdog.
+const WATCHDOG_JOB_NAME: &str = "rjob-watchdog";
+
+/// The schedule of the internal heartbeat job, every minute.
+const WATCHDOG_CRON: &str = "0 * * * * *";
+
+/// The interval at which the watchdog checks the last tick.
+const WATCHDOG_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The time the heartbeat job last fired, or the time the watchdog started.
+static LAST_TICK: Lazy<Mutex<Instant>> = Lazy::new(|| Mutex::new(Instant::now()));
+
+/// Starts the watchdog of the scheduler, if a `watchdog` section is configured.
+///
+/// An internal heartbeat job fires every minute on the timer shared by all jobs, on every
+/// instance and also during blackouts, and records a tick. A thread of its own, outside of the
+/// runtime of the jobs, checks the ticks every 10 seconds. When no tick arrived for `timeout`,
+/// e.g. because the timer loop died, the runtime is blocked or the run queue holds back the
+/// timer, the watchdog runs its actions in order:
+///
+/// - `log`: Logs that the scheduler stalled.
+/// - `notify`: Sends an alert to the global notification channels, with the `stale` event and
+///   the name of the heartbeat job, `rjob-watchdog`. The alert is sent from the watchdog
+///   thread, so it also goes out while the runtime of the jobs is blocked.
+/// - `exit`: Exits the process with status 1, so a supervisor such as systemd, Kubernetes or
+///   Docker restarts it.
+///
+/// A stall is acted upon once, and the end of the stall is logged when the ticks resume.
+///
+/// # Examples
+///
+/// ```
+/// start_timer();
+/// start_watchdog();
+/// ```
+pub fn start_watchdog() {
+    let Some(watchdog) = get_jobs().watchdog.clone() else {
+        return;
+    };
+
+    let schedule = match JobSchedule::cron(WATCHDOG_CRON) {
+        Ok(schedule) => schedule,
+        Err(err) => {
+            println!("Failed to start the watchdog, error: {}", err);
+            return;
+        }
+    };
+    *LAST_TICK.lock().unwrap() = Instant::now();
+    add_internal_trigger(WATCHDOG_JOB_NAME, &schedule, |_| {
+        *LAST_TICK.lock().unwrap() = Instant::now();
+    });
+
+    let actions = watchdog.actions.iter().map(|action| action.to_string()).collect::<Vec<_>>();
+    println!("Watchdog started, timeout: {}, action: {}", format_duration(watchdog.timeout), actions.join(", "));
+
+    let spawned = thread::Builder::new()
+        .name(WATCHDOG_JOB_NAME.to_string())
+        .spawn(move || watch(&watchdog));
+    if let Err(err) = spawned {
+        println!("Failed to start the watchdog, error: {}", err);
+    }
+}
+
+/// Checks the ticks of the heartbeat job until the process exits.
+fn watch(watchdog: &Watchdog) {
+    let mut stalled = false;
+
+    loop {
+        thread::sleep(WATCHDOG_CHECK_INTERVAL);
+        let since_last_tick = LAST_TICK.lock().unwrap().elapsed();
+        let local_time = get_local_datetime_in_timezone(&get_jobs().timezone);
+
+        if since_last_tick <= watchdog.timeout {
+            if stalled {
+                println!("{} Scheduler ticks resumed", local_time);
+                stalled = false;
+            }
+            continue;
+        }
+        if stalled {
+            continue;
+        }
+        stalled = true;
+
+        let error = format!("No scheduler tick for {}, the scheduler may be stalled", format_duration(since_last_tick));
+        for action in &watchdog.actions {
+            match action {
+                WatchdogAction::Log => println!("{} Scheduler stalled, last tick: {} ago", local_time, format_duration(since_last_tick)),
+                WatchdogAction::Notify => notify_stall(&error),
+                WatchdogAction::Exit => {
+                    println!("{} Scheduler stalled, exiting, last tick: {} ago", local_time, format_duration(since_last_tick));
+                    process::exit(1);
+                }
+            }
+        }
+    }
+}
+
+/// Sends an alert about the stalled scheduler from the watchdog thread.
+fn notify_stall(error: &str) {
+    let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+        Ok(runtime) => runtime,
+        Err(err) => {
+            println!("Failed to send the watchdog notification, error: {}", err);
+            return;
+        }
+    };
+
+    let outcome = RunOutcome::new(generate_uuid_without_hyphens(), WATCHDOG_JOB_NAME.to_string(), false, None, 0, Some(error.to_string()));
+    runtime.block_on(send_notifications(&JobNotifications::default(), NotificationEvent::Stale, &outcome));
+}
\ No newline at end of file
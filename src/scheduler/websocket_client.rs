@@ -0,0 +1,195 @@
+use std::error::Error;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use reqwest::{Client, StatusCode, Upgraded};
+use reqwest::header::{CONNECTION, HeaderMap, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, SEC_WEBSOCKET_VERSION, UPGRADE};
+use ring::digest::{digest, SHA1_FOR_LEGACY_USE_ONLY};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use uuid::Uuid;
+
+/// The GUID appended to the handshake key to compute `Sec-WebSocket-Accept` (RFC 6455).
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// The largest message accepted from the server.
+const MAX_MESSAGE_BYTES: u64 = 16 * 1024 * 1024;
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+/// A minimal client side of a WebSocket connection.
+///
+/// Only what the scheduled checks need is supported: sending text messages and reading
+/// (possibly fragmented) messages, answering pings on the way. Extensions are not negotiated.
+pub struct WebSocket {
+    stream: Upgraded,
+}
+
+impl WebSocket {
+    /// Opens a WebSocket connection with the opening handshake.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The HTTP client, which must use HTTP/1.1.
+    /// * `url` - The `ws://` or `wss://` URL to connect to.
+    /// * `headers` - Additional headers of the handshake request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is not a WebSocket URL, the request fails, or the server does
+    /// not accept the handshake.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let mut ws = WebSocket::connect(&client, "wss://echo.websocket.org", None).await?;
+    /// ws.send_text("ping").await?;
+    /// println!("Received: {}", ws.read_message().await?);
+    /// ```
+    pub async fn connect(client: &Client, url: &str, headers: Option<&HeaderMap>) -> Result<Self, Box<dyn Error>> {
+        let http_url = if let Some(rest) = url.strip_prefix("ws://") {
+            format!("http://{}", rest)
+        } else if let Some(rest) = url.strip_prefix("wss://") {
+            format!("https://{}", rest)
+        } else {
+            return Err(format!("Invalid WebSocket URL '{}'.", url).into());
+        };
+
+        let key = STANDARD.encode(Uuid::new_v4().as_bytes());
+        let resp = client.get(http_url)
+            .headers(headers.cloned().unwrap_or_default())
+            .header(CONNECTION, "Upgrade")
+            .header(UPGRADE, "websocket")
+            .header(SEC_WEBSOCKET_VERSION, "13")
+            .header(SEC_WEBSOCKET_KEY, &key)
+            .send()
+            .await?;
+
+        if resp.status() != StatusCode::SWITCHING_PROTOCOLS {
+            return Err(format!("WebSocket handshake failed with http status {}", resp.status().as_u16()).into());
+        }
+
+        let accept = resp.headers().get(SEC_WEBSOCKET_ACCEPT).and_then(|a| a.to_str().ok());
+        if accept != Some(get_accept_key(&key).as_str()) {
+            return Err("WebSocket handshake failed: invalid Sec-WebSocket-Accept header".into());
+        }
+
+        Ok(WebSocket {
+            stream: resp.upgrade().await?,
+        })
+    }
+
+    /// Sends a text message.
+    pub async fn send_text(&mut self, text: &str) -> Result<(), Box<dyn Error>> {
+        self.write_frame(OPCODE_TEXT, text.as_bytes()).await
+    }
+
+    /// Reads the next text or binary message, answering pings until it arrives.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection fails or is closed by the server, or if the message
+    /// exceeds 16 MiB.
+    pub async fn read_message(&mut self) -> Result<String, Box<dyn Error>> {
+        let mut message: Option<Vec<u8>> = None;
+
+        loop {
+            let (fin, opcode, payload) = self.read_frame().await?;
+            match opcode {
+                OPCODE_TEXT | OPCODE_BINARY => message = Some(payload),
+                OPCODE_CONTINUATION => match &mut message {
+                    Some(message) => message.extend(payload),
+                    None => return Err("Unexpected WebSocket continuation frame".into())
+                },
+                OPCODE_PING => {
+                    self.write_frame(OPCODE_PONG, &payload).await?;
+                    continue;
+                }
+                OPCODE_PONG => continue,
+                OPCODE_CLOSE => return Err("The WebSocket connection was closed by the server".into()),
+                _ => return Err(format!("Unsupported WebSocket opcode {}", opcode).into())
+            }
+
+            if let Some(data) = &message {
+                if data.len() as u64 > MAX_MESSAGE_BYTES {
+                    return Err("The WebSocket message is too large".into());
+                }
+                if fin {
+                    return Ok(String::from_utf8_lossy(data).to_string());
+                }
+            }
+        }
+    }
+
+    /// Closes the connection with a normal closure frame.
+    pub async fn close(mut self) {
+        let _ = self.write_frame(OPCODE_CLOSE, &1000u16.to_be_bytes()).await;
+        let _ = self.stream.shutdown().await;
+    }
+
+    /// Writes a single, masked frame.
+    async fn write_frame(&mut self, opcode: u8, payload: &[u8]) -> Result<(), Box<dyn Error>> {
+        let mut frame = vec![0x80 | opcode];
+
+        let len = payload.len();
+        if len < 126 {
+            frame.push(0x80 | len as u8);
+        } else if len <= u16::MAX as usize {
+            frame.push(0x80 | 126);
+            frame.extend((len as u16).to_be_bytes());
+        } else {
+            frame.push(0x80 | 127);
+            frame.extend((len as u64).to_be_bytes());
+        }
+
+        let mask_key = Uuid::new_v4();
+        let mask = &mask_key.as_bytes()[..4];
+        frame.extend(mask);
+        frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+
+        self.stream.write_all(&frame).await?;
+        self.stream.flush().await?;
+        Ok(())
+    }
+
+    /// Reads a single frame and returns whether it is final, its opcode and its payload.
+    async fn read_frame(&mut self) -> Result<(bool, u8, Vec<u8>), Box<dyn Error>> {
+        let mut head = [0u8; 2];
+        self.stream.read_exact(&mut head).await?;
+
+        let fin = head[0] & 0x80 != 0;
+        let opcode = head[0] & 0x0F;
+        let masked = head[1] & 0x80 != 0;
+
+        let len = match head[1] & 0x7F {
+            126 => self.stream.read_u16().await? as u64,
+            127 => self.stream.read_u64().await?,
+            len => len as u64
+        };
+        if len > MAX_MESSAGE_BYTES {
+            return Err("The WebSocket message is too large".into());
+        }
+
+        let mut mask = [0u8; 4];
+        if masked {
+            self.stream.read_exact(&mut mask).await?;
+        }
+
+        let mut payload = vec![0u8; len as usize];
+        self.stream.read_exact(&mut payload).await?;
+        if masked {
+            payload.iter_mut().enumerate().for_each(|(i, b)| *b ^= mask[i % 4]);
+        }
+
+        Ok((fin, opcode, payload))
+    }
+}
+
+/// Computes the expected `Sec-WebSocket-Accept` value for a handshake key.
+fn get_accept_key(key: &str) -> String {
+    let hash = digest(&SHA1_FOR_LEGACY_USE_ONLY, format!("{}{}", key, WEBSOCKET_GUID).as_bytes());
+    STANDARD.encode(hash.as_ref())
+}
\ No newline at end of file
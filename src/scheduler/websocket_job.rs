@@ -0,0 +1,128 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::configure::get_jobs;
+use crate::models::http_job_request::HttpVersion;
+use crate::models::job::{Job, JobFuture};
+use crate::models::job_options::JobOptions;
+use crate::models::job_schedule::JobSchedule;
+use crate::models::run_outcome::RunOutcome;
+use crate::models::websocket_job::WebSocketJob;
+use crate::scheduler::http_client::{ClientOptions, get_client};
+use crate::scheduler::websocket_client::WebSocket;
+
+impl Job for WebSocketJob {
+    fn kind(&self) -> &'static str {
+        "WebSocket"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn enable(&self) -> bool {
+        self.enable
+    }
+
+    fn schedule(&self) -> &JobSchedule {
+        &self.schedule
+    }
+
+    fn options(&self) -> &JobOptions {
+        &self.options
+    }
+
+    fn execute(self: Arc<Self>, uuid: String, local_time: String) -> JobFuture<RunOutcome> {
+        Box::pin(async move { execute_websocket_job(&self, &uuid, &local_time).await })
+    }
+}
+
+/// Checks the WebSocket endpoint of a job, retrying on failure, and returns the outcome.
+///
+/// # Arguments
+///
+/// * `websocket_job` - The WebSocket job to run.
+/// * `uuid` - The identifier of the current run.
+/// * `local_time` - The start time of the run, used as log prefix.
+///
+/// # Examples
+///
+/// ```
+/// let outcome = execute_websocket_job(websocket_job, &uuid, &local_time).await;
+/// println!("Success: {}", outcome.success);
+/// ```
+pub async fn execute_websocket_job(websocket_job: &WebSocketJob, uuid: &str, local_time: &str) -> RunOutcome {
+    let timeout = Duration::from_millis(websocket_job.timeout);
+    let mut attempts = 0;
+    let mut last_error = String::from("No attempt was made.");
+
+    while attempts < websocket_job.max_retry {
+        attempts += 1;
+
+        let result = match tokio::time::timeout(timeout, check_websocket(websocket_job)).await {
+            Ok(result) => result,
+            Err(_) => Err(format!("Timed out after {}ms", websocket_job.timeout))
+        };
+
+        match result {
+            Ok(Some(response)) => {
+                println!("{} {} WebSocket check success, job name: {}", uuid, local_time, &websocket_job.name);
+                println!("{} {} WebSocket response: {}", uuid, local_time, response);
+                return RunOutcome::new(uuid.to_string(), websocket_job.name.clone(), true, Some(101), attempts, None);
+            }
+            Ok(None) => {
+                println!("{} {} WebSocket handshake success, job name: {}", uuid, local_time, &websocket_job.name);
+                return RunOutcome::new(uuid.to_string(), websocket_job.name.clone(), true, Some(101), attempts, None);
+            }
+            Err(err) => {
+                println!("{} {} WebSocket check failed, job name: {}, error: {}. Retry attempt: {}/{}",
+                         uuid, local_time, &websocket_job.name, err, attempts, websocket_job.max_retry);
+                last_error = err;
+            }
+        }
+    }
+
+    RunOutcome::new(uuid.to_string(), websocket_job.name.clone(), false, None, attempts, Some(last_error))
+}
+
+/// Connects to the endpoint of a job and exchanges the configured message.
+///
+/// # Returns
+///
+/// The response to the message, or `None` if no message is configured.
+///
+/// # Errors
+///
+/// Returns an error if the handshake or the exchange fails, or if the response does not
+/// contain the expected text.
+async fn check_websocket(websocket_job: &WebSocketJob) -> Result<Option<String>, String> {
+    let options = ClientOptions::new(websocket_job.timeout, None, HttpVersion::Http1, get_jobs().user_agent.clone());
+    let client = get_client(&options).map_err(|e| e.to_string())?;
+
+    let mut ws = WebSocket::connect(&client, &websocket_job.url, websocket_job.headers.as_ref())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let message = match &websocket_job.message {
+        Some(message) => message,
+        None => {
+            ws.close().await;
+            return Ok(None);
+        }
+    };
+
+    let result = match ws.send_text(message).await.map_err(|e| e.to_string()) {
+        Ok(_) => ws.read_message().await.map_err(|e| e.to_string()),
+        Err(err) => Err(err)
+    };
+    ws.close().await;
+
+    let response = result?;
+    if let Some(expect) = &websocket_job.expect {
+        if !response.contains(expect.as_str()) {
+            return Err(format!("The response does not contain '{}': {}", expect, response));
+        }
+    }
+
+    Ok(Some(response))
+}
\ No newline at end of file
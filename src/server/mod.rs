@@ -0,0 +1,323 @@
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use hyper::header::{AUTHORIZATION, CONTENT_TYPE, WWW_AUTHENTICATE};
+use hyper::service::{make_service_fn, service_fn};
+use percent_encoding::percent_decode_str;
+use serde_json::{json, Value};
+
+use crate::configure::{get_jobs, parse_http_job, update_jobs_file};
+use crate::models::job_stats::JobStats;
+use crate::scheduler::cron_scheduler::schedule_job;
+use crate::scheduler::drift::{DRIFT_BUCKETS, get_drift_stats};
+use crate::scheduler::health::get_health;
+use crate::scheduler::leader_election::is_leader;
+use crate::scheduler::registry::{deregister_job, get_registered_job};
+use crate::scheduler::run_queue::get_run_queue_stats;
+use crate::scheduler::sharding::get_member_count;
+use crate::scheduler::state::{read_state, set_paused, RECENT_RUNS};
+
+/// Starts the admin HTTP server in the background.
+///
+/// The server exposes the following endpoints:
+///
+/// - `GET /healthz`: Reports whether the scheduler is alive, how many jobs are registered and
+///   whether the instance is the leader.
+///   Responds with `200 OK` when alive and `503 Service Unavailable` otherwise.
+/// - `GET /metrics`: Reports the runs in progress, the depth of the run queue, the drift of
+///   the scheduled runs and the run statistics of each job, in the Prometheus text format.
+///   The statistics of the jobs are read from the state file, so they survive a restart.
+/// - `GET /jobs/stats`: Reports the rolling statistics of each job that ran, computed over its
+///   last runs in the state file: the success rate, the average, p50, p95 and maximum duration,
+///   and the number of failed runs since the last successful run.
+/// - `POST /jobs`: Adds a job to the running scheduler. The body is a job with the same schema
+///   as an element of the 'http_jobs' array of the 'jobs' file.
+/// - `DELETE /jobs/{name}`: Removes a job from the running scheduler.
+/// - `POST /jobs/{name}/pause`: Pauses a job, its triggers are skipped until it is resumed.
+/// - `POST /jobs/{name}/resume`: Resumes a paused job.
+///
+/// With the `persist=true` query parameter, `POST /jobs` and `DELETE /jobs/{name}` also update
+/// the 'jobs' file, so the change survives a restart.
+///
+/// The `POST` and `DELETE` endpoints change the scheduler, so they require the `token` of the
+/// `admin` section in an `Authorization: Bearer` header, and are disabled without a token.
+/// The job names in the paths are percent-decoded.
+///
+/// # Arguments
+///
+/// * `address` - The socket address to listen on.
+///
+/// # Examples
+///
+/// ```
+/// start_admin_server("127.0.0.1:9090".parse().unwrap());
+/// ```
+pub fn start_admin_server(address: SocketAddr) {
+    let make_service = make_service_fn(|_conn| async {
+        Ok::<_, Infallible>(service_fn(handle))
+    });
+
+    let server = match Server::try_bind(&address) {
+        Ok(builder) => builder.serve(make_service),
+        Err(err) => {
+            eprintln!("Failed to start admin server on {}: {}", address, err);
+            return;
+        }
+    };
+
+    println!("Admin server listening on http://{}", address);
+
+    tokio::spawn(async move {
+        if let Err(err) = server.await {
+            eprintln!("Admin server error: {}", err);
+        }
+    });
+}
+
+/// Routes a request of the admin server.
+async fn handle(req: Request<Body>) -> Result<Response<Body>, Infallible> {
+    let segments: Result<Vec<String>, _> = req.uri().path().trim_matches('/').split('/')
+        .map(|segment| percent_decode_str(segment).decode_utf8().map(|s| s.into_owned()))
+        .collect();
+    let segments = match segments {
+        Ok(segments) => segments,
+        Err(err) => return Ok(json_response(StatusCode::BAD_REQUEST, json!({ "error": format!("Invalid path: {}", err) }))),
+    };
+    let segments: Vec<&str> = segments.iter().map(|segment| segment.as_str()).collect();
+    let persist = req.uri().query()
+        .is_some_and(|query| query.split('&').any(|param| param == "persist=true"));
+
+    if req.method() != Method::GET {
+        if let Some(response) = reject_unauthorized(&req) {
+            return Ok(response);
+        }
+    }
+
+    let response = match (req.method().clone(), segments.as_slice()) {
+        (Method::GET, ["healthz"]) => healthz(),
+        (Method::GET, ["metrics"]) => metrics(),
+        (Method::GET, ["jobs", "stats"]) => job_stats(),
+        (Method::POST, ["jobs"]) => add_job(req, persist).await,
+        (Method::DELETE, ["jobs", name]) => remove_job(name, persist),
+        (Method::POST, ["jobs", name, "pause"]) => pause_job(name, true),
+        (Method::POST, ["jobs", name, "resume"]) => pause_job(name, false),
+        _ => json_response(StatusCode::NOT_FOUND, json!({ "error": "Not found" })),
+    };
+    Ok(response)
+}
+
+/// Checks the bearer token of a request changing the scheduler.
+///
+/// # Returns
+///
+/// A `403 Forbidden` response if no token is configured, a `401 Unauthorized` response if the
+/// request lacks the token or has another one, or `None` if the request is authorized.
+fn reject_unauthorized(req: &Request<Body>) -> Option<Response<Body>> {
+    let Some(token) = &get_jobs().admin_token else {
+        return Some(json_response(StatusCode::FORBIDDEN, json!({ "error": "Set the 'token' of the 'admin' section to enable this endpoint" })));
+    };
+
+    let authorized = req.headers().get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|bearer| constant_time_eq(bearer.trim().as_bytes(), token.as_bytes()));
+    if !authorized {
+        let mut response = json_response(StatusCode::UNAUTHORIZED, json!({ "error": "Invalid or missing bearer token" }));
+        response.headers_mut().insert(WWW_AUTHENTICATE, "Bearer".parse().unwrap());
+        return Some(response);
+    }
+
+    None
+}
+
+/// Compares two byte strings in a time independent of their content, so the token cannot be
+/// guessed from the response times.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Handles `GET /healthz`.
+fn healthz() -> Response<Body> {
+    let health = get_health();
+    let status = if health.alive { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+
+    json_response(status, json!({
+        "status": if health.alive { "ok" } else { "stalled" },
+        "registered_jobs": health.registered_jobs,
+        "last_tick_ms_ago": health.since_last_tick.map(|d| d.as_millis() as u64),
+        "leader": is_leader(),
+    }))
+}
+
+/// Handles `GET /metrics`.
+fn metrics() -> Response<Body> {
+    let stats = get_run_queue_stats();
+
+    let mut body = [
+        ("rjob_runs_in_progress", "gauge", "The number of job runs in progress.", stats.running as u64),
+        ("rjob_run_queue_depth", "gauge", "The number of triggered runs waiting in the run queue.", stats.depth as u64),
+        ("rjob_run_queue_max_depth", "gauge", "The highest number of runs waiting in the run queue so far.", stats.max_depth as u64),
+        ("rjob_runs_dropped_total", "counter", "The number of triggered runs dropped because the run queue was full.", stats.dropped),
+        ("rjob_leader", "gauge", "Whether this instance fires the triggers of the jobs, 0 on a standby instance.", is_leader() as u64),
+        ("rjob_cluster_members", "gauge", "The number of instances sharing the jobs, 0 without sharding.", get_member_count() as u64),
+    ]
+        .iter()
+        .map(|(name, kind, help, value)| format!("# HELP {} {}\n# TYPE {} {}\n{} {}\n", name, help, name, kind, name, value))
+        .collect::<String>();
+
+    let drift = get_drift_stats();
+    body.push_str("# HELP rjob_run_drift_seconds The time between the scheduled fire time and the start of a run.\n");
+    body.push_str("# TYPE rjob_run_drift_seconds histogram\n");
+    for (bound, count) in DRIFT_BUCKETS.iter().zip(drift.buckets) {
+        body.push_str(&format!("rjob_run_drift_seconds_bucket{{le=\"{}\"}} {}\n", bound, count));
+    }
+    body.push_str(&format!("rjob_run_drift_seconds_bucket{{le=\"+Inf\"}} {}\n", drift.count));
+    body.push_str(&format!("rjob_run_drift_seconds_sum {}\n", drift.sum.as_secs_f64()));
+    body.push_str(&format!("rjob_run_drift_seconds_count {}\n", drift.count));
+    body.push_str("# HELP rjob_run_drift_max_seconds The highest drift of a run so far.\n");
+    body.push_str("# TYPE rjob_run_drift_max_seconds gauge\n");
+    body.push_str(&format!("rjob_run_drift_max_seconds {}\n", drift.max.as_secs_f64()));
+
+    match read_state(&get_jobs().state_file) {
+        Ok(state) => {
+            let jobs = state.jobs.iter().filter(|(_, job)| job.last_run.is_some()).collect::<Vec<_>>();
+            body.push_str("# HELP rjob_job_runs_total The number of finished runs of each job, kept across restarts.\n");
+            body.push_str("# TYPE rjob_job_runs_total counter\n");
+            for (name, job) in &jobs {
+                body.push_str(&format!("rjob_job_runs_total{{job=\"{}\",result=\"success\"}} {}\n", escape_label(name), job.successes));
+                body.push_str(&format!("rjob_job_runs_total{{job=\"{}\",result=\"failure\"}} {}\n", escape_label(name), job.failures));
+            }
+            body.push_str("# HELP rjob_job_consecutive_failures The number of failed runs of each job since its last successful run.\n");
+            body.push_str("# TYPE rjob_job_consecutive_failures gauge\n");
+            for (name, job) in &jobs {
+                body.push_str(&format!("rjob_job_consecutive_failures{{job=\"{}\"}} {}\n", escape_label(name), job.consecutive_failures));
+            }
+            body.push_str("# HELP rjob_job_last_success_timestamp_seconds The fire time, or the start time if not scheduled, of the last successful run of each job.\n");
+            body.push_str("# TYPE rjob_job_last_success_timestamp_seconds gauge\n");
+            for (name, job) in &jobs {
+                if let Some(last_success) = job.last_success {
+                    body.push_str(&format!("rjob_job_last_success_timestamp_seconds{{job=\"{}\"}} {}\n", escape_label(name), last_success.timestamp()));
+                }
+            }
+        }
+        Err(err) => println!("Failed to read state file for the metrics, error: {}", err)
+    }
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, "text/plain; version=0.0.4")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+/// Handles `GET /jobs/stats`.
+fn job_stats() -> Response<Body> {
+    let state = match read_state(&get_jobs().state_file) {
+        Ok(state) => state,
+        Err(err) => return json_response(StatusCode::INTERNAL_SERVER_ERROR, json!({ "error": err.to_string() })),
+    };
+
+    let jobs: BTreeMap<&String, Value> = state.jobs.iter()
+        .filter_map(|(name, job)| JobStats::from_state(job).map(|stats| (name, stats)))
+        .map(|(name, stats)| (name, json!({
+            "runs": stats.runs,
+            "success_rate": stats.success_rate,
+            "average_ms": stats.average.as_millis() as u64,
+            "p50_ms": stats.p50.as_millis() as u64,
+            "p95_ms": stats.p95.as_millis() as u64,
+            "max_ms": stats.max.as_millis() as u64,
+            "consecutive_failures": stats.consecutive_failures,
+        })))
+        .collect();
+
+    json_response(StatusCode::OK, json!({ "window": RECENT_RUNS, "jobs": jobs }))
+}
+
+/// Escapes the value of a label of the Prometheus text format.
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Handles `POST /jobs`.
+async fn add_job(req: Request<Body>, persist: bool) -> Response<Body> {
+    let bytes = match hyper::body::to_bytes(req.into_body()).await {
+        Ok(bytes) => bytes,
+        Err(err) => return json_response(StatusCode::BAD_REQUEST, json!({ "error": err.to_string() })),
+    };
+    let value: Value = match serde_json::from_slice(&bytes) {
+        Ok(value) => value,
+        Err(err) => return json_response(StatusCode::BAD_REQUEST, json!({ "error": format!("Invalid JSON: {}", err) })),
+    };
+    let http_job = match parse_http_job(&value) {
+        Ok(http_job) => http_job,
+        Err(err) => return json_response(StatusCode::BAD_REQUEST, json!({ "error": err.to_string() })),
+    };
+
+    if get_registered_job(&http_job.name).is_some() {
+        return json_response(StatusCode::CONFLICT, json!({ "error": format!("Job '{}' already exists", http_job.name) }));
+    }
+
+    if persist {
+        let result = update_jobs_file(|jobs| {
+            jobs.retain(|job| job.get("name").and_then(|n| n.as_str()) != Some(&http_job.name));
+            jobs.push(value);
+        });
+        if let Err(err) = result {
+            return json_response(StatusCode::INTERNAL_SERVER_ERROR, json!({ "error": err.to_string() }));
+        }
+    }
+
+    let name = http_job.name.clone();
+    let enable = http_job.enable;
+    if enable {
+        schedule_job(Arc::new(http_job));
+    }
+
+    json_response(StatusCode::CREATED, json!({ "job": name, "registered": enable, "persisted": persist }))
+}
+
+/// Handles `DELETE /jobs/{name}`.
+fn remove_job(name: &str, persist: bool) -> Response<Body> {
+    if !deregister_job(name) {
+        return json_response(StatusCode::NOT_FOUND, json!({ "error": format!("No job named '{}'", name) }));
+    }
+    println!("Job deregistered, job name: {}", name);
+
+    if persist {
+        let result = update_jobs_file(|jobs| {
+            jobs.retain(|job| job.get("name").and_then(|n| n.as_str()) != Some(name));
+        });
+        if let Err(err) = result {
+            return json_response(StatusCode::INTERNAL_SERVER_ERROR, json!({ "error": err.to_string() }));
+        }
+    }
+
+    json_response(StatusCode::OK, json!({ "job": name, "persisted": persist }))
+}
+
+/// Handles `POST /jobs/{name}/pause` and `POST /jobs/{name}/resume`.
+fn pause_job(name: &str, paused: bool) -> Response<Body> {
+    let exists = get_registered_job(name).is_some() || get_jobs().all_jobs().iter().any(|job| job.name() == name);
+    if !exists {
+        return json_response(StatusCode::NOT_FOUND, json!({ "error": format!("No job named '{}'", name) }));
+    }
+
+    match set_paused(name, paused) {
+        Ok(_) => {
+            println!("Job {}, job name: {}", if paused { "paused" } else { "resumed" }, name);
+            json_response(StatusCode::OK, json!({ "job": name, "paused": paused }))
+        }
+        Err(err) => json_response(StatusCode::INTERNAL_SERVER_ERROR, json!({ "error": err.to_string() })),
+    }
+}
+
+/// Builds a JSON response with the given status.
+fn json_response(status: StatusCode, body: serde_json::Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
\ No newline at end of file
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::error::Error;
+use opentelemetry::{global, Context};
+use opentelemetry::global::BoxedTracer;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+
+use crate::models::telemetry::Telemetry;
+
+/// Installs the global OpenTelemetry tracer provider exporting spans to the OTLP endpoint.
+///
+/// Until this function is called, the tracer returned by `get_tracer` is a no-op and no
+/// `traceparent` header is propagated.
+///
+/// # Arguments
+///
+/// * `telemetry` - The OpenTelemetry export settings.
+///
+/// # Errors
+///
+/// Returns an error if the OTLP exporter cannot be built.
+///
+/// # Examples
+///
+/// ```
+/// let telemetry = Telemetry::new("http://localhost:4318/v1/traces".to_string(), "rjob".to_string());
+/// init_telemetry(&telemetry)?;
+/// ```
+pub fn init_telemetry(telemetry: &Telemetry) -> Result<(), Box<dyn Error>> {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(&telemetry.otlp_endpoint)
+        .build()?;
+
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(telemetry.service_name.clone()).build())
+        .build();
+
+    global::set_tracer_provider(provider);
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    Ok(())
+}
+
+/// Returns the tracer used for job runs.
+pub fn get_tracer() -> BoxedTracer {
+    global::tracer("rjob")
+}
+
+/// Returns the trace context headers (such as `traceparent`) of the given context.
+///
+/// The map is empty if telemetry is not configured.
+///
+/// # Examples
+///
+/// ```
+/// for (name, value) in get_trace_headers(&Context::current()) {
+///     request_builder = request_builder.header(name, value);
+/// }
+/// ```
+pub fn get_trace_headers(cx: &Context) -> HashMap<String, String> {
+    let mut headers = HashMap::new();
+    global::get_text_map_propagator(|propagator| propagator.inject_context(cx, &mut headers));
+    headers
+}
\ No newline at end of file
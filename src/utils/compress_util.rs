@@ -0,0 +1,25 @@
+use std::io::Write;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+
+/// Compresses the given data with gzip using the default compression level.
+///
+/// # Arguments
+///
+/// * `data` - The bytes to compress.
+///
+/// # Errors
+///
+/// Returns an error if the encoder fails to write or finish the gzip stream.
+///
+/// # Examples
+///
+/// ```
+/// let compressed = gzip(b"{\"name\": \"rjob\"}").unwrap();
+/// println!("Compressed size: {}", compressed.len());
+/// ```
+pub fn gzip(data: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
\ No newline at end of file
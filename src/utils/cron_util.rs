@@ -0,0 +1,544 @@
+use std::error::Error;
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use cron::Schedule;
+
+use crate::models::job_schedule::JobSchedule;
+
+/// The names and allowed values of the fields of a cron expression, in order.
+const CRON_FIELDS: [(&str, &str); 7] = [
+    ("seconds", "0-59"),
+    ("minutes", "0-59"),
+    ("hours", "0-23"),
+    ("day of month", "1-31"),
+    ("month", "1-12 or JAN-DEC"),
+    ("day of week", "1-7 or SUN-SAT"),
+    ("year", "1970-2100"),
+];
+
+/// The cron macro running a job once when the scheduler starts.
+pub const REBOOT: &str = "@reboot";
+
+/// The standard crontab macros and the expressions they stand for.
+const CRON_MACROS: [(&str, &str); 7] = [
+    ("@yearly", "0 0 0 1 1 *"),
+    ("@annually", "0 0 0 1 1 *"),
+    ("@monthly", "0 0 0 1 * *"),
+    ("@weekly", "0 0 0 * * 1"),
+    ("@daily", "0 0 0 * * *"),
+    ("@midnight", "0 0 0 * * *"),
+    ("@hourly", "0 0 * * * *"),
+];
+
+/// Returns whether a cron expression is the `@reboot` macro, which has no fire times but runs
+/// the job once when the scheduler starts.
+pub fn is_reboot(cron: &str) -> bool {
+    cron.trim().eq_ignore_ascii_case(REBOOT)
+}
+
+/// Normalizes a cron expression to the format of the scheduler.
+///
+/// The crontab macros `@yearly` (or `@annually`), `@monthly`, `@weekly`, `@daily` (or
+/// `@midnight`) and `@hourly` are replaced with the expressions they stand for. `@reboot` is
+/// kept, see `is_reboot`.
+///
+/// An expression with 6 fields (`sec min hour day month weekday`) or 7 fields with a trailing
+/// year is returned as is. An expression with 5 fields is a Unix crontab expression
+/// (`min hour day month weekday`): it fires at second 0, and its numeric weekdays, where both
+/// 0 and 7 are Sunday, are renumbered to the scheduler's 1 (Sunday) to 7 (Saturday). Weekday
+/// names such as `MON-FRI` mean the same in both formats.
+///
+/// # Arguments
+///
+/// * `cron` - The cron expression.
+///
+/// # Returns
+///
+/// The expression in the format of the scheduler.
+///
+/// # Errors
+///
+/// Returns an error if the weekday field of a crontab expression is invalid. Other fields are
+/// not validated, use `validate_cron` for that.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(normalize_cron("*/5 * * * *")?, "0 */5 * * * *");
+/// assert_eq!(normalize_cron("30 2 * * 1-5")?, "0 30 2 * * 2,3,4,5,6");
+/// assert_eq!(normalize_cron("0 30 2 * * ?")?, "0 30 2 * * ?");
+/// assert_eq!(normalize_cron("@daily")?, "0 0 0 * * *");
+/// ```
+pub fn normalize_cron(cron: &str) -> Result<String, Box<dyn Error>> {
+    if is_reboot(cron) {
+        return Ok(REBOOT.to_string());
+    }
+    if let Some(name) = cron.trim().strip_prefix('@') {
+        return CRON_MACROS.iter()
+            .find(|(m, _)| m[1..].eq_ignore_ascii_case(name))
+            .map(|(_, expression)| expression.to_string())
+            .ok_or_else(|| format!("Unknown macro '{}', expected one of @yearly, @annually, @monthly, @weekly, @daily, @midnight, @hourly or @reboot.", cron.trim()).into());
+    }
+
+    let fields: Vec<&str> = cron.split_whitespace().collect();
+    if fields.len() != 5 {
+        return Ok(cron.to_string());
+    }
+
+    let weekday = normalize_crontab_weekday(fields[4])
+        .ok_or(format!("The weekday field '{}' is invalid, allowed values are 0-7 or SUN-SAT.", fields[4]))?;
+
+    Ok(format!("0 {} {} {} {} {}", fields[0], fields[1], fields[2], fields[3], weekday))
+}
+
+/// Renumbers the weekday field of a crontab expression from 0-7 to 1-7, both with Sunday first.
+///
+/// Numeric values, ranges and steps are expanded to a list of days, names are kept.
+fn normalize_crontab_weekday(field: &str) -> Option<String> {
+    if field == "*" || field == "?" || field.chars().any(|c| c.is_ascii_alphabetic()) {
+        return Some(field.to_string());
+    }
+
+    let mut days = [false; 7];
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<usize>().ok().filter(|s| *s > 0)?),
+            None => (part, 1)
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (0, 7),
+            Some((start, end)) => (start.parse::<usize>().ok()?, end.parse::<usize>().ok()?),
+            None if step > 1 => (range.parse::<usize>().ok()?, 7),
+            None => {
+                let day = range.parse::<usize>().ok()?;
+                (day, day)
+            }
+        };
+        if start > end || end > 7 {
+            return None;
+        }
+        for day in (start..=end).step_by(step) {
+            days[day % 7] = true;
+        }
+    }
+
+    Some(days.iter()
+        .enumerate()
+        .filter(|(_, selected)| **selected)
+        .map(|(day, _)| (day + 1).to_string())
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+/// Validates a cron expression field by field.
+///
+/// The expression must have 6 fields (`sec min hour day month weekday`) or 7 fields with a
+/// trailing year. Normalize crontab expressions with 5 fields first, see `normalize_cron`.
+///
+/// # Arguments
+///
+/// * `cron` - The cron expression.
+///
+/// # Errors
+///
+/// Returns an error naming the invalid field and its value, or the wrong number of fields.
+///
+/// # Examples
+///
+/// ```
+/// assert!(validate_cron("0 0 12 * * ?").is_ok());
+///
+/// let err = validate_cron("0 61 12 * * ?").unwrap_err();
+/// println!("{}", err); // The minutes field '61' is invalid, allowed values are 0-59.
+/// ```
+pub fn validate_cron(cron: &str) -> Result<(), Box<dyn Error>> {
+    if is_reboot(cron) {
+        return Ok(());
+    }
+
+    let fields: Vec<&str> = cron.split_whitespace().collect();
+    if fields.len() != 6 && fields.len() != 7 {
+        return Err(format!(
+            "Expected 6 or 7 fields (sec min hour day month weekday [year]) or 5 crontab fields (min hour day month weekday), found {}.",
+            fields.len()
+        ).into());
+    }
+
+    // Check each field on its own, with every other field matching anything.
+    for (i, field) in fields.iter().enumerate() {
+        let mut probe = ["0", "0", "0", "*", "*", "*", "*"];
+        probe[i] = field;
+        if Schedule::from_str(&probe.join(" ")).is_err() {
+            let (name, allowed) = CRON_FIELDS[i];
+            return Err(format!("The {} field '{}' is invalid, allowed values are {}.", name, field, allowed).into());
+        }
+    }
+
+    Schedule::from_str(cron)?;
+    Ok(())
+}
+
+/// Computes the next fire times of the schedule of a job.
+///
+/// # Arguments
+///
+/// * `schedule` - The schedule of the job.
+/// * `timezone` - The timezone the schedule is evaluated in.
+/// * `count` - The number of fire times to compute.
+///
+/// # Returns
+///
+/// The next `count` fire times after now, or fewer if the schedule ends earlier. `@reboot` has
+/// no fire times.
+///
+/// # Examples
+///
+/// ```
+/// let runs = get_next_runs(&JobSchedule::cron("0 0 12 * * ?")?, &Tz::Asia__Shanghai, 3);
+/// for run in runs {
+///     println!("Next run: {}", run);
+/// }
+/// ```
+pub fn get_next_runs(schedule: &JobSchedule, timezone: &Tz, count: usize) -> Vec<DateTime<Tz>> {
+    let now = Utc::now().with_timezone(timezone);
+
+    schedule.after(&now).take(count).collect()
+}
+
+/// Computes the fire times of the schedule of a job from now until a future time.
+///
+/// # Arguments
+///
+/// * `schedule` - The schedule of the job.
+/// * `timezone` - The timezone the schedule is evaluated in.
+/// * `until` - The time up to which fire times are computed.
+/// * `limit` - The maximum number of fire times to compute.
+///
+/// # Returns
+///
+/// The fire times after now and up to `until`, at most `limit` of them. `@reboot` has no fire
+/// times.
+///
+/// # Examples
+///
+/// ```
+/// let until = Utc::now() + chrono::Duration::days(1);
+/// let runs = get_runs_until(&JobSchedule::cron("0 0 * * * ?")?, &Tz::UTC, until, 1000);
+/// assert_eq!(runs.len(), 24);
+/// ```
+pub fn get_runs_until(schedule: &JobSchedule, timezone: &Tz, until: DateTime<Utc>, limit: usize) -> Vec<DateTime<Tz>> {
+    let now = Utc::now().with_timezone(timezone);
+
+    schedule.after(&now)
+        .take_while(|time| *time <= until)
+        .take(limit)
+        .collect()
+}
+
+/// Returns the fire times of the schedule of a job between a past time and now.
+///
+/// # Arguments
+///
+/// * `schedule` - The schedule of the job.
+/// * `timezone` - The timezone the schedule is evaluated in.
+/// * `since` - The time after which fire times are returned, e.g. the fire time of the last
+///   successful run.
+/// * `limit` - The maximum number of fire times to return.
+///
+/// # Returns
+///
+/// The fire times after `since` and up to now, the earliest first, at most `limit` of them.
+/// `@reboot` never misses a run.
+///
+/// # Examples
+///
+/// ```
+/// let since = Utc::now() - chrono::Duration::days(3);
+/// let missed = get_missed_runs(&JobSchedule::cron("0 0 8 * * ?")?, &Tz::UTC, since, 10);
+/// assert_eq!(missed.len(), 3);
+/// ```
+pub fn get_missed_runs(schedule: &JobSchedule, timezone: &Tz, since: DateTime<Utc>, limit: usize) -> Vec<DateTime<Utc>> {
+    let now = Utc::now();
+
+    schedule.after(&since.with_timezone(timezone))
+        .take_while(|time| *time <= now)
+        .take(limit)
+        .map(|time| time.with_timezone(&Utc))
+        .collect()
+}
+
+/// The names of the days of the week, numbered from 1 (Sunday) as in the cron expression.
+const WEEKDAY_NAMES: [&str; 7] = ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+
+/// The names of the months, numbered from 1 (January) as in the cron expression.
+const MONTH_NAMES: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+/// Renders a cron expression in natural language.
+///
+/// Common schedules are described in plain words, e.g. `every 5 minutes on weekdays`.
+/// Uncommon combinations fall back to a field-by-field description. The expression is not
+/// validated, use `validate_cron` for that.
+///
+/// # Arguments
+///
+/// * `cron` - The cron expression.
+///
+/// # Returns
+///
+/// The description of the schedule.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(describe_cron("0 */5 * * * MON-FRI"), "every 5 minutes on weekdays");
+/// assert_eq!(describe_cron("0 0 12 * * ?"), "every day at 12:00");
+/// assert_eq!(describe_cron("* * * * * ?"), "every second");
+/// ```
+pub fn describe_cron(cron: &str) -> String {
+    if is_reboot(cron) {
+        return "once at startup".to_string();
+    }
+    let fields: Vec<&str> = cron.split_whitespace().collect();
+    if fields.len() != 6 && fields.len() != 7 {
+        return cron.to_string();
+    }
+
+    let (second, minute, hour) = (fields[0], fields[1], fields[2]);
+    let (day, month, weekday) = (fields[3], fields[4], fields[5]);
+    let year = fields.get(6).copied().unwrap_or("*");
+
+    let mut parts = Vec::new();
+    let mut daily = false;
+
+    let every_hour = is_any(hour);
+    let fixed_second = is_number(second);
+    let fixed_minute = fixed_second && is_number(minute);
+
+    if every_hour && is_any(minute) && is_any(second) {
+        parts.push("every second".to_string());
+    } else if let Some(n) = get_step(second).filter(|_| every_hour && is_any(minute)) {
+        parts.push(describe_every(n, "second", "seconds"));
+    } else if every_hour && is_any(minute) && fixed_second {
+        parts.push("every minute".to_string());
+    } else if let Some(n) = get_step(minute).filter(|_| every_hour && fixed_second) {
+        parts.push(describe_every(n, "minute", "minutes"));
+    } else if every_hour && fixed_minute {
+        parts.push(format!("every hour at minute {}", minute));
+    } else if let Some(n) = get_step(hour).filter(|_| fixed_minute) {
+        parts.push(format!("{} at minute {}", describe_every(n, "hour", "hours"), minute));
+    } else if fixed_minute && hour.split(',').all(is_number) {
+        let times = hour.split(',')
+            .map(|h| format_time(h, minute, second))
+            .collect::<Vec<_>>()
+            .join(", ");
+        parts.push(format!("at {}", times));
+        daily = true;
+    } else {
+        parts.push(format!(
+            "at {}, {}, {}",
+            describe_field(second, "second", "seconds"),
+            describe_field(minute, "minute", "minutes"),
+            describe_field(hour, "hour", "hours")
+        ));
+    }
+
+    if !is_any(weekday) {
+        parts.push(describe_weekdays(weekday));
+    } else if !is_any(day) {
+        parts.push(format!("on {} of the month", describe_field(day, "day", "days")));
+    } else if daily && is_any(month) {
+        parts.insert(0, "every day".to_string());
+    }
+
+    if !is_any(month) {
+        parts.push(format!("in {}", describe_names(month, &MONTH_NAMES, "month", "months")));
+    }
+
+    if !is_any(year) {
+        parts.push(format!("in {}", describe_field(year, "year", "years")));
+    }
+
+    parts.join(" ")
+}
+
+/// Returns whether a field matches any value.
+fn is_any(field: &str) -> bool {
+    field == "*" || field == "?"
+}
+
+/// Returns whether a field is a single number.
+fn is_number(field: &str) -> bool {
+    !field.is_empty() && field.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Returns the step of a field of the form `*/n` or `0/n`.
+fn get_step(field: &str) -> Option<&str> {
+    let (start, step) = field.split_once('/')?;
+    (start == "*" || start == "0").then_some(step)
+}
+
+/// Describes a step, e.g. `every 5 minutes`, or `every minute` for a step of 1.
+fn describe_every(step: &str, singular: &str, plural: &str) -> String {
+    match step {
+        "1" => format!("every {}", singular),
+        _ => format!("every {} {}", step, plural),
+    }
+}
+
+/// Formats a time of day as `HH:MM`, or `HH:MM:SS` if the second is not zero.
+fn format_time(hour: &str, minute: &str, second: &str) -> String {
+    let hour = hour.parse::<u32>().unwrap_or(0);
+    let minute = minute.parse::<u32>().unwrap_or(0);
+    let second = second.parse::<u32>().unwrap_or(0);
+
+    if second == 0 {
+        format!("{:02}:{:02}", hour, minute)
+    } else {
+        format!("{:02}:{:02}:{:02}", hour, minute, second)
+    }
+}
+
+/// Describes a numeric field generically, e.g. `every 2 hours` or `minutes 0, 30`.
+fn describe_field(field: &str, singular: &str, plural: &str) -> String {
+    if is_any(field) {
+        return format!("every {}", singular);
+    }
+    if let Some((start, step)) = field.split_once('/') {
+        return match start {
+            "*" | "0" => describe_every(step, singular, plural),
+            _ => format!("{} from {} {}", describe_every(step, singular, plural), singular, start),
+        };
+    }
+    if let Some((from, to)) = field.split_once('-') {
+        return format!("{} {} through {}", plural, from, to);
+    }
+    if field.contains(',') {
+        return format!("{} {}", plural, field.replace(',', ", "));
+    }
+    format!("{} {}", singular, field)
+}
+
+/// Describes the day of week field, e.g. `on weekdays` or `on Monday, Friday`.
+fn describe_weekdays(field: &str) -> String {
+    match field.to_uppercase().as_str() {
+        "MON-FRI" | "2-6" => "on weekdays".to_string(),
+        "SAT,SUN" | "SUN,SAT" | "1,7" | "7,1" => "on weekends".to_string(),
+        _ => format!("on {}", describe_names(field, &WEEKDAY_NAMES, "weekday", "weekdays")),
+    }
+}
+
+/// Describes a field whose values have names, such as months or days of the week.
+///
+/// Numbers are replaced with their names, e.g. `1-3` of the month field becomes
+/// `January through March`.
+fn describe_names(field: &str, names: &[&str], singular: &str, plural: &str) -> String {
+    let name_of = |value: &str| -> String {
+        if let Ok(n) = value.parse::<usize>() {
+            if let Some(name) = n.checked_sub(1).and_then(|i| names.get(i)) {
+                return name.to_string();
+            }
+        }
+        names.iter()
+            .find(|name| name[..3].eq_ignore_ascii_case(value))
+            .map(|name| name.to_string())
+            .unwrap_or(value.to_string())
+    };
+
+    if field.contains('/') {
+        return describe_field(field, singular, plural);
+    }
+
+    field.split(',')
+        .map(|item| match item.split_once('-') {
+            Some((from, to)) => format!("{} through {}", name_of(from), name_of(to)),
+            None => name_of(item),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_doc_examples() {
+        assert_eq!(normalize_cron("*/5 * * * *").unwrap(), "0 */5 * * * *");
+        assert_eq!(normalize_cron("30 2 * * 1-5").unwrap(), "0 30 2 * * 2,3,4,5,6");
+        assert_eq!(normalize_cron("0 30 2 * * ?").unwrap(), "0 30 2 * * ?");
+        assert_eq!(normalize_cron("@daily").unwrap(), "0 0 0 * * *");
+    }
+
+    #[test]
+    fn normalizes_macros() {
+        assert_eq!(normalize_cron("@Hourly").unwrap(), "0 0 * * * *");
+        assert_eq!(normalize_cron(" @reboot ").unwrap(), REBOOT);
+        assert!(normalize_cron("@often").is_err());
+    }
+
+    #[test]
+    fn renumbers_sunday_as_0_and_7() {
+        assert_eq!(normalize_crontab_weekday("0").unwrap(), "1");
+        assert_eq!(normalize_crontab_weekday("7").unwrap(), "1");
+        assert_eq!(normalize_crontab_weekday("0,7").unwrap(), "1");
+        assert_eq!(normalize_crontab_weekday("5-7").unwrap(), "1,6,7");
+        assert_eq!(normalize_crontab_weekday("6").unwrap(), "7");
+    }
+
+    #[test]
+    fn expands_weekday_steps() {
+        assert_eq!(normalize_crontab_weekday("*/2").unwrap(), "1,3,5,7");
+        // A single start day with a step runs up to 7, which is Sunday again.
+        assert_eq!(normalize_crontab_weekday("1/2").unwrap(), "1,2,4,6");
+        assert_eq!(normalize_crontab_weekday("1-5/2").unwrap(), "2,4,6");
+    }
+
+    #[test]
+    fn keeps_weekday_wildcards_and_names() {
+        assert_eq!(normalize_crontab_weekday("*").unwrap(), "*");
+        assert_eq!(normalize_crontab_weekday("?").unwrap(), "?");
+        assert_eq!(normalize_crontab_weekday("MON-FRI").unwrap(), "MON-FRI");
+    }
+
+    #[test]
+    fn rejects_invalid_weekdays() {
+        for field in ["8", "5-3", "*/0", "1-", "1,,2"] {
+            assert!(normalize_crontab_weekday(field).is_none(), "{}", field);
+        }
+        let err = normalize_cron("0 0 * * 8").unwrap_err();
+        assert_eq!(err.to_string(), "The weekday field '8' is invalid, allowed values are 0-7 or SUN-SAT.");
+    }
+
+    #[test]
+    fn validates_doc_examples() {
+        assert!(validate_cron("0 0 12 * * ?").is_ok());
+        let err = validate_cron("0 61 12 * * ?").unwrap_err();
+        assert_eq!(err.to_string(), "The minutes field '61' is invalid, allowed values are 0-59.");
+    }
+
+    #[test]
+    fn names_the_invalid_field() {
+        let cases = [
+            ("60 0 12 * * ?", "The seconds field '60' is invalid, allowed values are 0-59."),
+            ("0 0 24 * * ?", "The hours field '24' is invalid, allowed values are 0-23."),
+            ("0 0 12 32 * ?", "The day of month field '32' is invalid, allowed values are 1-31."),
+            ("0 0 12 * 13 ?", "The month field '13' is invalid, allowed values are 1-12 or JAN-DEC."),
+            ("0 0 12 ? * 8", "The day of week field '8' is invalid, allowed values are 1-7 or SUN-SAT."),
+        ];
+        for (cron, message) in cases {
+            assert_eq!(validate_cron(cron).unwrap_err().to_string(), message);
+        }
+    }
+
+    #[test]
+    fn validates_the_number_of_fields() {
+        assert!(validate_cron("0 0 12 * * ? 2030").is_ok());
+        assert!(validate_cron("@reboot").is_ok());
+        assert!(validate_cron("0 12 * *").unwrap_err().to_string().ends_with("found 4."));
+        assert!(validate_cron(&normalize_cron("*/2 9-17 * * */2").unwrap()).is_ok());
+    }
+}
\ No newline at end of file
@@ -37,3 +37,40 @@ pub fn get_local_datetime_in_timezone(timezone: &Tz) -> String {
     let local_time: DateTime<Tz> = timezone.from_utc_datetime(&Local::now().naive_utc());
     local_time.format("%Y-%m-%d %H:%M:%S.%3f").to_string()
 }
+
+
+/// Get the current datetime in the specified timezone in a compact form suitable for file names.
+/// The format of the string is "%Y%m%d%H%M%S%3f".
+///
+/// # Arguments
+///
+/// * `timezone` - The timezone to convert the datetime to.
+///
+/// # Example
+///
+/// ```
+/// let datetime = get_compact_datetime_in_timezone(&Tz::UTC);
+/// println!("Current datetime in UTC: {}", datetime);
+/// ```
+pub fn get_compact_datetime_in_timezone(timezone: &Tz) -> String {
+    let local_time: DateTime<Tz> = timezone.from_utc_datetime(&Local::now().naive_utc());
+    local_time.format("%Y%m%d%H%M%S%3f").to_string()
+}
+
+/// Get the current date in the specified timezone as a formatted string.
+/// The format of the string is "%Y-%m-%d".
+///
+/// # Arguments
+///
+/// * `timezone` - The timezone to convert the date to.
+///
+/// # Example
+///
+/// ```
+/// let date = get_date_in_timezone(&Tz::UTC);
+/// println!("Current date in UTC: {}", date);
+/// ```
+pub fn get_date_in_timezone(timezone: &Tz) -> String {
+    let local_time: DateTime<Tz> = timezone.from_utc_datetime(&Local::now().naive_utc());
+    local_time.format("%Y-%m-%d").to_string()
+}
\ No newline at end of file
@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+/// Parses a human-readable duration such as `500ms`, `30s`, `5m`, `6h` or `1d`.
+///
+/// Several components can be combined, e.g. `1h30m`. A bare number is interpreted as seconds.
+///
+/// # Arguments
+///
+/// * `value` - The duration string.
+///
+/// # Returns
+///
+/// The parsed `Duration`, or `None` if the string is empty, malformed or too large.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(parse_duration("2m"), Some(Duration::from_secs(120)));
+/// assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+/// assert_eq!(parse_duration("250ms"), Some(Duration::from_millis(250)));
+/// ```
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = value;
+
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        if digits == 0 {
+            return None;
+        }
+        let amount = rest[..digits].parse::<u64>().ok()?;
+        rest = &rest[digits..];
+
+        let unit_len = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        let component = match &rest[..unit_len] {
+            "ms" => Duration::from_millis(amount),
+            "s" => Duration::from_secs(amount),
+            "m" => Duration::from_secs(amount.checked_mul(60)?),
+            "h" => Duration::from_secs(amount.checked_mul(3600)?),
+            "d" => Duration::from_secs(amount.checked_mul(86400)?),
+            _ => return None
+        };
+        total = total.checked_add(component)?;
+        rest = &rest[unit_len..];
+    }
+
+    Some(total)
+}
+
+/// Formats a duration in the format of `parse_duration`, e.g. `1h30m` or `7d`.
+///
+/// The duration is rounded down to the second, except below a second.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(format_duration(Duration::from_secs(5400)), "1h30m");
+/// assert_eq!(format_duration(Duration::from_millis(250)), "250ms");
+/// ```
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let units = [(86400, "d"), (3600, "h"), (60, "m"), (1, "s")];
+
+    let formatted = units.iter()
+        .scan(secs, |rest, (unit, suffix)| {
+            let amount = *rest / unit;
+            *rest %= unit;
+            Some((amount, suffix))
+        })
+        .filter(|(amount, _)| *amount > 0)
+        .map(|(amount, suffix)| format!("{}{}", amount, suffix))
+        .collect::<String>();
+
+    if formatted.is_empty() { format!("{}ms", duration.as_millis()) } else { formatted }
+}
\ No newline at end of file
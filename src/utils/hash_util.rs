@@ -0,0 +1,28 @@
+/// Hashes bytes with FNV-1a, a hash that doesn't depend on the version of the standard library.
+///
+/// The hash is stable across builds and hosts, unlike `DefaultHasher`, so it can seed values
+/// that must be the same on every run or every instance.
+///
+/// # Examples
+///
+/// ```
+/// let seed = fnv1a("users".as_bytes());
+/// assert_eq!(seed, fnv1a(b"users"));
+/// ```
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325_u64, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
+
+/// Scrambles the bits of a number (SplitMix64), turning a seed into a uniform random number.
+///
+/// # Examples
+///
+/// ```
+/// let offset = mix(seed ^ day) % window;
+/// ```
+pub fn mix(seed: u64) -> u64 {
+    let mut x = seed.wrapping_add(0x9e3779b97f4a7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
+}
\ No newline at end of file
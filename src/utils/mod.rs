@@ -1,2 +1,9 @@
+pub mod compress_util;
+pub mod cron_util;
 pub mod datetime_util;
+pub mod duration_util;
+pub mod hash_util;
+pub mod redact_util;
+pub mod solar_util;
+pub mod template_util;
 pub mod uuid_util;
\ No newline at end of file
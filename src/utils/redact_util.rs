@@ -0,0 +1,158 @@
+use std::sync::RwLock;
+use once_cell::sync::{Lazy, OnceCell};
+use reqwest::header::HeaderMap;
+
+/// The placeholder logged instead of a sensitive value.
+pub const REDACTED: &str = "***";
+
+/// The minimum length of a registered secret, shorter values would redact unrelated text.
+const MIN_SECRET_LEN: usize = 4;
+
+/// The header names whose values are always redacted.
+const SENSITIVE_HEADERS: [&str; 4] = ["authorization", "proxy-authorization", "cookie", "set-cookie"];
+
+/// The fragments of header names that mark a header as sensitive.
+const SENSITIVE_FRAGMENTS: [&str; 5] = ["token", "secret", "password", "api-key", "apikey"];
+
+/// The additional sensitive header names of the `sensitive_headers` setting, in lowercase.
+static CONFIGURED_HEADERS: OnceCell<Vec<String>> = OnceCell::new();
+
+/// The secret values that are redacted from logged text, see `register_secret`.
+static SECRETS: Lazy<RwLock<Vec<String>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+/// Sets the header names of the `sensitive_headers` setting, redacted in addition to the
+/// built-in ones.
+///
+/// Only the first call has an effect.
+///
+/// # Arguments
+///
+/// * `names` - The header names, matched case-insensitively.
+pub fn set_sensitive_headers(names: Vec<String>) {
+    let _ = CONFIGURED_HEADERS.set(names.iter().map(|name| name.to_lowercase()).collect());
+}
+
+/// Registers a secret value, such as a credential read from an environment variable, so that
+/// it is redacted by `redact_secrets`.
+///
+/// Values shorter than 4 characters are ignored.
+///
+/// # Arguments
+///
+/// * `value` - The secret value.
+///
+/// # Examples
+///
+/// ```
+/// register_secret("wJalrXUtnFEMI");
+/// assert_eq!(redact_secrets("secret_key: wJalrXUtnFEMI"), "secret_key: ***");
+/// ```
+pub fn register_secret(value: &str) {
+    if value.len() < MIN_SECRET_LEN {
+        return;
+    }
+
+    let mut secrets = SECRETS.write().unwrap();
+    if !secrets.iter().any(|secret| secret == value) {
+        secrets.push(value.to_string());
+        // Longer secrets first, so a secret containing another one is redacted as a whole.
+        secrets.sort_by_key(|secret| std::cmp::Reverse(secret.len()));
+    }
+}
+
+/// Replaces every registered secret in a text with `***`.
+///
+/// # Arguments
+///
+/// * `text` - The text to be logged.
+///
+/// # Examples
+///
+/// ```
+/// println!("{} {} Job: [{}]", uuid, local_time, redact_secrets(&job.to_string()));
+/// ```
+pub fn redact_secrets(text: &str) -> String {
+    let secrets = SECRETS.read().unwrap();
+    secrets.iter().fold(text.to_string(), |text, secret| text.replace(secret.as_str(), REDACTED))
+}
+
+/// Returns the value of a header as it may be logged.
+///
+/// Values of credential headers such as `Authorization`, `Cookie` or `X-Api-Key`, and of the
+/// headers of the `sensitive_headers` setting, are replaced with `***`.
+///
+/// # Arguments
+///
+/// * `name` - The header name.
+/// * `value` - The header value.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(redact_header("Authorization", "Bearer abc"), "***");
+/// assert_eq!(redact_header("Accept", "application/json"), "application/json");
+/// ```
+pub fn redact_header(name: &str, value: &str) -> String {
+    let name = name.to_lowercase();
+    let sensitive = SENSITIVE_HEADERS.contains(&name.as_str())
+        || SENSITIVE_FRAGMENTS.iter().any(|fragment| name.contains(fragment))
+        || CONFIGURED_HEADERS.get().is_some_and(|names| names.contains(&name));
+
+    if sensitive {
+        REDACTED.to_string()
+    } else {
+        redact_secrets(value)
+    }
+}
+
+/// Formats headers as they may be logged, e.g. `{accept: */*, authorization: ***}`.
+///
+/// # Arguments
+///
+/// * `headers` - The headers.
+///
+/// # Examples
+///
+/// ```
+/// println!("{} {} Dry run headers: {}", uuid, local_time, redact_headers(built.headers()));
+/// ```
+pub fn redact_headers(headers: &HeaderMap) -> String {
+    let headers = headers.iter()
+        .map(|(name, value)| format!("{}: {}", name, redact_header(name.as_str(), value.to_str().unwrap_or_default())))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("{{{}}}", headers)
+}
+
+/// Returns a connection URL as it may be logged, with the password replaced by `***`.
+///
+/// # Arguments
+///
+/// * `url` - The connection URL, e.g. a database DSN.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(redact_url("postgres://app:secret@db:5432/app"), "postgres://app:***@db:5432/app");
+/// assert_eq!(redact_url("mysql://db/app"), "mysql://db/app");
+/// ```
+pub fn redact_url(url: &str) -> String {
+    let authority_start = match url.find("://") {
+        Some(i) => i + 3,
+        None => return url.to_string()
+    };
+    let authority_end = url[authority_start..].find('/')
+        .map(|i| authority_start + i)
+        .unwrap_or(url.len());
+
+    let user_info_end = match url[authority_start..authority_end].rfind('@') {
+        Some(i) => authority_start + i,
+        None => return url.to_string()
+    };
+
+    match url[authority_start..user_info_end].find(':') {
+        Some(i) => format!("{}{}{}", &url[..authority_start + i + 1], REDACTED, &url[user_info_end..]),
+        None => url.to_string()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,66 @@
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+
+/// The Julian date of 2000-01-01 12:00 UTC, the J2000 epoch.
+const J2000: f64 = 2451545.0;
+
+/// The Julian date of the Unix epoch, 1970-01-01 00:00 UTC.
+const UNIX_EPOCH_JULIAN_DATE: f64 = 2440587.5;
+
+/// The altitude of the center of the sun at sunrise and sunset, in degrees, accounting for the
+/// refraction of the atmosphere and the radius of the sun.
+const SUNRISE_ALTITUDE: f64 = -0.833;
+
+/// The tilt of the axis of the earth, in degrees.
+const OBLIQUITY: f64 = 23.4397;
+
+/// Computes the sunrise and the sunset of a day at a location.
+///
+/// Uses the sunrise equation with the approximations of the NOAA, precise to about a minute
+/// between the polar circles. The day is the calendar day at the location, whatever the
+/// configured timezone.
+///
+/// # Arguments
+///
+/// * `date` - The day.
+/// * `latitude` - The latitude of the location in degrees, positive north of the equator.
+/// * `longitude` - The longitude of the location in degrees, positive east of Greenwich.
+///
+/// # Returns
+///
+/// The sunrise and the sunset, or `None` if the sun doesn't rise or doesn't set that day, i.e.
+/// during the polar night or the midnight sun.
+///
+/// # Examples
+///
+/// ```
+/// let date = NaiveDate::from_ymd_opt(2024, 6, 21).unwrap();
+/// let (sunrise, sunset) = get_sunrise_sunset(date, 48.8566, 2.3522).unwrap();
+/// println!("Sunrise in Paris: {}, sunset: {}", sunrise, sunset); // 03:47 and 19:58 UTC
+/// ```
+pub fn get_sunrise_sunset(date: NaiveDate, latitude: f64, longitude: f64) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let days = (date - NaiveDate::from_ymd_opt(2000, 1, 1)?).num_days() as f64;
+    // The mean solar noon at the longitude, in days since the J2000 epoch.
+    let mean_noon = days - longitude / 360.0;
+
+    let anomaly = (357.5291 + 0.98560028 * mean_noon).rem_euclid(360.0).to_radians();
+    let center = 1.9148 * anomaly.sin() + 0.0200 * (2.0 * anomaly).sin() + 0.0003 * (3.0 * anomaly).sin();
+    let ecliptic_longitude = (anomaly.to_degrees() + center + 180.0 + 102.9372).rem_euclid(360.0).to_radians();
+    let transit = J2000 + mean_noon + 0.0053 * anomaly.sin() - 0.0069 * (2.0 * ecliptic_longitude).sin();
+
+    let declination = (ecliptic_longitude.sin() * OBLIQUITY.to_radians().sin()).asin();
+    let latitude = latitude.to_radians();
+    let cos_hour_angle = (SUNRISE_ALTITUDE.to_radians().sin() - latitude.sin() * declination.sin())
+        / (latitude.cos() * declination.cos());
+    if !(-1.0..=1.0).contains(&cos_hour_angle) {
+        return None;
+    }
+    let hour_angle = cos_hour_angle.acos().to_degrees() / 360.0;
+
+    Some((from_julian_date(transit - hour_angle)?, from_julian_date(transit + hour_angle)?))
+}
+
+/// Converts a Julian date to a time, to the second.
+fn from_julian_date(julian_date: f64) -> Option<DateTime<Utc>> {
+    let timestamp = ((julian_date - UNIX_EPOCH_JULIAN_DATE) * 86400.0).round() as i64;
+    Utc.timestamp_opt(timestamp, 0).single()
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use chrono_tz::Tz;
+
+use crate::utils::datetime_util::{get_compact_datetime_in_timezone, get_date_in_timezone};
+
+/// Renders a template by replacing `{{name}}` placeholders with the given variables.
+///
+/// Whitespace inside the braces is ignored, so `{{ job_name }}` and `{{job_name}}` are equivalent.
+/// Placeholders without a matching variable are left untouched.
+///
+/// # Arguments
+///
+/// * `template` - The template string.
+/// * `vars` - The variables available to the template.
+///
+/// # Returns
+///
+/// The rendered string.
+///
+/// # Examples
+///
+/// ```
+/// let mut vars = HashMap::new();
+/// vars.insert("job_name", "users2".to_string());
+///
+/// let path = render_template("./out/{{job_name}}.json", &vars);
+/// assert_eq!(path, "./out/users2.json");
+/// ```
+pub fn render_template(template: &str, vars: &HashMap<&str, String>) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + end].trim();
+
+        rendered.push_str(&rest[..start]);
+        match vars.get(name) {
+            Some(value) => rendered.push_str(value),
+            None => rendered.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &rest[start + end + 2..];
+    }
+
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Returns the variables available to the path templates of a job run.
+///
+/// The variables are `{{job_name}}`, `{{run_id}}`, `{{now}}` and `{{date}}`.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job.
+/// * `run_id` - The identifier of the current run.
+/// * `timezone` - The timezone used to render the date variables.
+///
+/// # Examples
+///
+/// ```
+/// let vars = get_run_vars("users2", &uuid, &Tz::UTC);
+/// let path = render_template("./out/{{job_name}}-{{date}}.json", &vars);
+/// ```
+pub fn get_run_vars(job_name: &str, run_id: &str, timezone: &Tz) -> HashMap<&'static str, String> {
+    let mut vars = HashMap::new();
+    vars.insert("job_name", job_name.to_string());
+    vars.insert("run_id", run_id.to_string());
+    vars.insert("now", get_compact_datetime_in_timezone(timezone));
+    vars.insert("date", get_date_in_timezone(timezone));
+    vars
+}
\ No newline at end of file
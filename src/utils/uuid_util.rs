@@ -1,5 +1,10 @@
+use ulid::Ulid;
 use uuid::Uuid;
 
+use crate::configure::get_jobs;
+use crate::models::run_id_format::RunIdFormat;
+use crate::scheduler::state::next_sequence;
+
 /// Generates a UUID without hyphens.
 ///
 /// # Examples
@@ -15,4 +20,39 @@ use uuid::Uuid;
 pub fn generate_uuid_without_hyphens() -> String {
     let uuid = Uuid::new_v4();
     uuid.to_string().replace("-", "")
+}
+
+/// Generates the identifier of a new run of a job, in the configured `run_id_format`.
+///
+/// With the `sequence` format, the sequence number of the job is incremented in the state
+/// file. If the state file cannot be updated, a UUIDv7 is returned instead, so the run still
+/// gets a unique and time-ordered identifier.
+///
+/// # Arguments
+///
+/// * `job_name` - The name of the job.
+///
+/// # Returns
+///
+/// The identifier of the run.
+///
+/// # Examples
+///
+/// ```
+/// let uuid = generate_run_id(&http_job.name);
+/// println!("{} Http job start, job name: {}", uuid, &http_job.name);
+/// ```
+pub fn generate_run_id(job_name: &str) -> String {
+    match get_jobs().run_id_format {
+        RunIdFormat::Uuid => generate_uuid_without_hyphens(),
+        RunIdFormat::UuidV7 => Uuid::now_v7().simple().to_string(),
+        RunIdFormat::Ulid => Ulid::new().to_string(),
+        RunIdFormat::Sequence => match next_sequence(job_name) {
+            Ok(sequence) => format!("{:010}", sequence),
+            Err(err) => {
+                eprintln!("Failed to update the run sequence of job '{}', using a UUIDv7: {}", job_name, err);
+                Uuid::now_v7().simple().to_string()
+            }
+        }
+    }
 }
\ No newline at end of file